@@ -0,0 +1,185 @@
+//! Compares the memory and time cost of parsing a large product list.
+//!
+//! List endpoints parse the response body as it arrives instead of buffering
+//! it in full first. This benchmark serves a response the size of `list_all`
+//! on the products endpoint from a local HTTP server and fetches it through
+//! [`RestClient::get`] (buffered) and [`RestClient::get_streamed`], reporting
+//! the peak heap usage and request time of both.
+//!
+//! Run with `cargo bench --bench response_parsing`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use coinbase_advanced::models::ListProductsResponse;
+use coinbase_advanced::{Environment, RestClient};
+
+/// Global allocator that tracks current and peak heap usage.
+struct CountingAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const PRODUCTS: usize = 5_000;
+const ITERATIONS: u32 = 20;
+
+fn products_body(count: usize) -> Vec<u8> {
+    let products: Vec<_> = (0..count)
+        .map(|i| {
+            serde_json::json!({
+                "product_id": format!("COIN{}-USD", i),
+                "price": "123.456",
+                "price_percentage_change_24h": "-1.23",
+                "volume_24h": "987654.321",
+                "volume_percentage_change_24h": "4.56",
+                "base_increment": "0.00000001",
+                "quote_increment": "0.01",
+                "quote_min_size": "1",
+                "quote_max_size": "10000000",
+                "base_min_size": "0.00000001",
+                "base_max_size": "1000000",
+                "base_name": format!("Coin {}", i),
+                "quote_name": "US Dollar",
+                "watched": false,
+                "is_disabled": false,
+                "new": false,
+                "status": "online",
+                "cancel_only": false,
+                "limit_only": false,
+                "post_only": false,
+                "trading_disabled": false,
+                "auction_mode": false,
+                "product_type": "SPOT",
+                "quote_currency_id": "USD",
+                "base_currency_id": format!("COIN{}", i),
+                "base_display_symbol": format!("COIN{}", i),
+                "quote_display_symbol": "USD"
+            })
+        })
+        .collect();
+    serde_json::to_vec(&serde_json::json!({
+        "products": products,
+        "num_products": count
+    }))
+    .unwrap()
+}
+
+/// Serve `body` to every request on a local port, keeping connections open.
+///
+/// The server runs on plain threads and writes from the shared body, so it
+/// adds no per-request heap beyond a small line buffer.
+fn serve(body: &'static [u8]) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = stream.unwrap();
+            std::thread::spawn(move || handle(stream, body));
+        }
+    });
+    format!("http://{}", addr)
+}
+
+fn handle(mut stream: TcpStream, body: &[u8]) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut line = String::new();
+    loop {
+        // Skip the request line and headers; every request gets the body.
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+            if line == "\r\n" {
+                break;
+            }
+        }
+        let header = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+            body.len()
+        );
+        if stream.write_all(header.as_bytes()).is_err() || stream.write_all(body).is_err() {
+            return;
+        }
+    }
+}
+
+/// Run `fetch` repeatedly, reporting peak extra heap and mean time.
+async fn measure<F, Fut>(name: &str, fetch: F)
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = coinbase_advanced::Result<ListProductsResponse>>,
+{
+    // Warm up the connection pool so it is not counted.
+    fetch().await.unwrap();
+
+    let mut elapsed = Duration::ZERO;
+    let mut peak = 0;
+    for _ in 0..ITERATIONS {
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+
+        let started = Instant::now();
+        let parsed = fetch().await.unwrap();
+        elapsed += started.elapsed();
+
+        peak = peak.max(PEAK.load(Ordering::Relaxed) - baseline);
+        assert_eq!(parsed.products.len(), PRODUCTS);
+    }
+
+    println!(
+        "{:<10} peak heap {:>8.2} MiB   mean request {:>8.2?}",
+        name,
+        peak as f64 / (1024.0 * 1024.0),
+        elapsed / ITERATIONS
+    );
+}
+
+#[tokio::main]
+async fn main() {
+    let body: &'static [u8] = products_body(PRODUCTS).leak();
+    println!(
+        "{} products, body {:.2} MiB",
+        PRODUCTS,
+        body.len() as f64 / (1024.0 * 1024.0)
+    );
+
+    let client = RestClient::builder()
+        .environment(Environment::custom(serve(body), "ws://localhost"))
+        .build()
+        .unwrap();
+
+    // Collect the whole body, then parse it.
+    measure("buffered", || {
+        client.get::<ListProductsResponse>("/products")
+    })
+    .await;
+
+    // Parse chunks as they arrive, as the list endpoints do.
+    measure("streamed", || {
+        client.get_streamed::<ListProductsResponse>("/products")
+    })
+    .await;
+}
@@ -0,0 +1,98 @@
+//! Measures WebSocket message decoding throughput.
+//!
+//! Level 2 updates across many products arrive faster than a single task can
+//! parse them. This benchmark decodes a batch of synthetic `l2_data` frames
+//! inline, as the message stream does by default, and on the blocking pool
+//! with a bounded number of frames in flight, as it does with
+//! `WebSocketClientBuilder::decode_workers`, and reports messages per second.
+//!
+//! Run with `cargo bench --bench ws_decode`.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use coinbase_advanced::ws::Message;
+
+const PRODUCTS: usize = 100;
+const MESSAGES: usize = 20_000;
+const UPDATES_PER_MESSAGE: usize = 50;
+
+fn level2_frame(sequence_num: usize) -> String {
+    let updates: Vec<_> = (0..UPDATES_PER_MESSAGE)
+        .map(|i| {
+            serde_json::json!({
+                "side": if i % 2 == 0 { "bid" } else { "offer" },
+                "event_time": "2024-01-01T00:00:00.000000Z",
+                "price_level": format!("{}.{:02}", 50_000 + i, i),
+                "new_quantity": "0.12345678"
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "channel": "l2_data",
+        "client_id": "",
+        "timestamp": "2024-01-01T00:00:00.000000Z",
+        "sequence_num": sequence_num,
+        "events": [{
+            "type": "update",
+            "product_id": format!("COIN{}-USD", sequence_num % PRODUCTS),
+            "updates": updates
+        }]
+    })
+    .to_string()
+}
+
+fn decode(text: &str) -> Message {
+    serde_json::from_str(text).unwrap()
+}
+
+fn report(name: &str, started: Instant) {
+    let elapsed = started.elapsed();
+    println!(
+        "{:<12} {:>10.0} msg/s   ({:.2?} for {} messages)",
+        name,
+        MESSAGES as f64 / elapsed.as_secs_f64(),
+        elapsed,
+        MESSAGES
+    );
+}
+
+async fn offloaded(frames: &[String], workers: usize) {
+    let mut in_flight = VecDeque::with_capacity(workers);
+    let mut decoded = 0;
+    for frame in frames {
+        if in_flight.len() == workers {
+            let worker: tokio::task::JoinHandle<Message> = in_flight.pop_front().unwrap();
+            worker.await.unwrap();
+            decoded += 1;
+        }
+        let frame = frame.clone();
+        in_flight.push_back(tokio::task::spawn_blocking(move || decode(&frame)));
+    }
+    for worker in in_flight {
+        worker.await.unwrap();
+        decoded += 1;
+    }
+    assert_eq!(decoded, frames.len());
+}
+
+fn main() {
+    let frames: Vec<String> = (0..MESSAGES).map(level2_frame).collect();
+    println!(
+        "{} level2 messages over {} products, {} updates each",
+        MESSAGES, PRODUCTS, UPDATES_PER_MESSAGE
+    );
+
+    let started = Instant::now();
+    for frame in &frames {
+        decode(frame);
+    }
+    report("inline", started);
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    for workers in [2, 4, 8] {
+        let started = Instant::now();
+        runtime.block_on(offloaded(&frames, workers));
+        report(&format!("{} workers", workers), started);
+    }
+}
@@ -65,7 +65,7 @@ async fn main() -> coinbase_advanced::Result<()> {
 
     // Get best bid/ask
     println!("\n--- Best Bid/Ask ---");
-    let params = GetBestBidAskParams::new().product_ids(&["BTC-USD", "ETH-USD"]);
+    let params = GetBestBidAskParams::new().product_ids(["BTC-USD", "ETH-USD"]);
     let bid_ask = client.products().get_best_bid_ask(params).await?;
     for pricebook in &bid_ask.pricebooks {
         println!(
@@ -0,0 +1,26 @@
+//! Scrub a response body before sharing it.
+//!
+//! Reads a body from a file, or from stdin if no file is given, and prints
+//! it with credentials, personal details and identifiers removed.
+//!
+//! ```sh
+//! cargo run --example scrub --features debug-tools -- response.json > scrubbed.json
+//! ```
+
+use std::io::Read;
+
+use coinbase_advanced::debug_tools::Scrubber;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let body = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => {
+            let mut body = String::new();
+            std::io::stdin().read_to_string(&mut body)?;
+            body
+        }
+    };
+
+    println!("{}", Scrubber::new().scrub(&body));
+    Ok(())
+}
@@ -0,0 +1,562 @@
+//! Price alerts evaluated on ticker updates.
+//!
+//! [`AlertEngine`] holds [`Alert`]s on products, each with a threshold or
+//! percent-change [`Condition`], and checks them against every ticker update
+//! it observes. When an alert triggers it produces an [`AlertEvent`], which
+//! is returned from [`AlertEngine::observe`] and passed to every callback
+//! registered with [`AlertEngine::on_trigger`].
+//!
+//! After triggering, an alert re-arms by itself: a threshold alert once the
+//! price has moved back past the threshold by its
+//! [hysteresis](Alert::hysteresis), a percent-change alert immediately,
+//! measuring from the price it triggered at. A [cooldown](Alert::cooldown)
+//! spaces out repeated triggers, and [`once`](Alert::once) alerts are
+//! removed after triggering.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use coinbase_advanced::Decimal;
+//! use coinbase_advanced::alerts::{Alert, AlertEngine, Condition};
+//! use coinbase_advanced::ws::WebSocketClient;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let engine = AlertEngine::new();
+//! engine.add(
+//!     Alert::new("BTC-USD", Condition::Above(Decimal::from(100_000)))
+//!         .hysteresis(Decimal::from(500)),
+//! );
+//! engine.add(Alert::new(
+//!     "ETH-USD",
+//!     Condition::PercentChange {
+//!         percent: Decimal::from(5),
+//!         window: Some(Duration::from_secs(3600)),
+//!     },
+//! ));
+//! engine.on_trigger(|event| {
+//!     println!("{} {} at {}", event.product_id, event.condition, event.price)
+//! });
+//!
+//! let ws = WebSocketClient::builder().build()?;
+//! let stream = ws.connect().await?;
+//! if let Some(channel) = engine.channel() {
+//!     ws.subscribe(&[channel]).await?;
+//! }
+//! engine.run(stream).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use rust_decimal::Decimal;
+
+use crate::error::Result;
+use crate::runtime::Instant;
+use crate::ws::{Channel, Events, Message};
+
+/// When an alert triggers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// The price is at or above a level.
+    Above(Decimal),
+    /// The price is at or below a level.
+    Below(Decimal),
+    /// The price has moved by at least `percent` in either direction.
+    PercentChange {
+        /// Size of the move, in percent.
+        percent: Decimal,
+        /// Measure the move over this trailing window. Without a window the
+        /// move is measured from the first price seen, or from the price
+        /// the alert last triggered at.
+        window: Option<Duration>,
+    },
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Above(level) => write!(f, "above {}", level),
+            Self::Below(level) => write!(f, "below {}", level),
+            Self::PercentChange {
+                percent,
+                window: Some(window),
+            } => write!(f, "{}% move within {:?}", percent, window),
+            Self::PercentChange {
+                percent,
+                window: None,
+            } => write!(f, "{}% move", percent),
+        }
+    }
+}
+
+/// A condition to watch on a product.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    /// Product ID.
+    pub product_id: String,
+    /// When the alert triggers.
+    pub condition: Condition,
+    /// How far the price must move back past a threshold before the alert
+    /// re-arms. Ignored for percent-change alerts.
+    pub hysteresis: Decimal,
+    /// Minimum time between triggers.
+    pub cooldown: Duration,
+    /// Remove the alert after it triggers.
+    pub once: bool,
+}
+
+impl Alert {
+    /// Create an alert that re-arms with no hysteresis or cooldown.
+    pub fn new(product_id: impl Into<String>, condition: Condition) -> Self {
+        Self {
+            product_id: product_id.into(),
+            condition,
+            hysteresis: Decimal::ZERO,
+            cooldown: Duration::ZERO,
+            once: false,
+        }
+    }
+
+    /// Set how far the price must move back past a threshold to re-arm.
+    pub fn hysteresis(mut self, hysteresis: Decimal) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Set the minimum time between triggers.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Remove the alert after it triggers.
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+}
+
+/// Identifies an alert registered with an [`AlertEngine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AlertId(u64);
+
+impl fmt::Display for AlertId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "alert-{}", self.0)
+    }
+}
+
+/// A triggered alert.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertEvent {
+    /// The alert that triggered.
+    pub id: AlertId,
+    /// Product ID.
+    pub product_id: String,
+    /// The condition that was met.
+    pub condition: Condition,
+    /// The price that met it.
+    pub price: Decimal,
+    /// For percent-change alerts, the price the move was measured from.
+    pub reference: Option<Decimal>,
+    /// When the alert triggered.
+    pub time: DateTime<Utc>,
+}
+
+/// An alert and its evaluation state.
+#[derive(Debug)]
+struct Entry {
+    id: AlertId,
+    alert: Alert,
+    armed: bool,
+    triggered_at: Option<Instant>,
+    /// Price a percent-change alert without a window measures from.
+    reference: Option<Decimal>,
+    /// Recent prices of a percent-change alert with a window.
+    samples: VecDeque<(Instant, Decimal)>,
+}
+
+impl Entry {
+    fn new(id: AlertId, alert: Alert) -> Self {
+        Self {
+            id,
+            alert,
+            armed: true,
+            triggered_at: None,
+            reference: None,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Check a price, returning an event if the alert triggers.
+    fn evaluate(&mut self, price: Decimal, now: Instant) -> Option<AlertEvent> {
+        let cooldown = self.alert.cooldown;
+        let cooled = self
+            .triggered_at
+            .is_none_or(|at| now.duration_since(at) >= cooldown);
+        let hysteresis = self.alert.hysteresis;
+
+        let reference = match self.alert.condition {
+            Condition::Above(level) => {
+                if !self.armed && price <= level - hysteresis {
+                    self.armed = true;
+                }
+                if !(self.armed && cooled && price >= level) {
+                    return None;
+                }
+                self.armed = false;
+                None
+            }
+            Condition::Below(level) => {
+                if !self.armed && price >= level + hysteresis {
+                    self.armed = true;
+                }
+                if !(self.armed && cooled && price <= level) {
+                    return None;
+                }
+                self.armed = false;
+                None
+            }
+            Condition::PercentChange { percent, window } => {
+                let reference = match window {
+                    None => *self.reference.get_or_insert(price),
+                    Some(window) => {
+                        self.samples.push_back((now, price));
+                        while self
+                            .samples
+                            .front()
+                            .is_some_and(|(at, _)| now.duration_since(*at) > window)
+                        {
+                            self.samples.pop_front();
+                        }
+                        self.samples.front().map_or(price, |(_, p)| *p)
+                    }
+                };
+                if reference.is_zero() {
+                    return None;
+                }
+                let change = (price - reference) / reference * Decimal::ONE_HUNDRED;
+                if !(cooled && change.abs() >= percent) {
+                    return None;
+                }
+                // Measure the next move from here.
+                self.reference = Some(price);
+                self.samples.clear();
+                self.samples.push_back((now, price));
+                Some(reference)
+            }
+        };
+
+        self.triggered_at = Some(now);
+        Some(AlertEvent {
+            id: self.id,
+            product_id: self.alert.product_id.clone(),
+            condition: self.alert.condition.clone(),
+            price,
+            reference,
+            time: Utc::now(),
+        })
+    }
+}
+
+type Callback = Box<dyn Fn(&AlertEvent) + Send + Sync>;
+
+/// Evaluates alerts against ticker updates.
+///
+/// All methods take `&self`, so an engine can be shared between the task
+/// feeding it and the code managing alerts.
+#[derive(Default)]
+pub struct AlertEngine {
+    entries: Mutex<Vec<Entry>>,
+    callbacks: RwLock<Vec<Callback>>,
+    next_id: AtomicU64,
+}
+
+impl AlertEngine {
+    /// Create an engine with no alerts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an alert.
+    pub fn add(&self, alert: Alert) -> AlertId {
+        let id = AlertId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.entries().push(Entry::new(id, alert));
+        id
+    }
+
+    /// Remove an alert. Returns false if it was not registered.
+    pub fn remove(&self, id: AlertId) -> bool {
+        let mut entries = self.entries();
+        let len = entries.len();
+        entries.retain(|entry| entry.id != id);
+        entries.len() != len
+    }
+
+    /// Get the registered alerts.
+    pub fn alerts(&self) -> Vec<(AlertId, Alert)> {
+        self.entries()
+            .iter()
+            .map(|entry| (entry.id, entry.alert.clone()))
+            .collect()
+    }
+
+    /// Returns true if the alert is registered and ready to trigger.
+    pub fn is_armed(&self, id: AlertId) -> bool {
+        self.entries()
+            .iter()
+            .any(|entry| entry.id == id && entry.armed)
+    }
+
+    /// Call `callback` with every triggered alert.
+    pub fn on_trigger(&self, callback: impl Fn(&AlertEvent) + Send + Sync + 'static) {
+        self.callbacks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Box::new(callback));
+    }
+
+    /// Get the products with alerts, sorted.
+    pub fn product_ids(&self) -> Vec<String> {
+        let mut product_ids: Vec<String> = self
+            .entries()
+            .iter()
+            .map(|entry| entry.alert.product_id.clone())
+            .collect();
+        product_ids.sort();
+        product_ids.dedup();
+        product_ids
+    }
+
+    /// Get the `ticker` channel for the products with alerts.
+    ///
+    /// Returns `None` if there are no alerts.
+    pub fn channel(&self) -> Option<Channel> {
+        let product_ids = self.product_ids();
+        (!product_ids.is_empty()).then_some(Channel::Ticker { product_ids })
+    }
+
+    /// Get the `ticker_batch` channel for the products with alerts.
+    ///
+    /// Batched tickers arrive every few seconds instead of on every trade,
+    /// which is enough for most alerts. Returns `None` if there are no alerts.
+    pub fn batch_channel(&self) -> Option<Channel> {
+        let product_ids = self.product_ids();
+        (!product_ids.is_empty()).then_some(Channel::TickerBatch { product_ids })
+    }
+
+    /// Check the alerts against a price.
+    pub fn update(&self, product_id: &str, price: Decimal) -> Vec<AlertEvent> {
+        let events = self.evaluate(product_id, price, Instant::now());
+        self.notify(&events);
+        events
+    }
+
+    /// Check the alerts against the ticker updates in a WebSocket message.
+    ///
+    /// Messages from other channels, and tickers with unparseable prices,
+    /// are ignored.
+    pub fn observe(&self, message: &Message) -> Vec<AlertEvent> {
+        let Events::Ticker(events) = &message.events else {
+            return Vec::new();
+        };
+
+        let now = Instant::now();
+        let triggered: Vec<AlertEvent> = events
+            .iter()
+            .flat_map(|e| &e.tickers)
+            .filter_map(|ticker| {
+                let price = ticker.price.parse::<Decimal>().ok()?;
+                Some(self.evaluate(&ticker.product_id, price, now))
+            })
+            .flatten()
+            .collect();
+        self.notify(&triggered);
+        triggered
+    }
+
+    /// Check every message from a stream until it ends.
+    ///
+    /// Subscribe the stream to [`channel`](Self::channel) or
+    /// [`batch_channel`](Self::batch_channel). Triggered alerts are passed to
+    /// the callbacks. Returns the first stream error.
+    pub async fn run<S>(&self, mut stream: S) -> Result<()>
+    where
+        S: Stream<Item = Result<Message>> + Unpin,
+    {
+        while let Some(message) = stream.next().await {
+            self.observe(&message?);
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, product_id: &str, price: Decimal, now: Instant) -> Vec<AlertEvent> {
+        let mut entries = self.entries();
+        let mut events = Vec::new();
+        entries.retain_mut(|entry| {
+            if entry.alert.product_id != product_id {
+                return true;
+            }
+            match entry.evaluate(price, now) {
+                Some(event) => {
+                    events.push(event);
+                    !entry.alert.once
+                }
+                None => true,
+            }
+        });
+        events
+    }
+
+    fn notify(&self, events: &[AlertEvent]) {
+        if events.is_empty() {
+            return;
+        }
+        let callbacks = self.callbacks.read().unwrap_or_else(|e| e.into_inner());
+        for event in events {
+            tracing::debug!(id = %event.id, product_id = %event.product_id, price = %event.price, "alert triggered");
+            for callback in callbacks.iter() {
+                callback(event);
+            }
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, Vec<Entry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn prices(engine: &AlertEngine, product_id: &str, ticks: &[(u64, i64)]) -> Vec<Decimal> {
+        let start = Instant::now();
+        ticks
+            .iter()
+            .flat_map(|(secs, price)| {
+                engine.evaluate(
+                    product_id,
+                    Decimal::from(*price),
+                    start + Duration::from_secs(*secs),
+                )
+            })
+            .map(|event| event.price)
+            .collect()
+    }
+
+    #[test]
+    fn test_threshold_hysteresis() {
+        let engine = AlertEngine::new();
+        let id = engine.add(
+            Alert::new("BTC-USD", Condition::Above(Decimal::from(100)))
+                .hysteresis(Decimal::from(5)),
+        );
+
+        let triggered = prices(
+            &engine,
+            "BTC-USD",
+            &[(0, 99), (1, 101), (2, 98), (3, 102), (4, 94), (5, 100)],
+        );
+        assert_eq!(triggered, [Decimal::from(101), Decimal::from(100)]);
+        assert!(!engine.is_armed(id));
+
+        // Other products are not checked.
+        assert!(prices(&engine, "ETH-USD", &[(0, 94), (1, 200)]).is_empty());
+    }
+
+    #[test]
+    fn test_cooldown_and_once() {
+        let engine = AlertEngine::new();
+        engine.add(
+            Alert::new("BTC-USD", Condition::Below(Decimal::from(50)))
+                .cooldown(Duration::from_secs(10)),
+        );
+        let triggered = prices(&engine, "BTC-USD", &[(0, 49), (1, 51), (2, 48), (11, 47)]);
+        assert_eq!(triggered, [Decimal::from(49), Decimal::from(47)]);
+
+        let id = engine.add(Alert::new("ETH-USD", Condition::Below(Decimal::from(10))).once());
+        assert_eq!(
+            prices(&engine, "ETH-USD", &[(0, 9), (1, 11), (2, 9)]).len(),
+            1
+        );
+        assert!(!engine.remove(id));
+        assert_eq!(engine.product_ids(), ["BTC-USD"]);
+    }
+
+    #[test]
+    fn test_percent_change() {
+        let engine = AlertEngine::new();
+        engine.add(Alert::new(
+            "BTC-USD",
+            Condition::PercentChange {
+                percent: Decimal::from(10),
+                window: Some(Duration::from_secs(60)),
+            },
+        ));
+        // A slow 10% move spread over more than the window does not trigger,
+        // a fast one does and re-arms from the new price.
+        let triggered = prices(
+            &engine,
+            "BTC-USD",
+            &[(0, 100), (50, 105), (100, 110), (110, 121), (120, 108)],
+        );
+        assert_eq!(triggered, [Decimal::from(121), Decimal::from(108)]);
+    }
+
+    #[test]
+    fn test_observe_calls_back() {
+        let engine = AlertEngine::new();
+        engine.add(Alert::new(
+            "BTC-USD",
+            Condition::Above(Decimal::from(50_000)),
+        ));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        engine.on_trigger(move |event| sink.lock().unwrap().push(event.product_id.clone()));
+
+        let message: Message = serde_json::from_value(serde_json::json!({
+            "channel": "ticker",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": 1,
+            "events": [{
+                "type": "update",
+                "tickers": [{
+                    "type": "ticker",
+                    "product_id": "BTC-USD",
+                    "price": "50001.50",
+                    "volume_24_h": "0",
+                    "low_24_h": "0",
+                    "high_24_h": "0",
+                    "low_52_w": "0",
+                    "high_52_w": "0",
+                    "price_percent_chg_24_h": "0"
+                }]
+            }]
+        }))
+        .unwrap();
+
+        let events = engine.observe(&message);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].price, "50001.50".parse::<Decimal>().unwrap());
+        assert_eq!(*seen.lock().unwrap(), ["BTC-USD"]);
+        assert_eq!(
+            engine.channel(),
+            Some(Channel::Ticker {
+                product_ids: vec!["BTC-USD".to_string()]
+            })
+        );
+    }
+}
@@ -0,0 +1,546 @@
+//! Audit trail of order activity.
+//!
+//! An [`AuditLog`] records every create, edit and cancel request placed
+//! through [`AuditedOrders`], and every status change the user channel
+//! reports for those orders, as [`AuditRecord`]s keyed by `client_order_id`.
+//! Records are written to a pluggable [`AuditSink`]:
+//!
+//! - [`TracingSink`] - `tracing` events with the target
+//!   `coinbase_advanced::audit`.
+//! - [`FileSink`] - one JSON object per line, appended to a file.
+//! - [`ChannelSink`] - bridges records into a `tokio::sync::mpsc` channel.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::audit::{AuditLog, FileSink};
+//! use coinbase_advanced::models::{CreateOrderRequest, OrderConfiguration, OrderSide};
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//! use coinbase_advanced::{Credentials, RestClient};
+//! use futures::StreamExt;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let credentials = Credentials::from_env()?;
+//! let rest = RestClient::builder().credentials(credentials.clone()).build()?;
+//! let ws = WebSocketClient::builder().credentials(credentials).build()?;
+//! let log = AuditLog::new(FileSink::open("orders.audit.jsonl")?);
+//!
+//! let mut stream = ws.connect().await?;
+//! ws.subscribe(&[Channel::User]).await?;
+//!
+//! rest.audited(&log)
+//!     .create(CreateOrderRequest::new(
+//!         "my-order-id",
+//!         "BTC-USD",
+//!         OrderSide::Buy,
+//!         OrderConfiguration::limit_gtc("0.01", "50000", false),
+//!     ))
+//!     .await?;
+//!
+//! while let Some(message) = stream.next().await {
+//!     log.observe(&message?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::client::RestClient;
+use crate::error::{Error, Result};
+use crate::models::{
+    CancelOrdersRequest, CancelOrdersResponse, CreateOrderRequest, CreateOrderResponse,
+    EditOrderRequest, EditOrderResponse, OrderStatus,
+};
+use crate::ws::{Events, Message};
+
+/// How a request ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// The exchange accepted the request.
+    Accepted,
+    /// The exchange rejected the request.
+    Rejected {
+        /// Why the request was rejected.
+        reason: String,
+    },
+    /// The request failed before the exchange answered.
+    Failed {
+        /// The error.
+        error: String,
+    },
+}
+
+/// Something that happened to an order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// A create request was sent.
+    Create {
+        /// The request, as sent.
+        request: serde_json::Value,
+        /// How the request ended.
+        outcome: AuditOutcome,
+    },
+    /// An edit request was sent.
+    Edit {
+        /// The request, as sent.
+        request: serde_json::Value,
+        /// How the request ended.
+        outcome: AuditOutcome,
+    },
+    /// A cancel request was sent.
+    Cancel {
+        /// How the request ended.
+        outcome: AuditOutcome,
+    },
+    /// The user channel reported a new status.
+    Status {
+        /// The new status.
+        status: String,
+        /// The status last reported, if any.
+        previous: Option<String>,
+        /// Quantity filled so far.
+        cumulative_quantity: String,
+        /// Quantity left to fill.
+        leaves_quantity: String,
+    },
+}
+
+/// A single entry of the audit trail.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditRecord {
+    /// When the record was made.
+    pub time: DateTime<Utc>,
+    /// Client order ID, if known.
+    pub client_order_id: Option<String>,
+    /// Exchange order ID, if known.
+    pub order_id: Option<String>,
+    /// Product ID, if known.
+    pub product_id: Option<String>,
+    /// What happened.
+    #[serde(flatten)]
+    pub event: AuditEvent,
+}
+
+/// A destination for audit records.
+pub trait AuditSink: Send + Sync {
+    /// Write a single record.
+    ///
+    /// Sinks cannot fail the order flow; report write errors some other way.
+    fn record(&self, record: &AuditRecord);
+}
+
+/// Sink that emits each record as a `tracing` event.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingSink;
+
+impl AuditSink for TracingSink {
+    fn record(&self, record: &AuditRecord) {
+        let event = serde_json::to_string(&record.event).unwrap_or_default();
+        tracing::info!(
+            target: "coinbase_advanced::audit",
+            client_order_id = record.client_order_id.as_deref(),
+            order_id = record.order_id.as_deref(),
+            product_id = record.product_id.as_deref(),
+            event = %event,
+            "order audit"
+        );
+    }
+}
+
+/// Sink that appends each record to a file as a line of JSON.
+#[derive(Debug)]
+pub struct FileSink {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl FileSink {
+    /// Open a file for appending, creating it if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+}
+
+impl AuditSink for FileSink {
+    fn record(&self, record: &AuditRecord) {
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let written = serde_json::to_writer(&mut *writer, record)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writer.write_all(b"\n"))
+            .and_then(|_| writer.flush());
+        if let Err(e) = written {
+            tracing::warn!(error = %e, "failed to write audit record");
+        }
+    }
+}
+
+/// Sink that bridges records into a `tokio::sync::mpsc` channel.
+#[derive(Debug, Clone)]
+pub struct ChannelSink {
+    sender: mpsc::UnboundedSender<AuditRecord>,
+}
+
+impl ChannelSink {
+    /// Create a sink from an existing sender.
+    pub fn new(sender: mpsc::UnboundedSender<AuditRecord>) -> Self {
+        Self { sender }
+    }
+
+    /// Create a sink along with the receiving end of a new channel.
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<AuditRecord>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (Self::new(sender), receiver)
+    }
+}
+
+impl AuditSink for ChannelSink {
+    fn record(&self, record: &AuditRecord) {
+        // A dropped receiver means nobody is listening any more.
+        let _ = self.sender.send(record.clone());
+    }
+}
+
+/// What the log knows about an order.
+#[derive(Debug, Default)]
+struct TrackedOrder {
+    client_order_id: Option<String>,
+    product_id: Option<String>,
+    status: Option<String>,
+}
+
+/// Records order activity to an [`AuditSink`].
+///
+/// The log remembers the client order ID and product of each order it has
+/// seen, so records for edits, cancels and status changes, which only carry
+/// the exchange order ID, are correlated with the original request.
+pub struct AuditLog {
+    sink: Box<dyn AuditSink>,
+    orders: Mutex<HashMap<String, TrackedOrder>>,
+}
+
+impl AuditLog {
+    /// Create a log writing to the given sink.
+    pub fn new(sink: impl AuditSink + 'static) -> Self {
+        Self {
+            sink: Box::new(sink),
+            orders: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Write a record to the sink.
+    ///
+    /// Missing client order and product IDs are filled in from earlier
+    /// records for the same order.
+    pub fn record(&self, mut record: AuditRecord) {
+        if let Some(order_id) = &record.order_id {
+            let mut orders = self.orders();
+            let tracked = orders.entry(order_id.clone()).or_default();
+            if record.client_order_id.is_none() {
+                record.client_order_id = tracked.client_order_id.clone();
+            } else {
+                tracked.client_order_id = record.client_order_id.clone();
+            }
+            if record.product_id.is_none() {
+                record.product_id = tracked.product_id.clone();
+            } else {
+                tracked.product_id = record.product_id.clone();
+            }
+        }
+        self.sink.record(&record);
+    }
+
+    /// Record the status changes in a user channel message.
+    ///
+    /// An order is recorded when its status differs from the one last seen.
+    /// Orders are forgotten once they reach a terminal status. Messages from
+    /// other channels are ignored.
+    pub fn observe(&self, message: &Message) {
+        let Events::User(events) = &message.events else {
+            return;
+        };
+
+        for update in events.iter().flat_map(|e| &e.orders) {
+            let previous = {
+                let mut orders = self.orders();
+                let tracked = orders.entry(update.order_id.clone()).or_default();
+                if tracked.status.as_deref() == Some(update.status.as_str()) {
+                    continue;
+                }
+                tracked.status.replace(update.status.clone())
+            };
+
+            self.record(AuditRecord {
+                time: Utc::now(),
+                client_order_id: Some(update.client_order_id.clone()).filter(|id| !id.is_empty()),
+                order_id: Some(update.order_id.clone()),
+                product_id: Some(update.product_id.clone()),
+                event: AuditEvent::Status {
+                    status: update.status.clone(),
+                    previous,
+                    cumulative_quantity: update.cumulative_quantity.clone(),
+                    leaves_quantity: update.leaves_quantity.clone(),
+                },
+            });
+
+            if is_terminal(&update.status) {
+                self.orders().remove(&update.order_id);
+            }
+        }
+    }
+
+    fn orders(&self) -> MutexGuard<'_, HashMap<String, TrackedOrder>> {
+        self.orders.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn is_terminal(status: &str) -> bool {
+    matches!(
+        status.parse::<OrderStatus>(),
+        Ok(OrderStatus::Filled
+            | OrderStatus::Cancelled
+            | OrderStatus::Expired
+            | OrderStatus::Failed)
+    )
+}
+
+/// Order operations that are recorded to an [`AuditLog`].
+///
+/// Created with [`RestClient::audited`].
+pub struct AuditedOrders<'a> {
+    client: &'a RestClient,
+    log: &'a AuditLog,
+}
+
+impl<'a> AuditedOrders<'a> {
+    /// Get the log the operations are recorded to.
+    pub fn log(&self) -> &AuditLog {
+        self.log
+    }
+
+    /// Create an order, recording the request and its outcome.
+    pub async fn create(&self, request: CreateOrderRequest) -> Result<CreateOrderResponse> {
+        let body = to_value(&request);
+        let client_order_id = request.client_order_id.clone();
+        let product_id = request.product_id.clone();
+        let result = self.client.orders().create(request).await;
+
+        let (order_id, outcome) = match &result {
+            Ok(response) if response.success => (response.order_id.clone(), AuditOutcome::Accepted),
+            Ok(response) => (
+                response.order_id.clone(),
+                AuditOutcome::Rejected {
+                    reason: response
+                        .failure_reason
+                        .clone()
+                        .or_else(|| response.error_response.as_ref().map(|e| e.to_string()))
+                        .unwrap_or_default(),
+                },
+            ),
+            Err(e) => (None, failed(e)),
+        };
+        self.log.record(AuditRecord {
+            time: Utc::now(),
+            client_order_id: Some(client_order_id),
+            order_id,
+            product_id: Some(product_id),
+            event: AuditEvent::Create {
+                request: body,
+                outcome,
+            },
+        });
+        result
+    }
+
+    /// Edit an order, recording the request and its outcome.
+    pub async fn edit(&self, request: EditOrderRequest) -> Result<EditOrderResponse> {
+        let body = to_value(&request);
+        let order_id = request.order_id.clone();
+        let result = self.client.orders().edit(request).await;
+
+        let outcome = match &result {
+            Ok(response) if response.success => AuditOutcome::Accepted,
+            Ok(response) => AuditOutcome::Rejected {
+                reason: response
+                    .errors
+                    .iter()
+                    .flatten()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            },
+            Err(e) => failed(e),
+        };
+        self.log.record(AuditRecord {
+            time: Utc::now(),
+            client_order_id: None,
+            order_id: Some(order_id),
+            product_id: None,
+            event: AuditEvent::Edit {
+                request: body,
+                outcome,
+            },
+        });
+        result
+    }
+
+    /// Cancel orders, recording the outcome for each order.
+    pub async fn cancel(&self, request: CancelOrdersRequest) -> Result<CancelOrdersResponse> {
+        let order_ids = request.order_ids.clone();
+        let result = self.client.orders().cancel(request).await;
+
+        let outcomes: Vec<(String, AuditOutcome)> = match &result {
+            Ok(response) => response
+                .results
+                .iter()
+                .map(|r| {
+                    let outcome = if r.success {
+                        AuditOutcome::Accepted
+                    } else {
+                        AuditOutcome::Rejected {
+                            reason: r.failure_reason.clone().unwrap_or_default(),
+                        }
+                    };
+                    (r.order_id.clone(), outcome)
+                })
+                .collect(),
+            Err(e) => order_ids.into_iter().map(|id| (id, failed(e))).collect(),
+        };
+        for (order_id, outcome) in outcomes {
+            self.log.record(AuditRecord {
+                time: Utc::now(),
+                client_order_id: None,
+                order_id: Some(order_id),
+                product_id: None,
+                event: AuditEvent::Cancel { outcome },
+            });
+        }
+        result
+    }
+}
+
+fn to_value<T: Serialize>(request: &T) -> serde_json::Value {
+    serde_json::to_value(request).unwrap_or(serde_json::Value::Null)
+}
+
+fn failed(error: &Error) -> AuditOutcome {
+    AuditOutcome::Failed {
+        error: error.to_string(),
+    }
+}
+
+impl RestClient {
+    /// Place, edit and cancel orders with every request recorded to an
+    /// [`AuditLog`].
+    pub fn audited<'a>(&'a self, log: &'a AuditLog) -> AuditedOrders<'a> {
+        AuditedOrders { client: self, log }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_message(status: &str, filled: &str) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "channel": "user",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": 1,
+            "events": [{
+                "type": "update",
+                "orders": [{
+                    "order_id": "order-1",
+                    "product_id": "BTC-USD",
+                    "order_side": "BUY",
+                    "order_type": "LIMIT",
+                    "status": status,
+                    "cumulative_quantity": filled,
+                    "leaves_quantity": "1"
+                }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        let (sink, mut receiver) = ChannelSink::channel();
+        let log = AuditLog::new(sink);
+        log.record(AuditRecord {
+            time: Utc::now(),
+            client_order_id: Some("client-1".to_string()),
+            order_id: Some("order-1".to_string()),
+            product_id: Some("BTC-USD".to_string()),
+            event: AuditEvent::Create {
+                request: serde_json::Value::Null,
+                outcome: AuditOutcome::Accepted,
+            },
+        });
+
+        log.observe(&user_message("OPEN", "0"));
+        log.observe(&user_message("OPEN", "0.5"));
+        log.observe(&user_message("FILLED", "1"));
+
+        let records: Vec<AuditRecord> = std::iter::from_fn(|| receiver.try_recv().ok()).collect();
+        assert_eq!(records.len(), 3);
+        assert!(
+            records
+                .iter()
+                .all(|r| r.client_order_id.as_deref() == Some("client-1"))
+        );
+        assert_eq!(
+            records[2].event,
+            AuditEvent::Status {
+                status: "FILLED".to_string(),
+                previous: Some("OPEN".to_string()),
+                cumulative_quantity: "1".to_string(),
+                leaves_quantity: "1".to_string(),
+            }
+        );
+        assert!(log.orders().is_empty());
+    }
+
+    #[test]
+    fn test_file_sink() {
+        let path =
+            std::env::temp_dir().join(format!("coinbase-audit-{}.jsonl", std::process::id()));
+        let sink = FileSink::open(&path).unwrap();
+        let record = AuditRecord {
+            time: Utc::now(),
+            client_order_id: Some("client-1".to_string()),
+            order_id: None,
+            product_id: None,
+            event: AuditEvent::Cancel {
+                outcome: AuditOutcome::Rejected {
+                    reason: "UNKNOWN_CANCEL_ORDER".to_string(),
+                },
+            },
+        };
+        sink.record(&record);
+        sink.record(&record);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "cancel");
+        assert_eq!(lines[0]["client_order_id"], "client-1");
+        assert_eq!(lines[0]["outcome"]["result"], "rejected");
+    }
+}
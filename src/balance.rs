@@ -0,0 +1,438 @@
+//! Live account balances.
+//!
+//! [`BalanceTracker`] keeps the available and held amount of each currency up
+//! to date without polling. It is seeded from the accounts endpoint and then
+//! adjusted by the fills reported on the `user` WebSocket channel.
+//!
+//! Fills move funds between a product's base and quote currencies, net of
+//! fees. Orders that were already resting at the last
+//! [`refresh`](BalanceTracker::refresh), or that appear in the `user`
+//! channel snapshot, have their funds on hold, so their fills are taken out
+//! of the held amount; fills of newer orders are taken out of the available
+//! amount. New holds and the holds released by cancellations are only seen
+//! on the next refresh; call it periodically, or after deposits and
+//! withdrawals, to correct any drift.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::balance::BalanceTracker;
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//! use coinbase_advanced::{Credentials, RestClient};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let credentials = Credentials::from_env()?;
+//! let rest = RestClient::builder().credentials(credentials.clone()).build()?;
+//! let tracker = BalanceTracker::new(rest);
+//! tracker.refresh().await?;
+//!
+//! let ws = WebSocketClient::builder().credentials(credentials).build()?;
+//! let stream = ws.connect().await?;
+//! ws.subscribe(&[Channel::User]).await?;
+//!
+//! let runner = tracker.clone();
+//! tokio::spawn(async move { runner.run(stream).await });
+//!
+//! let mut usd = tracker.watch("USD");
+//! while usd.changed().await.is_ok() {
+//!     if let Some(balance) = usd.borrow().as_ref() {
+//!         println!("USD available {} hold {}", balance.available, balance.hold);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures::{Stream, StreamExt};
+use rust_decimal::Decimal;
+use tokio::sync::watch;
+
+use crate::client::RestClient;
+use crate::error::Result;
+use crate::models::{ListAccountsParams, OrderSide, OrderStatus, parse_decimal_field};
+use crate::ws::{EventType, Events, Message, TypedOrderUpdate};
+
+/// Balance of a single currency, summed over its accounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrencyBalance {
+    /// The currency code (e.g., "BTC").
+    pub currency: String,
+    /// Amount available for trading.
+    pub available: Decimal,
+    /// Amount on hold.
+    pub hold: Decimal,
+}
+
+impl CurrencyBalance {
+    pub(crate) fn empty(currency: &str) -> Self {
+        Self {
+            currency: currency.to_string(),
+            available: Decimal::ZERO,
+            hold: Decimal::ZERO,
+        }
+    }
+
+    /// Available plus held amount.
+    pub fn total(&self) -> Decimal {
+        self.available + self.hold
+    }
+}
+
+/// How much of an order had been filled at its last update.
+#[derive(Debug, Clone, Copy, Default)]
+struct FillProgress {
+    quantity: Decimal,
+    value: Decimal,
+    fees: Decimal,
+    /// Whether the order's funds are part of the tracked hold.
+    held: bool,
+}
+
+#[derive(Default)]
+struct State {
+    balances: HashMap<String, watch::Sender<Option<CurrencyBalance>>>,
+    fills: HashMap<String, FillProgress>,
+}
+
+impl State {
+    fn sender(&mut self, currency: &str) -> &watch::Sender<Option<CurrencyBalance>> {
+        self.balances
+            .entry(currency.to_string())
+            .or_insert_with(|| watch::Sender::new(None))
+    }
+
+    fn adjust(&mut self, currency: &str, available: Decimal, hold: Decimal) {
+        if available.is_zero() && hold.is_zero() {
+            return;
+        }
+        self.sender(currency).send_modify(|balance| {
+            let balance = balance.get_or_insert_with(|| CurrencyBalance::empty(currency));
+            balance.available += available;
+            balance.hold += hold;
+        });
+    }
+}
+
+/// Up-to-date balances per currency, driven by user channel fills.
+///
+/// Cloning a tracker is cheap; clones share the same balances.
+#[derive(Clone)]
+pub struct BalanceTracker {
+    client: RestClient,
+    state: Arc<Mutex<State>>,
+}
+
+impl BalanceTracker {
+    /// Create an empty tracker that seeds from the given REST client.
+    pub fn new(client: RestClient) -> Self {
+        Self {
+            client,
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// Load every account and replace the tracked balances.
+    ///
+    /// The holds of orders open at this point are now part of the tracked
+    /// hold, so their later fills are taken out of it. Watchers are notified
+    /// of every currency whose balance changed.
+    pub async fn refresh(&self) -> Result<()> {
+        let mut totals: HashMap<String, CurrencyBalance> = HashMap::new();
+        let mut params = ListAccountsParams::new();
+        loop {
+            let response = self.client.accounts().list(params.clone()).await?;
+            for account in &response.accounts {
+                let balance = totals
+                    .entry(account.currency.clone())
+                    .or_insert_with(|| CurrencyBalance::empty(&account.currency));
+                balance.available +=
+                    parse_decimal_field("available_balance", &account.available_balance.value)?;
+                balance.hold += parse_decimal_field("hold", &account.hold.value)?;
+            }
+            match response
+                .cursor
+                .filter(|c| response.has_next && !c.is_empty())
+            {
+                Some(cursor) => params = params.cursor(cursor),
+                None => break,
+            }
+        }
+
+        let mut state = self.lock();
+        for (currency, sender) in &state.balances {
+            if !totals.contains_key(currency) {
+                sender.send_if_modified(|b| b.take().is_some());
+            }
+        }
+        for (currency, balance) in totals {
+            state.sender(&currency).send_if_modified(|current| {
+                let changed = current.as_ref() != Some(&balance);
+                *current = Some(balance);
+                changed
+            });
+        }
+        for progress in state.fills.values_mut() {
+            progress.held = true;
+        }
+        Ok(())
+    }
+
+    /// Get the current balance of a currency.
+    pub fn get(&self, currency: &str) -> Option<CurrencyBalance> {
+        self.lock()
+            .balances
+            .get(currency)
+            .and_then(|sender| sender.borrow().clone())
+    }
+
+    /// Get the current balance of every tracked currency.
+    pub fn balances(&self) -> Vec<CurrencyBalance> {
+        self.lock()
+            .balances
+            .values()
+            .filter_map(|sender| sender.borrow().clone())
+            .collect()
+    }
+
+    /// Watch a currency for balance changes.
+    ///
+    /// The receiver holds `None` until the currency is seen in an account or
+    /// a fill.
+    pub fn watch(&self, currency: &str) -> watch::Receiver<Option<CurrencyBalance>> {
+        self.lock().sender(currency).subscribe()
+    }
+
+    /// Apply the fills in a WebSocket message.
+    ///
+    /// The snapshot sent when subscribing to the `user` channel only records
+    /// how far each open order has been filled, since those fills are already
+    /// reflected in the seeded balances. Later updates move the newly filled
+    /// amount between the product's base and quote currencies. Messages from
+    /// other channels are ignored.
+    ///
+    /// Every update is parsed before any is applied, so a message with an
+    /// invalid update returns an error and leaves the balances unchanged.
+    pub fn observe(&self, message: &Message) -> Result<()> {
+        let Events::User(events) = &message.events else {
+            return Ok(());
+        };
+
+        let updates = events
+            .iter()
+            .flat_map(|event| {
+                let snapshot = event.r#type == EventType::Snapshot;
+                event
+                    .orders
+                    .iter()
+                    .map(move |update| Ok((snapshot, update.typed()?)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut state = self.lock();
+        for (snapshot, update) in updates {
+            let previous = state.fills.get(&update.order_id).copied();
+            let progress = FillProgress {
+                quantity: update.cumulative_quantity.unwrap_or_default(),
+                value: update.filled_value.unwrap_or_default(),
+                fees: update.total_fees.unwrap_or_default(),
+                // Open orders in the snapshot are covered by the seeded hold.
+                held: snapshot || previous.is_some_and(|p| p.held),
+            };
+            if is_terminal(update.status) {
+                state.fills.remove(&update.order_id);
+            } else {
+                state.fills.insert(update.order_id.clone(), progress);
+            }
+            if !snapshot {
+                apply_fill(&mut state, &update, previous.unwrap_or_default(), progress);
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply every message from a stream until it ends.
+    ///
+    /// Subscribe the stream to the `user` channel. Returns the first stream
+    /// or parse error.
+    pub async fn run<S>(&self, mut stream: S) -> Result<()>
+    where
+        S: Stream<Item = Result<Message>> + Unpin,
+    {
+        while let Some(message) = stream.next().await {
+            self.observe(&message?)?;
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Move the amount filled since `previous` between base and quote balances.
+///
+/// The amount spent comes out of the hold if the order's funds are held,
+/// otherwise out of the available balance.
+fn apply_fill(
+    state: &mut State,
+    update: &TypedOrderUpdate,
+    previous: FillProgress,
+    current: FillProgress,
+) {
+    let Some((base, quote)) = update.product_id.split_once('-') else {
+        return;
+    };
+    let quantity = current.quantity - previous.quantity;
+    let value = current.value - previous.value;
+    let fees = current.fees - previous.fees;
+    if quantity <= Decimal::ZERO {
+        return;
+    }
+
+    let spend = |amount: Decimal| {
+        if current.held {
+            (Decimal::ZERO, -amount)
+        } else {
+            (-amount, Decimal::ZERO)
+        }
+    };
+    match update.order_side {
+        Some(OrderSide::Buy) => {
+            state.adjust(base, quantity, Decimal::ZERO);
+            let (available, hold) = spend(value + fees);
+            state.adjust(quote, available, hold);
+        }
+        Some(OrderSide::Sell) => {
+            let (available, hold) = spend(quantity);
+            state.adjust(base, available, hold);
+            state.adjust(quote, value - fees, Decimal::ZERO);
+        }
+        None => {}
+    }
+}
+
+fn is_terminal(status: OrderStatus) -> bool {
+    matches!(
+        status,
+        OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Expired | OrderStatus::Failed
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_message(
+        r#type: &str,
+        order_id: &str,
+        status: &str,
+        cumulative: &str,
+        value: &str,
+    ) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "channel": "user",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": 1,
+            "events": [{
+                "type": r#type,
+                "orders": [{
+                    "order_id": order_id,
+                    "product_id": "BTC-USD",
+                    "order_side": "BUY",
+                    "order_type": "LIMIT",
+                    "status": status,
+                    "cumulative_quantity": cumulative,
+                    "filled_value": value,
+                    "total_fees": "0"
+                }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn seed(tracker: &BalanceTracker, currency: &str, available: &str, hold: &str) {
+        tracker
+            .lock()
+            .sender(currency)
+            .send_replace(Some(CurrencyBalance {
+                currency: currency.to_string(),
+                available: available.parse().unwrap(),
+                hold: hold.parse().unwrap(),
+            }));
+    }
+
+    fn dec(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_observe_fills() {
+        let tracker = BalanceTracker::new(RestClient::builder().build().unwrap());
+        // A resting buy of 0.5 BTC at 50000, 0.1 filled: 20000 USD on hold.
+        seed(&tracker, "USD", "1000", "20000");
+        let mut usd = tracker.watch("USD");
+
+        // The snapshot only sets the baseline.
+        tracker
+            .observe(&user_message("snapshot", "order-1", "OPEN", "0.1", "5000"))
+            .unwrap();
+        assert!(tracker.get("BTC").is_none());
+
+        // Fills of a resting order are paid from the hold.
+        tracker
+            .observe(&user_message("update", "order-1", "OPEN", "0.3", "15000"))
+            .unwrap();
+        assert_eq!(tracker.get("BTC").unwrap().available, dec("0.2"));
+        assert!(usd.has_changed().unwrap());
+        let balance = usd.borrow_and_update().clone().unwrap();
+        assert_eq!(balance.available, dec("1000"));
+        assert_eq!(balance.hold, dec("10000"));
+
+        tracker
+            .observe(&user_message("update", "order-1", "FILLED", "0.5", "25000"))
+            .unwrap();
+        assert_eq!(tracker.get("BTC").unwrap().available, dec("0.4"));
+        assert_eq!(tracker.get("USD").unwrap().hold, Decimal::ZERO);
+        assert_eq!(tracker.get("USD").unwrap().available, dec("1000"));
+        assert!(tracker.lock().fills.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_observe_new_order_fills() {
+        let tracker = BalanceTracker::new(RestClient::builder().build().unwrap());
+        seed(&tracker, "USD", "10000", "0");
+
+        // Orders placed after seeding are paid from the available balance.
+        tracker
+            .observe(&user_message("update", "order-2", "FILLED", "0.1", "5000"))
+            .unwrap();
+        assert_eq!(tracker.get("USD").unwrap().available, dec("5000"));
+        assert_eq!(tracker.get("USD").unwrap().hold, Decimal::ZERO);
+        assert_eq!(tracker.get("BTC").unwrap().available, dec("0.1"));
+    }
+
+    #[tokio::test]
+    async fn test_observe_invalid_update() {
+        let tracker = BalanceTracker::new(RestClient::builder().build().unwrap());
+        seed(&tracker, "USD", "10000", "0");
+
+        let mut message = user_message("update", "order-1", "FILLED", "0.1", "5000");
+        let Events::User(events) = &mut message.events else {
+            unreachable!()
+        };
+        let mut invalid = events[0].orders[0].clone();
+        invalid.order_id = "order-2".to_string();
+        invalid.cumulative_quantity = "not a number".to_string();
+        events[0].orders.push(invalid);
+
+        // Nothing is applied when any update is invalid.
+        assert!(tracker.observe(&message).is_err());
+        assert_eq!(tracker.get("USD").unwrap().available, dec("10000"));
+        assert!(tracker.get("BTC").is_none());
+        assert!(tracker.lock().fills.is_empty());
+    }
+}
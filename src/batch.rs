@@ -0,0 +1,171 @@
+//! Running many independent requests with bounded concurrency.
+//!
+//! [`Batcher`] drives a set of request futures with at most a fixed number in
+//! flight and returns every result in input order, so one failure does not
+//! stop the rest. The futures still go through the client they were created
+//! from, so its rate limiter paces them as usual.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::batch::Batcher;
+//! use coinbase_advanced::models::{GetCandlesParams, Granularity};
+//! use coinbase_advanced::{Credentials, RestClient};
+//!
+//! # async fn example(product_ids: Vec<String>) -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder()
+//!     .credentials(Credentials::from_env()?)
+//!     .rate_limiting(true)
+//!     .build()?;
+//!
+//! let results = Batcher::new(8)
+//!     .map(product_ids, |id| {
+//!         let client = &client;
+//!         async move {
+//!             let params =
+//!                 GetCandlesParams::new(id, "1704067200", "1704153600", Granularity::OneHour);
+//!             client.products().get_candles(params).await
+//!         }
+//!     })
+//!     .await;
+//!
+//! for (id, result) in results {
+//!     match result {
+//!         Ok(candles) => println!("{}: {} candles", id, candles.len()),
+//!         Err(e) => eprintln!("{}: {}", id, e),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use futures::{StreamExt, stream};
+
+use crate::client::RestClient;
+use crate::error::Result;
+
+/// Number of requests in flight when using [`RestClient::batch`].
+pub const DEFAULT_CONCURRENCY: usize = 10;
+
+/// Runs independent requests with bounded concurrency.
+#[derive(Debug, Clone, Copy)]
+pub struct Batcher {
+    concurrency: usize,
+}
+
+impl Default for Batcher {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONCURRENCY)
+    }
+}
+
+impl Batcher {
+    /// Create a batcher that keeps at most `concurrency` requests in flight.
+    ///
+    /// A concurrency of zero is treated as one.
+    pub fn new(concurrency: usize) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+        }
+    }
+
+    /// Get the maximum number of requests in flight.
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Run every future and collect the results in input order.
+    pub async fn run<I, F, T>(&self, futures: I) -> Vec<Result<T>>
+    where
+        I: IntoIterator<Item = F>,
+        F: Future<Output = Result<T>>,
+    {
+        stream::iter(futures)
+            .buffered(self.concurrency)
+            .collect()
+            .await
+    }
+
+    /// Run `f` for every item and pair each item with its result.
+    ///
+    /// Results are returned in input order.
+    pub async fn map<I, K, F, Fut, T>(&self, items: I, mut f: F) -> Vec<(K, Result<T>)>
+    where
+        I: IntoIterator<Item = K>,
+        K: Clone,
+        F: FnMut(K) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        stream::iter(items)
+            .map(|item| {
+                let future = f(item.clone());
+                async move { (item, future.await) }
+            })
+            .buffered(self.concurrency)
+            .collect()
+            .await
+    }
+}
+
+impl RestClient {
+    /// Run independent requests with [`DEFAULT_CONCURRENCY`] in flight.
+    ///
+    /// Results are returned in input order. Use [`Batcher`] to choose the
+    /// concurrency.
+    pub async fn batch<I, F, T>(&self, futures: I) -> Vec<Result<T>>
+    where
+        I: IntoIterator<Item = F>,
+        F: Future<Output = Result<T>>,
+    {
+        Batcher::default().run(futures).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::error::Error;
+
+    #[tokio::test]
+    async fn test_batch_bounded_and_ordered() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let results = Batcher::new(3)
+            .map(0..10u64, |n| {
+                let (in_flight, peak) = (&in_flight, &peak);
+                async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10 - n)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    if n == 4 {
+                        Err(Error::request("boom"))
+                    } else {
+                        Ok(n * 2)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(results.len(), 10);
+        for (n, result) in &results {
+            match n {
+                4 => assert!(result.is_err()),
+                n => assert_eq!(*result.as_ref().unwrap(), n * 2),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_batch() {
+        let client = RestClient::builder().build().unwrap();
+        let results = client.batch((1..=3).map(|n| async move { Ok(n) })).await;
+        let values: Vec<i32> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,487 @@
+//! Managed Level 2 order books and microstructure analytics.
+//!
+//! [`OrderBook`] keeps the full book of one product up to date from the
+//! `level2` channel. [`BookAnalytics`] wraps a book and derives the figures
+//! market-making strategies usually need: volume imbalance, the weighted
+//! mid-price, book pressure near the mid, and spread and depth averaged over
+//! a rolling window.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::book::BookAnalytics;
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//! use futures::StreamExt;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let ws = WebSocketClient::builder().build()?;
+//! let mut stream = ws.connect().await?;
+//! ws.subscribe(&[Channel::Level2 {
+//!     product_ids: vec!["BTC-USD".to_string()],
+//! }]).await?;
+//!
+//! let mut analytics = BookAnalytics::new("BTC-USD");
+//! while let Some(message) = stream.next().await {
+//!     if analytics.observe(&message?)? {
+//!         println!(
+//!             "imbalance {:?} microprice {:?}",
+//!             analytics.imbalance(5),
+//!             analytics.weighted_mid()
+//!         );
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+
+use crate::error::Result;
+use crate::models::parse_decimal_field;
+use crate::runtime::Instant;
+use crate::ws::{EventType, Events, Level2Event, Level2Side, Message};
+
+/// Default window over which liquidity metrics are averaged.
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Default number of levels per side counted as depth in liquidity metrics.
+pub const DEFAULT_DEPTH_LEVELS: usize = 10;
+
+/// A price level of an order book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Level {
+    /// Price of the level.
+    pub price: Decimal,
+    /// Quantity resting at the level.
+    pub quantity: Decimal,
+}
+
+/// The Level 2 order book of a single product.
+#[derive(Debug, Clone)]
+pub struct OrderBook {
+    product_id: String,
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+    initialized: bool,
+}
+
+impl OrderBook {
+    /// Create an empty book for a product.
+    pub fn new(product_id: impl Into<String>) -> Self {
+        Self {
+            product_id: product_id.into(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            initialized: false,
+        }
+    }
+
+    /// Get the product ID.
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+
+    /// Returns true once a snapshot has been applied.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// Apply the Level 2 events in a WebSocket message.
+    ///
+    /// Events for other products and messages from other channels are
+    /// ignored. Returns true if the book changed.
+    pub fn observe(&mut self, message: &Message) -> Result<bool> {
+        let Events::Level2(events) = &message.events else {
+            return Ok(false);
+        };
+        let mut changed = false;
+        for event in events {
+            changed |= self.apply(event)?;
+        }
+        Ok(changed)
+    }
+
+    /// Apply a single Level 2 event.
+    ///
+    /// A snapshot replaces the whole book; an update sets the quantity of
+    /// each level, removing levels whose quantity is zero. Updates received
+    /// before the first snapshot are ignored. Returns true if the book
+    /// changed.
+    pub fn apply(&mut self, event: &Level2Event) -> Result<bool> {
+        if event.product_id != self.product_id {
+            return Ok(false);
+        }
+        match event.r#type {
+            EventType::Snapshot => {
+                self.bids.clear();
+                self.asks.clear();
+                self.initialized = true;
+            }
+            EventType::Update if self.initialized => {}
+            EventType::Update => return Ok(false),
+        }
+
+        for update in &event.updates {
+            let price = parse_decimal_field("price_level", &update.price_level)?;
+            let quantity = parse_decimal_field("new_quantity", &update.new_quantity)?;
+            let side = match update.side {
+                Level2Side::Bid => &mut self.bids,
+                Level2Side::Ask => &mut self.asks,
+            };
+            if quantity.is_zero() {
+                side.remove(&price);
+            } else {
+                side.insert(price, quantity);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Iterate over the bids, best (highest) first.
+    pub fn bids(&self) -> impl Iterator<Item = Level> + '_ {
+        self.bids
+            .iter()
+            .rev()
+            .map(|(&price, &quantity)| Level { price, quantity })
+    }
+
+    /// Iterate over the asks, best (lowest) first.
+    pub fn asks(&self) -> impl Iterator<Item = Level> + '_ {
+        self.asks
+            .iter()
+            .map(|(&price, &quantity)| Level { price, quantity })
+    }
+
+    /// Get the best bid.
+    pub fn best_bid(&self) -> Option<Level> {
+        self.bids().next()
+    }
+
+    /// Get the best ask.
+    pub fn best_ask(&self) -> Option<Level> {
+        self.asks().next()
+    }
+
+    /// Get the midpoint between the best bid and ask.
+    pub fn mid(&self) -> Option<Decimal> {
+        Some((self.best_bid()?.price + self.best_ask()?.price) / Decimal::TWO)
+    }
+
+    /// Get the difference between the best ask and bid.
+    pub fn spread(&self) -> Option<Decimal> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    /// Total bid quantity over the best `levels` levels.
+    pub fn bid_depth(&self, levels: usize) -> Decimal {
+        self.bids().take(levels).map(|l| l.quantity).sum()
+    }
+
+    /// Total ask quantity over the best `levels` levels.
+    pub fn ask_depth(&self, levels: usize) -> Decimal {
+        self.asks().take(levels).map(|l| l.quantity).sum()
+    }
+}
+
+/// Spread and depth averaged over the rolling window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiquidityStats {
+    /// Number of book states the averages cover.
+    pub samples: usize,
+    /// Average spread.
+    pub average_spread: Decimal,
+    /// Smallest spread seen.
+    pub min_spread: Decimal,
+    /// Largest spread seen.
+    pub max_spread: Decimal,
+    /// Average bid quantity over the depth levels.
+    pub average_bid_depth: Decimal,
+    /// Average ask quantity over the depth levels.
+    pub average_ask_depth: Decimal,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    at: Instant,
+    spread: Decimal,
+    bid_depth: Decimal,
+    ask_depth: Decimal,
+}
+
+/// Microstructure analytics over a managed [`OrderBook`].
+///
+/// Point-in-time figures are computed from the current book. Liquidity
+/// metrics are sampled after every event that changes the book and
+/// averaged over a rolling window.
+#[derive(Debug, Clone)]
+pub struct BookAnalytics {
+    book: OrderBook,
+    window: Duration,
+    depth_levels: usize,
+    samples: VecDeque<Sample>,
+}
+
+impl BookAnalytics {
+    /// Create analytics over an empty book for a product.
+    pub fn new(product_id: impl Into<String>) -> Self {
+        Self {
+            book: OrderBook::new(product_id),
+            window: DEFAULT_WINDOW,
+            depth_levels: DEFAULT_DEPTH_LEVELS,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Set the window over which liquidity metrics are averaged.
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Set how many levels per side count as depth in liquidity metrics.
+    pub fn with_depth_levels(mut self, levels: usize) -> Self {
+        self.depth_levels = levels;
+        self
+    }
+
+    /// Get the underlying book.
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    /// Apply the Level 2 events in a WebSocket message.
+    ///
+    /// Returns true if the book changed.
+    pub fn observe(&mut self, message: &Message) -> Result<bool> {
+        let changed = self.book.observe(message)?;
+        if changed {
+            self.sample();
+        }
+        Ok(changed)
+    }
+
+    /// Apply a single Level 2 event.
+    ///
+    /// Returns true if the book changed.
+    pub fn apply(&mut self, event: &Level2Event) -> Result<bool> {
+        let changed = self.book.apply(event)?;
+        if changed {
+            self.sample();
+        }
+        Ok(changed)
+    }
+
+    /// Volume imbalance over the best `levels` levels of each side.
+    ///
+    /// Ranges from -1 (only asks) to 1 (only bids). `None` if both sides
+    /// are empty.
+    pub fn imbalance(&self, levels: usize) -> Option<Decimal> {
+        let bids = self.book.bid_depth(levels);
+        let asks = self.book.ask_depth(levels);
+        (bids - asks).checked_div(bids + asks)
+    }
+
+    /// Mid-price weighted by the quantities at the top of the book.
+    ///
+    /// Also known as the microprice: it leans towards the ask when the bid
+    /// is heavier, and towards the bid when the ask is heavier.
+    pub fn weighted_mid(&self) -> Option<Decimal> {
+        let bid = self.book.best_bid()?;
+        let ask = self.book.best_ask()?;
+        (bid.price * ask.quantity + ask.price * bid.quantity)
+            .checked_div(bid.quantity + ask.quantity)
+    }
+
+    /// Net notional resting within `band` (a fraction of the mid-price) of
+    /// the mid, bids minus asks, in the quote currency.
+    ///
+    /// Positive values mean more buying than selling interest near the
+    /// current price.
+    pub fn pressure(&self, band: Decimal) -> Option<Decimal> {
+        let mid = self.book.mid()?;
+        let low = mid * (Decimal::ONE - band);
+        let high = mid * (Decimal::ONE + band);
+        let bids: Decimal = self
+            .book
+            .bids()
+            .take_while(|l| l.price >= low)
+            .map(|l| l.price * l.quantity)
+            .sum();
+        let asks: Decimal = self
+            .book
+            .asks()
+            .take_while(|l| l.price <= high)
+            .map(|l| l.price * l.quantity)
+            .sum();
+        Some(bids - asks)
+    }
+
+    /// Spread and depth averaged over the rolling window.
+    ///
+    /// `None` if the book has not had both sides during the window.
+    pub fn liquidity(&self) -> Option<LiquidityStats> {
+        let cutoff = Instant::now().checked_sub(self.window);
+        let samples: Vec<&Sample> = self
+            .samples
+            .iter()
+            .filter(|s| cutoff.is_none_or(|cutoff| s.at >= cutoff))
+            .collect();
+        let count = Decimal::from(samples.len());
+        let first = samples.first()?;
+
+        let mut stats = LiquidityStats {
+            samples: samples.len(),
+            average_spread: Decimal::ZERO,
+            min_spread: first.spread,
+            max_spread: first.spread,
+            average_bid_depth: Decimal::ZERO,
+            average_ask_depth: Decimal::ZERO,
+        };
+        for sample in &samples {
+            stats.average_spread += sample.spread;
+            stats.min_spread = stats.min_spread.min(sample.spread);
+            stats.max_spread = stats.max_spread.max(sample.spread);
+            stats.average_bid_depth += sample.bid_depth;
+            stats.average_ask_depth += sample.ask_depth;
+        }
+        stats.average_spread /= count;
+        stats.average_bid_depth /= count;
+        stats.average_ask_depth /= count;
+        Some(stats)
+    }
+
+    /// Record the current book state and drop samples outside the window.
+    fn sample(&mut self) {
+        let now = Instant::now();
+        if let Some(cutoff) = now.checked_sub(self.window) {
+            while self.samples.front().is_some_and(|s| s.at < cutoff) {
+                self.samples.pop_front();
+            }
+        }
+        if let Some(spread) = self.book.spread() {
+            self.samples.push_back(Sample {
+                at: now,
+                spread,
+                bid_depth: self.book.bid_depth(self.depth_levels),
+                ask_depth: self.book.ask_depth(self.depth_levels),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(r#type: &str, levels: &[(&str, &str, &str)]) -> Level2Event {
+        let updates: Vec<_> = levels
+            .iter()
+            .map(|(side, price, quantity)| {
+                serde_json::json!({
+                    "side": side,
+                    "event_time": "2024-01-01T00:00:00Z",
+                    "price_level": price,
+                    "new_quantity": quantity
+                })
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "type": r#type,
+            "product_id": "BTC-USD",
+            "updates": updates
+        }))
+        .unwrap()
+    }
+
+    fn analytics() -> BookAnalytics {
+        analytics_with(BookAnalytics::new("BTC-USD"))
+    }
+
+    fn analytics_with(mut analytics: BookAnalytics) -> BookAnalytics {
+        analytics
+            .apply(&event(
+                "snapshot",
+                &[
+                    ("bid", "99", "3"),
+                    ("bid", "98", "2"),
+                    ("offer", "101", "1"),
+                    ("offer", "102", "4"),
+                ],
+            ))
+            .unwrap();
+        analytics
+    }
+
+    #[test]
+    fn test_order_book() {
+        let mut book = OrderBook::new("BTC-USD");
+        assert!(!book.apply(&event("update", &[("bid", "99", "1")])).unwrap());
+        assert!(book.best_bid().is_none());
+
+        book.apply(&event(
+            "snapshot",
+            &[("bid", "99", "1"), ("offer", "101", "2")],
+        ))
+        .unwrap();
+        book.apply(&event("update", &[("bid", "100", "1"), ("bid", "99", "0")]))
+            .unwrap();
+        assert_eq!(book.best_bid().unwrap().price, Decimal::from(100));
+        assert_eq!(book.bids().count(), 1);
+        assert_eq!(book.mid(), Some(Decimal::new(1005, 1)));
+        assert_eq!(book.spread(), Some(Decimal::ONE));
+
+        let mut other = event("update", &[("bid", "1", "1")]);
+        other.product_id = "ETH-USD".to_string();
+        assert!(!book.apply(&other).unwrap());
+        assert!(book.apply(&event("update", &[("bid", "x", "1")])).is_err());
+    }
+
+    #[test]
+    fn test_imbalance_and_weighted_mid() {
+        let analytics = analytics();
+        // Top level: 3 bid vs 1 ask.
+        assert_eq!(analytics.imbalance(1), Some(Decimal::new(5, 1)));
+        // Two levels: 5 bid vs 5 ask.
+        assert_eq!(analytics.imbalance(2), Some(Decimal::ZERO));
+        // (99 * 1 + 101 * 3) / 4 leans towards the ask.
+        assert_eq!(analytics.weighted_mid(), Some(Decimal::new(1005, 1)));
+        assert_eq!(BookAnalytics::new("BTC-USD").imbalance(5), None);
+    }
+
+    #[test]
+    fn test_pressure() {
+        let analytics = analytics();
+        // Within 1.5% of 100: bids at 99, asks at 101.
+        assert_eq!(
+            analytics.pressure(Decimal::new(15, 3)),
+            Some(Decimal::from(99 * 3 - 101))
+        );
+        // Within 2.5%: every level.
+        assert_eq!(
+            analytics.pressure(Decimal::new(25, 3)),
+            Some(Decimal::from(99 * 3 + 98 * 2 - 101 - 102 * 4))
+        );
+    }
+
+    #[test]
+    fn test_liquidity() {
+        let mut analytics = analytics_with(BookAnalytics::new("BTC-USD").with_depth_levels(1));
+        assert!(BookAnalytics::new("BTC-USD").liquidity().is_none());
+
+        analytics
+            .apply(&event(
+                "update",
+                &[("offer", "103", "1"), ("offer", "101", "0")],
+            ))
+            .unwrap();
+        let stats = analytics.liquidity().unwrap();
+        assert_eq!(stats.samples, 2);
+        assert_eq!(stats.min_spread, Decimal::from(2));
+        assert_eq!(stats.max_spread, Decimal::from(3));
+        assert_eq!(stats.average_spread, Decimal::new(25, 1));
+        assert_eq!(stats.average_bid_depth, Decimal::from(3));
+    }
+}
@@ -0,0 +1,242 @@
+//! Persistent caching of historical market data.
+//!
+//! A [`CacheStore`] keeps data that does not change between runs, so that
+//! restarting a process does not mean downloading it again. Set one on the
+//! client with [`RestClientBuilder::cache`](crate::RestClientBuilder::cache)
+//! and it is used by:
+//!
+//! - [`ProductsApi::get_candles_range`](crate::rest::ProductsApi::get_candles_range),
+//!   which stores every fully closed page of candles.
+//! - [`ProductCatalog`](crate::catalog::ProductCatalog), which stores the
+//!   product list and reuses it while it is younger than the catalog's TTL.
+//!
+//! [`FileCache`] stores one JSON file per entry in a directory;
+//! [`MemoryCache`] keeps entries for the life of the process. Implement
+//! [`CacheStore`] to use another backend.
+//!
+//! Requires the `cache` feature.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use coinbase_advanced::RestClient;
+//! use coinbase_advanced::cache::FileCache;
+//! use coinbase_advanced::models::Granularity;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder()
+//!     .cache(Arc::new(FileCache::new(".coinbase-cache")?))
+//!     .build()?;
+//!
+//! // Only the pages not already on disk are downloaded.
+//! let candles = client
+//!     .products()
+//!     .get_candles_range("BTC-USD", 1_704_067_200, 1_706_745_600, Granularity::OneHour)
+//!     .await?;
+//! println!("{} candles", candles.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::error::{Error, Result};
+
+/// A key-value store for cached responses.
+///
+/// Keys are short ASCII strings such as `candles/BTC-USD/1h/1704067200`.
+/// Values are opaque bytes. Stores must be safe to share between tasks.
+pub trait CacheStore: fmt::Debug + Send + Sync {
+    /// Get the value stored under `key`, if any.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Store `value` under `key`, replacing any previous value.
+    fn put(&self, key: &str, value: &[u8]) -> Result<()>;
+
+    /// Remove the value stored under `key`, if any.
+    fn remove(&self, key: &str) -> Result<()>;
+}
+
+/// Read and deserialize a cached JSON value.
+///
+/// Entries that fail to read or parse are logged and treated as missing, so
+/// a corrupt cache only costs a download.
+pub(crate) fn get_json<T: DeserializeOwned>(store: &dyn CacheStore, key: &str) -> Option<T> {
+    let bytes = match store.get(key) {
+        Ok(bytes) => bytes?,
+        Err(e) => {
+            tracing::warn!(key, error = %e, "failed to read cache entry");
+            return None;
+        }
+    };
+    match serde_json::from_slice(&bytes) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            tracing::warn!(key, error = %e, "discarding unreadable cache entry");
+            None
+        }
+    }
+}
+
+/// Serialize and store a JSON value, logging failures.
+pub(crate) fn put_json<T: Serialize>(store: &dyn CacheStore, key: &str, value: &T) {
+    let result = serde_json::to_vec(value)
+        .map_err(|e| Error::parse(format!("Failed to serialize cache entry: {}", e), None))
+        .and_then(|bytes| store.put(key, &bytes));
+    if let Err(e) = result {
+        tracing::warn!(key, error = %e, "failed to write cache entry");
+    }
+}
+
+/// A cache storing each entry as a JSON file in a directory.
+#[derive(Debug, Clone)]
+pub struct FileCache {
+    dir: PathBuf,
+}
+
+impl FileCache {
+    /// Use `dir` for the cache, creating it if needed.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Get the cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the file holding `key`.
+    fn path(&self, key: &str) -> PathBuf {
+        let name: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{}.json", name))
+    }
+}
+
+impl CacheStore for FileCache {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match std::fs::read(self.path(key)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+        // Write to a temporary file first so readers never see a partial entry.
+        let path = self.path(key);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, value)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        match std::fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A cache held in memory, for tests and short-lived processes.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Returns true if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, Vec<u8>>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl CacheStore for MemoryCache {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.entries().get(key).cloned())
+    }
+
+    fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+        self.entries().insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        self.entries().remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_cache() {
+        let dir = std::env::temp_dir().join(format!("coinbase-cache-{}", std::process::id()));
+        let cache = FileCache::new(&dir).unwrap();
+        let key = "candles/BTC-USD/1h/1704067200";
+
+        assert_eq!(cache.get(key).unwrap(), None);
+        put_json(&cache, key, &vec![1, 2, 3]);
+        assert_eq!(get_json::<Vec<u32>>(&cache, key), Some(vec![1, 2, 3]));
+        assert!(
+            cache
+                .path(key)
+                .ends_with("candles_BTC-USD_1h_1704067200.json")
+        );
+
+        cache.put(key, b"not json").unwrap();
+        assert_eq!(get_json::<Vec<u32>>(&cache, key), None);
+
+        cache.remove(key).unwrap();
+        cache.remove(key).unwrap();
+        assert_eq!(cache.get(key).unwrap(), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_memory_cache() {
+        let cache = MemoryCache::new();
+        cache.put("a", b"1").unwrap();
+        assert_eq!(cache.get("a").unwrap().as_deref(), Some(&b"1"[..]));
+        assert_eq!(cache.len(), 1);
+        cache.remove("a").unwrap();
+        assert!(cache.is_empty());
+    }
+}
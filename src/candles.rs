@@ -0,0 +1,704 @@
+//! Closed candles from the WebSocket `candles` channel.
+//!
+//! The `candles` channel sends five-minute candles and repeats the candle
+//! that is still in progress every time it changes. [`CandleStream`] wraps a
+//! message stream and yields each candle once, after it has closed, which it
+//! detects when the first update for the next candle of the same product
+//! arrives.
+//!
+//! The channel has no granularity parameter, so longer intervals are built
+//! locally: with [`CandleStream::interval`] the closed five-minute candles are
+//! merged into candles of any multiple of five minutes.
+//!
+//! Candles fetched over REST can be checked with [`CandleSeries`], which
+//! reports duplicate, out-of-order and missing candles and fills the gaps
+//! before they reach an indicator.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use coinbase_advanced::candles::CandleStream;
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//! use futures::StreamExt;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = WebSocketClient::builder().build()?;
+//! let stream = client.connect().await?;
+//! client
+//!     .subscribe(&[Channel::Candles {
+//!         product_ids: vec!["BTC-USD".to_string(), "ETH-USD".to_string()],
+//!     }])
+//!     .await?;
+//!
+//! let mut candles = CandleStream::new(stream).interval(Duration::from_secs(15 * 60))?;
+//! while let Some(candle) = candles.next().await {
+//!     let candle = candle?;
+//!     println!("{} {} close {}", candle.product_id, candle.start, candle.close);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Checking candles fetched over REST:
+//!
+//! ```no_run
+//! use coinbase_advanced::RestClient;
+//! use coinbase_advanced::candles::{CandleSeries, FillStrategy};
+//! use coinbase_advanced::models::Granularity;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder().build()?;
+//! let candles = client
+//!     .products()
+//!     .get_candles_range("BTC-USD", 1704067200, 1706745600, Granularity::OneHour)
+//!     .await?;
+//!
+//! let series = CandleSeries::new(candles, Granularity::OneHour);
+//! for issue in series.validate() {
+//!     eprintln!("{}", issue);
+//! }
+//! let filled = series.fill_gaps(FillStrategy::ForwardFill);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use rust_decimal::Decimal;
+
+use crate::error::{Error, Result};
+use crate::models::{Candle, Granularity};
+use crate::ws::{CandleUpdate, Events, Message};
+
+/// Interval of the candles sent on the `candles` channel.
+pub const BASE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// A candle that has closed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosedCandle {
+    /// Product ID.
+    pub product_id: String,
+    /// Start of the candle.
+    pub start: DateTime<Utc>,
+    /// Length of the candle.
+    pub interval: Duration,
+    /// Open price.
+    pub open: Decimal,
+    /// High price.
+    pub high: Decimal,
+    /// Low price.
+    pub low: Decimal,
+    /// Close price.
+    pub close: Decimal,
+    /// Volume.
+    pub volume: Decimal,
+}
+
+impl ClosedCandle {
+    /// Parse a candle update from the `candles` channel.
+    fn from_update(update: &CandleUpdate) -> Result<Self> {
+        let start: i64 = update
+            .start
+            .parse()
+            .map_err(|_| Error::parse(format!("invalid candle start: {:?}", update.start), None))?;
+        let start = DateTime::from_timestamp(start, 0).ok_or_else(|| {
+            Error::parse(format!("invalid candle start: {:?}", update.start), None)
+        })?;
+        Ok(Self {
+            product_id: update.product_id.clone(),
+            start,
+            interval: BASE_INTERVAL,
+            open: parse_price("open", &update.open)?,
+            high: parse_price("high", &update.high)?,
+            low: parse_price("low", &update.low)?,
+            close: parse_price("close", &update.close)?,
+            volume: parse_price("volume", &update.volume)?,
+        })
+    }
+
+    /// End of the candle.
+    pub fn end(&self) -> DateTime<Utc> {
+        self.start + chrono::Duration::from_std(self.interval).unwrap_or_default()
+    }
+
+    /// Merge a later candle into this one.
+    fn merge(&mut self, later: &ClosedCandle) {
+        self.high = self.high.max(later.high);
+        self.low = self.low.min(later.low);
+        self.close = later.close;
+        self.volume += later.volume;
+    }
+}
+
+/// Per-product progress.
+#[derive(Debug, Default)]
+struct ProductState {
+    /// Latest update of the five-minute candle in progress.
+    current: Option<ClosedCandle>,
+    /// Closed five-minute candles merged into the composite candle so far.
+    bucket: Option<ClosedCandle>,
+}
+
+/// Stream of closed candles built from a WebSocket message stream.
+///
+/// Messages from other channels are skipped. Errors from the underlying
+/// stream are passed through.
+pub struct CandleStream<S> {
+    inner: S,
+    interval: Duration,
+    products: HashMap<String, ProductState>,
+    ready: VecDeque<Result<ClosedCandle>>,
+}
+
+impl<S> CandleStream<S>
+where
+    S: Stream<Item = Result<Message>> + Unpin,
+{
+    /// Wrap a message stream subscribed to the `candles` channel.
+    ///
+    /// Yields five-minute candles until an [`interval`](Self::interval) is set.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            interval: BASE_INTERVAL,
+            products: HashMap::new(),
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// Merge candles into candles of this length.
+    ///
+    /// Returns an error unless the interval is a positive multiple of five
+    /// minutes. Composite candles are aligned to the Unix epoch, so a
+    /// one-hour candle starts on the hour.
+    pub fn interval(mut self, interval: Duration) -> Result<Self> {
+        let base = BASE_INTERVAL.as_secs();
+        if interval.is_zero() || interval.subsec_nanos() != 0 || interval.as_secs() % base != 0 {
+            return Err(Error::config(format!(
+                "candle interval must be a multiple of {} seconds, got {:?}",
+                base, interval
+            )));
+        }
+        self.interval = interval;
+        Ok(self)
+    }
+
+    /// Get the length of the candles yielded.
+    pub fn candle_interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Record the candle updates in a message.
+    fn observe(&mut self, message: &Message) {
+        let Events::Candles(events) = &message.events else {
+            return;
+        };
+        for update in events.iter().flat_map(|e| &e.candles) {
+            match ClosedCandle::from_update(update) {
+                Ok(candle) => self.update(candle),
+                Err(e) => self.ready.push_back(Err(e)),
+            }
+        }
+    }
+
+    fn update(&mut self, candle: ClosedCandle) {
+        let state = self.products.entry(candle.product_id.clone()).or_default();
+        let closed = match &state.current {
+            // Repeated updates of the candle in progress replace it.
+            Some(current) if current.start == candle.start => None,
+            // Late updates for a candle that already closed are dropped.
+            Some(current) if current.start > candle.start => return,
+            _ => state.current.take(),
+        };
+        state.current = Some(candle);
+
+        let Some(closed) = closed else {
+            return;
+        };
+        if self.interval == BASE_INTERVAL {
+            self.ready.push_back(Ok(closed));
+            return;
+        }
+
+        let interval = self.interval.as_secs() as i64;
+        let bucket_start = closed.start.timestamp().div_euclid(interval) * interval;
+        if let Some(bucket) = state
+            .bucket
+            .take_if(|b| b.start.timestamp() != bucket_start)
+        {
+            // A gap in the data left the previous composite candle incomplete.
+            self.ready.push_back(Ok(bucket));
+        }
+        match state.bucket.as_mut() {
+            Some(bucket) => bucket.merge(&closed),
+            None => {
+                state.bucket = Some(ClosedCandle {
+                    start: DateTime::from_timestamp(bucket_start, 0).unwrap_or(closed.start),
+                    interval: self.interval,
+                    ..closed.clone()
+                })
+            }
+        }
+        if let Some(bucket) = state.bucket.take_if(|b| closed.end() >= b.end()) {
+            self.ready.push_back(Ok(bucket));
+        }
+    }
+}
+
+impl<S> Stream for CandleStream<S>
+where
+    S: Stream<Item = Result<Message>> + Unpin,
+{
+    type Item = Result<ClosedCandle>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(candle) = self.ready.pop_front() {
+                return Poll::Ready(Some(candle));
+            }
+            match std::task::ready!(Pin::new(&mut self.inner).poll_next(cx)) {
+                Some(Ok(message)) => self.observe(&message),
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// A problem found by [`CandleSeries::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CandleIssue {
+    /// A candle's start is not a Unix timestamp.
+    InvalidStart {
+        /// Position of the candle in the series.
+        index: usize,
+        /// The start as sent.
+        start: String,
+    },
+    /// A candle's start is not a multiple of the granularity.
+    Misaligned {
+        /// Start of the candle (Unix seconds).
+        start: u64,
+    },
+    /// More than one candle starts at the same time.
+    Duplicate {
+        /// Start of the candles (Unix seconds).
+        start: u64,
+    },
+    /// A candle starts before the one preceding it.
+    OutOfOrder {
+        /// Start of the candle (Unix seconds).
+        start: u64,
+        /// Start of the candle preceding it.
+        previous: u64,
+    },
+    /// Candles are missing between two candles.
+    Gap {
+        /// Start of the first missing candle (Unix seconds).
+        from: u64,
+        /// Number of missing candles.
+        missing: u64,
+    },
+}
+
+impl fmt::Display for CandleIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidStart { index, start } => {
+                write!(f, "candle {} has an invalid start {:?}", index, start)
+            }
+            Self::Misaligned { start } => {
+                write!(f, "candle at {} is not aligned to the granularity", start)
+            }
+            Self::Duplicate { start } => write!(f, "duplicate candles at {}", start),
+            Self::OutOfOrder { start, previous } => {
+                write!(
+                    f,
+                    "candle at {} comes after the candle at {}",
+                    start, previous
+                )
+            }
+            Self::Gap { from, missing } => {
+                write!(f, "{} candles missing from {}", missing, from)
+            }
+        }
+    }
+}
+
+/// How [`CandleSeries::fill_gaps`] fills missing candles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillStrategy {
+    /// Repeat the previous close as open, high, low and close, with no
+    /// volume.
+    ForwardFill,
+    /// Leave the gaps; only sort and remove duplicates.
+    Skip,
+    /// Insert candles whose prices and volume are all "NaN", which parse as
+    /// `f64::NAN`.
+    Nan,
+}
+
+/// Candles of one product and granularity, checked for integrity.
+///
+/// The series is kept oldest first. Candles in the newest-first order
+/// returned by [`get_candles`](crate::rest::ProductsApi::get_candles) are
+/// reversed when the series is created.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandleSeries {
+    candles: Vec<Candle>,
+    granularity: Granularity,
+}
+
+impl CandleSeries {
+    /// Wrap fetched candles.
+    pub fn new(mut candles: Vec<Candle>, granularity: Granularity) -> Self {
+        let first = candles.first().and_then(candle_start);
+        let last = candles.last().and_then(candle_start);
+        if first.zip(last).is_some_and(|(first, last)| first > last) {
+            candles.reverse();
+        }
+        Self {
+            candles,
+            granularity,
+        }
+    }
+
+    /// Get the candles, oldest first.
+    pub fn candles(&self) -> &[Candle] {
+        &self.candles
+    }
+
+    /// Get the candles, oldest first.
+    pub fn into_candles(self) -> Vec<Candle> {
+        self.candles
+    }
+
+    /// Get the granularity of the candles.
+    pub fn granularity(&self) -> Granularity {
+        self.granularity
+    }
+
+    /// Get the number of candles.
+    pub fn len(&self) -> usize {
+        self.candles.len()
+    }
+
+    /// Returns true if there are no candles.
+    pub fn is_empty(&self) -> bool {
+        self.candles.is_empty()
+    }
+
+    /// Find every problem in the series, in order.
+    pub fn validate(&self) -> Vec<CandleIssue> {
+        let step = self.granularity.as_seconds();
+        let mut issues = Vec::new();
+        let mut previous: Option<u64> = None;
+        for (index, candle) in self.candles.iter().enumerate() {
+            let Some(start) = candle_start(candle) else {
+                issues.push(CandleIssue::InvalidStart {
+                    index,
+                    start: candle.start.clone(),
+                });
+                continue;
+            };
+            if start % step != 0 {
+                issues.push(CandleIssue::Misaligned { start });
+            }
+            match previous {
+                Some(previous) if start == previous => {
+                    issues.push(CandleIssue::Duplicate { start });
+                }
+                Some(previous) if start < previous => {
+                    issues.push(CandleIssue::OutOfOrder { start, previous });
+                }
+                Some(previous) if start - previous > step => {
+                    issues.push(CandleIssue::Gap {
+                        from: previous + step,
+                        missing: (start - previous - 1) / step,
+                    });
+                }
+                _ => {}
+            }
+            previous = Some(start);
+        }
+        issues
+    }
+
+    /// Returns true if [`validate`](Self::validate) finds no problems.
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_empty()
+    }
+
+    /// Sort the candles, drop duplicates and candles without a valid
+    /// start, and fill the gaps between the rest.
+    ///
+    /// Of duplicate candles, the last one is kept, since a later copy of an
+    /// in-progress candle is the more complete one.
+    pub fn fill_gaps(self, strategy: FillStrategy) -> Self {
+        let step = self.granularity.as_seconds();
+        let mut candles: Vec<(u64, Candle)> = self
+            .candles
+            .into_iter()
+            .filter_map(|candle| candle_start(&candle).map(|start| (start, candle)))
+            .collect();
+        // Stable, so duplicates stay in arrival order.
+        candles.sort_by_key(|(start, _)| *start);
+
+        let mut filled: Vec<(u64, Candle)> = Vec::with_capacity(candles.len());
+        for (start, candle) in candles {
+            match filled.last_mut() {
+                Some((last, kept)) if *last == start => {
+                    *kept = candle;
+                    continue;
+                }
+                Some((last, kept)) if strategy != FillStrategy::Skip => {
+                    let previous = kept.clone();
+                    let mut missing = *last + step;
+                    while missing < start {
+                        filled.push((missing, filler(missing, &previous, strategy)));
+                        missing += step;
+                    }
+                }
+                _ => {}
+            }
+            filled.push((start, candle));
+        }
+
+        Self {
+            candles: filled.into_iter().map(|(_, candle)| candle).collect(),
+            granularity: self.granularity,
+        }
+    }
+}
+
+/// Build a candle standing in for a missing one.
+fn filler(start: u64, previous: &Candle, strategy: FillStrategy) -> Candle {
+    let (price, volume) = match strategy {
+        FillStrategy::Nan => ("NaN".to_string(), "NaN".to_string()),
+        _ => (previous.close.clone(), "0".to_string()),
+    };
+    Candle {
+        start: start.to_string(),
+        low: price.clone(),
+        high: price.clone(),
+        open: price.clone(),
+        close: price,
+        volume,
+    }
+}
+
+fn candle_start(candle: &Candle) -> Option<u64> {
+    candle.start.parse().ok()
+}
+
+fn parse_price(field: &str, value: &str) -> Result<Decimal> {
+    value
+        .parse()
+        .map_err(|_| Error::parse(format!("invalid candle {}: {:?}", field, value), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    fn candle_message(updates: &[(u64, &str, &str)]) -> Result<Message> {
+        let candles: Vec<_> = updates
+            .iter()
+            .map(|(start, close, volume)| {
+                serde_json::json!({
+                    "product_id": "BTC-USD",
+                    "start": start.to_string(),
+                    "open": "100",
+                    "high": close,
+                    "low": "90",
+                    "close": close,
+                    "volume": volume
+                })
+            })
+            .collect();
+        Ok(serde_json::from_value(serde_json::json!({
+            "channel": "candles",
+            "client_id": "",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "sequence_num": 0,
+            "events": [{"type": "update", "candles": candles}]
+        }))
+        .unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_closed_candles_deduped() {
+        let messages = futures::stream::iter(vec![
+            candle_message(&[(0, "101", "1")]),
+            candle_message(&[(0, "105", "2")]),
+            candle_message(&[(300, "106", "1")]),
+            candle_message(&[(300, "107", "3")]),
+            candle_message(&[(600, "108", "1")]),
+        ]);
+
+        let candles: Vec<_> = CandleStream::new(messages)
+            .map(|c| c.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].start.timestamp(), 0);
+        assert_eq!(candles[0].close, Decimal::from(105));
+        assert_eq!(candles[0].volume, Decimal::from(2));
+        assert_eq!(candles[1].start.timestamp(), 300);
+        assert_eq!(candles[1].close, Decimal::from(107));
+    }
+
+    #[tokio::test]
+    async fn test_downsampled_candles() {
+        let messages = futures::stream::iter(vec![
+            candle_message(&[(0, "101", "1")]),
+            candle_message(&[(300, "103", "2")]),
+            candle_message(&[(600, "102", "3")]),
+            candle_message(&[(900, "110", "4")]),
+            // Skips 1200, leaving the second composite candle incomplete.
+            candle_message(&[(1500, "104", "5")]),
+            candle_message(&[(1800, "104", "6")]),
+        ]);
+
+        let candles: Vec<_> = CandleStream::new(messages)
+            .interval(Duration::from_secs(900))
+            .unwrap()
+            .map(|c| c.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].start.timestamp(), 0);
+        assert_eq!(candles[0].interval, Duration::from_secs(900));
+        assert_eq!(candles[0].high, Decimal::from(103));
+        assert_eq!(candles[0].close, Decimal::from(102));
+        assert_eq!(candles[0].volume, Decimal::from(6));
+        assert_eq!(candles[1].start.timestamp(), 900);
+        assert_eq!(candles[1].close, Decimal::from(104));
+        assert_eq!(candles[1].volume, Decimal::from(9));
+
+        assert!(
+            CandleStream::new(futures::stream::empty::<Result<Message>>())
+                .interval(Duration::from_secs(60))
+                .is_err()
+        );
+    }
+
+    fn rest_candle(start: u64, close: &str) -> Candle {
+        Candle {
+            start: start.to_string(),
+            low: close.to_string(),
+            high: close.to_string(),
+            open: close.to_string(),
+            close: close.to_string(),
+            volume: "1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_candle_series_validate() {
+        // Newest first, as returned by get_candles.
+        let series = CandleSeries::new(
+            vec![
+                rest_candle(300, "4"),
+                rest_candle(240, "3"),
+                rest_candle(60, "2"),
+                rest_candle(0, "1"),
+            ],
+            Granularity::OneMinute,
+        );
+        assert_eq!(series.candles()[0].start, "0");
+        assert_eq!(
+            series.validate(),
+            [CandleIssue::Gap {
+                from: 120,
+                missing: 2
+            }]
+        );
+
+        let series = CandleSeries::new(
+            vec![
+                rest_candle(0, "1"),
+                rest_candle(120, "2"),
+                rest_candle(60, "3"),
+                rest_candle(60, "4"),
+                rest_candle(90, "5"),
+                rest_candle(0, "x"),
+            ],
+            Granularity::OneMinute,
+        );
+        let mut bad = rest_candle(0, "6");
+        bad.start = "soon".to_string();
+        let mut candles = series.into_candles();
+        candles.push(bad);
+        let series = CandleSeries::new(candles, Granularity::OneMinute);
+        assert_eq!(
+            series.validate(),
+            [
+                CandleIssue::Gap {
+                    from: 60,
+                    missing: 1
+                },
+                CandleIssue::OutOfOrder {
+                    start: 60,
+                    previous: 120
+                },
+                CandleIssue::Duplicate { start: 60 },
+                CandleIssue::Misaligned { start: 90 },
+                CandleIssue::OutOfOrder {
+                    start: 0,
+                    previous: 90
+                },
+                CandleIssue::InvalidStart {
+                    index: 6,
+                    start: "soon".to_string()
+                },
+            ]
+        );
+        assert!(!series.is_valid());
+    }
+
+    #[test]
+    fn test_candle_series_fill_gaps() {
+        let series = CandleSeries::new(
+            vec![
+                rest_candle(0, "1"),
+                rest_candle(180, "4"),
+                rest_candle(60, "2"),
+                rest_candle(60, "3"),
+            ],
+            Granularity::OneMinute,
+        );
+
+        let filled = series.clone().fill_gaps(FillStrategy::ForwardFill);
+        assert!(filled.is_valid());
+        let closes: Vec<&str> = filled.candles().iter().map(|c| c.close.as_str()).collect();
+        assert_eq!(closes, ["1", "3", "3", "4"]);
+        assert_eq!(filled.candles()[2].volume, "0");
+
+        let nan = series.clone().fill_gaps(FillStrategy::Nan);
+        assert!(nan.candles()[2].close.parse::<f64>().unwrap().is_nan());
+
+        let skipped = series.fill_gaps(FillStrategy::Skip);
+        assert_eq!(skipped.len(), 3);
+        assert_eq!(
+            skipped.validate(),
+            [CandleIssue::Gap {
+                from: 120,
+                missing: 1
+            }]
+        );
+    }
+}
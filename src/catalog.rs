@@ -0,0 +1,288 @@
+//! Cached product metadata.
+//!
+//! [`ProductCatalog`] fetches all products once and serves lookups from memory
+//! until the cache is older than its TTL, so that order validation and
+//! quantization do not need a REST call per order.
+//!
+//! With the `cache` feature and a [cache](crate::RestClientBuilder::cache)
+//! set on the client, fetched products are also stored there, and a new
+//! catalog starts from the stored products while they are younger than its
+//! TTL.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use coinbase_advanced::catalog::ProductCatalog;
+//! use coinbase_advanced::{Decimal, RestClient};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder().build()?;
+//! let catalog = ProductCatalog::new(client).with_ttl(Duration::from_secs(600));
+//!
+//! if let Some(increments) = catalog.increments("BTC-USD").await? {
+//!     println!("Size: {}", increments.quantize_base(Decimal::new(123456789, 9)));
+//! }
+//!
+//! let usd_products = catalog.by_quote_currency("USD").await?;
+//! println!("{} USD products", usd_products.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::sync::RwLock;
+
+use crate::client::RestClient;
+use crate::error::Result;
+use crate::models::{Product, ProductIncrements};
+use crate::runtime::Instant;
+
+/// Default time before the catalog is refreshed.
+pub const DEFAULT_CATALOG_TTL: Duration = Duration::from_secs(300);
+
+/// Products cached at a point in time.
+#[derive(Debug)]
+struct CatalogState {
+    products: HashMap<String, Product>,
+    fetched_at: Instant,
+}
+
+impl CatalogState {
+    /// Returns true if the state is younger than `ttl`.
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        self.fetched_at.elapsed() < ttl
+    }
+}
+
+/// A TTL cache of all products, keyed by product ID.
+pub struct ProductCatalog {
+    client: RestClient,
+    ttl: Duration,
+    state: RwLock<Option<CatalogState>>,
+}
+
+impl ProductCatalog {
+    /// Create an empty catalog that fetches products with the given client.
+    ///
+    /// Products are fetched on first use.
+    pub fn new(client: RestClient) -> Self {
+        Self {
+            client,
+            ttl: DEFAULT_CATALOG_TTL,
+            state: RwLock::new(None),
+        }
+    }
+
+    /// Set how long fetched products are served before refreshing.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Get the cache TTL.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Fetch all products now, replacing the cache.
+    pub async fn refresh(&self) -> Result<()> {
+        let mut state = self.state.write().await;
+        *state = Some(self.fetch().await?);
+        Ok(())
+    }
+
+    /// Discard the cached products so the next lookup fetches them again.
+    pub async fn invalidate(&self) {
+        *self.state.write().await = None;
+    }
+
+    /// Look up a product by ID.
+    pub async fn get(&self, product_id: &str) -> Result<Option<Product>> {
+        self.with_products(|products| products.get(product_id).cloned())
+            .await
+    }
+
+    /// Get the increments of a product by ID.
+    pub async fn increments(&self, product_id: &str) -> Result<Option<ProductIncrements>> {
+        self.with_products(|products| products.get(product_id).map(Product::increments))
+            .await
+    }
+
+    /// Get all cached products.
+    pub async fn products(&self) -> Result<Vec<Product>> {
+        self.with_products(|products| products.values().cloned().collect())
+            .await
+    }
+
+    /// Get all products quoted in the given currency (e.g., "USD").
+    pub async fn by_quote_currency(&self, quote_currency: &str) -> Result<Vec<Product>> {
+        self.filter(|p| p.quote_currency_id.eq_ignore_ascii_case(quote_currency))
+            .await
+    }
+
+    /// Get all products with the given status (e.g., "online").
+    pub async fn by_status(&self, status: &str) -> Result<Vec<Product>> {
+        self.filter(|p| p.status.eq_ignore_ascii_case(status)).await
+    }
+
+    /// Get all products matching a predicate.
+    pub async fn filter<F>(&self, predicate: F) -> Result<Vec<Product>>
+    where
+        F: Fn(&Product) -> bool,
+    {
+        self.with_products(|products| {
+            products
+                .values()
+                .filter(|p| predicate(p))
+                .cloned()
+                .collect()
+        })
+        .await
+    }
+
+    /// Run `f` against fresh products, refreshing the cache first if needed.
+    async fn with_products<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&HashMap<String, Product>) -> T,
+    {
+        {
+            let state = self.state.read().await;
+            if let Some(state) = state.as_ref().filter(|s| s.is_fresh(self.ttl)) {
+                return Ok(f(&state.products));
+            }
+        }
+
+        let mut state = self.state.write().await;
+        #[cfg(feature = "cache")]
+        if state.is_none() {
+            *state = self.load_cached();
+        }
+        // Another task may have refreshed while we waited for the lock.
+        if !state.as_ref().is_some_and(|s| s.is_fresh(self.ttl)) {
+            *state = Some(self.fetch().await?);
+        }
+        let state = state.as_ref().expect("catalog state was just populated");
+        Ok(f(&state.products))
+    }
+
+    async fn fetch(&self) -> Result<CatalogState> {
+        let response = self.client.products().list_all().await?;
+        tracing::debug!(count = response.products.len(), "refreshed product catalog");
+
+        #[cfg(feature = "cache")]
+        if let Some(cache) = self.client.cache() {
+            let entry = CachedCatalog {
+                fetched_at: unix_now(),
+                products: response.products.clone(),
+            };
+            crate::cache::put_json(cache, CATALOG_CACHE_KEY, &entry);
+        }
+
+        Ok(CatalogState {
+            products: response
+                .products
+                .into_iter()
+                .map(|p| (p.product_id.clone(), p))
+                .collect(),
+            fetched_at: Instant::now(),
+        })
+    }
+}
+
+/// Key of the product list in the client's cache.
+#[cfg(feature = "cache")]
+const CATALOG_CACHE_KEY: &str = "catalog/products";
+
+/// Products as stored in the client's cache.
+#[cfg(feature = "cache")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CachedCatalog {
+    /// When the products were fetched, in Unix seconds.
+    fetched_at: u64,
+    products: Vec<Product>,
+}
+
+#[cfg(feature = "cache")]
+impl ProductCatalog {
+    /// Load the products stored in the client's cache, if any.
+    ///
+    /// Stored products keep their original fetch time, so an entry older
+    /// than the TTL is refreshed straight away.
+    fn load_cached(&self) -> Option<CatalogState> {
+        let cache = self.client.cache()?;
+        let entry: CachedCatalog = crate::cache::get_json(cache, CATALOG_CACHE_KEY)?;
+        let age = Duration::from_secs(unix_now().saturating_sub(entry.fetched_at));
+        tracing::debug!(
+            count = entry.products.len(),
+            ?age,
+            "loaded cached product catalog"
+        );
+
+        Some(CatalogState {
+            products: entry
+                .products
+                .into_iter()
+                .map(|p| (p.product_id.clone(), p))
+                .collect(),
+            fetched_at: Instant::now().checked_sub(age)?,
+        })
+    }
+}
+
+#[cfg(feature = "cache")]
+fn unix_now() -> u64 {
+    crate::runtime::SystemTime::now()
+        .duration_since(crate::runtime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_state_freshness() {
+        let state = CatalogState {
+            products: HashMap::new(),
+            fetched_at: Instant::now(),
+        };
+        assert!(state.is_fresh(Duration::from_secs(60)));
+        assert!(!state.is_fresh(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_catalog_ttl() {
+        let client = RestClient::builder().build().unwrap();
+        let catalog = ProductCatalog::new(client);
+        assert_eq!(catalog.ttl(), DEFAULT_CATALOG_TTL);
+
+        let catalog = catalog.with_ttl(Duration::from_secs(10));
+        assert_eq!(catalog.ttl(), Duration::from_secs(10));
+    }
+    #[cfg(feature = "cache")]
+    #[test]
+    fn test_catalog_loads_cache() {
+        use std::sync::Arc;
+
+        use crate::cache::{MemoryCache, put_json};
+
+        let cache = Arc::new(MemoryCache::new());
+        let client = RestClient::builder().cache(cache.clone()).build().unwrap();
+        let catalog = ProductCatalog::new(client);
+        assert!(catalog.load_cached().is_none());
+
+        let entry = CachedCatalog {
+            fetched_at: unix_now() - 60,
+            products: Vec::new(),
+        };
+        put_json(cache.as_ref(), CATALOG_CACHE_KEY, &entry);
+        let state = catalog.load_cached().unwrap();
+        assert!(state.is_fresh(DEFAULT_CATALOG_TTL));
+        assert!(!state.is_fresh(Duration::from_secs(30)));
+    }
+}
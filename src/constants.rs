@@ -7,6 +7,9 @@ pub const API_SANDBOX_BASE_URL: &str = "https://api-sandbox.coinbase.com";
 /// API version path prefix.
 pub const API_PATH_PREFIX: &str = "/api/v3/brokerage";
 
+/// Path prefix of the Coinbase App (v2) API, used for deposits and withdrawals.
+pub const API_V2_PATH_PREFIX: &str = "/v2";
+
 /// WebSocket production URL.
 pub const WS_URL: &str = "wss://advanced-trade-ws.coinbase.com";
 
@@ -27,3 +30,177 @@ pub const JWT_EXPIRY_SECONDS: u64 = 120;
 
 /// Default request timeout in seconds.
 pub const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
+
+/// Most order IDs accepted by one batch cancel request.
+pub const MAX_CANCEL_ORDER_IDS: usize = 100;
+
+/// Endpoint paths, relative to the API path prefix.
+///
+/// These are the paths passed to the client's request methods. The client
+/// prepends the prefixes of its [`Endpoints`](crate::Endpoints); paths
+/// under [`API_V2_PATH_PREFIX`] are served by the v2 API.
+pub mod paths {
+    use super::API_V2_PATH_PREFIX;
+    use crate::models::TransferKind;
+
+    /// List accounts.
+    pub const ACCOUNTS: &str = "/accounts";
+    /// List products.
+    pub const PRODUCTS: &str = "/products";
+    /// Get a product's order book.
+    pub const PRODUCT_BOOK: &str = "/product_book";
+    /// Get the best bid and ask of products.
+    pub const BEST_BID_ASK: &str = "/best_bid_ask";
+    /// Get the server time.
+    pub const TIME: &str = "/time";
+    /// List products without authentication.
+    pub const MARKET_PRODUCTS: &str = "/market/products";
+    /// Get a product's order book without authentication.
+    pub const MARKET_PRODUCT_BOOK: &str = "/market/product_book";
+    /// Get the best bid and ask of products without authentication.
+    pub const MARKET_BEST_BID_ASK: &str = "/market/best_bid_ask";
+    /// Create an order.
+    pub const ORDERS: &str = "/orders";
+    /// Preview an order.
+    pub const ORDERS_PREVIEW: &str = "/orders/preview";
+    /// Edit an order.
+    pub const ORDERS_EDIT: &str = "/orders/edit";
+    /// Preview an order edit.
+    pub const ORDERS_EDIT_PREVIEW: &str = "/orders/edit_preview";
+    /// Cancel orders.
+    pub const ORDERS_BATCH_CANCEL: &str = "/orders/batch_cancel";
+    /// List orders.
+    pub const ORDERS_HISTORICAL: &str = "/orders/historical/batch";
+    /// List fills.
+    pub const FILLS: &str = "/orders/historical/fills";
+    /// Close a futures position.
+    pub const CLOSE_POSITION: &str = "/orders/close_position";
+    /// List or create portfolios.
+    pub const PORTFOLIOS: &str = "/portfolios";
+    /// Move funds between portfolios.
+    pub const PORTFOLIOS_MOVE_FUNDS: &str = "/portfolios/move_funds";
+    /// Get the fee transaction summary.
+    pub const TRANSACTION_SUMMARY: &str = "/transaction_summary";
+    /// Get the permissions of the API key.
+    pub const KEY_PERMISSIONS: &str = "/key_permissions";
+    /// Create a convert quote.
+    pub const CONVERT_QUOTE: &str = "/convert/quote";
+    /// List payment methods.
+    pub const PAYMENT_METHODS: &str = "/payment_methods";
+    /// List futures positions.
+    pub const FUTURES_POSITIONS: &str = "/cfm/positions";
+    /// Get the futures balance summary.
+    pub const FUTURES_BALANCE_SUMMARY: &str = "/cfm/balance_summary";
+    /// Get or set the intraday margin setting.
+    pub const FUTURES_MARGIN_SETTING: &str = "/cfm/intraday/margin_setting";
+    /// Get the current margin window.
+    pub const FUTURES_MARGIN_WINDOW: &str = "/cfm/intraday/current_margin_window";
+    /// List or cancel futures sweeps.
+    pub const FUTURES_SWEEPS: &str = "/cfm/sweeps";
+    /// Schedule a futures sweep.
+    pub const FUTURES_SWEEPS_SCHEDULE: &str = "/cfm/sweeps/schedule";
+    /// Allocate funds to a perpetuals portfolio.
+    pub const PERPETUALS_ALLOCATE: &str = "/intx/allocate";
+    /// Opt in or out of multi-asset collateral.
+    pub const PERPETUALS_MULTI_ASSET_COLLATERAL: &str = "/intx/multi_asset_collateral";
+
+    /// Get an account.
+    pub fn account(account_uuid: &str) -> String {
+        format!("{}/{}", ACCOUNTS, account_uuid)
+    }
+
+    /// Get a product.
+    pub fn product(product_id: &str) -> String {
+        format!("{}/{}", PRODUCTS, product_id)
+    }
+
+    /// Get a product's candles.
+    pub fn product_candles(product_id: &str) -> String {
+        format!("{}/{}/candles", PRODUCTS, product_id)
+    }
+
+    /// Get a product's recent trades.
+    pub fn product_ticker(product_id: &str) -> String {
+        format!("{}/{}/ticker", PRODUCTS, product_id)
+    }
+
+    /// Get a product without authentication.
+    pub fn market_product(product_id: &str) -> String {
+        format!("{}/{}", MARKET_PRODUCTS, product_id)
+    }
+
+    /// Get a product's candles without authentication.
+    pub fn market_product_candles(product_id: &str) -> String {
+        format!("{}/{}/candles", MARKET_PRODUCTS, product_id)
+    }
+
+    /// Get a product's recent trades without authentication.
+    pub fn market_product_ticker(product_id: &str) -> String {
+        format!("{}/{}/ticker", MARKET_PRODUCTS, product_id)
+    }
+
+    /// Get an order.
+    pub fn order(order_id: &str) -> String {
+        format!("/orders/historical/{}", order_id)
+    }
+
+    /// Get, edit or delete a portfolio.
+    pub fn portfolio(portfolio_uuid: &str) -> String {
+        format!("{}/{}", PORTFOLIOS, portfolio_uuid)
+    }
+
+    /// Get or commit a convert trade.
+    pub fn convert_trade(trade_id: &str) -> String {
+        format!("/convert/trade/{}", trade_id)
+    }
+
+    /// Get a payment method.
+    pub fn payment_method(payment_method_id: &str) -> String {
+        format!("{}/{}", PAYMENT_METHODS, payment_method_id)
+    }
+
+    /// Get a futures position.
+    pub fn futures_position(product_id: &str) -> String {
+        format!("{}/{}", FUTURES_POSITIONS, product_id)
+    }
+
+    /// List a perpetuals portfolio's positions.
+    pub fn perpetuals_positions(portfolio_uuid: &str) -> String {
+        format!("/intx/positions/{}", portfolio_uuid)
+    }
+
+    /// Get a perpetuals position.
+    pub fn perpetuals_position(portfolio_uuid: &str, symbol: &str) -> String {
+        format!("/intx/positions/{}/{}", portfolio_uuid, symbol)
+    }
+
+    /// Get a perpetuals portfolio's balances.
+    pub fn perpetuals_balances(portfolio_uuid: &str) -> String {
+        format!("/intx/balances/{}", portfolio_uuid)
+    }
+
+    /// Get a perpetuals portfolio summary.
+    pub fn perpetuals_portfolio(portfolio_uuid: &str) -> String {
+        format!("/intx/portfolio/{}", portfolio_uuid)
+    }
+
+    /// Create deposits or withdrawals for an account (v2 API).
+    pub fn account_transfers(account_id: &str, kind: TransferKind) -> String {
+        format!(
+            "{}/accounts/{}/{}",
+            API_V2_PATH_PREFIX,
+            account_id,
+            kind.as_str()
+        )
+    }
+
+    /// Get a deposit or withdrawal (v2 API).
+    pub fn account_transfer(account_id: &str, kind: TransferKind, transfer_id: &str) -> String {
+        format!("{}/{}", account_transfers(account_id, kind), transfer_id)
+    }
+
+    /// Commit a deposit or withdrawal (v2 API).
+    pub fn commit_transfer(account_id: &str, kind: TransferKind, transfer_id: &str) -> String {
+        format!("{}/commit", account_transfer(account_id, kind, transfer_id))
+    }
+}
@@ -0,0 +1,322 @@
+//! Tools for reporting response parsing bugs.
+//!
+//! Enabled by the `debug-tools` feature. When a response fails to parse,
+//! the raw body is kept on [`Error::Parse`] and [`Error::Api`]. [`dump`]
+//! writes that body to a file after passing it through a [`Scrubber`], which
+//! removes credentials, personal details and identifiers, so it can be
+//! attached to a bug report or added to the test fixtures as is.
+//!
+//! Scrubbing keeps the shape of the body: every field stays where it was,
+//! numbers are kept, and each UUID is replaced by a placeholder that is
+//! still a valid UUID. The same UUID always gets the same placeholder, so
+//! references between objects (such as an order and its fills) survive.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::{Credentials, RestClient, debug_tools};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder()
+//!     .credentials(Credentials::from_env()?)
+//!     .build()?;
+//!
+//! if let Err(e) = client.orders().list_all().await {
+//!     if let Some(path) = debug_tools::dump(&e, "bug-reports")? {
+//!         eprintln!("{e}; scrubbed response written to {}", path.display());
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+
+/// Replacement for sensitive values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Field names whose values are always redacted, compared case-insensitively.
+const SENSITIVE_FIELDS: &[&str] = &[
+    "jwt",
+    "token",
+    "access_token",
+    "refresh_token",
+    "authorization",
+    "api_key",
+    "api_key_id",
+    "key_name",
+    "private_key",
+    "secret",
+    "signature",
+    "password",
+    "email",
+    "name",
+    "display_name",
+    "first_name",
+    "last_name",
+    "address",
+    "address_line1",
+    "address_line2",
+    "phone",
+    "phone_number",
+    "account_number",
+    "routing_number",
+    "iban",
+    "crypto_address",
+    "destination_address",
+    "destination_tag",
+];
+
+/// Removes credentials, personal details and identifiers from response
+/// bodies.
+///
+/// A scrubber remembers the placeholders it hands out, so scrubbing several
+/// related bodies with the same scrubber keeps their UUIDs consistent.
+#[derive(Debug, Clone, Default)]
+pub struct Scrubber {
+    uuids: HashMap<String, String>,
+}
+
+impl Scrubber {
+    /// Create a scrubber.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scrub a response body.
+    ///
+    /// JSON bodies are scrubbed field by field and pretty-printed. Other
+    /// bodies are scrubbed as text.
+    pub fn scrub(&mut self, body: &str) -> String {
+        match serde_json::from_str::<Value>(body) {
+            Ok(mut value) => {
+                self.scrub_value(&mut value);
+                serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string())
+            }
+            Err(_) => self.scrub_text(body),
+        }
+    }
+
+    /// Scrub a JSON value in place.
+    pub fn scrub_value(&mut self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if is_sensitive(key) {
+                        redact(value);
+                    } else {
+                        self.scrub_value(value);
+                    }
+                }
+            }
+            Value::Array(values) => {
+                for value in values {
+                    self.scrub_value(value);
+                }
+            }
+            Value::String(s) => *s = self.scrub_text(s),
+            _ => {}
+        }
+    }
+
+    /// Scrub free text: replace UUIDs and redact JWTs and API key names.
+    pub fn scrub_text(&mut self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while !rest.is_empty() {
+            if rest.starts_with("eyJ") {
+                let len = token_len(rest);
+                if rest[..len].matches('.').count() == 2 {
+                    out.push_str(REDACTED);
+                    rest = &rest[len..];
+                    continue;
+                }
+            }
+            if rest.starts_with("organizations/") {
+                let len = rest
+                    .find(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+                    .unwrap_or(rest.len());
+                out.push_str(REDACTED);
+                rest = &rest[len..];
+                continue;
+            }
+            if rest.len() >= 36 && is_uuid(&rest.as_bytes()[..36]) {
+                out.push_str(&self.placeholder(&rest[..36]));
+                rest = &rest[36..];
+                continue;
+            }
+            let c = rest.chars().next().unwrap_or_default();
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        out
+    }
+
+    /// Get the placeholder for a UUID, handing out the next one if it is new.
+    fn placeholder(&mut self, uuid: &str) -> String {
+        let next = self.uuids.len() + 1;
+        self.uuids
+            .entry(uuid.to_ascii_lowercase())
+            .or_insert_with(|| format!("00000000-0000-4000-8000-{:012x}", next))
+            .clone()
+    }
+}
+
+/// Scrub a single response body.
+///
+/// Shorthand for [`Scrubber::scrub`] with a new scrubber.
+pub fn scrub(body: &str) -> String {
+    Scrubber::new().scrub(body)
+}
+
+impl Error {
+    /// Get the raw response body, if this error kept one.
+    ///
+    /// Only [`Error::Parse`] and [`Error::Api`] keep the body.
+    pub fn raw_body(&self) -> Option<&str> {
+        match self {
+            Self::Parse { body, .. } | Self::Api { body, .. } => body.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Write the scrubbed response body of an error to a new file in `dir`.
+///
+/// The directory is created if needed. Returns the path of the file, or
+/// `None` if the error has no response body.
+pub fn dump(error: &Error, dir: impl AsRef<Path>) -> Result<Option<PathBuf>> {
+    let Some(body) = error.raw_body() else {
+        return Ok(None);
+    };
+
+    let kind = match error {
+        Error::Api { status, .. } => format!("api-{}", status),
+        _ => "parse".to_string(),
+    };
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!(
+        "{}-{}.json",
+        kind,
+        Utc::now().format("%Y%m%dT%H%M%S%.6fZ")
+    ));
+    std::fs::write(&path, Scrubber::new().scrub(body))?;
+    tracing::debug!(path = %path.display(), "dumped scrubbed response body");
+    Ok(Some(path))
+}
+
+fn is_sensitive(key: &str) -> bool {
+    SENSITIVE_FIELDS
+        .iter()
+        .any(|field| field.eq_ignore_ascii_case(key))
+}
+
+/// Redact every string in a value, keeping its shape.
+fn redact(value: &mut Value) {
+    match value {
+        Value::String(s) => *s = REDACTED.to_string(),
+        Value::Object(map) => map.values_mut().for_each(redact),
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// Length of the run of base64url characters and dots at the start of `s`.
+fn token_len(s: &str) -> usize {
+    s.find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(s.len())
+}
+
+/// Check for the 8-4-4-4-12 hex digit layout of a UUID.
+fn is_uuid(bytes: &[u8]) -> bool {
+    bytes.iter().enumerate().all(|(i, b)| match i {
+        8 | 13 | 18 | 23 => *b == b'-',
+        _ => b.is_ascii_hexdigit(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_scrub_json() {
+        let order_id = "0b8c6b5a-55b2-4a4b-9b5f-7f7d6c2e1a10";
+        let body = json!({
+            "order": {
+                "order_id": order_id,
+                "user_id": "a1b2c3d4-e5f6-4789-abcd-ef0123456789",
+                "filled_size": "0.001",
+                "number_of_fills": 2,
+                "name": "Jane Doe",
+                "note": format!("see order {order_id}")
+            },
+            "auth": {"jwt": "eyJhbGciOi.eyJzdWIi.c2lnbmF0dXJl"},
+            "message": "key organizations/org-1/apiKeys/key-1 denied"
+        });
+
+        let mut scrubber = Scrubber::new();
+        let scrubbed: Value = serde_json::from_str(&scrubber.scrub(&body.to_string())).unwrap();
+        let order = &scrubbed["order"];
+        assert_eq!(order["order_id"], "00000000-0000-4000-8000-000000000001");
+        assert_eq!(order["user_id"], "00000000-0000-4000-8000-000000000002");
+        assert_eq!(
+            order["note"],
+            "see order 00000000-0000-4000-8000-000000000001"
+        );
+        assert_eq!(order["filled_size"], "0.001");
+        assert_eq!(order["number_of_fills"], 2);
+        assert_eq!(order["name"], REDACTED);
+        assert_eq!(scrubbed["auth"]["jwt"], REDACTED);
+        assert_eq!(scrubbed["message"], "key [REDACTED] denied");
+
+        // Later bodies reuse the placeholders.
+        assert_eq!(
+            scrubber.scrub_text(&order_id.to_uppercase()),
+            "00000000-0000-4000-8000-000000000001"
+        );
+    }
+
+    #[test]
+    fn test_scrub_text() {
+        assert_eq!(
+            scrub("Bearer eyJhbGciOi.eyJzdWIi.sig for 0b8c6b5a-55b2-4a4b-9b5f-7f7d6c2e1a10"),
+            "Bearer [REDACTED] for 00000000-0000-4000-8000-000000000001"
+        );
+        assert_eq!(
+            scrub("not-a-uuid: 12345678-1234"),
+            "not-a-uuid: 12345678-1234"
+        );
+    }
+
+    #[test]
+    fn test_dump() {
+        let dir = std::env::temp_dir().join(format!("coinbase-dump-{}", std::process::id()));
+        let error = Error::parse(
+            "missing field `side`",
+            Some(r#"{"order_id":"0b8c6b5a-55b2-4a4b-9b5f-7f7d6c2e1a10"}"#.to_string()),
+        );
+        let path = dump(&error, &dir).unwrap().unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("00000000-0000-4000-8000-000000000001"));
+        assert!(
+            path.file_name()
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .starts_with("parse-")
+        );
+
+        assert!(dump(&Error::config("no body"), &dir).unwrap().is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+//! API path prefixes.
+
+use crate::constants::{API_PATH_PREFIX, API_V2_PATH_PREFIX};
+
+/// The path prefixes REST endpoints are served under.
+///
+/// Endpoints are requested relative to the Advanced Trade API prefix
+/// (`/api/v3/brokerage`), except for the deposit and withdrawal endpoints
+/// of the v2 API (`/v2`). Override the prefixes when a proxy remaps these
+/// paths. Requests are signed for the path actually sent.
+///
+/// # Example
+///
+/// ```no_run
+/// use coinbase_advanced::{Endpoints, Environment, RestClient};
+///
+/// # fn example() -> coinbase_advanced::Result<()> {
+/// let client = RestClient::builder()
+///     .environment(Environment::custom("https://proxy.internal", "wss://proxy.internal/ws"))
+///     .endpoints(Endpoints::new().with_api_prefix("/coinbase/brokerage"))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    api_prefix: String,
+    v2_prefix: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Endpoints {
+    /// Use the prefixes of the Coinbase API.
+    pub fn new() -> Self {
+        Self {
+            api_prefix: API_PATH_PREFIX.to_string(),
+            v2_prefix: API_V2_PATH_PREFIX.to_string(),
+        }
+    }
+
+    /// Serve Advanced Trade endpoints under another prefix.
+    pub fn with_api_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.api_prefix = normalize(prefix.into());
+        self
+    }
+
+    /// Serve v2 endpoints under another prefix.
+    pub fn with_v2_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.v2_prefix = normalize(prefix.into());
+        self
+    }
+
+    /// Get the prefix of Advanced Trade endpoints.
+    pub fn api_prefix(&self) -> &str {
+        &self.api_prefix
+    }
+
+    /// Get the prefix of v2 endpoints.
+    pub fn v2_prefix(&self) -> &str {
+        &self.v2_prefix
+    }
+
+    /// Get the full path of an endpoint.
+    ///
+    /// `endpoint` is the path passed to the client's request methods, such
+    /// as [`paths::ACCOUNTS`](crate::consts::paths::ACCOUNTS). Endpoints
+    /// under `/v2` are moved to the v2 prefix; all others are appended to
+    /// the Advanced Trade prefix.
+    pub fn path(&self, endpoint: &str) -> String {
+        match endpoint
+            .strip_prefix(API_V2_PATH_PREFIX)
+            .filter(|rest| rest.starts_with('/'))
+        {
+            Some(rest) => format!("{}{}", self.v2_prefix, rest),
+            None => format!("{}{}", self.api_prefix, endpoint),
+        }
+    }
+}
+
+/// Ensure a prefix starts with a slash and does not end with one.
+fn normalize(prefix: String) -> String {
+    let trimmed = prefix.trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::paths;
+    use crate::models::TransferKind;
+
+    #[test]
+    fn test_default_paths() {
+        let endpoints = Endpoints::default();
+        assert_eq!(
+            endpoints.path(paths::ACCOUNTS),
+            "/api/v3/brokerage/accounts"
+        );
+        assert_eq!(
+            endpoints.path(&paths::account_transfers("abc", TransferKind::Deposit)),
+            "/v2/accounts/abc/deposits"
+        );
+    }
+
+    #[test]
+    fn test_overridden_paths() {
+        let endpoints = Endpoints::new()
+            .with_api_prefix("proxy/brokerage/")
+            .with_v2_prefix("/proxy/v2");
+        assert_eq!(endpoints.api_prefix(), "/proxy/brokerage");
+        assert_eq!(
+            endpoints.path(&paths::order("abc")),
+            "/proxy/brokerage/orders/historical/abc"
+        );
+        assert_eq!(
+            endpoints.path(&paths::account_transfers("abc", TransferKind::Withdrawal)),
+            "/proxy/v2/accounts/abc/withdrawals"
+        );
+
+        let root = Endpoints::new().with_api_prefix("/");
+        assert_eq!(root.path(paths::TIME), "/time");
+    }
+}
@@ -0,0 +1,117 @@
+//! API environments.
+
+use crate::constants::{API_BASE_URL, API_SANDBOX_BASE_URL, WS_SANDBOX_URL, WS_URL};
+
+/// WebSocket endpoint for authenticated channels in production.
+pub(crate) const WS_USER_URL: &str = "wss://advanced-trade-ws-user.coinbase.com";
+
+/// Endpoint prefixes served by the sandbox, relative to the Advanced Trade
+/// API path. The sandbox returns static responses for these only.
+const SANDBOX_ENDPOINTS: &[&str] = &["/accounts", "/orders"];
+
+/// The Coinbase environment a client connects to.
+///
+/// Used by both [`RestClientBuilder`](crate::RestClientBuilder) and
+/// [`WebSocketClientBuilder`](crate::ws::WebSocketClientBuilder).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Environment {
+    /// The production API.
+    #[default]
+    Production,
+    /// The sandbox, which only serves the accounts and orders endpoints.
+    Sandbox,
+    /// A custom deployment, such as a local mock server.
+    ///
+    /// All endpoints are assumed to be available.
+    Custom {
+        /// Base URL of the REST API, without the API path (e.g. `http://localhost:8080`).
+        rest_url: String,
+        /// URL of the WebSocket feed, used for public and user channels.
+        ws_url: String,
+    },
+}
+
+impl Environment {
+    /// Create a custom environment.
+    pub fn custom(rest_url: impl Into<String>, ws_url: impl Into<String>) -> Self {
+        Self::Custom {
+            rest_url: rest_url.into(),
+            ws_url: ws_url.into(),
+        }
+    }
+
+    /// Get the base URL of the REST API.
+    pub fn rest_url(&self) -> &str {
+        match self {
+            Self::Production => API_BASE_URL,
+            Self::Sandbox => API_SANDBOX_BASE_URL,
+            Self::Custom { rest_url, .. } => rest_url.trim_end_matches('/'),
+        }
+    }
+
+    /// Get the WebSocket URL for public market data channels.
+    pub fn ws_url(&self) -> &str {
+        match self {
+            Self::Production => WS_URL,
+            Self::Sandbox => WS_SANDBOX_URL,
+            Self::Custom { ws_url, .. } => ws_url,
+        }
+    }
+
+    /// Get the WebSocket URL for authenticated channels.
+    pub fn ws_user_url(&self) -> &str {
+        match self {
+            Self::Production => WS_USER_URL,
+            Self::Sandbox => WS_SANDBOX_URL,
+            Self::Custom { ws_url, .. } => ws_url,
+        }
+    }
+
+    /// Returns true for the sandbox.
+    pub fn is_sandbox(&self) -> bool {
+        matches!(self, Self::Sandbox)
+    }
+
+    /// Check if a REST endpoint is available in this environment.
+    ///
+    /// `endpoint` is the path passed to the client's request methods, such
+    /// as `/products` or `/orders/batch_cancel`.
+    pub fn supports(&self, endpoint: &str) -> bool {
+        if !self.is_sandbox() {
+            return true;
+        }
+        let path = endpoint.split('?').next().unwrap_or_default();
+        SANDBOX_ENDPOINTS.iter().any(|prefix| {
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment_urls() {
+        assert_eq!(Environment::default().rest_url(), API_BASE_URL);
+        assert_eq!(Environment::Sandbox.ws_user_url(), WS_SANDBOX_URL);
+
+        let custom = Environment::custom("http://localhost:8080/", "ws://localhost:8081");
+        assert_eq!(custom.rest_url(), "http://localhost:8080");
+        assert_eq!(custom.ws_url(), "ws://localhost:8081");
+        assert_eq!(custom.ws_user_url(), "ws://localhost:8081");
+    }
+
+    #[test]
+    fn test_sandbox_endpoints() {
+        let sandbox = Environment::Sandbox;
+        assert!(sandbox.supports("/accounts"));
+        assert!(sandbox.supports("/accounts/abc"));
+        assert!(sandbox.supports("/orders/historical/fills"));
+        assert!(!sandbox.supports("/products"));
+        assert!(!sandbox.supports("/ordersx"));
+        assert!(!sandbox.supports("/v2/accounts/abc/deposits"));
+        assert!(Environment::Production.supports("/products"));
+    }
+}
@@ -1,10 +1,16 @@
+use std::fmt;
 use std::time::Duration;
 
+use serde::Deserialize;
+
+use crate::risk::RiskViolation;
+
 /// Result type alias for coinbase-client operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Error types for the Coinbase client.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Configuration error (missing credentials, invalid format, etc.)
     #[error("Configuration error: {0}")]
@@ -31,6 +37,8 @@ pub enum Error {
         status: u16,
         /// Raw error response body
         body: Option<String>,
+        /// Error body parsed into its known fields
+        details: Option<Box<ApiErrorBody>>,
     },
 
     /// Rate limit exceeded
@@ -60,6 +68,210 @@ pub enum Error {
     /// WebSocket error
     #[error("WebSocket error: {0}")]
     WebSocket(String),
+
+    /// Event publishing error
+    #[error("Publish error: {0}")]
+    Publish(String),
+
+    /// File I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// An order was blocked by the client's order guard
+    #[error("Order blocked by safety guard: {0}")]
+    Guard(String),
+
+    /// The request did not complete before its deadline
+    #[error("Request deadline exceeded")]
+    DeadlineExceeded,
+
+    /// Client-side validation error
+    #[error("Validation error: {0}")]
+    Validation(ValidationError),
+
+    /// An order was rejected by a risk engine
+    #[error("Risk limit violated: {0}")]
+    Risk(RiskViolation),
+}
+
+/// A single invalid field found during client-side validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The name of the invalid field.
+    pub field: &'static str,
+    /// Why the field is invalid.
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.field, self.message)
+    }
+}
+
+/// All violations found while validating a request.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ValidationError {
+    /// The violations, in the order they were found.
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationError {
+    /// Returns true if a violation was recorded for the given field.
+    pub fn has_field(&self, field: &str) -> bool {
+        self.violations.iter().any(|v| v.field == field)
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Error code reported by the API.
+///
+/// Coinbase reports gRPC status names in the `error` field (e.g.,
+/// `PERMISSION_DENIED`) and sometimes the numeric status in `code`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiErrorCode {
+    /// The request was malformed or had invalid values.
+    InvalidArgument,
+    /// The requested resource does not exist.
+    NotFound,
+    /// The resource already exists.
+    AlreadyExists,
+    /// The API key lacks a required permission.
+    PermissionDenied,
+    /// A quota or rate limit was exhausted.
+    ResourceExhausted,
+    /// The request cannot be fulfilled in the current state.
+    FailedPrecondition,
+    /// The operation was aborted.
+    Aborted,
+    /// The server failed internally.
+    Internal,
+    /// The service is temporarily unavailable.
+    Unavailable,
+    /// The request was not authenticated.
+    Unauthenticated,
+    /// The request ran out of time.
+    DeadlineExceeded,
+    /// A code not recognized by this crate.
+    Other(String),
+}
+
+impl ApiErrorCode {
+    /// Parse a code as reported in the `error` field.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "INVALID_ARGUMENT" => Self::InvalidArgument,
+            "NOT_FOUND" => Self::NotFound,
+            "ALREADY_EXISTS" => Self::AlreadyExists,
+            "PERMISSION_DENIED" => Self::PermissionDenied,
+            "RESOURCE_EXHAUSTED" => Self::ResourceExhausted,
+            "FAILED_PRECONDITION" => Self::FailedPrecondition,
+            "ABORTED" => Self::Aborted,
+            "INTERNAL" => Self::Internal,
+            "UNAVAILABLE" => Self::Unavailable,
+            "UNAUTHENTICATED" => Self::Unauthenticated,
+            "DEADLINE_EXCEEDED" => Self::DeadlineExceeded,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// Parse a numeric gRPC status code.
+    pub fn from_number(code: i64) -> Self {
+        match code {
+            3 => Self::InvalidArgument,
+            4 => Self::DeadlineExceeded,
+            5 => Self::NotFound,
+            6 => Self::AlreadyExists,
+            7 => Self::PermissionDenied,
+            8 => Self::ResourceExhausted,
+            9 => Self::FailedPrecondition,
+            10 => Self::Aborted,
+            13 => Self::Internal,
+            14 => Self::Unavailable,
+            16 => Self::Unauthenticated,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ApiErrorCode {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(i64),
+            Name(String),
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Number(code) => Self::from_number(code),
+            Raw::Name(name) => name
+                .parse::<i64>()
+                .map(Self::from_number)
+                .unwrap_or_else(|_| Self::from_name(&name)),
+        })
+    }
+}
+
+/// Fields of an error response body.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ApiErrorBody {
+    /// Error name (e.g., "PERMISSION_DENIED").
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Error code, as a name or gRPC status number.
+    #[serde(default)]
+    pub code: Option<ApiErrorCode>,
+    /// Human-readable message.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Further detail about the error.
+    #[serde(default)]
+    pub error_details: Option<String>,
+    /// Why an order preview failed (e.g., "PREVIEW_INSUFFICIENT_FUND").
+    #[serde(default)]
+    pub preview_failure_reason: Option<String>,
+    /// Why an order was rejected (e.g., "INSUFFICIENT_FUND").
+    #[serde(default)]
+    pub new_order_failure_reason: Option<String>,
+}
+
+impl ApiErrorBody {
+    /// Parse an error response body, if it is a JSON object.
+    pub fn parse(body: &str) -> Option<Self> {
+        serde_json::from_str(body).ok()
+    }
+
+    /// Get the error code, preferring the `error` name over `code`.
+    pub fn error_code(&self) -> Option<ApiErrorCode> {
+        self.error
+            .as_deref()
+            .map(ApiErrorCode::from_name)
+            .or_else(|| self.code.clone())
+    }
+
+    /// Get the most descriptive message in the body.
+    pub fn message(&self) -> Option<&str> {
+        self.message
+            .as_deref()
+            .or(self.error_details.as_deref())
+            .or(self.error.as_deref())
+    }
 }
 
 impl Error {
@@ -79,11 +291,15 @@ impl Error {
     }
 
     /// Create a new API error.
+    ///
+    /// The body is parsed into [`ApiErrorBody`] when it is JSON.
     pub fn api(status: u16, message: impl Into<String>, body: Option<String>) -> Self {
+        let details = body.as_deref().and_then(ApiErrorBody::parse).map(Box::new);
         Self::Api {
             message: message.into(),
             status,
             body,
+            details,
         }
     }
 
@@ -105,16 +321,87 @@ impl Error {
         Self::WebSocket(msg.into())
     }
 
+    /// Create a new event publishing error.
+    pub fn publish(msg: impl Into<String>) -> Self {
+        Self::Publish(msg.into())
+    }
+
+    /// Create a new order guard error.
+    pub fn guard(msg: impl Into<String>) -> Self {
+        Self::Guard(msg.into())
+    }
+
+    /// Get the validation violations, if this is a validation error.
+    pub fn validation_error(&self) -> Option<&ValidationError> {
+        match self {
+            Self::Validation(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Get the violated limit, if this is a risk error.
+    pub fn risk_violation(&self) -> Option<&RiskViolation> {
+        match self {
+            Self::Risk(violation) => Some(violation),
+            _ => None,
+        }
+    }
+
+    /// Get the parsed error body, if this is an API error with a JSON body.
+    pub fn api_error_body(&self) -> Option<&ApiErrorBody> {
+        match self {
+            Self::Api { details, .. } => details.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Get the API error code, if this is an API error that reported one.
+    pub fn api_error_code(&self) -> Option<ApiErrorCode> {
+        self.api_error_body().and_then(ApiErrorBody::error_code)
+    }
+
+    /// Check if the request was rejected as unauthenticated: a 401 from the
+    /// API, or an authentication error raised by the client.
+    ///
+    /// See [`AuthDiagnostics`](crate::diagnostics::AuthDiagnostics) for
+    /// finding out why.
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, Self::Auth(_) | Self::Api { status: 401, .. })
+    }
+
     /// Check if this error is a rate limit error.
     pub fn is_rate_limited(&self) -> bool {
         matches!(self, Self::RateLimited { .. })
     }
 
+    /// Get how long the server asked to wait before retrying, if this is a
+    /// rate limit error that said so.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Check if the request ran out of time, either against the client
+    /// timeout or a [`RequestOptions`](crate::RequestOptions) deadline.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Self::DeadlineExceeded => true,
+            Self::Http(e) => e.is_timeout(),
+            _ => false,
+        }
+    }
+
     /// Check if this error is retryable.
     pub fn is_retryable(&self) -> bool {
         match self {
             Self::RateLimited { .. } => true,
+            // reqwest cannot tell connection errors apart in the browser.
+            #[cfg(not(target_arch = "wasm32"))]
             Self::Http(e) => e.is_timeout() || e.is_connect(),
+            #[cfg(target_arch = "wasm32")]
+            Self::Http(e) => e.is_timeout(),
             Self::Api { status, .. } => *status >= 500,
             _ => false,
         }
@@ -0,0 +1,279 @@
+//! Safety interlocks for order placement.
+//!
+//! An [`OrderGuard`] on a [`RestClient`] checks every
+//! [`OrdersApi::create`](crate::rest::OrdersApi::create) call before it is
+//! sent. It can:
+//!
+//! - turn order creation into a preview ([`dry_run`](OrderGuard::dry_run)),
+//! - reject orders worth more than a notional limit
+//!   ([`max_order_notional`](OrderGuard::max_order_notional)), and
+//! - reject orders when the client points at production but the code expects
+//!   the sandbox ([`require_sandbox`](OrderGuard::require_sandbox)).
+//!
+//! Blocked orders fail with [`Error::Guard`] before anything is sent.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::guard::OrderGuard;
+//! use coinbase_advanced::{Credentials, RestClient};
+//!
+//! # fn example() -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder()
+//!     .credentials(Credentials::from_env()?)
+//!     .order_guard(OrderGuard::new().max_order_notional("500".parse().unwrap()))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use rust_decimal::Decimal;
+
+use crate::client::RestClient;
+use crate::error::{Error, Result};
+use crate::models::{
+    CreateOrderRequest, CreateOrderResponse, GetBestBidAskParams, OrderConfiguration, OrderSide,
+    parse_decimal_field,
+};
+
+/// Failure reason reported for orders converted to previews.
+pub const DRY_RUN_FAILURE_REASON: &str = "DRY_RUN";
+
+/// Checks applied to orders before they are created.
+///
+/// The default guard allows every order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrderGuard {
+    dry_run: bool,
+    max_order_notional: Option<Decimal>,
+    require_sandbox: bool,
+}
+
+impl OrderGuard {
+    /// Create a guard that allows every order.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Preview orders instead of creating them.
+    ///
+    /// `create` then returns an unsuccessful [`CreateOrderResponse`] with the
+    /// failure reason [`DRY_RUN_FAILURE_REASON`] and the preview in
+    /// `error_response`.
+    pub fn dry_run(mut self, enabled: bool) -> Self {
+        self.dry_run = enabled;
+        self
+    }
+
+    /// Reject orders worth more than `max` in the product's quote currency.
+    ///
+    /// Limit and stop-limit orders are valued at their limit price. Market
+    /// orders sized in the base currency are valued at the current best bid
+    /// or ask, which costs one extra request.
+    pub fn max_order_notional(mut self, max: Decimal) -> Self {
+        self.max_order_notional = Some(max);
+        self
+    }
+
+    /// Reject orders unless the client targets the sandbox.
+    pub fn require_sandbox(mut self, required: bool) -> Self {
+        self.require_sandbox = required;
+        self
+    }
+
+    /// Returns true if orders are previewed instead of created.
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
+impl RestClient {
+    /// Apply the order guard to a request.
+    ///
+    /// Also checks the key can trade when
+    /// [`verify_permissions`](crate::RestClientBuilder::verify_permissions)
+    /// is enabled.
+    ///
+    /// Returns `Some` with the response to hand back instead of creating the
+    /// order when running dry, `None` when the order may be created.
+    pub(crate) async fn guard_order(
+        &self,
+        request: &CreateOrderRequest,
+    ) -> Result<Option<CreateOrderResponse>> {
+        self.ensure_can_trade().await?;
+        let guard = self.order_guard();
+
+        if guard.require_sandbox && !self.is_sandbox() {
+            return Err(Error::guard(format!(
+                "refusing to place order {} against production while sandbox is required",
+                request.client_order_id
+            )));
+        }
+
+        if let Some(max) = guard.max_order_notional {
+            let notional = self.order_notional(request).await?;
+            if notional > max {
+                return Err(Error::guard(format!(
+                    "order {} notional {} exceeds the limit of {}",
+                    request.client_order_id, notional, max
+                )));
+            }
+        }
+
+        if !guard.dry_run {
+            return Ok(None);
+        }
+        tracing::info!(client_order_id = %request.client_order_id, "dry run, previewing order");
+        let preview = self.orders().preview(request.clone()).await?;
+        Ok(Some(CreateOrderResponse {
+            success: false,
+            failure_reason: Some(DRY_RUN_FAILURE_REASON.to_string()),
+            order_id: None,
+            success_response: None,
+            error_response: serde_json::to_value(preview).ok(),
+        }))
+    }
+
+    /// Value of an order in its quote currency.
+    pub(crate) async fn order_notional(&self, request: &CreateOrderRequest) -> Result<Decimal> {
+        let (size, price) = match &request.order_configuration {
+            OrderConfiguration::MarketIoc { market_market_ioc } => {
+                if let Some(quote_size) = &market_market_ioc.quote_size {
+                    return parse_decimal_field("quote_size", quote_size);
+                }
+                let base_size = market_market_ioc.base_size.as_deref().unwrap_or("0");
+                let price = self.market_price(&request.product_id, request.side).await?;
+                (base_size, price)
+            }
+            OrderConfiguration::LimitGtc { limit_limit_gtc: c } => (
+                c.base_size.as_str(),
+                parse_decimal_field("limit_price", &c.limit_price)?,
+            ),
+            OrderConfiguration::LimitGtd { limit_limit_gtd: c } => (
+                c.base_size.as_str(),
+                parse_decimal_field("limit_price", &c.limit_price)?,
+            ),
+            OrderConfiguration::LimitFok { limit_limit_fok: c } => (
+                c.base_size.as_str(),
+                parse_decimal_field("limit_price", &c.limit_price)?,
+            ),
+            OrderConfiguration::SorLimitIoc { sor_limit_ioc: c } => (
+                c.base_size.as_str(),
+                parse_decimal_field("limit_price", &c.limit_price)?,
+            ),
+            OrderConfiguration::StopLimitGtc {
+                stop_limit_stop_limit_gtc: c,
+            } => (
+                c.base_size.as_str(),
+                parse_decimal_field("limit_price", &c.limit_price)?,
+            ),
+            OrderConfiguration::StopLimitGtd {
+                stop_limit_stop_limit_gtd: c,
+            } => (
+                c.base_size.as_str(),
+                parse_decimal_field("limit_price", &c.limit_price)?,
+            ),
+            OrderConfiguration::TwapLimitGtd { twap_limit_gtd: c } => {
+                if let Some(quote_size) = &c.quote_size {
+                    return parse_decimal_field("quote_size", quote_size);
+                }
+                (
+                    c.base_size.as_deref().unwrap_or("0"),
+                    parse_decimal_field("limit_price", &c.limit_price)?,
+                )
+            }
+            OrderConfiguration::Custom(_) => {
+                return Err(Error::guard(format!(
+                    "cannot value order {} with a custom configuration",
+                    request.client_order_id
+                )));
+            }
+        };
+        Ok(parse_decimal_field("base_size", size)? * price)
+    }
+
+    /// Price a market order would trade at: the best ask for buys and the
+    /// best bid for sells.
+    async fn market_price(&self, product_id: &str, side: OrderSide) -> Result<Decimal> {
+        let response = self
+            .products()
+            .get_best_bid_ask(GetBestBidAskParams::new().product_ids([product_id]))
+            .await?;
+        let book = response
+            .pricebooks
+            .iter()
+            .find(|b| b.product_id == product_id);
+        let level = book.and_then(|b| match side {
+            OrderSide::Buy => b.asks.first().or(b.bids.first()),
+            OrderSide::Sell => b.bids.first().or(b.asks.first()),
+        });
+        let level = level.ok_or_else(|| {
+            Error::guard(format!(
+                "no price available for {} to check the order notional",
+                product_id
+            ))
+        })?;
+        parse_decimal_field("price", &level.price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(guard: OrderGuard) -> RestClient {
+        RestClient::builder().order_guard(guard).build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_max_order_notional() {
+        let client = client(OrderGuard::new().max_order_notional(Decimal::from(500)));
+
+        let small = CreateOrderRequest::new(
+            "small",
+            "BTC-USD",
+            OrderSide::Buy,
+            OrderConfiguration::limit_gtc("0.001", "50000", false),
+        );
+        assert!(client.guard_order(&small).await.unwrap().is_none());
+
+        let large = CreateOrderRequest::new(
+            "large",
+            "BTC-USD",
+            OrderSide::Buy,
+            OrderConfiguration::market_buy_quote("1000"),
+        );
+        let err = client.guard_order(&large).await.unwrap_err();
+        assert!(matches!(err, Error::Guard(_)));
+
+        // Custom configurations cannot be valued, so they are refused.
+        let custom = CreateOrderRequest::new(
+            "custom",
+            "BTC-USD",
+            OrderSide::Buy,
+            OrderConfiguration::custom("new_order_type", serde_json::json!({ "size": "1" })),
+        );
+        let err = client.guard_order(&custom).await.unwrap_err();
+        assert!(matches!(err, Error::Guard(_)));
+    }
+
+    #[tokio::test]
+    async fn test_require_sandbox() {
+        let request = CreateOrderRequest::new(
+            "order",
+            "BTC-USD",
+            OrderSide::Sell,
+            OrderConfiguration::market_sell("0.01"),
+        );
+
+        let production = client(OrderGuard::new().require_sandbox(true));
+        assert!(production.guard_order(&request).await.is_err());
+
+        let sandbox = RestClient::builder()
+            .sandbox(true)
+            .order_guard(OrderGuard::new().require_sandbox(true))
+            .build()
+            .unwrap();
+        assert!(sandbox.guard_order(&request).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,530 @@
+//! Streaming technical indicators.
+//!
+//! Enabled by the `indicators` feature. Each [`Indicator`] is fed one
+//! [`Bar`] at a time and keeps only the state it needs, so it can follow a
+//! live feed as well as a fetched history. Bars are built from REST
+//! [`Candle`]s, WebSocket [`CandleUpdate`]s or [`ClosedCandle`]s, parsing
+//! their string prices into [`Decimal`]s.
+//!
+//! Indicators expect closed bars. The `candles` channel repeats the candle
+//! in progress every time it changes; wrap an indicator in [`Live`] to have
+//! each repeat replace the previous version of its bar instead of being
+//! counted again.
+//!
+//! - [`Sma`] - simple moving average of the close.
+//! - [`Ema`] - exponential moving average of the close.
+//! - [`Rsi`] - relative strength index, with Wilder's smoothing.
+//! - [`Vwap`] - volume-weighted average price, optionally reset daily.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::RestClient;
+//! use coinbase_advanced::indicators::{Bar, Ema, Indicator, Rsi};
+//! use coinbase_advanced::models::Granularity;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder().build()?;
+//! let candles = client
+//!     .products()
+//!     .get_candles_range("BTC-USD", 1704067200, 1706745600, Granularity::OneHour)
+//!     .await?;
+//!
+//! let mut ema = Ema::new(20);
+//! let mut rsi = Rsi::new(14);
+//! for candle in &candles {
+//!     let bar = Bar::try_from(candle)?;
+//!     if let (Some(ema), Some(rsi)) = (ema.update(&bar), rsi.update(&bar)) {
+//!         println!("{} ema {} rsi {}", bar.start, ema.round_dp(2), rsi.round_dp(1));
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+
+use crate::candles::ClosedCandle;
+use crate::error::{Error, Result};
+use crate::models::Candle;
+use crate::ws::CandleUpdate;
+
+/// A single OHLCV bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bar {
+    /// Start of the bar.
+    pub start: DateTime<Utc>,
+    /// Open price.
+    pub open: Decimal,
+    /// High price.
+    pub high: Decimal,
+    /// Low price.
+    pub low: Decimal,
+    /// Close price.
+    pub close: Decimal,
+    /// Volume.
+    pub volume: Decimal,
+}
+
+impl Bar {
+    /// Parse a bar from a Unix start time and string prices.
+    fn parse(
+        start: &str,
+        open: &str,
+        high: &str,
+        low: &str,
+        close: &str,
+        volume: &str,
+    ) -> Result<Self> {
+        let invalid = |field: &str, value: &str| {
+            Error::parse(format!("invalid candle {}: {:?}", field, value), None)
+        };
+        let price = |field: &str, value: &str| -> Result<Decimal> {
+            value.parse().map_err(|_| invalid(field, value))
+        };
+        let start = start
+            .parse()
+            .ok()
+            .and_then(|start| DateTime::from_timestamp(start, 0))
+            .ok_or_else(|| invalid("start", start))?;
+        Ok(Self {
+            start,
+            open: price("open", open)?,
+            high: price("high", high)?,
+            low: price("low", low)?,
+            close: price("close", close)?,
+            volume: price("volume", volume)?,
+        })
+    }
+
+    /// Get the typical price: the average of the high, low and close.
+    pub fn typical_price(&self) -> Decimal {
+        (self.high + self.low + self.close) / Decimal::from(3)
+    }
+}
+
+impl TryFrom<&Candle> for Bar {
+    type Error = Error;
+
+    fn try_from(candle: &Candle) -> Result<Self> {
+        Self::parse(
+            &candle.start,
+            &candle.open,
+            &candle.high,
+            &candle.low,
+            &candle.close,
+            &candle.volume,
+        )
+    }
+}
+
+impl TryFrom<&CandleUpdate> for Bar {
+    type Error = Error;
+
+    fn try_from(update: &CandleUpdate) -> Result<Self> {
+        Self::parse(
+            &update.start,
+            &update.open,
+            &update.high,
+            &update.low,
+            &update.close,
+            &update.volume,
+        )
+    }
+}
+
+impl From<&ClosedCandle> for Bar {
+    fn from(candle: &ClosedCandle) -> Self {
+        Self {
+            start: candle.start,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume: candle.volume,
+        }
+    }
+}
+
+/// An indicator computed one bar at a time.
+pub trait Indicator {
+    /// Add a closed bar, returning the new value once enough bars have been
+    /// seen.
+    fn update(&mut self, bar: &Bar) -> Option<Decimal>;
+
+    /// Get the latest value, if enough bars have been seen.
+    fn value(&self) -> Option<Decimal>;
+
+    /// Forget every bar seen.
+    fn reset(&mut self);
+
+    /// Add a REST candle, parsing its prices.
+    fn update_candle(&mut self, candle: &Candle) -> Result<Option<Decimal>> {
+        Ok(self.update(&Bar::try_from(candle)?))
+    }
+}
+
+/// Simple moving average of the close over a number of bars.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sma {
+    period: usize,
+    window: VecDeque<Decimal>,
+    sum: Decimal,
+}
+
+impl Sma {
+    /// Average over `period` bars (at least 1).
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            sum: Decimal::ZERO,
+        }
+    }
+}
+
+impl Indicator for Sma {
+    fn update(&mut self, bar: &Bar) -> Option<Decimal> {
+        self.window.push_back(bar.close);
+        self.sum += bar.close;
+        if self.window.len() > self.period {
+            self.sum -= self.window.pop_front().unwrap_or_default();
+        }
+        self.value()
+    }
+
+    fn value(&self) -> Option<Decimal> {
+        (self.window.len() == self.period).then(|| self.sum / Decimal::from(self.period))
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum = Decimal::ZERO;
+    }
+}
+
+/// Exponential moving average of the close.
+///
+/// Seeded with the simple average of the first `period` bars, then
+/// smoothed by `2 / (period + 1)` per bar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ema {
+    alpha: Decimal,
+    seed: Sma,
+    value: Option<Decimal>,
+}
+
+impl Ema {
+    /// Average over `period` bars (at least 1).
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            alpha: Decimal::TWO / Decimal::from(period + 1),
+            seed: Sma::new(period),
+            value: None,
+        }
+    }
+}
+
+impl Indicator for Ema {
+    fn update(&mut self, bar: &Bar) -> Option<Decimal> {
+        self.value = match self.value {
+            Some(previous) => Some(previous + self.alpha * (bar.close - previous)),
+            None => self.seed.update(bar),
+        };
+        self.value
+    }
+
+    fn value(&self) -> Option<Decimal> {
+        self.value
+    }
+
+    fn reset(&mut self) {
+        self.seed.reset();
+        self.value = None;
+    }
+}
+
+/// Relative strength index of the close, between 0 and 100.
+///
+/// Uses Wilder's smoothing: the first averages of gains and losses are
+/// simple averages over `period` changes, after which each new change is
+/// weighted by `1 / period`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rsi {
+    period: usize,
+    previous_close: Option<Decimal>,
+    changes: usize,
+    average_gain: Decimal,
+    average_loss: Decimal,
+}
+
+impl Rsi {
+    /// Compute over `period` price changes (at least 1).
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            previous_close: None,
+            changes: 0,
+            average_gain: Decimal::ZERO,
+            average_loss: Decimal::ZERO,
+        }
+    }
+}
+
+impl Indicator for Rsi {
+    fn update(&mut self, bar: &Bar) -> Option<Decimal> {
+        let previous = self.previous_close.replace(bar.close)?;
+        let change = bar.close - previous;
+        let gain = change.max(Decimal::ZERO);
+        let loss = (-change).max(Decimal::ZERO);
+
+        let period = Decimal::from(self.period);
+        if self.changes < self.period {
+            // Accumulate simple averages for the seed.
+            self.average_gain += gain / period;
+            self.average_loss += loss / period;
+        } else {
+            self.average_gain = (self.average_gain * (period - Decimal::ONE) + gain) / period;
+            self.average_loss = (self.average_loss * (period - Decimal::ONE) + loss) / period;
+        }
+        self.changes += 1;
+        self.value()
+    }
+
+    fn value(&self) -> Option<Decimal> {
+        if self.changes < self.period {
+            return None;
+        }
+        let hundred = Decimal::ONE_HUNDRED;
+        if self.average_loss.is_zero() {
+            return Some(if self.average_gain.is_zero() {
+                hundred / Decimal::TWO
+            } else {
+                hundred
+            });
+        }
+        let strength = self.average_gain / self.average_loss;
+        Some(hundred - hundred / (Decimal::ONE + strength))
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.period);
+    }
+}
+
+/// Volume-weighted average of the typical price.
+///
+/// Accumulates from the first bar, or from the first bar of each UTC day
+/// when created with [`Vwap::daily`]. Bars without volume do not move it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Vwap {
+    daily: bool,
+    day: Option<NaiveDate>,
+    price_volume: Decimal,
+    volume: Decimal,
+}
+
+impl Vwap {
+    /// Accumulate over every bar until [`reset`](Indicator::reset).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start over at the first bar of each UTC day.
+    pub fn daily() -> Self {
+        Self {
+            daily: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl Indicator for Vwap {
+    fn update(&mut self, bar: &Bar) -> Option<Decimal> {
+        let day = bar.start.date_naive();
+        if self.daily && self.day.is_some_and(|current| current != day) {
+            self.price_volume = Decimal::ZERO;
+            self.volume = Decimal::ZERO;
+        }
+        self.day = Some(day);
+        self.price_volume += bar.typical_price() * bar.volume;
+        self.volume += bar.volume;
+        self.value()
+    }
+
+    fn value(&self) -> Option<Decimal> {
+        (!self.volume.is_zero()).then(|| self.price_volume / self.volume)
+    }
+
+    fn reset(&mut self) {
+        *self = Self {
+            daily: self.daily,
+            ..Self::default()
+        };
+    }
+}
+
+/// An indicator fed with the candle in progress as well as closed ones.
+///
+/// Bars with the same start as the previous bar replace it: the indicator
+/// is recomputed from its state before that bar. A bar with a new start
+/// closes the previous one. Bars older than the previous one are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Live<I> {
+    /// State after the last closed bar.
+    closed: I,
+    /// State including the bar in progress.
+    current: I,
+    start: Option<DateTime<Utc>>,
+}
+
+impl<I: Indicator + Clone> Live<I> {
+    /// Wrap an indicator.
+    pub fn new(indicator: I) -> Self {
+        Self {
+            closed: indicator.clone(),
+            current: indicator,
+            start: None,
+        }
+    }
+
+    /// Add a bar, or replace the bar in progress if it has the same start.
+    pub fn update(&mut self, bar: &Bar) -> Option<Decimal> {
+        match self.start {
+            Some(start) if bar.start < start => return self.current.value(),
+            Some(start) if bar.start == start => {}
+            _ => {
+                self.closed = self.current.clone();
+                self.start = Some(bar.start);
+            }
+        }
+        self.current = self.closed.clone();
+        self.current.update(bar)
+    }
+
+    /// Add a candle update from the `candles` channel.
+    pub fn update_candle(&mut self, update: &CandleUpdate) -> Result<Option<Decimal>> {
+        Ok(self.update(&Bar::try_from(update)?))
+    }
+
+    /// Get the latest value, including the bar in progress.
+    pub fn value(&self) -> Option<Decimal> {
+        self.current.value()
+    }
+
+    /// Get the value as of the last closed bar.
+    pub fn closed_value(&self) -> Option<Decimal> {
+        self.closed.value()
+    }
+
+    /// Get the wrapped indicator, including the bar in progress.
+    pub fn indicator(&self) -> &I {
+        &self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(minute: i64, close: &str, volume: &str) -> Bar {
+        let close: Decimal = close.parse().unwrap();
+        Bar {
+            start: DateTime::from_timestamp(minute * 60, 0).unwrap(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: volume.parse().unwrap(),
+        }
+    }
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_moving_averages() {
+        let closes = ["1", "2", "3", "4", "5"];
+        let mut sma = Sma::new(3);
+        let mut ema = Ema::new(3);
+        let values: Vec<_> = closes
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let bar = bar(i as i64, c, "1");
+                (sma.update(&bar), ema.update(&bar))
+            })
+            .collect();
+
+        assert_eq!(values[1], (None, None));
+        assert_eq!(values[2], (Some(dec("2")), Some(dec("2"))));
+        // EMA: 2 + 0.5 * (4 - 2) = 3, then 3 + 0.5 * (5 - 3) = 4.
+        assert_eq!(values[3], (Some(dec("3")), Some(dec("3"))));
+        assert_eq!(values[4], (Some(dec("4")), Some(dec("4"))));
+
+        sma.reset();
+        assert_eq!(sma.value(), None);
+    }
+
+    #[test]
+    fn test_rsi() {
+        let mut rsi = Rsi::new(2);
+        assert_eq!(rsi.update(&bar(0, "10", "1")), None);
+        assert_eq!(rsi.update(&bar(1, "12", "1")), None);
+        // Gains 2, losses 1 over two changes: RS = 2, RSI = 100 - 100 / 3.
+        let value = rsi.update(&bar(2, "11", "1")).unwrap();
+        assert_eq!(value.round_dp(4), dec("66.6667"));
+        // Wilder: gain (1 + 0) / 2 = 0.5, loss (0.5 + 3) / 2 = 1.75.
+        let value = rsi.update(&bar(3, "8", "1")).unwrap();
+        assert_eq!(value.round_dp(4), dec("22.2222"));
+
+        let mut flat = Rsi::new(1);
+        flat.update(&bar(0, "5", "1"));
+        assert_eq!(flat.update(&bar(1, "5", "1")), Some(dec("50")));
+    }
+
+    #[test]
+    fn test_vwap() {
+        let mut vwap = Vwap::daily();
+        vwap.update(&bar(0, "10", "1"));
+        assert_eq!(vwap.update(&bar(1, "20", "3")), Some(dec("17.5")));
+        assert_eq!(vwap.update(&bar(2, "30", "0")), Some(dec("17.5")));
+        // The next UTC day starts over.
+        assert_eq!(vwap.update(&bar(24 * 60, "40", "2")), Some(dec("40")));
+
+        let candle = Candle {
+            start: "60".to_string(),
+            low: "9".to_string(),
+            high: "12".to_string(),
+            open: "10".to_string(),
+            close: "12".to_string(),
+            volume: "2".to_string(),
+        };
+        let mut vwap = Vwap::new();
+        assert_eq!(vwap.update_candle(&candle).unwrap(), Some(dec("11")));
+        let mut invalid = candle.clone();
+        invalid.close = "n/a".to_string();
+        assert!(vwap.update_candle(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_live_updates() {
+        let mut live = Live::new(Sma::new(2));
+        live.update(&bar(0, "10", "1"));
+        assert_eq!(live.update(&bar(1, "20", "1")), Some(dec("15")));
+        // The candle in progress changes: replaced, not added.
+        assert_eq!(live.update(&bar(1, "30", "1")), Some(dec("20")));
+        assert_eq!(live.closed_value(), None);
+        // A new candle closes the previous one at 30.
+        assert_eq!(live.update(&bar(2, "40", "1")), Some(dec("35")));
+        assert_eq!(live.closed_value(), Some(dec("20")));
+        // Stale bars are ignored.
+        assert_eq!(live.update(&bar(0, "99", "1")), Some(dec("35")));
+    }
+}
@@ -0,0 +1,332 @@
+//! Latency budget tracing for order placement.
+//!
+//! Placing an order passes through several stages, each of which adds to the
+//! time before the order is live on the exchange:
+//!
+//! 1. Builder validation
+//! 2. JWT signing
+//! 3. HTTP send (until the response headers arrive)
+//! 4. Response parsing
+//! 5. Confirmation on the WebSocket user channel
+//!
+//! The REST stages run inside a single `order_placement` tracing span with
+//! `sign_jwt`, `http_send` and `parse_response` child spans, and are summarized
+//! in an [`OrderLatencyReport`]. The confirmation stage is filled in by an
+//! [`OrderLatencyTracker`] watching the user channel.
+//!
+//! For monitoring execution quality without tracing, each report also
+//! carries a [`Latency`] with the round trip and Coinbase's own processing
+//! time.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::latency::OrderLatencyTracker;
+//! use coinbase_advanced::{Credentials, RestClient};
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//! use futures::StreamExt;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let credentials = Credentials::from_env()?;
+//! let rest = RestClient::builder().credentials(credentials.clone()).build()?;
+//! let ws = WebSocketClient::builder().credentials(credentials).build()?;
+//! let mut stream = ws.connect().await?;
+//! ws.subscribe(&[Channel::User]).await?;
+//!
+//! let (_, report) = rest
+//!     .market_order()
+//!     .buy("BTC-USD")
+//!     .quote_size("10.00")
+//!     .send_with_latency()
+//!     .await?;
+//!
+//! let mut tracker = OrderLatencyTracker::new();
+//! tracker.track(report);
+//!
+//! while let Some(message) = stream.next().await {
+//!     for report in tracker.observe(&message?) {
+//!         println!("{}", report);
+//!     }
+//!     if tracker.is_empty() {
+//!         break;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{DATE, HeaderMap};
+
+use crate::runtime::Instant;
+use crate::ws::{Events, Message};
+
+/// Response header in which Coinbase's proxy reports the milliseconds the
+/// upstream service spent on a request.
+const UPSTREAM_SERVICE_TIME_HEADER: &str = "x-envoy-upstream-service-time";
+
+/// Timing of a single REST request, measured around the HTTP round trip.
+///
+/// Part of an [`OrderLatencyReport`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Latency {
+    /// When the signed request was handed to the HTTP client, on the final
+    /// attempt if it was retried after a rate limit.
+    pub sent_at: Instant,
+    /// When the response headers arrived.
+    pub received_at: Instant,
+    /// Time from sending the request until the response headers arrived.
+    ///
+    /// Only the final attempt is measured, so waits for rate-limit retries
+    /// are not included.
+    pub rtt: Duration,
+    /// Time Coinbase reports spending on the request, if it sent one.
+    pub server_processing: Option<Duration>,
+    /// Server clock from the response's `Date` header, to the second.
+    pub server_time: Option<DateTime<Utc>>,
+}
+
+impl Latency {
+    /// Measure a request from its send time, receive time and response headers.
+    pub(crate) fn from_response(
+        sent_at: Instant,
+        received_at: Instant,
+        headers: &HeaderMap,
+    ) -> Self {
+        let server_processing = headers
+            .get(UPSTREAM_SERVICE_TIME_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+        let server_time = headers
+            .get(DATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|s| DateTime::parse_from_rfc2822(s).ok())
+            .map(|t| t.with_timezone(&Utc));
+
+        Self {
+            sent_at,
+            received_at,
+            rtt: received_at.saturating_duration_since(sent_at),
+            server_processing,
+            server_time,
+        }
+    }
+
+    /// Round-trip time not spent processing on the server, i.e. the network
+    /// and any queueing in front of it.
+    pub fn network(&self) -> Option<Duration> {
+        self.server_processing
+            .map(|processing| self.rtt.saturating_sub(processing))
+    }
+}
+
+impl fmt::Display for Latency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rtt={:?}", self.rtt)?;
+        if let Some(processing) = self.server_processing {
+            write!(f, " server_processing={:?}", processing)?;
+        }
+        Ok(())
+    }
+}
+
+/// Summary of where time was spent placing a single order.
+#[derive(Debug, Clone)]
+pub struct OrderLatencyReport {
+    /// The client order ID of the order.
+    pub client_order_id: String,
+    /// When order placement started.
+    pub started_at: Instant,
+    /// Time spent validating the order builder.
+    pub validation: Duration,
+    /// Time spent generating the JWT and request headers.
+    pub signing: Duration,
+    /// Time from sending the request until the response headers arrived,
+    /// including any rate-limit retries.
+    pub transport: Duration,
+    /// Time spent reading and deserializing the response body.
+    pub parse: Duration,
+    /// Round trip of the HTTP request, or `None` if no request was sent
+    /// (a dry run).
+    pub latency: Option<Latency>,
+    /// Time from the start of placement until the order was seen on the
+    /// user channel, if it has been confirmed.
+    pub confirmation: Option<Duration>,
+}
+
+impl OrderLatencyReport {
+    /// Total time spent in the REST request, from validation to parsed response.
+    pub fn placement(&self) -> Duration {
+        self.validation + self.signing + self.transport + self.parse
+    }
+
+    /// End-to-end latency: the confirmation time if known, otherwise the placement time.
+    pub fn total(&self) -> Duration {
+        self.confirmation.unwrap_or_else(|| self.placement())
+    }
+
+    /// Record that the order was confirmed at the given instant.
+    pub fn confirm_at(&mut self, at: Instant) {
+        self.confirmation = Some(at.saturating_duration_since(self.started_at));
+    }
+}
+
+impl fmt::Display for OrderLatencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "order {}: validation={:?} signing={:?} transport={:?} parse={:?} placement={:?}",
+            self.client_order_id,
+            self.validation,
+            self.signing,
+            self.transport,
+            self.parse,
+            self.placement()
+        )?;
+        if let Some(confirmation) = self.confirmation {
+            write!(f, " confirmation={:?}", confirmation)?;
+        }
+        Ok(())
+    }
+}
+
+/// Completes [`OrderLatencyReport`]s when their orders appear on the user channel.
+#[derive(Debug, Default)]
+pub struct OrderLatencyTracker {
+    pending: HashMap<String, OrderLatencyReport>,
+}
+
+impl OrderLatencyTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start waiting for confirmation of an order.
+    pub fn track(&mut self, report: OrderLatencyReport) {
+        self.pending.insert(report.client_order_id.clone(), report);
+    }
+
+    /// Inspect a WebSocket message, returning the reports of any orders it confirms.
+    ///
+    /// Confirmed orders are no longer tracked.
+    pub fn observe(&mut self, message: &Message) -> Vec<OrderLatencyReport> {
+        let Events::User(events) = &message.events else {
+            return Vec::new();
+        };
+
+        let now = Instant::now();
+        let mut confirmed = Vec::new();
+        for order in events.iter().flat_map(|e| &e.orders) {
+            if let Some(mut report) = self.pending.remove(&order.client_order_id) {
+                report.confirm_at(now);
+                tracing::debug!(%report, "order confirmed");
+                confirmed.push(report);
+            }
+        }
+        confirmed
+    }
+
+    /// Number of orders awaiting confirmation.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns true if no orders are awaiting confirmation.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(client_order_id: &str) -> OrderLatencyReport {
+        OrderLatencyReport {
+            client_order_id: client_order_id.to_string(),
+            started_at: Instant::now(),
+            validation: Duration::from_micros(10),
+            signing: Duration::from_micros(200),
+            transport: Duration::from_millis(30),
+            parse: Duration::from_micros(50),
+            latency: None,
+            confirmation: None,
+        }
+    }
+
+    fn user_message(client_order_id: &str) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "channel": "user",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": 1,
+            "events": [{
+                "type": "update",
+                "orders": [{
+                    "client_order_id": client_order_id,
+                    "order_id": "order-1",
+                    "order_side": "BUY",
+                    "order_type": "MARKET",
+                    "product_id": "BTC-USD",
+                    "status": "OPEN"
+                }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_latency_from_response() {
+        let mut headers = HeaderMap::new();
+        headers.insert(UPSTREAM_SERVICE_TIME_HEADER, "12".parse().unwrap());
+        headers.insert(DATE, "Mon, 15 Jan 2024 12:00:00 GMT".parse().unwrap());
+
+        let sent_at = Instant::now();
+        let latency =
+            Latency::from_response(sent_at, sent_at + Duration::from_millis(40), &headers);
+        assert_eq!(latency.rtt, Duration::from_millis(40));
+        assert_eq!(latency.server_processing, Some(Duration::from_millis(12)));
+        assert_eq!(latency.network(), Some(Duration::from_millis(28)));
+        assert_eq!(
+            latency.server_time.unwrap().to_rfc3339(),
+            "2024-01-15T12:00:00+00:00"
+        );
+
+        let latency = Latency::from_response(sent_at, sent_at, &HeaderMap::new());
+        assert_eq!(latency.server_processing, None);
+        assert_eq!(latency.server_time, None);
+        assert_eq!(latency.to_string(), "rtt=0ns");
+    }
+
+    #[test]
+    fn test_report_totals() {
+        let mut report = report("a");
+        assert_eq!(report.placement(), Duration::from_micros(30_260));
+        assert_eq!(report.total(), report.placement());
+
+        report.confirm_at(report.started_at + Duration::from_millis(80));
+        assert_eq!(report.total(), Duration::from_millis(80));
+        assert!(report.to_string().contains("confirmation=80ms"));
+    }
+
+    #[test]
+    fn test_tracker_confirms_matching_order() {
+        let mut tracker = OrderLatencyTracker::new();
+        tracker.track(report("a"));
+        tracker.track(report("b"));
+
+        assert!(tracker.observe(&user_message("other")).is_empty());
+
+        let confirmed = tracker.observe(&user_message("a"));
+        assert_eq!(confirmed.len(), 1);
+        assert_eq!(confirmed[0].client_order_id, "a");
+        assert!(confirmed[0].confirmation.is_some());
+        assert_eq!(tracker.len(), 1);
+    }
+}
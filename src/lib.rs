@@ -9,6 +9,55 @@
 //! - Async/await support with tokio
 //! - Support for both production and sandbox environments
 //!
+//! ## Cargo Features
+//!
+//! - `tokio` (default) - WebSocket client, recording and replay, recurring
+//!   purchases, and streamed response parsing on Tokio's blocking pool.
+//!   Without it the REST client only uses executor-independent timers and
+//!   locks, so it can be polled from other runtimes. The HTTP transport (`reqwest`) still needs a
+//!   Tokio reactor to drive its connections; bridge it with a compatibility
+//!   layer such as `async-compat` when embedding into another executor.
+//! - `ring` (default) / `p256` - JWT signing backend.
+//! - `wasm` - browser support for the REST client. Timers use the browser's
+//!   clock and no signing backend is required, so public endpoints such as
+//!   products and candles work without credentials. Build with
+//!   `default-features = false, features = ["wasm"]`; add `p256` to sign
+//!   requests as well. A dependency needs `getrandom`'s browser backend, so
+//!   set `RUSTFLAGS='--cfg getrandom_backend="wasm_js"'` when building for
+//!   `wasm32-unknown-unknown`.
+//! - `capture-unknown` - keep response fields the models do not know about
+//!   in an `extra` map on [`Order`](models::Order), [`Fill`](models::Fill),
+//!   [`Product`](models::Product) and the ticker, trade and order updates of
+//!   the WebSocket feed, so new data is usable before the types catch up.
+//!   Takes precedence over `strict-models` for those types.
+//! - `debug-tools` - scrubbing credentials and identifiers out of response
+//!   bodies that failed to parse, for bug reports. See [`debug_tools`].
+//! - `encrypted-keys` - loading password-protected PKCS#8 private keys with
+//!   [`Credentials::from_encrypted_pem`].
+//! - `gzip` / `brotli` / `deflate` - compressed responses. Each scheme
+//!   enabled here is requested by default; see
+//!   [`RestClientBuilder::compression`].
+//! - `cache` - persistent caching of candles and products across restarts.
+//!   See [`cache`].
+//! - `indicators` - streaming SMA, EMA, RSI and VWAP over candles. See
+//!   [`indicators`].
+//! - `metrics` - request metrics through the `metrics` crate.
+//! - `nats` - NATS publisher for WebSocket messages.
+//! - `paper` - a paper trading client that fills orders against live
+//!   WebSocket quotes with simulated balances. See [`paper`].
+//! - `rustls` - custom TLS settings for the REST and WebSocket clients:
+//!   private root certificates, pinned roots and client certificates. See
+//!   [`TlsConfig`].
+//! - `socks` - SOCKS5 proxies for the REST client. The WebSocket client
+//!   supports them without it. See [`Proxy`].
+//! - `strict-models` - reject response fields the models do not know
+//!   about, so API changes surface as parse errors instead of silently
+//!   dropped data.
+//! - `test-utils` - response fixtures, a mock REST server and WebSocket
+//!   message builders for testing downstream code. See [`test_utils`].
+//! - `webhook` - signed delivery of order and fill events to an HTTP
+//!   endpoint. See [`webhook`].
+//!
 //! ## Quick Start
 //!
 //! ```no_run
@@ -51,22 +100,88 @@
 //!     .build()
 //!     .unwrap();
 //! ```
+//!
+//! The sandbox only serves the accounts and orders endpoints; other requests
+//! fail with [`Error::Config`] before they are sent. Use [`Environment`] to
+//! point the REST and WebSocket clients at a custom deployment instead.
+
+#[cfg(not(any(feature = "ring", feature = "p256", feature = "wasm")))]
+compile_error!("at least one signing backend must be enabled: `ring` or `p256`");
+
+#[cfg(all(target_arch = "wasm32", feature = "tokio"))]
+compile_error!(
+    "the `tokio` feature is not supported on wasm32; use `default-features = false, features = [\"wasm\"]`"
+);
 
 mod client;
 mod constants;
 mod credentials;
+mod endpoints;
+mod environment;
 mod error;
 mod jwt;
+mod proxy;
 
+pub mod alerts;
+pub mod audit;
+pub mod balance;
+pub mod batch;
+pub mod book;
+#[cfg(feature = "cache")]
+pub mod cache;
+pub mod candles;
+pub mod catalog;
+#[cfg(feature = "debug-tools")]
+pub mod debug_tools;
+pub mod diagnostics;
+pub mod guard;
+#[cfg(feature = "indicators")]
+pub mod indicators;
+pub mod latency;
 pub mod models;
+pub mod oco;
+pub mod order_ids;
+#[cfg(feature = "paper")]
+pub mod paper;
+pub mod pnl;
+pub mod price_feed;
+pub mod publish;
+pub mod quote_board;
 pub mod rate_limit;
+pub mod reconcile;
+#[cfg(feature = "tokio")]
+pub mod recorder;
+pub mod recovery;
+#[cfg(feature = "tokio")]
+pub mod replay;
 pub mod rest;
+pub mod risk;
+mod runtime;
+#[cfg(feature = "tokio")]
+pub mod scheduler;
+pub mod telemetry;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "rustls")]
+mod tls;
+pub mod watchlist;
+#[cfg(feature = "webhook")]
+pub mod webhook;
 pub mod ws;
 
 // Re-export main types.
-pub use client::{RestClient, RestClientBuilder};
+pub use client::{RequestOptions, ResponseEnvelope, RestClient, RestClientBuilder};
 pub use credentials::Credentials;
-pub use error::{Error, Result};
+pub use endpoints::Endpoints;
+pub use environment::Environment;
+pub use error::{ApiErrorBody, ApiErrorCode, Error, Result, ValidationError, Violation};
+pub use proxy::{Proxy, ProxyScheme};
+pub use reqwest::Method;
+pub use rust_decimal::Decimal;
+#[cfg(feature = "rustls")]
+pub use rustls;
+#[cfg(feature = "rustls")]
+pub use tls::{TlsConfig, TlsConfigBuilder};
 
 // Re-export API types for convenience.
 pub use rest::{
@@ -1,11 +1,14 @@
 //! Account-related types.
 
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-use super::Balance;
+use super::common::{ExtraParams, impl_extra_param};
+use super::{Balance, Order};
 
 /// A Coinbase trading account.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct Account {
     /// Unique identifier for the account.
     pub uuid: String,
@@ -37,7 +40,7 @@ pub struct Account {
 }
 
 /// Request parameters for listing accounts.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct ListAccountsParams {
     /// Maximum number of accounts to return.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -48,8 +51,13 @@ pub struct ListAccountsParams {
     /// Filter by retail portfolio ID.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retail_portfolio_id: Option<String>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(ListAccountsParams);
+
 impl ListAccountsParams {
     /// Create new list accounts parameters.
     pub fn new() -> Self {
@@ -76,7 +84,8 @@ impl ListAccountsParams {
 }
 
 /// Response from listing accounts.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ListAccountsResponse {
     /// The list of accounts.
     pub accounts: Vec<Account>,
@@ -89,8 +98,90 @@ pub struct ListAccountsResponse {
 }
 
 /// Response from getting a single account.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetAccountResponse {
     /// The account details.
     pub account: Account,
 }
+
+/// An open order that accounts for part of an account hold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeldOrder {
+    /// The order ID.
+    pub order_id: String,
+    /// The product the order is for.
+    pub product_id: String,
+    /// The order side.
+    pub side: String,
+    /// The amount the order holds, in the account currency.
+    pub amount: Decimal,
+}
+
+/// Breakdown of what is holding an account's balance.
+///
+/// Built by matching the account hold against the `outstanding_hold_amount` of
+/// open orders. Buy orders hold the quote currency of their product and sell
+/// orders hold the base currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoldExplanation {
+    /// The account currency.
+    pub currency: String,
+    /// The total hold reported for the account.
+    pub total_hold: Decimal,
+    /// Open orders holding this currency.
+    pub orders: Vec<HeldOrder>,
+    /// Portion of the hold not accounted for by open orders.
+    ///
+    /// Non-zero remainders usually come from pending withdrawals, transfers or
+    /// orders in other portfolios.
+    pub unexplained: Decimal,
+}
+
+impl HoldExplanation {
+    /// Explain an account's hold using a list of open orders.
+    pub fn explain(account: &Account, open_orders: &[Order]) -> Self {
+        let total_hold = account.hold.value.parse().unwrap_or_default();
+
+        let orders: Vec<HeldOrder> = open_orders
+            .iter()
+            .filter(|order| {
+                hold_currency(order).is_some_and(|c| c.eq_ignore_ascii_case(&account.currency))
+            })
+            .filter_map(|order| {
+                let amount: Decimal = order.outstanding_hold_amount.as_deref()?.parse().ok()?;
+                (amount > Decimal::ZERO).then(|| HeldOrder {
+                    order_id: order.order_id.clone(),
+                    product_id: order.product_id.clone(),
+                    side: order.side.clone(),
+                    amount,
+                })
+            })
+            .collect();
+
+        let explained: Decimal = orders.iter().map(|o| o.amount).sum();
+
+        Self {
+            currency: account.currency.clone(),
+            total_hold,
+            orders,
+            unexplained: total_hold - explained,
+        }
+    }
+
+    /// Total amount held by open orders.
+    pub fn explained(&self) -> Decimal {
+        self.orders.iter().map(|o| o.amount).sum()
+    }
+}
+
+/// Get the currency an order holds: the quote currency for buys and the base
+/// currency for sells.
+fn hold_currency(order: &Order) -> Option<&str> {
+    let (base, quote) = order.product_id.split_once('-')?;
+    if order.side.eq_ignore_ascii_case("BUY") {
+        Some(quote)
+    } else {
+        Some(base)
+    }
+}
@@ -1,9 +1,11 @@
 //! Common types shared across API modules.
 
-use serde::{Deserialize, Serialize};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
 
 /// A monetary amount with value and currency.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct Balance {
     /// The numeric value as a string.
     pub value: String,
@@ -12,7 +14,7 @@ pub struct Balance {
 }
 
 /// Pagination parameters for list requests.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct PaginationParams {
     /// Maximum number of results to return.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -20,10 +22,14 @@ pub struct PaginationParams {
     /// Cursor for the next page of results.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
 /// Pagination information in responses.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct Pagination {
     /// Cursor for the next page (None if no more pages).
     pub cursor: Option<String>,
@@ -49,3 +55,149 @@ impl PaginationParams {
         self
     }
 }
+
+/// Query parameters that a params type does not model.
+///
+/// Every `*Params` type sent as a query string carries one, filled with its
+/// `extra_param` method, so parameters Coinbase adds can be used before
+/// this crate knows about them. They are sent after the modelled fields; a
+/// key added more than once is sent once per value, and a key that matches
+/// a modelled field replaces it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtraParams(Vec<(String, String)>);
+
+impl ExtraParams {
+    /// Add a parameter.
+    pub fn push(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.0.push((key.into(), value.into()));
+    }
+
+    /// Returns true if there are no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the parameters, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+impl Serialize for ExtraParams {
+    /// Serialize as a map, with repeated keys collected into a list.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut keys: Vec<(&str, Vec<&str>)> = Vec::new();
+        for (key, value) in self.iter() {
+            match keys.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, values)) => values.push(value),
+                None => keys.push((key, vec![value])),
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(keys.len()))?;
+        for (key, values) in &keys {
+            match values.as_slice() {
+                [value] => map.serialize_entry(key, value)?,
+                values => map.serialize_entry(key, values)?,
+            }
+        }
+        map.end()
+    }
+}
+
+/// Add `extra_param` to params types with an `extra: ExtraParams` field.
+macro_rules! impl_extra_param {
+    ($($params:ty),* $(,)?) => {$(
+        impl $params {
+            /// Add a query parameter that this type does not model.
+            ///
+            /// See [`ExtraParams`](crate::models::ExtraParams).
+            pub fn extra_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+                self.extra.push(key, value);
+                self
+            }
+        }
+    )*};
+}
+
+pub(crate) use impl_extra_param;
+
+impl_extra_param!(PaginationParams);
+
+/// Serde helpers for optional decimal amounts that the API sends as an
+/// empty string when they are unset.
+pub(crate) mod optional_decimal {
+    use rust_decimal::Decimal;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Option<Decimal>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Decimal>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Decimal(Decimal),
+            Text(String),
+        }
+
+        match Option::<Raw>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(Raw::Decimal(value)) => Ok(Some(value)),
+            Some(Raw::Text(text)) if text.trim().is_empty() => Ok(None),
+            Some(Raw::Text(text)) => Err(D::Error::custom(format!("invalid decimal '{}'", text))),
+        }
+    }
+}
+
+/// Parse a decimal amount in the plain notation the API accepts.
+///
+/// Only an optional minus sign, digits and a decimal point are allowed, so
+/// exponents (`1e5`), `inf` and `NaN` are rejected.
+pub(crate) fn parse_plain_decimal(value: &str) -> Option<rust_decimal::Decimal> {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let plain = !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit() || b == b'.');
+    if !plain {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// Parse a required decimal field with [`parse_plain_decimal`].
+///
+/// Surrounding whitespace is ignored. An empty or malformed value is a parse
+/// error naming the field.
+pub(crate) fn parse_decimal_field(
+    field: &str,
+    value: &str,
+) -> crate::error::Result<rust_decimal::Decimal> {
+    parse_plain_decimal(value.trim()).ok_or_else(|| {
+        crate::error::Error::parse(
+            format!(
+                "Invalid {} {:?}: expected a plain decimal number",
+                field, value
+            ),
+            None,
+        )
+    })
+}
+
+/// Parse an optional decimal field with [`parse_decimal_field`].
+///
+/// A missing or empty value is `None`.
+pub(crate) fn parse_optional_decimal_field(
+    field: &str,
+    value: Option<&str>,
+) -> crate::error::Result<Option<rust_decimal::Decimal>> {
+    value
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| parse_decimal_field(field, value))
+        .transpose()
+}
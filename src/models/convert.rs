@@ -2,8 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::common::{ExtraParams, impl_extra_param};
+
 /// Trade status for a conversion.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ConvertTradeStatus {
     /// Unspecified status.
     #[serde(rename = "TRADE_STATUS_UNSPECIFIED")]
@@ -26,7 +28,8 @@ pub enum ConvertTradeStatus {
 }
 
 /// Amount with currency.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ConvertAmount {
     /// The amount value.
     pub value: String,
@@ -35,7 +38,8 @@ pub struct ConvertAmount {
 }
 
 /// Fee information for a conversion.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ConvertFee {
     /// Fee title.
     #[serde(default)]
@@ -48,10 +52,74 @@ pub struct ConvertFee {
     /// Fee label.
     #[serde(default)]
     pub label: Option<String>,
+    /// Disclosure shown alongside the fee.
+    #[serde(default)]
+    pub disclosure: Option<ConvertDisclosure>,
+}
+
+/// A link shown to the user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertLink {
+    /// Link text.
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Link URL.
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Disclosure attached to a fee.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertDisclosure {
+    /// Disclosure title.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Disclosure text.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Link to more information.
+    #[serde(default)]
+    pub link: Option<ConvertLink>,
+}
+
+/// Owner of a ledger account.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertLedgerOwner {
+    /// Owner ID.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Owner UUID.
+    #[serde(default)]
+    pub uuid: Option<String>,
+    /// UUID of the user owning the account.
+    #[serde(default)]
+    pub user_uuid: Option<String>,
+    /// Owner type.
+    #[serde(rename = "type", default)]
+    pub owner_type: Option<String>,
+}
+
+/// Ledger account a conversion draws from or credits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertLedgerAccount {
+    /// Account ID.
+    #[serde(default)]
+    pub account_id: Option<String>,
+    /// Account currency.
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// Account owner.
+    #[serde(default)]
+    pub owner: Option<ConvertLedgerOwner>,
 }
 
 /// Account details for conversion source/target.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ConvertAccountDetail {
     /// Account type.
     #[serde(rename = "type", default)]
@@ -61,11 +129,158 @@ pub struct ConvertAccountDetail {
     pub network: Option<String>,
     /// Ledger account details.
     #[serde(default)]
-    pub ledger_account: Option<serde_json::Value>,
+    pub ledger_account: Option<ConvertLedgerAccount>,
+}
+
+/// A price with the number of decimal places it is quoted to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertScaledAmount {
+    /// The price.
+    pub amount: ConvertAmount,
+    /// Number of decimal places.
+    #[serde(default)]
+    pub scale: Option<i32>,
+}
+
+/// Unit prices of a conversion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertUnitPrice {
+    /// Price of one unit of the target currency in fiat.
+    #[serde(default)]
+    pub target_to_fiat: Option<ConvertScaledAmount>,
+    /// Price of one unit of the target currency in the source currency.
+    #[serde(default)]
+    pub target_to_source: Option<ConvertScaledAmount>,
+    /// Price of one unit of fiat in the target currency.
+    #[serde(default)]
+    pub fiat_to_target: Option<ConvertScaledAmount>,
+}
+
+/// Why a conversion was cancelled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertCancellationReason {
+    /// Human readable message.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Reason code.
+    #[serde(default)]
+    pub code: Option<String>,
+    /// Error code.
+    #[serde(default)]
+    pub error_code: Option<String>,
+    /// Action suggested to the user.
+    #[serde(default)]
+    pub error_cta: Option<String>,
+}
+
+/// A tax charged on a conversion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertTaxDetail {
+    /// Tax name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Tax amount.
+    #[serde(default)]
+    pub amount: Option<ConvertAmount>,
+}
+
+/// Context of a user warning.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertWarningContext {
+    /// Warning details.
+    #[serde(default)]
+    pub details: Vec<String>,
+    /// Warning title.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Text of the warning link.
+    #[serde(default)]
+    pub link_text: Option<String>,
+}
+
+/// A warning to show the user before committing a conversion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertUserWarning {
+    /// Warning ID.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Link to more information.
+    #[serde(default)]
+    pub link: Option<ConvertLink>,
+    /// Warning context.
+    #[serde(default)]
+    pub context: Option<ConvertWarningContext>,
+    /// Warning code.
+    #[serde(default)]
+    pub code: Option<String>,
+    /// Warning message.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Subscription benefits applied to a conversion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertSubscriptionInfo {
+    /// When the free trading allowance resets.
+    #[serde(default)]
+    pub free_trading_reset_date: Option<String>,
+    /// Zero fee trading volume used.
+    #[serde(default)]
+    pub used_zero_fee_trading: Option<ConvertAmount>,
+    /// Zero fee trading volume remaining.
+    #[serde(default)]
+    pub remaining_free_trading_volume: Option<ConvertAmount>,
+    /// Zero fee trading volume allowed.
+    #[serde(default)]
+    pub max_free_trading_volume: Option<ConvertAmount>,
+    /// Whether the benefit is capped.
+    #[serde(default)]
+    pub has_benefit_cap: bool,
+    /// Whether the subscription benefit was applied.
+    #[serde(default)]
+    pub applied_subscription_benefit: bool,
+    /// Fee without the subscription benefit.
+    #[serde(default)]
+    pub fee_without_subscription_benefit: Option<ConvertAmount>,
+    /// Payment method fee without the subscription benefit.
+    #[serde(default)]
+    pub payment_method_fee_without_subscription_benefit: Option<ConvertAmount>,
+}
+
+/// Trade incentive applied to a conversion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ConvertTradeIncentiveInfo {
+    /// Whether an incentive was applied.
+    #[serde(default)]
+    pub applied_incentive: bool,
+    /// User incentive ID.
+    #[serde(default)]
+    pub user_incentive_id: Option<String>,
+    /// Promo code.
+    #[serde(default)]
+    pub code_val: Option<String>,
+    /// When the incentive ends.
+    #[serde(default)]
+    pub ends_at: Option<String>,
+    /// Fee without the incentive.
+    #[serde(default)]
+    pub fee_without_incentive: Option<ConvertAmount>,
+    /// Whether the incentive was redeemed.
+    #[serde(default)]
+    pub redeemed: bool,
 }
 
 /// A conversion trade.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ConvertTrade {
     /// The trade ID.
     pub id: String,
@@ -113,17 +328,42 @@ pub struct ConvertTrade {
     /// User reference.
     #[serde(default)]
     pub user_reference: Option<String>,
+    /// Unit prices of the conversion.
+    #[serde(default)]
+    pub unit_price: Option<ConvertUnitPrice>,
+    /// Warnings to show before committing.
+    #[serde(default)]
+    pub user_warnings: Vec<ConvertUserWarning>,
+    /// Why the trade was cancelled.
+    #[serde(default)]
+    pub cancellation_reason: Option<ConvertCancellationReason>,
+    /// Taxes charged.
+    #[serde(default)]
+    pub tax_details: Vec<ConvertTaxDetail>,
+    /// Total fee before taxes.
+    #[serde(default)]
+    pub total_fee_without_tax: Option<ConvertFee>,
+    /// Total in fiat.
+    #[serde(default)]
+    pub fiat_denoted_total: Option<ConvertAmount>,
+    /// Subscription benefits applied.
+    #[serde(default)]
+    pub subscription_info: Option<ConvertSubscriptionInfo>,
+    /// Trade incentive applied.
+    #[serde(default)]
+    pub trade_incentive_info: Option<ConvertTradeIncentiveInfo>,
 }
 
 /// Response containing a trade.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ConvertTradeResponse {
     /// The trade.
     pub trade: ConvertTrade,
 }
 
 /// Trade incentive metadata for waiving fees.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct TradeIncentiveMetadata {
     /// User incentive ID.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -134,7 +374,7 @@ pub struct TradeIncentiveMetadata {
 }
 
 /// Request to create a convert quote.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CreateConvertQuoteRequest {
     /// Source account ID (the account to convert from).
     pub from_account: String,
@@ -170,7 +410,7 @@ impl CreateConvertQuoteRequest {
 }
 
 /// Request to commit a convert trade.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CommitConvertTradeRequest {
     /// Source account ID.
     pub from_account: String,
@@ -189,20 +429,26 @@ impl CommitConvertTradeRequest {
 }
 
 /// Parameters for getting a convert trade.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct GetConvertTradeParams {
     /// Source account ID.
     pub from_account: String,
     /// Target account ID.
     pub to_account: String,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(GetConvertTradeParams);
+
 impl GetConvertTradeParams {
     /// Create new get convert trade parameters.
     pub fn new(from_account: impl Into<String>, to_account: impl Into<String>) -> Self {
         Self {
             from_account: from_account.into(),
             to_account: to_account.into(),
+            extra: ExtraParams::default(),
         }
     }
 }
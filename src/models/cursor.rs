@@ -0,0 +1,187 @@
+//! Checkpointable pagination cursors.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::paths;
+use crate::error::{Error, Result};
+
+use super::{
+    ListAccountsParams, ListAccountsResponse, ListFillsParams, ListFillsResponse, ListOrdersParams,
+    ListOrdersResponse,
+};
+
+/// Parameters of a paginated list endpoint.
+pub trait Paginated: Serialize + Clone {
+    /// The endpoint the parameters are sent to.
+    const ENDPOINT: &'static str;
+
+    /// Get the cursor of the page to fetch.
+    fn cursor_mut(&mut self) -> &mut Option<String>;
+}
+
+impl Paginated for ListOrdersParams {
+    const ENDPOINT: &'static str = paths::ORDERS_HISTORICAL;
+
+    fn cursor_mut(&mut self) -> &mut Option<String> {
+        &mut self.cursor
+    }
+}
+
+impl Paginated for ListFillsParams {
+    const ENDPOINT: &'static str = paths::FILLS;
+
+    fn cursor_mut(&mut self) -> &mut Option<String> {
+        &mut self.cursor
+    }
+}
+
+impl Paginated for ListAccountsParams {
+    const ENDPOINT: &'static str = paths::ACCOUNTS;
+
+    fn cursor_mut(&mut self) -> &mut Option<String> {
+        &mut self.cursor
+    }
+}
+
+/// A position in a paginated listing, safe to persist between runs.
+///
+/// Besides the raw cursor, records the endpoint and a hash of the other
+/// parameters it was returned for. [`apply`](Self::apply) refuses to use it
+/// with different parameters, which Coinbase would otherwise answer with an
+/// arbitrary page. The hash is stable across builds, so a cursor written to
+/// disk by one version of a program can be resumed by the next.
+///
+/// # Example
+///
+/// ```no_run
+/// # use coinbase_advanced::{RestClient, Credentials, models::{Cursor, ListFillsParams}};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = RestClient::builder()
+///     .credentials(Credentials::from_env()?)
+///     .build()?;
+///
+/// let params = ListFillsParams::new().product_id("BTC-USD");
+/// let checkpoint = std::fs::read_to_string("fills.cursor").ok();
+/// let mut page = match checkpoint {
+///     Some(json) => {
+///         let cursor: Cursor<ListFillsParams> = serde_json::from_str(&json)?;
+///         client.orders().resume_fills(params.clone(), &cursor).await?
+///     }
+///     None => client.orders().list_fills(params.clone()).await?,
+/// };
+/// loop {
+///     println!("exported {} fills", page.fills.len());
+///     let Some(cursor) = page.next_cursor(&params) else { break };
+///     std::fs::write("fills.cursor", serde_json::to_string(&cursor)?)?;
+///     page = client.orders().resume_fills(params.clone(), &cursor).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Cursor<T> {
+    endpoint: String,
+    params_hash: String,
+    cursor: String,
+    #[serde(skip)]
+    params: PhantomData<fn() -> T>,
+}
+
+impl<T: Paginated> Cursor<T> {
+    /// Record a cursor returned for `params`.
+    pub fn new(params: &T, cursor: impl Into<String>) -> Self {
+        Self {
+            endpoint: T::ENDPOINT.to_string(),
+            params_hash: params_hash(params),
+            cursor: cursor.into(),
+            params: PhantomData,
+        }
+    }
+
+    /// Get the endpoint the cursor was returned by.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Get the raw cursor.
+    pub fn as_str(&self) -> &str {
+        &self.cursor
+    }
+
+    /// Returns true if the cursor was returned for these parameters.
+    ///
+    /// Any cursor already set in `params` is ignored.
+    pub fn matches(&self, params: &T) -> bool {
+        self.endpoint == T::ENDPOINT && self.params_hash == params_hash(params)
+    }
+
+    /// Set the cursor on `params`, to fetch the page it points to.
+    ///
+    /// Fails if the cursor was returned for other parameters.
+    pub fn apply(&self, mut params: T) -> Result<T> {
+        if !self.matches(&params) {
+            return Err(Error::config(format!(
+                "cursor for {} was returned for different parameters",
+                self.endpoint
+            )));
+        }
+        *params.cursor_mut() = Some(self.cursor.clone());
+        Ok(params)
+    }
+}
+
+impl<T> fmt::Display for Cursor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.cursor)
+    }
+}
+
+/// Hash the parameters other than the cursor with 64-bit FNV-1a.
+///
+/// The standard library's hasher may change between Rust releases, which
+/// would orphan persisted cursors.
+fn params_hash<T: Paginated>(params: &T) -> String {
+    let mut params = params.clone();
+    *params.cursor_mut() = None;
+    let bytes = serde_json::to_vec(&params).unwrap_or_default();
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Get a cursor that is set and not empty.
+fn next(cursor: Option<&String>) -> Option<&str> {
+    cursor.map(String::as_str).filter(|c| !c.is_empty())
+}
+
+impl ListOrdersResponse {
+    /// Get a cursor for the next page, if there is one.
+    pub fn next_cursor(&self, params: &ListOrdersParams) -> Option<Cursor<ListOrdersParams>> {
+        if !self.has_next {
+            return None;
+        }
+        next(self.cursor.as_ref()).map(|cursor| Cursor::new(params, cursor))
+    }
+}
+
+impl ListFillsResponse {
+    /// Get a cursor for the next page, if there is one.
+    pub fn next_cursor(&self, params: &ListFillsParams) -> Option<Cursor<ListFillsParams>> {
+        next(self.cursor.as_ref()).map(|cursor| Cursor::new(params, cursor))
+    }
+}
+
+impl ListAccountsResponse {
+    /// Get a cursor for the next page, if there is one.
+    pub fn next_cursor(&self, params: &ListAccountsParams) -> Option<Cursor<ListAccountsParams>> {
+        if !self.has_next {
+            return None;
+        }
+        next(self.cursor.as_ref()).map(|cursor| Cursor::new(params, cursor))
+    }
+}
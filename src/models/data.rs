@@ -1,9 +1,14 @@
 //! Data API types.
 
-use serde::Deserialize;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{PortfolioBreakdown, PortfolioType};
 
 /// API key permissions.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ApiKeyPermissions {
     /// Whether the API key can view data.
     pub can_view: bool,
@@ -13,6 +18,34 @@ pub struct ApiKeyPermissions {
     pub can_transfer: bool,
     /// The portfolio UUID associated with this key.
     pub portfolio_uuid: String,
-    /// The portfolio type.
-    pub portfolio_type: String,
+    /// The type of the key's portfolio.
+    pub portfolio_type: PortfolioType,
+}
+
+/// What an API key can do and the portfolio it belongs to.
+///
+/// Returned by [`RestClient::whoami`](crate::RestClient::whoami).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct KeySummary {
+    /// The key's permissions.
+    pub permissions: ApiKeyPermissions,
+    /// Balances and positions of the key's portfolio.
+    pub portfolio: PortfolioBreakdown,
+}
+
+impl fmt::Display for KeySummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let portfolio = &self.portfolio.portfolio;
+        write!(
+            f,
+            "portfolio {:?} ({}, {:?}) view={} trade={} transfer={}",
+            portfolio.name,
+            portfolio.uuid,
+            portfolio.portfolio_type,
+            self.permissions.can_view,
+            self.permissions.can_trade,
+            self.permissions.can_transfer
+        )
+    }
 }
@@ -1,52 +1,140 @@
 //! Fee-related types.
 
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use super::common::{ExtraParams, impl_extra_param};
+
 /// Fee tier for the user, determined by notional (USD) volume.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct FeeTier {
     /// Current fee tier for the user.
     pub pricing_tier: String,
     /// Lower bound (inclusive) of pricing tier in notional volume.
-    pub usd_from: String,
-    /// Upper bound (exclusive) of pricing tier in notional volume.
-    pub usd_to: String,
+    pub usd_from: Decimal,
+    /// Upper bound (exclusive) of pricing tier in notional volume, if the
+    /// tier has one.
+    #[serde(default, with = "super::common::optional_decimal")]
+    pub usd_to: Option<Decimal>,
     /// Taker fee rate, applied if the order takes liquidity.
-    pub taker_fee_rate: String,
+    pub taker_fee_rate: Decimal,
     /// Maker fee rate, applied if the order creates liquidity.
-    pub maker_fee_rate: String,
+    pub maker_fee_rate: Decimal,
     /// AOP (Advanced Order Placement) lower bound.
-    #[serde(default)]
-    pub aop_from: Option<String>,
+    #[serde(default, with = "super::common::optional_decimal")]
+    pub aop_from: Option<Decimal>,
     /// AOP upper bound.
-    #[serde(default)]
-    pub aop_to: Option<String>,
+    #[serde(default, with = "super::common::optional_decimal")]
+    pub aop_to: Option<Decimal>,
+}
+
+impl FeeTier {
+    /// Returns true if a trailing 30-day notional volume falls in this tier.
+    pub fn contains(&self, volume: Decimal) -> bool {
+        volume >= self.usd_from && self.usd_to.is_none_or(|to| volume < to)
+    }
+
+    /// Get the maker and taker rates of this tier.
+    pub fn rates(&self) -> FeeRates {
+        FeeRates {
+            maker: self.maker_fee_rate,
+            taker: self.taker_fee_rate,
+        }
+    }
+}
+
+/// Maker and taker fee rates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeRates {
+    /// Rate for orders that create liquidity.
+    pub maker: Decimal,
+    /// Rate for orders that take liquidity.
+    pub taker: Decimal,
+}
+
+impl FeeRates {
+    /// Get the average rate when `maker_share` of the volume, between 0
+    /// and 1, is made and the rest taken.
+    pub fn blended(&self, maker_share: Decimal) -> Decimal {
+        let maker_share = maker_share.clamp(Decimal::ZERO, Decimal::ONE);
+        self.maker * maker_share + self.taker * (Decimal::ONE - maker_share)
+    }
+}
+
+/// A table of fee tiers, for projecting rates at other volumes.
+///
+/// The API only reports the user's current tier; build a schedule from the
+/// published tiers to plan for the others.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeeSchedule {
+    tiers: Vec<FeeTier>,
+}
+
+impl FeeSchedule {
+    /// Create a schedule from its tiers, in any order.
+    pub fn new(mut tiers: Vec<FeeTier>) -> Self {
+        tiers.sort_by_key(|t| t.usd_from);
+        Self { tiers }
+    }
+
+    /// Get the tiers, from the lowest volume up.
+    pub fn tiers(&self) -> &[FeeTier] {
+        &self.tiers
+    }
+
+    /// Get the tier that applies at a trailing 30-day notional volume.
+    pub fn tier_for(&self, volume: Decimal) -> Option<&FeeTier> {
+        self.tiers.iter().rev().find(|tier| tier.contains(volume))
+    }
+
+    /// Get the rates that apply at a trailing 30-day notional volume.
+    pub fn effective_fee_rate(&self, volume: Decimal) -> Option<FeeRates> {
+        self.tier_for(volume).map(FeeTier::rates)
+    }
 }
 
 /// Margin rate information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct MarginRate {
     /// The margin rate value.
-    pub value: String,
+    pub value: Decimal,
 }
 
 /// Goods and Services Tax information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GoodsAndServicesTax {
     /// The GST rate.
-    pub rate: String,
+    pub rate: Decimal,
     /// The GST type (e.g., "INCLUSIVE", "EXCLUSIVE").
     #[serde(rename = "type")]
     pub gst_type: String,
 }
 
+/// Volume and fees of a single product venue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct VenueSummary {
+    /// Product venue (e.g., "CBE", "FCM", "INTX").
+    pub product_venue: String,
+    /// Volume on the venue, denoted in USD.
+    #[serde(default)]
+    pub volume: Decimal,
+    /// Fees paid on the venue, denoted in USD.
+    #[serde(default)]
+    pub fees: Decimal,
+}
+
 /// Transaction summary containing fee information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct TransactionSummary {
     /// Total volume across assets, denoted in USD.
-    pub total_volume: f64,
+    pub total_volume: Decimal,
     /// Total fees across assets, denoted in USD.
-    pub total_fees: f64,
+    pub total_fees: Decimal,
     /// Fee tier information.
     pub fee_tier: FeeTier,
     /// Margin rate (if applicable).
@@ -54,20 +142,58 @@ pub struct TransactionSummary {
     /// Goods and Services Tax (if applicable).
     pub goods_and_services_tax: Option<GoodsAndServicesTax>,
     /// Advanced Trade volume (non-inclusive of Pro) across assets, denoted in USD.
-    pub advanced_trade_only_volume: f64,
+    pub advanced_trade_only_volume: Decimal,
     /// Advanced Trade fees (non-inclusive of Pro) across assets, denoted in USD.
-    pub advanced_trade_only_fees: f64,
+    pub advanced_trade_only_fees: Decimal,
     /// Coinbase Pro volume across assets, denoted in USD.
-    pub coinbase_pro_volume: f64,
+    pub coinbase_pro_volume: Decimal,
     /// Coinbase Pro fees across assets, denoted in USD.
-    pub coinbase_pro_fees: f64,
-    /// Total balance (optional).
+    pub coinbase_pro_fees: Decimal,
+    /// Total balance across assets, denoted in USD (optional).
+    #[serde(default, with = "super::common::optional_decimal")]
+    pub total_balance: Option<Decimal>,
+    /// Whether a promotional fee rate applies.
     #[serde(default)]
-    pub total_balance: Option<String>,
+    pub has_promo_fee: Option<bool>,
+    /// Volume and fees by product venue.
+    #[serde(default)]
+    pub venue_summaries: Vec<VenueSummary>,
+}
+
+impl TransactionSummary {
+    /// Get the rates that apply at a trailing 30-day notional volume, if
+    /// it falls in the current tier.
+    ///
+    /// Use a [`FeeSchedule`] to project rates in other tiers.
+    pub fn effective_fee_rate(&self, volume: Decimal) -> Option<FeeRates> {
+        self.fee_tier
+            .contains(volume)
+            .then(|| self.fee_tier.rates())
+    }
+
+    /// Get the fees paid per unit of volume so far.
+    pub fn realized_fee_rate(&self) -> Option<Decimal> {
+        (!self.total_volume.is_zero()).then(|| self.total_fees / self.total_volume)
+    }
+
+    /// Get the additional volume needed to leave the current tier, if it
+    /// has an upper bound.
+    pub fn volume_to_next_tier(&self) -> Option<Decimal> {
+        self.fee_tier
+            .usd_to
+            .map(|to| (to - self.total_volume).max(Decimal::ZERO))
+    }
+
+    /// Get the summary of a product venue, if it is listed.
+    pub fn venue(&self, product_venue: &str) -> Option<&VenueSummary> {
+        self.venue_summaries
+            .iter()
+            .find(|venue| venue.product_venue.eq_ignore_ascii_case(product_venue))
+    }
 }
 
 /// Parameters for getting transaction summary.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct TransactionSummaryParams {
     /// Product type filter (e.g., "SPOT", "FUTURE").
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -78,8 +204,13 @@ pub struct TransactionSummaryParams {
     /// Product venue filter.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub product_venue: Option<String>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(TransactionSummaryParams);
+
 impl TransactionSummaryParams {
     /// Create new transaction summary parameters.
     pub fn new() -> Self {
@@ -2,8 +2,11 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::common::{ExtraParams, impl_extra_param};
+
 /// CFM futures position.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct FuturesPosition {
     /// Product ID.
     pub product_id: String,
@@ -31,7 +34,8 @@ pub struct FuturesPosition {
 }
 
 /// Response for listing futures positions.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ListFuturesPositionsResponse {
     /// Positions.
     #[serde(default)]
@@ -39,14 +43,16 @@ pub struct ListFuturesPositionsResponse {
 }
 
 /// Response for getting a single futures position.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetFuturesPositionResponse {
     /// The position.
     pub position: FuturesPosition,
 }
 
 /// Futures balance summary.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct FuturesBalanceSummary {
     /// Futures buying power.
     #[serde(default)]
@@ -87,14 +93,16 @@ pub struct FuturesBalanceSummary {
 }
 
 /// Response for getting balance summary.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetFuturesBalanceSummaryResponse {
     /// Balance summary.
     pub balance_summary: FuturesBalanceSummary,
 }
 
 /// Intraday margin setting.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct IntradayMarginSetting {
     /// The margin setting value.
     #[serde(default)]
@@ -102,7 +110,8 @@ pub struct IntradayMarginSetting {
 }
 
 /// Response for getting intraday margin setting.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetIntradayMarginSettingResponse {
     /// The setting.
     #[serde(default)]
@@ -110,7 +119,8 @@ pub struct GetIntradayMarginSettingResponse {
 }
 
 /// Current margin window.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct MarginWindow {
     /// Margin window type.
     #[serde(default)]
@@ -127,20 +137,26 @@ pub struct MarginWindow {
 }
 
 /// Response for getting current margin window.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetCurrentMarginWindowResponse {
     /// The margin window.
     pub margin_window: MarginWindow,
 }
 
 /// Parameters for getting current margin window.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct GetCurrentMarginWindowParams {
     /// Margin profile type.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub margin_profile_type: Option<String>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(GetCurrentMarginWindowParams);
+
 impl GetCurrentMarginWindowParams {
     /// Create new parameters.
     pub fn new() -> Self {
@@ -155,7 +171,8 @@ impl GetCurrentMarginWindowParams {
 }
 
 /// A futures sweep.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct FuturesSweep {
     /// Sweep ID.
     #[serde(default)]
@@ -175,7 +192,8 @@ pub struct FuturesSweep {
 }
 
 /// Response for listing futures sweeps.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ListFuturesSweepsResponse {
     /// Sweeps.
     #[serde(default)]
@@ -183,7 +201,7 @@ pub struct ListFuturesSweepsResponse {
 }
 
 /// Request to schedule a futures sweep.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ScheduleFuturesSweepRequest {
     /// USD amount to sweep.
     pub usd_amount: String,
@@ -199,7 +217,8 @@ impl ScheduleFuturesSweepRequest {
 }
 
 /// Response from scheduling a futures sweep.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ScheduleFuturesSweepResponse {
     /// Success status.
     #[serde(default)]
@@ -207,7 +226,7 @@ pub struct ScheduleFuturesSweepResponse {
 }
 
 /// Request to set intraday margin setting.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct SetIntradayMarginSettingRequest {
     /// The setting value.
     pub setting: String,
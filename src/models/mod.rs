@@ -3,6 +3,7 @@
 mod account;
 mod common;
 mod convert;
+mod cursor;
 mod data;
 mod fee;
 mod futures;
@@ -11,10 +12,12 @@ mod payment;
 mod perpetuals;
 mod portfolio;
 mod product;
+mod transfer;
 
 pub use account::*;
 pub use common::*;
 pub use convert::*;
+pub use cursor::*;
 pub use data::*;
 pub use fee::*;
 pub use futures::*;
@@ -23,3 +26,4 @@ pub use payment::*;
 pub use perpetuals::*;
 pub use portfolio::*;
 pub use product::*;
+pub use transfer::*;
@@ -1,9 +1,19 @@
 //! Order-related types.
 
+#[cfg(feature = "capture-unknown")]
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::ProductType;
+use super::common::{ExtraParams, impl_extra_param};
+
 /// Order side (buy or sell).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderSide {
     /// Buy order.
@@ -13,7 +23,7 @@ pub enum OrderSide {
 }
 
 /// Order status.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderStatus {
     /// Order is pending.
@@ -33,8 +43,91 @@ pub enum OrderStatus {
     Unknown,
 }
 
+impl OrderStatus {
+    /// Returns true if the order can no longer change: filled, cancelled,
+    /// expired or failed.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Filled | Self::Cancelled | Self::Expired | Self::Failed
+        )
+    }
+}
+
+impl FromStr for OrderStatus {
+    type Err = Infallible;
+
+    /// Parse a status as reported by the API (e.g., "FILLED").
+    ///
+    /// Unrecognized statuses parse as [`OrderStatus::Unknown`].
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "PENDING" => OrderStatus::Pending,
+            "OPEN" => OrderStatus::Open,
+            "FILLED" => OrderStatus::Filled,
+            "CANCELLED" => OrderStatus::Cancelled,
+            "EXPIRED" => OrderStatus::Expired,
+            "FAILED" => OrderStatus::Failed,
+            _ => OrderStatus::Unknown,
+        })
+    }
+}
+
+/// Order type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderType {
+    /// Market order.
+    Market,
+    /// Limit order.
+    Limit,
+    /// Stop order.
+    Stop,
+    /// Stop-limit order.
+    StopLimit,
+    /// Bracket order.
+    Bracket,
+    /// Time-weighted average price order.
+    Twap,
+    /// Futures roll opening leg.
+    RollOpen,
+    /// Futures roll closing leg.
+    RollClose,
+    /// Liquidation order.
+    Liquidation,
+    /// Scaled order.
+    Scaled,
+    /// Unknown order type.
+    #[serde(rename = "UNKNOWN_ORDER_TYPE", other)]
+    Unknown,
+}
+
+/// Time in force for orders built with [`OrderBuilder`](crate::rest::OrderBuilder).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tif {
+    /// Good until cancelled.
+    Gtc,
+    /// Good until the given time.
+    Gtd(DateTime<Utc>),
+    /// Immediate or cancel.
+    Ioc,
+    /// Fill or kill.
+    Fok,
+}
+
+impl fmt::Display for Tif {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gtc => f.write_str("GTC"),
+            Self::Gtd(_) => f.write_str("GTD"),
+            Self::Ioc => f.write_str("IOC"),
+            Self::Fok => f.write_str("FOK"),
+        }
+    }
+}
+
 /// Stop direction for stop orders.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum StopDirection {
     /// Stop triggers when price goes up.
@@ -44,7 +137,7 @@ pub enum StopDirection {
 }
 
 /// Market IOC order configuration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct MarketIoc {
     /// Size in quote currency (e.g., USD).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -55,7 +148,7 @@ pub struct MarketIoc {
 }
 
 /// Limit GTC order configuration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct LimitGtc {
     /// Size in base currency.
     pub base_size: String,
@@ -67,7 +160,7 @@ pub struct LimitGtc {
 }
 
 /// Limit GTD order configuration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct LimitGtd {
     /// Size in base currency.
     pub base_size: String,
@@ -81,7 +174,7 @@ pub struct LimitGtd {
 }
 
 /// Limit FOK order configuration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct LimitFok {
     /// Size in base currency.
     pub base_size: String,
@@ -89,8 +182,47 @@ pub struct LimitFok {
     pub limit_price: String,
 }
 
+/// Smart-order-routed limit IOC order configuration.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SorLimitIoc {
+    /// Size in base currency.
+    pub base_size: String,
+    /// Limit price.
+    pub limit_price: String,
+}
+
+/// TWAP limit GTD order configuration.
+///
+/// Coinbase splits the order into buckets traded evenly between the start
+/// and end time, none of them above (for buys) or below (for sells) the
+/// limit price.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TwapLimitGtd {
+    /// Size in quote currency.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_size: Option<String>,
+    /// Size in base currency.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_size: Option<String>,
+    /// Start time (ISO 8601).
+    pub start_time: String,
+    /// End time (ISO 8601).
+    pub end_time: String,
+    /// Limit price.
+    pub limit_price: String,
+    /// Number of buckets to split the order into.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_buckets: Option<String>,
+    /// Size of each bucket.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket_size: Option<String>,
+    /// Duration of each bucket (e.g. "300s").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket_duration: Option<String>,
+}
+
 /// Stop-limit GTC order configuration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct StopLimitGtc {
     /// Size in base currency.
     pub base_size: String,
@@ -103,7 +235,7 @@ pub struct StopLimitGtc {
 }
 
 /// Stop-limit GTD order configuration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct StopLimitGtd {
     /// Size in base currency.
     pub base_size: String,
@@ -118,7 +250,7 @@ pub struct StopLimitGtd {
 }
 
 /// Order configuration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum OrderConfiguration {
     /// Market order (immediate-or-cancel).
@@ -141,6 +273,11 @@ pub enum OrderConfiguration {
         /// Limit FOK configuration.
         limit_limit_fok: LimitFok,
     },
+    /// Limit order (immediate-or-cancel), routed across venues by Coinbase.
+    SorLimitIoc {
+        /// SOR limit IOC configuration.
+        sor_limit_ioc: SorLimitIoc,
+    },
     /// Stop-limit order (good-til-cancelled).
     StopLimitGtc {
         /// Stop-limit GTC configuration.
@@ -151,6 +288,16 @@ pub enum OrderConfiguration {
         /// Stop-limit GTD configuration.
         stop_limit_stop_limit_gtd: StopLimitGtd,
     },
+    /// TWAP limit order (good-til-date).
+    TwapLimitGtd {
+        /// TWAP limit GTD configuration.
+        twap_limit_gtd: TwapLimitGtd,
+    },
+    /// A configuration this crate does not model, sent as-is.
+    ///
+    /// The value is the whole `order_configuration` object, keyed by the
+    /// configuration name. Build it with [`custom`](Self::custom).
+    Custom(serde_json::Value),
 }
 
 impl OrderConfiguration {
@@ -226,6 +373,52 @@ impl OrderConfiguration {
         }
     }
 
+    /// Create a limit IOC order.
+    ///
+    /// Fills as much as possible at the limit price or better immediately,
+    /// and cancels the rest.
+    pub fn limit_ioc(base_size: impl Into<String>, limit_price: impl Into<String>) -> Self {
+        Self::SorLimitIoc {
+            sor_limit_ioc: SorLimitIoc {
+                base_size: base_size.into(),
+                limit_price: limit_price.into(),
+            },
+        }
+    }
+
+    /// Create a TWAP limit order sized in base currency, traded between
+    /// `start_time` and `end_time`.
+    pub fn twap_limit_gtd(
+        base_size: impl Into<String>,
+        limit_price: impl Into<String>,
+        start_time: impl Into<String>,
+        end_time: impl Into<String>,
+    ) -> Self {
+        Self::TwapLimitGtd {
+            twap_limit_gtd: TwapLimitGtd {
+                quote_size: None,
+                base_size: Some(base_size.into()),
+                start_time: start_time.into(),
+                end_time: end_time.into(),
+                limit_price: limit_price.into(),
+                number_buckets: None,
+                bucket_size: None,
+                bucket_duration: None,
+            },
+        }
+    }
+
+    /// Create a configuration this crate does not model yet.
+    ///
+    /// `name` is the configuration's key in `order_configuration` (e.g.
+    /// `trigger_bracket_gtc`) and `config` its fields, which are sent without
+    /// validation.
+    pub fn custom(name: impl Into<String>, config: serde_json::Value) -> Self {
+        let mut object = serde_json::Map::new();
+        object.insert(name.into(), config);
+        Self::Custom(serde_json::Value::Object(object))
+    }
+
     /// Create a stop-limit GTC order.
     pub fn stop_limit_gtc(
         base_size: impl Into<String>,
@@ -263,8 +456,62 @@ impl OrderConfiguration {
     }
 }
 
+/// Margin type for leveraged orders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MarginType {
+    /// Margin is shared across all positions in the portfolio.
+    Cross,
+    /// Margin is held separately for each position.
+    Isolated,
+}
+
+impl MarginType {
+    /// Get the API string for this margin type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cross => "CROSS",
+            Self::Isolated => "ISOLATED",
+        }
+    }
+}
+
+/// Add the margin and self-trade prevention setters to order requests and
+/// builders.
+///
+/// Each type may name the field path, relative to `self`, of the struct that
+/// holds `leverage`, `margin_type` and `self_trade_prevention_id`; types
+/// without a path hold the fields directly.
+macro_rules! impl_order_options {
+    ($($ty:ty $(=> $($path:ident).+)?),* $(,)?) => {$(
+        impl $ty {
+            /// Set the leverage for a margin order (e.g., "5").
+            pub fn leverage(mut self, leverage: impl Into<String>) -> Self {
+                self$($(.$path)+)?.leverage = Some(leverage.into());
+                self
+            }
+
+            /// Set the margin type for a margin order.
+            pub fn margin_type(mut self, margin_type: $crate::models::MarginType) -> Self {
+                self$($(.$path)+)?.margin_type = Some(margin_type.as_str().to_string());
+                self
+            }
+
+            /// Set the self-trade prevention ID.
+            ///
+            /// Orders that share an ID will not match against each other.
+            pub fn stp(mut self, self_trade_prevention_id: impl Into<String>) -> Self {
+                self$($(.$path)+)?.self_trade_prevention_id = Some(self_trade_prevention_id.into());
+                self
+            }
+        }
+    )*};
+}
+
+pub(crate) use impl_order_options;
+
 /// Request to create an order.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CreateOrderRequest {
     /// Client-generated order ID (UUID recommended).
     pub client_order_id: String,
@@ -307,10 +554,19 @@ impl CreateOrderRequest {
             retail_portfolio_id: None,
         }
     }
+
+    /// Place the order in the given retail portfolio.
+    pub fn portfolio(mut self, portfolio_id: impl Into<String>) -> Self {
+        self.retail_portfolio_id = Some(portfolio_id.into());
+        self
+    }
 }
 
+impl_order_options!(CreateOrderRequest);
+
 /// Success response when creating an order.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct OrderSuccessResponse {
     /// The order ID.
     pub order_id: String,
@@ -323,7 +579,8 @@ pub struct OrderSuccessResponse {
 }
 
 /// Response from creating an order.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct CreateOrderResponse {
     /// Whether the order was successful.
     pub success: bool,
@@ -337,8 +594,114 @@ pub struct CreateOrderResponse {
     pub error_response: Option<serde_json::Value>,
 }
 
+impl CreateOrderResponse {
+    /// Get the ID of the created order, turning rejections into errors.
+    pub(crate) fn into_order_id(self) -> crate::error::Result<String> {
+        if !self.success {
+            return Err(crate::Error::request(format!(
+                "order rejected: {}",
+                self.failure_reason.as_deref().unwrap_or("unknown reason")
+            )));
+        }
+        self.order_id
+            .or_else(|| self.success_response.map(|s| s.order_id))
+            .ok_or_else(|| crate::Error::request("order response did not include an order ID"))
+    }
+}
+
+/// Response from previewing an order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct PreviewOrderResponse {
+    /// Expected total cost of the order, including commission.
+    #[serde(default)]
+    pub order_total: Option<String>,
+    /// Expected commission.
+    #[serde(default)]
+    pub commission_total: Option<String>,
+    /// Errors that would cause the order to be rejected.
+    #[serde(default)]
+    pub errs: Vec<String>,
+    /// Warnings about the order.
+    #[serde(default)]
+    pub warning: Vec<String>,
+    /// Expected quote size.
+    #[serde(default)]
+    pub quote_size: Option<String>,
+    /// Expected base size.
+    #[serde(default)]
+    pub base_size: Option<String>,
+    /// Current best bid.
+    #[serde(default)]
+    pub best_bid: Option<String>,
+    /// Current best ask.
+    #[serde(default)]
+    pub best_ask: Option<String>,
+    /// Expected slippage.
+    #[serde(default)]
+    pub slippage: Option<String>,
+    /// Whether the order uses the maximum available size.
+    #[serde(default)]
+    pub is_max: bool,
+    /// Expected margin required for the order.
+    #[serde(default)]
+    pub order_margin_total: Option<String>,
+    /// Leverage of the order.
+    #[serde(default)]
+    pub leverage: Option<String>,
+    /// ID of the preview, which can be passed when creating the order.
+    #[serde(default)]
+    pub preview_id: Option<String>,
+}
+
+impl PreviewOrderResponse {
+    /// Returns true if the preview reported no errors.
+    pub fn is_ok(&self) -> bool {
+        self.errs.is_empty()
+    }
+}
+
+/// Outcome of [`OrdersApi::create_if_preview_ok`](crate::rest::OrdersApi::create_if_preview_ok).
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewedOrder {
+    /// The preview reported errors, so the order was not placed.
+    PreviewFailed(PreviewOrderResponse),
+    /// The predicate rejected the preview, so the order was not placed.
+    Rejected(PreviewOrderResponse),
+    /// The predicate accepted the preview and the order was sent.
+    Placed {
+        /// The accepted preview.
+        preview: PreviewOrderResponse,
+        /// Response from creating the order.
+        response: Box<CreateOrderResponse>,
+    },
+}
+
+impl PreviewedOrder {
+    /// Get the preview the decision was based on.
+    pub fn preview(&self) -> &PreviewOrderResponse {
+        match self {
+            Self::PreviewFailed(preview) | Self::Rejected(preview) => preview,
+            Self::Placed { preview, .. } => preview,
+        }
+    }
+
+    /// Get the create response, if the order was sent.
+    pub fn response(&self) -> Option<&CreateOrderResponse> {
+        match self {
+            Self::Placed { response, .. } => Some(response.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the order was sent.
+    pub fn is_placed(&self) -> bool {
+        matches!(self, Self::Placed { .. })
+    }
+}
+
 /// Request to cancel orders.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CancelOrdersRequest {
     /// Order IDs to cancel.
     pub order_ids: Vec<String>,
@@ -359,7 +722,8 @@ impl CancelOrdersRequest {
 }
 
 /// Result of cancelling a single order.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct CancelOrderResult {
     /// Whether the cancellation was successful.
     pub success: bool,
@@ -370,14 +734,15 @@ pub struct CancelOrderResult {
 }
 
 /// Response from cancelling orders.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct CancelOrdersResponse {
     /// Results for each order.
     pub results: Vec<CancelOrderResult>,
 }
 
 /// Request to edit an order.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct EditOrderRequest {
     /// The order ID to edit.
     pub order_id: String,
@@ -413,7 +778,8 @@ impl EditOrderRequest {
 }
 
 /// Response from editing an order.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct EditOrderResponse {
     /// Whether the edit was successful.
     pub success: bool,
@@ -422,7 +788,11 @@ pub struct EditOrderResponse {
 }
 
 /// An order.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(
+    all(feature = "strict-models", not(feature = "capture-unknown")),
+    serde(deny_unknown_fields)
+)]
 pub struct Order {
     /// Order ID.
     pub order_id: String,
@@ -482,43 +852,59 @@ pub struct Order {
     pub order_placement_source: Option<String>,
     /// Outstanding hold amount.
     pub outstanding_hold_amount: Option<String>,
+    /// Fields not yet modelled by this crate.
+    #[cfg(feature = "capture-unknown")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Parameters for listing orders.
-#[derive(Debug, Clone, Default, Serialize)]
+///
+/// Build with [`ListOrdersParams::new`] and the setters; fields may be added
+/// in minor releases.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[non_exhaustive]
 pub struct ListOrdersParams {
-    /// Filter by product IDs.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub product_ids: Option<String>,
-    /// Filter by order status.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub order_status: Option<String>,
+    /// Filter by product IDs, sent as one `product_ids` parameter each.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "super::product::serialize_product_ids"
+    )]
+    pub product_ids: Vec<String>,
+    /// Filter by order statuses, sent as one `order_status` parameter each.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order_status: Vec<OrderStatus>,
     /// Maximum number of orders.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u32>,
-    /// Start date (ISO 8601).
+    /// Only orders created at or after this time.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub start_date: Option<String>,
-    /// End date (ISO 8601).
+    pub start_date: Option<DateTime<Utc>>,
+    /// Only orders created before this time.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub end_date: Option<String>,
+    pub end_date: Option<DateTime<Utc>>,
     /// Order side.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub order_side: Option<String>,
+    pub order_side: Option<OrderSide>,
     /// Cursor for pagination.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
     /// Product type.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub product_type: Option<String>,
+    pub product_type: Option<ProductType>,
     /// Order type.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub order_type: Option<String>,
+    pub order_type: Option<OrderType>,
     /// Retail portfolio ID.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retail_portfolio_id: Option<String>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(ListOrdersParams);
+
 impl ListOrdersParams {
     /// Create new list orders parameters.
     pub fn new() -> Self {
@@ -526,17 +912,82 @@ impl ListOrdersParams {
     }
 
     /// Filter by product ID.
+    ///
+    /// May be called more than once to match any of several products.
     pub fn product_id(mut self, product_id: impl Into<String>) -> Self {
-        self.product_ids = Some(product_id.into());
+        self.product_ids.push(product_id.into());
+        self
+    }
+
+    /// Filter by several product IDs.
+    ///
+    /// Accepts string slices, `String`s or [`ProductId`](super::ProductId)s.
+    ///
+    /// ```
+    /// use coinbase_advanced::models::ListOrdersParams;
+    ///
+    /// let params = ListOrdersParams::new().products(&["BTC-USD", "ETH-USD"]);
+    /// assert_eq!(params.product_ids, ["BTC-USD", "ETH-USD"]);
+    /// ```
+    pub fn products<I, S>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.product_ids
+            .extend(ids.into_iter().map(|id| id.as_ref().to_string()));
         self
     }
 
     /// Filter by order status.
-    pub fn status(mut self, status: impl Into<String>) -> Self {
-        self.order_status = Some(status.into());
+    ///
+    /// May be called more than once to match any of several statuses.
+    pub fn status(mut self, status: OrderStatus) -> Self {
+        self.order_status.push(status);
+        self
+    }
+
+    /// Filter by any of several order statuses.
+    pub fn statuses(mut self, statuses: impl IntoIterator<Item = OrderStatus>) -> Self {
+        self.order_status.extend(statuses);
         self
     }
 
+    /// Filter by order side.
+    pub fn side(mut self, side: OrderSide) -> Self {
+        self.order_side = Some(side);
+        self
+    }
+
+    /// Filter by order type.
+    pub fn order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = Some(order_type);
+        self
+    }
+
+    /// Filter by product type.
+    pub fn product_type(mut self, product_type: ProductType) -> Self {
+        self.product_type = Some(product_type);
+        self
+    }
+
+    /// Only include orders created at or after `start`.
+    pub fn start_date(mut self, start: DateTime<Utc>) -> Self {
+        self.start_date = Some(start);
+        self
+    }
+
+    /// Only include orders created before `end`.
+    pub fn end_date(mut self, end: DateTime<Utc>) -> Self {
+        self.end_date = Some(end);
+        self
+    }
+
+    /// Only include orders created in `[start, end)`.
+    pub fn date_range(self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.start_date(start).end_date(end)
+    }
+
     /// Set the limit.
     pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
@@ -548,10 +999,17 @@ impl ListOrdersParams {
         self.cursor = Some(cursor.into());
         self
     }
+
+    /// Filter by portfolio ID.
+    pub fn portfolio(mut self, portfolio_id: impl Into<String>) -> Self {
+        self.retail_portfolio_id = Some(portfolio_id.into());
+        self
+    }
 }
 
 /// Response from listing orders.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ListOrdersResponse {
     /// The orders.
     pub orders: Vec<Order>,
@@ -563,8 +1021,37 @@ pub struct ListOrdersResponse {
     pub cursor: Option<String>,
 }
 
+impl ListOrdersResponse {
+    /// Returns true if another page can be fetched with [`cursor`](Self::cursor).
+    pub fn has_next_page(&self) -> bool {
+        self.has_next && self.cursor.as_deref().is_some_and(|c| !c.is_empty())
+    }
+}
+
+impl IntoIterator for ListOrdersResponse {
+    type Item = Order;
+    type IntoIter = std::vec::IntoIter<Order>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.orders.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ListOrdersResponse {
+    type Item = &'a Order;
+    type IntoIter = std::slice::Iter<'a, Order>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.orders.iter()
+    }
+}
+
 /// An order fill (execution).
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(
+    all(feature = "strict-models", not(feature = "capture-unknown")),
+    serde(deny_unknown_fields)
+)]
 pub struct Fill {
     /// Entry ID.
     pub entry_id: String,
@@ -594,17 +1081,31 @@ pub struct Fill {
     pub user_id: Option<String>,
     /// Order side.
     pub side: Option<String>,
+    /// Fields not yet modelled by this crate.
+    #[cfg(feature = "capture-unknown")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Parameters for listing fills.
-#[derive(Debug, Clone, Default, Serialize)]
+///
+/// Build with [`ListFillsParams::new`] and the setters; fields may be added
+/// in minor releases.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[non_exhaustive]
 pub struct ListFillsParams {
-    /// Filter by order ID.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub order_id: Option<String>,
-    /// Filter by product ID.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub product_id: Option<String>,
+    /// Filter by order IDs.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order_ids: Vec<String>,
+    /// Filter by trade IDs.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub trade_ids: Vec<String>,
+    /// Filter by product IDs.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "super::product::serialize_product_ids"
+    )]
+    pub product_ids: Vec<String>,
     /// Start sequence timestamp.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start_sequence_timestamp: Option<String>,
@@ -617,8 +1118,16 @@ pub struct ListFillsParams {
     /// Cursor for pagination.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
+    /// Filter by retail portfolio ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retail_portfolio_id: Option<String>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(ListFillsParams);
+
 impl ListFillsParams {
     /// Create new list fills parameters.
     pub fn new() -> Self {
@@ -626,14 +1135,33 @@ impl ListFillsParams {
     }
 
     /// Filter by order ID.
+    ///
+    /// May be called more than once to match fills of any of several orders.
     pub fn order_id(mut self, order_id: impl Into<String>) -> Self {
-        self.order_id = Some(order_id.into());
+        self.order_ids.push(order_id.into());
+        self
+    }
+
+    /// Filter by trade ID.
+    ///
+    /// May be called more than once to match any of several trades.
+    pub fn trade_id(mut self, trade_id: impl Into<String>) -> Self {
+        self.trade_ids.push(trade_id.into());
         self
     }
 
     /// Filter by product ID.
+    ///
+    /// May be called more than once to match any of several products.
     pub fn product_id(mut self, product_id: impl Into<String>) -> Self {
-        self.product_id = Some(product_id.into());
+        self.product_ids.push(product_id.into());
+        self
+    }
+
+    /// Only include fills at or after `start`.
+    pub fn since(mut self, start: DateTime<Utc>) -> Self {
+        self.start_sequence_timestamp =
+            Some(start.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true));
         self
     }
 
@@ -648,10 +1176,17 @@ impl ListFillsParams {
         self.cursor = Some(cursor.into());
         self
     }
+
+    /// Filter by portfolio ID.
+    pub fn portfolio(mut self, portfolio_id: impl Into<String>) -> Self {
+        self.retail_portfolio_id = Some(portfolio_id.into());
+        self
+    }
 }
 
 /// Response from listing fills.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ListFillsResponse {
     /// The fills.
     pub fills: Vec<Fill>,
@@ -660,7 +1195,7 @@ pub struct ListFillsResponse {
 }
 
 /// Request to close a position.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ClosePositionRequest {
     /// Client order ID.
     pub client_order_id: String,
@@ -1,9 +1,12 @@
 //! Payment method types.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::models::MoneyAmount;
 
 /// A payment method available to the user.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct PaymentMethod {
     /// Unique identifier for the payment method.
     pub id: String,
@@ -35,17 +38,49 @@ pub struct PaymentMethod {
     /// Time at which this payment method was updated.
     #[serde(default)]
     pub updated_at: Option<String>,
+    /// Transfer limits, when reported for this payment method.
+    #[serde(default)]
+    pub limits: Option<PaymentMethodLimits>,
+}
+
+/// Transfer limits for a payment method.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct PaymentMethodLimits {
+    /// Deposit limits.
+    #[serde(default)]
+    pub deposit: Vec<PaymentMethodLimit>,
+    /// Withdrawal limits.
+    #[serde(default)]
+    pub withdraw: Vec<PaymentMethodLimit>,
+}
+
+/// A single transfer limit over a period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct PaymentMethodLimit {
+    /// The period the limit applies to, in days.
+    #[serde(default)]
+    pub period_in_days: Option<u32>,
+    /// The total limit for the period.
+    #[serde(default)]
+    pub total: Option<MoneyAmount>,
+    /// The amount remaining in the period.
+    #[serde(default)]
+    pub remaining: Option<MoneyAmount>,
 }
 
 /// Response containing a list of payment methods.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ListPaymentMethodsResponse {
     /// The payment methods.
     pub payment_methods: Vec<PaymentMethod>,
 }
 
 /// Response containing a single payment method.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetPaymentMethodResponse {
     /// The payment method.
     pub payment_method: PaymentMethod,
@@ -3,7 +3,8 @@
 use serde::{Deserialize, Serialize};
 
 /// Amount with value and currency.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct IntxAmount {
     /// The amount value.
     #[serde(default)]
@@ -14,7 +15,8 @@ pub struct IntxAmount {
 }
 
 /// INTX (perpetuals) position.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct IntxPosition {
     /// Product ID.
     pub product_id: String,
@@ -69,7 +71,8 @@ pub struct IntxPosition {
 }
 
 /// INTX position summary.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct IntxSummary {
     /// Aggregated PnL.
     #[serde(default)]
@@ -77,7 +80,8 @@ pub struct IntxSummary {
 }
 
 /// Response for listing perpetuals positions.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ListPerpetualsPositionsResponse {
     /// Positions.
     #[serde(default)]
@@ -88,14 +92,16 @@ pub struct ListPerpetualsPositionsResponse {
 }
 
 /// Response for getting a single perpetuals position.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetPerpetualsPositionResponse {
     /// The position.
     pub position: IntxPosition,
 }
 
 /// INTX portfolio balance.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct IntxPortfolioBalance {
     /// Asset.
     #[serde(default)]
@@ -118,7 +124,8 @@ pub struct IntxPortfolioBalance {
 }
 
 /// Response for getting portfolio balances.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetPortfolioBalancesResponse {
     /// Portfolio balances.
     #[serde(default)]
@@ -126,7 +133,8 @@ pub struct GetPortfolioBalancesResponse {
 }
 
 /// INTX portfolio summary.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct IntxPortfolioSummary {
     /// Unrealized PnL.
     #[serde(default)]
@@ -142,15 +150,82 @@ pub struct IntxPortfolioSummary {
     pub max_withdrawal_amount: Option<IntxAmount>,
 }
 
+/// Margin and borrowing state of an INTX portfolio.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct IntxPortfolioMargin {
+    /// Portfolio UUID.
+    #[serde(default)]
+    pub portfolio_uuid: Option<String>,
+    /// Collateral value.
+    #[serde(default)]
+    pub collateral: Option<String>,
+    /// Notional value of open positions.
+    #[serde(default)]
+    pub position_notional: Option<String>,
+    /// Notional value of open positions and open orders.
+    #[serde(default)]
+    pub open_position_notional: Option<String>,
+    /// Fees not yet settled.
+    #[serde(default)]
+    pub pending_fees: Option<String>,
+    /// Amount borrowed.
+    #[serde(default)]
+    pub borrow: Option<String>,
+    /// Interest accrued on the amount borrowed.
+    #[serde(default)]
+    pub accrued_interest: Option<String>,
+    /// Debt rolled over from previous periods.
+    #[serde(default)]
+    pub rolling_debt: Option<String>,
+    /// Initial margin requirement, as a fraction.
+    #[serde(default)]
+    pub portfolio_initial_margin: Option<String>,
+    /// Initial margin requirement, as an amount.
+    #[serde(default)]
+    pub portfolio_im_notional: Option<IntxAmount>,
+    /// Maintenance margin requirement, as a fraction.
+    #[serde(default)]
+    pub portfolio_maintenance_margin: Option<String>,
+    /// Maintenance margin requirement, as an amount.
+    #[serde(default)]
+    pub portfolio_mm_notional: Option<IntxAmount>,
+    /// How close the portfolio is to liquidation, as a percentage.
+    #[serde(default)]
+    pub liquidation_percentage: Option<String>,
+    /// Collateral above the liquidation threshold.
+    #[serde(default)]
+    pub liquidation_buffer: Option<String>,
+    /// Margin type (e.g., MARGIN_TYPE_CROSS).
+    #[serde(default)]
+    pub margin_type: Option<String>,
+    /// Margin flags (e.g., PORTFOLIO_MARGIN_FLAGS_UNSPECIFIED).
+    #[serde(default)]
+    pub margin_flags: Option<String>,
+    /// Liquidation status (e.g., PORTFOLIO_LIQUIDATION_STATUS_NOT_LIQUIDATING).
+    #[serde(default)]
+    pub liquidation_status: Option<String>,
+    /// Unrealized PnL.
+    #[serde(default)]
+    pub unrealized_pnl: Option<IntxAmount>,
+    /// Total balance.
+    #[serde(default)]
+    pub total_balance: Option<IntxAmount>,
+}
+
 /// Response for getting portfolio summary.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetPerpetualsPortfolioSummaryResponse {
+    /// Margin state of each portfolio.
+    #[serde(default)]
+    pub portfolios: Vec<IntxPortfolioMargin>,
     /// Summary.
     pub summary: IntxPortfolioSummary,
 }
 
 /// Request to allocate funds to a portfolio.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct AllocatePortfolioRequest {
     /// Portfolio UUID.
     pub portfolio_uuid: String,
@@ -180,8 +255,14 @@ impl AllocatePortfolioRequest {
 }
 
 /// Request to set multi-asset collateral.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct SetMultiAssetCollateralRequest {
+    /// Portfolio UUID.
+    ///
+    /// Filled in by [`PerpetualsApi::set_multi_asset_collateral`](crate::rest::PerpetualsApi::set_multi_asset_collateral)
+    /// when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub portfolio_uuid: Option<String>,
     /// Whether multi-asset collateral is enabled.
     pub multi_asset_collateral_enabled: bool,
 }
@@ -190,7 +271,22 @@ impl SetMultiAssetCollateralRequest {
     /// Create a new set multi-asset collateral request.
     pub fn new(enabled: bool) -> Self {
         Self {
+            portfolio_uuid: None,
             multi_asset_collateral_enabled: enabled,
         }
     }
 }
+
+/// Query parameters for getting the multi-asset collateral state.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct MultiAssetCollateralQuery<'a> {
+    pub portfolio_uuid: &'a str,
+}
+
+/// Multi-asset collateral state of a portfolio.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct MultiAssetCollateral {
+    /// Whether multi-asset collateral is enabled.
+    pub multi_asset_collateral_enabled: bool,
+}
@@ -2,8 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::common::{ExtraParams, impl_extra_param};
+
 /// Portfolio type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum PortfolioType {
     /// User's default portfolio.
@@ -18,7 +20,8 @@ pub enum PortfolioType {
 }
 
 /// A user's portfolio.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct Portfolio {
     /// Name of the portfolio.
     pub name: String,
@@ -33,7 +36,8 @@ pub struct Portfolio {
 }
 
 /// Balance information.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct PortfolioBalance {
     /// The balance value.
     pub value: String,
@@ -42,7 +46,8 @@ pub struct PortfolioBalance {
 }
 
 /// Portfolio balances breakdown.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct PortfolioBalances {
     /// Total balance.
     pub total_balance: PortfolioBalance,
@@ -64,7 +69,8 @@ pub struct PortfolioBalances {
 }
 
 /// Spot position in a portfolio.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct SpotPosition {
     /// The asset symbol (e.g., BTC, ETH).
     pub asset: String,
@@ -94,7 +100,8 @@ pub struct SpotPosition {
 }
 
 /// Portfolio breakdown with positions.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct PortfolioBreakdown {
     /// The portfolio.
     pub portfolio: Portfolio,
@@ -113,34 +120,42 @@ pub struct PortfolioBreakdown {
 }
 
 /// Response containing a list of portfolios.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ListPortfoliosResponse {
     /// The portfolios.
     pub portfolios: Vec<Portfolio>,
 }
 
 /// Response containing a portfolio breakdown.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetPortfolioBreakdownResponse {
     /// The breakdown.
     pub breakdown: PortfolioBreakdown,
 }
 
 /// Response containing a single portfolio.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct PortfolioResponse {
     /// The portfolio.
     pub portfolio: Portfolio,
 }
 
 /// Parameters for listing portfolios.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct ListPortfoliosParams {
     /// Filter by portfolio type.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub portfolio_type: Option<String>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(ListPortfoliosParams);
+
 impl ListPortfoliosParams {
     /// Create new list portfolios parameters.
     pub fn new() -> Self {
@@ -155,7 +170,7 @@ impl ListPortfoliosParams {
 }
 
 /// Request to create a portfolio.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CreatePortfolioRequest {
     /// The portfolio name.
     pub name: String,
@@ -169,7 +184,7 @@ impl CreatePortfolioRequest {
 }
 
 /// Request to edit a portfolio.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct EditPortfolioRequest {
     /// The new portfolio name.
     pub name: String,
@@ -183,7 +198,7 @@ impl EditPortfolioRequest {
 }
 
 /// Funds to move between portfolios.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct MoveFunds {
     /// The amount value.
     pub value: String,
@@ -202,7 +217,7 @@ impl MoveFunds {
 }
 
 /// Request to move funds between portfolios.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct MoveFundsRequest {
     /// The funds to move.
     pub funds: MoveFunds,
@@ -228,7 +243,8 @@ impl MoveFundsRequest {
 }
 
 /// Response from moving funds.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct MoveFundsResponse {
     /// Source portfolio UUID.
     pub source_portfolio_uuid: String,
@@ -1,9 +1,24 @@
 //! Product-related types.
 
+#[cfg(feature = "capture-unknown")]
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use super::common::{ExtraParams, impl_extra_param};
+use crate::error::Error;
+
 /// A tradeable product (trading pair).
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(
+    all(feature = "strict-models", not(feature = "capture-unknown")),
+    serde(deny_unknown_fields)
+)]
 pub struct Product {
     /// Product identifier (e.g., "BTC-USD").
     pub product_id: String,
@@ -59,10 +74,414 @@ pub struct Product {
     pub base_display_symbol: Option<String>,
     /// Display symbol for quote currency.
     pub quote_display_symbol: Option<String>,
+    /// Minimum price increment, if different from the quote increment.
+    #[serde(default)]
+    pub price_increment: Option<String>,
+    /// Whether the product can only be viewed, not traded.
+    #[serde(default)]
+    pub view_only: bool,
+    /// Display name of the product.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Product ID this product is an alias of (e.g., "BTC-USD" for "BTC-USDC").
+    #[serde(default)]
+    pub alias: Option<String>,
+    /// Product IDs that are aliases of this product.
+    #[serde(default)]
+    pub alias_to: Vec<String>,
+    /// Venue the product trades on (CBE, FCM, INTX).
+    #[serde(default)]
+    pub product_venue: Option<String>,
+    /// Approximate 24 hour volume in the quote currency.
+    #[serde(default)]
+    pub approximate_quote_24h_volume: Option<String>,
+    /// Midpoint of the best bid and ask.
+    #[serde(default)]
+    pub mid_market_price: Option<String>,
+    /// Trading session details for futures (FCM) products.
+    #[serde(default)]
+    pub fcm_trading_session_details: Option<FcmTradingSessionDetails>,
+    /// Contract details for futures and perpetuals.
+    #[serde(default)]
+    pub future_product_details: Option<FutureProductDetails>,
+    /// Fields not yet modelled by this crate.
+    #[cfg(feature = "capture-unknown")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Contract details of a futures or perpetual product.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct FutureProductDetails {
+    /// Venue the contract trades on.
+    #[serde(default)]
+    pub venue: Option<String>,
+    /// Contract code (e.g., "BIT").
+    #[serde(default)]
+    pub contract_code: Option<String>,
+    /// When the contract expires, empty for perpetuals.
+    #[serde(default)]
+    pub contract_expiry: Option<String>,
+    /// Amount of the underlying asset per contract.
+    #[serde(default)]
+    pub contract_size: Option<String>,
+    /// Unit of the underlying asset (e.g., "BTC").
+    #[serde(default)]
+    pub contract_root_unit: Option<String>,
+    /// Description of the contract group.
+    #[serde(default)]
+    pub group_description: Option<String>,
+    /// Time zone of the contract expiry.
+    #[serde(default)]
+    pub contract_expiry_timezone: Option<String>,
+    /// Short description of the contract group.
+    #[serde(default)]
+    pub group_short_description: Option<String>,
+    /// Who manages the risk of the contract.
+    #[serde(default)]
+    pub risk_managed_by: Option<String>,
+    /// Expiry type ("EXPIRING" or "PERPETUAL").
+    #[serde(default)]
+    pub contract_expiry_type: Option<String>,
+    /// Details specific to perpetuals.
+    #[serde(default)]
+    pub perpetual_details: Option<PerpetualDetails>,
+    /// Display name of the contract.
+    #[serde(default)]
+    pub contract_display_name: Option<String>,
+    /// Milliseconds until the contract expires.
+    #[serde(default)]
+    pub time_to_expiry_ms: Option<String>,
+    /// Whether the underlying is not a crypto asset.
+    #[serde(default)]
+    pub non_crypto: bool,
+    /// Name of the expiry (e.g., "31 JAN 25").
+    #[serde(default)]
+    pub contract_expiry_name: Option<String>,
+    /// Whether the contract trades around the clock.
+    #[serde(default)]
+    pub twenty_four_by_seven: bool,
+}
+
+impl FutureProductDetails {
+    /// Check if the contract is a perpetual.
+    pub fn is_perpetual(&self) -> bool {
+        self.contract_expiry_type.as_deref() == Some("PERPETUAL")
+    }
+
+    /// Get the contract expiry, if the contract expires.
+    pub fn expiry(&self) -> Option<DateTime<Utc>> {
+        self.contract_expiry
+            .as_deref()
+            .and_then(|expiry| DateTime::parse_from_rfc3339(expiry).ok())
+            .map(|expiry| expiry.with_timezone(&Utc))
+    }
+}
+
+/// Details of a perpetual contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct PerpetualDetails {
+    /// Open interest.
+    #[serde(default)]
+    pub open_interest: Option<String>,
+    /// Current funding rate.
+    #[serde(default)]
+    pub funding_rate: Option<String>,
+    /// Time of the next funding.
+    #[serde(default)]
+    pub funding_time: Option<String>,
+    /// Maximum leverage allowed.
+    #[serde(default)]
+    pub max_leverage: Option<String>,
+    /// UUID of the base asset.
+    #[serde(default)]
+    pub base_asset_uuid: Option<String>,
+    /// Type of the underlying.
+    #[serde(default)]
+    pub underlying_type: Option<String>,
+}
+
+/// Trading session of a futures product on the FCM venue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct FcmTradingSessionDetails {
+    /// Whether the trading session is currently open.
+    #[serde(default)]
+    pub is_session_open: bool,
+    /// When the current or next session opens.
+    #[serde(default)]
+    pub open_time: Option<DateTime<Utc>>,
+    /// When the current or next session closes.
+    #[serde(default)]
+    pub close_time: Option<DateTime<Utc>>,
+    /// Session state (e.g., "FCM_TRADING_SESSION_STATE_OPEN").
+    #[serde(default)]
+    pub session_state: Option<String>,
+    /// Whether orders cannot be entered outside the session.
+    #[serde(default)]
+    pub after_hours_order_entry_disabled: bool,
+}
+
+/// Quote currencies recognized when suggesting a fix for a product ID
+/// without a separator, such as "BTCUSD".
+const COMMON_QUOTES: &[&str] = &["USDC", "USDT", "USD", "EUR", "GBP", "BTC", "ETH"];
+
+/// A validated product ID, such as "BTC-USD".
+///
+/// Parsing trims whitespace and uppercases the ID, so "btc-usd" becomes
+/// "BTC-USD", and rejects IDs that could not name a product, with a
+/// suggestion where one is obvious. Futures IDs with more than two parts,
+/// such as "BIT-31JAN25-CDE", are accepted.
+///
+/// Converts into a `String`, so it can be passed to any parameter or
+/// channel that takes product IDs. Those parameters and channels check
+/// their IDs the same way before anything is sent, so "BTCUSD" is reported
+/// client-side rather than as a not-found error from the API.
+///
+/// ```
+/// use coinbase_advanced::models::ProductId;
+///
+/// let id: ProductId = "eth-usd".parse().unwrap();
+/// assert_eq!(id.as_str(), "ETH-USD");
+/// assert_eq!(id.base(), "ETH");
+/// assert_eq!(id.quote(), "USD");
+///
+/// let err = "ETHUSD".parse::<ProductId>().unwrap_err();
+/// assert!(err.to_string().contains("ETH-USD"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ProductId(String);
+
+impl ProductId {
+    /// Create a product ID from its base and quote currencies.
+    pub fn new(base: &str, quote: &str) -> Result<Self, Error> {
+        format!("{}-{}", base, quote).parse()
+    }
+
+    /// Get the product ID as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Get the base currency (the first part, e.g. "BTC" in "BTC-USD").
+    pub fn base(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+
+    /// Get the quote currency (the second part, e.g. "USD" in "BTC-USD").
+    ///
+    /// For futures IDs this is the second part of the ID, such as the
+    /// expiry in "BIT-31JAN25-CDE".
+    pub fn quote(&self) -> &str {
+        self.0.split('-').nth(1).unwrap_or_default()
+    }
+
+    /// Normalize and check a product ID, describing the problem if it is invalid.
+    fn check(s: &str) -> Result<String, String> {
+        let id = s.trim().to_ascii_uppercase();
+        if id.is_empty() {
+            return Err("Product ID is empty".to_string());
+        }
+        if let Some(c) = id.chars().find(|c| !c.is_ascii_alphanumeric() && *c != '-') {
+            return Err(format!("Invalid character {:?} in product ID {:?}", c, s));
+        }
+        if !id.contains('-') {
+            let suggestion = COMMON_QUOTES
+                .iter()
+                .find(|quote| id.len() > quote.len() && id.ends_with(*quote))
+                .map(|quote| {
+                    format!(
+                        "; did you mean {}-{}?",
+                        &id[..id.len() - quote.len()],
+                        quote
+                    )
+                })
+                .unwrap_or_default();
+            return Err(format!(
+                "Product ID {:?} must separate base and quote with '-'{}",
+                s, suggestion
+            ));
+        }
+        if id.split('-').any(str::is_empty) {
+            return Err(format!("Product ID {:?} has an empty part", s));
+        }
+        Ok(id)
+    }
+}
+
+impl FromStr for ProductId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::check(s)
+            .map(Self)
+            .map_err(|message| Error::parse(message, None))
+    }
+}
+
+/// Serialize a product ID parameter through [`ProductId`].
+///
+/// The ID is sent normalized, and an invalid one fails to encode, so it is
+/// reported before the request is sent.
+pub(crate) fn serialize_product_id<S: serde::Serializer>(
+    id: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    ProductId::check(id)
+        .map_err(serde::ser::Error::custom)?
+        .serialize(serializer)
+}
+
+/// Serialize a list of product ID parameters through [`ProductId`].
+///
+/// See [`serialize_product_id`].
+pub(crate) fn serialize_product_ids<S: serde::Serializer>(
+    ids: &[String],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    ids.iter()
+        .map(|id| ProductId::check(id))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(serde::ser::Error::custom)?
+        .serialize(serializer)
+}
+
+impl TryFrom<String> for ProductId {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ProductId> for String {
+    fn from(id: ProductId) -> Self {
+        id.0
+    }
+}
+
+impl AsRef<str> for ProductId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for ProductId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ProductId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl fmt::Display for ProductId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Product type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ProductType {
+    /// Spot trading pair.
+    Spot,
+    /// Futures contract.
+    Future,
+    /// Unknown product type.
+    #[serde(rename = "UNKNOWN_PRODUCT_TYPE", other)]
+    Unknown,
+}
+
+impl Product {
+    /// Check if the product's market is open.
+    ///
+    /// Spot products trade around the clock, so this is only false for
+    /// futures products outside their trading session.
+    pub fn is_session_open(&self) -> bool {
+        self.fcm_trading_session_details
+            .as_ref()
+            .is_none_or(|session| session.is_session_open)
+    }
+
+    /// Get the size and price increments of the product.
+    pub fn increments(&self) -> ProductIncrements {
+        ProductIncrements {
+            base_increment: self.base_increment.clone(),
+            quote_increment: self.quote_increment.clone(),
+            price_increment: self
+                .price_increment
+                .clone()
+                .filter(|p| !p.is_empty())
+                .unwrap_or_else(|| self.quote_increment.clone()),
+            base_min_size: self.base_min_size.clone(),
+            base_max_size: self.base_max_size.clone(),
+            quote_min_size: self.quote_min_size.clone(),
+            quote_max_size: self.quote_max_size.clone(),
+        }
+    }
+}
+
+/// Size and price increments for a product, used to quantize order values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductIncrements {
+    /// Minimum base size increment.
+    pub base_increment: String,
+    /// Minimum quote size increment.
+    pub quote_increment: String,
+    /// Minimum price increment.
+    pub price_increment: String,
+    /// Minimum base order size.
+    pub base_min_size: String,
+    /// Maximum base order size.
+    pub base_max_size: String,
+    /// Minimum quote order size.
+    pub quote_min_size: String,
+    /// Maximum quote order size.
+    pub quote_max_size: String,
+}
+
+impl ProductIncrements {
+    /// Round a base size down to the base increment.
+    pub fn quantize_base(&self, size: Decimal) -> String {
+        quantize(size, &self.base_increment)
+    }
+
+    /// Round a quote size down to the quote increment.
+    pub fn quantize_quote(&self, size: Decimal) -> String {
+        quantize(size, &self.quote_increment)
+    }
+
+    /// Round a price down to the price increment.
+    pub fn quantize_price(&self, price: Decimal) -> String {
+        quantize(price, &self.price_increment)
+    }
+}
+
+/// Round a value down to a multiple of `increment`, formatted with the
+/// increment's number of decimal places.
+fn quantize(value: Decimal, increment: &str) -> String {
+    let decimals = increment
+        .split_once('.')
+        .map(|(_, frac)| frac.trim_end_matches('0').len())
+        .unwrap_or(0);
+    let step: Decimal = increment.parse().unwrap_or_default();
+    if step <= Decimal::ZERO {
+        return format!("{:.*}", decimals, value);
+    }
+
+    format!("{:.*}", decimals, (value / step).floor() * step)
 }
 
 /// Request parameters for listing products.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct ListProductsParams {
     /// Maximum number of products to return.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -73,14 +492,22 @@ pub struct ListProductsParams {
     /// Filter by product type.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub product_type: Option<String>,
-    /// Filter by specific product IDs (comma-separated).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub product_ids: Option<String>,
+    /// Filter by specific product IDs, sent as one `product_ids` parameter each.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_product_ids"
+    )]
+    pub product_ids: Vec<String>,
     /// Include all products.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub get_all_products: Option<bool>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(ListProductsParams);
+
 impl ListProductsParams {
     /// Create new list products parameters.
     pub fn new() -> Self {
@@ -106,8 +533,15 @@ impl ListProductsParams {
     }
 
     /// Filter by specific product IDs.
-    pub fn product_ids(mut self, ids: &[&str]) -> Self {
-        self.product_ids = Some(ids.join(","));
+    ///
+    /// Accepts string slices, `String`s or [`ProductId`]s.
+    pub fn product_ids<I, S>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.product_ids
+            .extend(ids.into_iter().map(|id| id.as_ref().to_string()));
         self
     }
 
@@ -119,7 +553,8 @@ impl ListProductsParams {
 }
 
 /// Response from listing products.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ListProductsResponse {
     /// The list of products.
     pub products: Vec<Product>,
@@ -128,15 +563,21 @@ pub struct ListProductsResponse {
 }
 
 /// Request parameters for getting product details.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct GetProductParams {
     /// Whether to include tradability status.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub get_tradability_status: Option<bool>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(GetProductParams);
+
 /// An entry in the order book (bid or ask).
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct BookLevel {
     /// Price level.
     pub price: String,
@@ -145,7 +586,8 @@ pub struct BookLevel {
 }
 
 /// Order book for a product.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ProductBook {
     /// Product ID.
     pub product_id: String,
@@ -158,16 +600,18 @@ pub struct ProductBook {
 }
 
 /// Response from getting product book.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetProductBookResponse {
     /// The order book.
     pub pricebook: ProductBook,
 }
 
 /// Request parameters for getting product book.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct GetProductBookParams {
     /// Product ID.
+    #[serde(serialize_with = "serialize_product_id")]
     pub product_id: String,
     /// Number of levels to return.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -175,8 +619,13 @@ pub struct GetProductBookParams {
     /// Price aggregation increment.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub aggregation_price_increment: Option<String>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(GetProductBookParams);
+
 impl GetProductBookParams {
     /// Create new product book parameters.
     pub fn new(product_id: impl Into<String>) -> Self {
@@ -184,6 +633,7 @@ impl GetProductBookParams {
             product_id: product_id.into(),
             limit: None,
             aggregation_price_increment: None,
+            extra: ExtraParams::default(),
         }
     }
 
@@ -201,7 +651,8 @@ impl GetProductBookParams {
 }
 
 /// Best bid and ask for a product.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct BestBidAsk {
     /// Product ID.
     pub product_id: String,
@@ -214,20 +665,29 @@ pub struct BestBidAsk {
 }
 
 /// Response from getting best bid/ask.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetBestBidAskResponse {
     /// Best bid/ask for each product.
     pub pricebooks: Vec<BestBidAsk>,
 }
 
 /// Request parameters for getting best bid/ask.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct GetBestBidAskParams {
-    /// Product IDs (comma-separated).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub product_ids: Option<String>,
+    /// Product IDs, sent as one `product_ids` parameter each.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_product_ids"
+    )]
+    pub product_ids: Vec<String>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(GetBestBidAskParams);
+
 impl GetBestBidAskParams {
     /// Create new best bid/ask parameters.
     pub fn new() -> Self {
@@ -235,14 +695,21 @@ impl GetBestBidAskParams {
     }
 
     /// Set the product IDs.
-    pub fn product_ids(mut self, ids: &[&str]) -> Self {
-        self.product_ids = Some(ids.join(","));
+    ///
+    /// Accepts string slices, `String`s or [`ProductId`]s.
+    pub fn product_ids<I, S>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.product_ids
+            .extend(ids.into_iter().map(|id| id.as_ref().to_string()));
         self
     }
 }
 
 /// Candle data type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Granularity {
     /// 1 minute candles.
@@ -263,8 +730,103 @@ pub enum Granularity {
     OneDay,
 }
 
+impl Granularity {
+    /// All granularities, from smallest to largest.
+    pub const ALL: [Granularity; 8] = [
+        Granularity::OneMinute,
+        Granularity::FiveMinute,
+        Granularity::FifteenMinute,
+        Granularity::ThirtyMinute,
+        Granularity::OneHour,
+        Granularity::TwoHour,
+        Granularity::SixHour,
+        Granularity::OneDay,
+    ];
+
+    /// Get the length of one candle in seconds.
+    pub fn as_seconds(&self) -> u64 {
+        match self {
+            Granularity::OneMinute => 60,
+            Granularity::FiveMinute => 5 * 60,
+            Granularity::FifteenMinute => 15 * 60,
+            Granularity::ThirtyMinute => 30 * 60,
+            Granularity::OneHour => 60 * 60,
+            Granularity::TwoHour => 2 * 60 * 60,
+            Granularity::SixHour => 6 * 60 * 60,
+            Granularity::OneDay => 24 * 60 * 60,
+        }
+    }
+
+    /// Get the length of one candle.
+    pub fn as_duration(&self) -> Duration {
+        Duration::from_secs(self.as_seconds())
+    }
+
+    /// Get the short form of the granularity (e.g., "1m", "1h", "1d").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Granularity::OneMinute => "1m",
+            Granularity::FiveMinute => "5m",
+            Granularity::FifteenMinute => "15m",
+            Granularity::ThirtyMinute => "30m",
+            Granularity::OneHour => "1h",
+            Granularity::TwoHour => "2h",
+            Granularity::SixHour => "6h",
+            Granularity::OneDay => "1d",
+        }
+    }
+
+    /// Get the start of the candle containing a Unix timestamp (in seconds).
+    pub fn bucket_start(&self, timestamp: u64) -> u64 {
+        timestamp - timestamp % self.as_seconds()
+    }
+
+    /// Get the start of the candle following the one containing a Unix timestamp.
+    pub fn bucket_end(&self, timestamp: u64) -> u64 {
+        self.bucket_start(timestamp) + self.as_seconds()
+    }
+}
+
+impl From<Granularity> for Duration {
+    fn from(granularity: Granularity) -> Self {
+        granularity.as_duration()
+    }
+}
+
+impl fmt::Display for Granularity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Granularity {
+    type Err = Error;
+
+    /// Parse a granularity from its short form ("1m", "1h", "1d") or its API
+    /// name ("ONE_MINUTE").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "1M" | "ONE_MINUTE" => Ok(Granularity::OneMinute),
+            "5M" | "FIVE_MINUTE" => Ok(Granularity::FiveMinute),
+            "15M" | "FIFTEEN_MINUTE" => Ok(Granularity::FifteenMinute),
+            "30M" | "THIRTY_MINUTE" => Ok(Granularity::ThirtyMinute),
+            "1H" | "ONE_HOUR" => Ok(Granularity::OneHour),
+            "2H" | "TWO_HOUR" => Ok(Granularity::TwoHour),
+            "6H" | "SIX_HOUR" => Ok(Granularity::SixHour),
+            "1D" | "ONE_DAY" => Ok(Granularity::OneDay),
+            _ => Err(Error::parse(format!("Unknown granularity: {}", s), None)),
+        }
+    }
+}
+
+/// Get the start of the candle containing a Unix timestamp (in seconds).
+pub fn bucket_start(timestamp: u64, granularity: Granularity) -> u64 {
+    granularity.bucket_start(timestamp)
+}
+
 /// A candlestick (OHLCV) data point.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct Candle {
     /// Start time (Unix timestamp).
     pub start: String,
@@ -281,7 +843,7 @@ pub struct Candle {
 }
 
 /// Request parameters for getting candles.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct GetCandlesParams {
     /// Product ID.
     #[serde(skip_serializing)]
@@ -295,8 +857,13 @@ pub struct GetCandlesParams {
     /// Maximum number of candles.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u32>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(GetCandlesParams);
+
 impl GetCandlesParams {
     /// Create new candles parameters.
     pub fn new(
@@ -311,6 +878,7 @@ impl GetCandlesParams {
             end: end.into(),
             granularity,
             limit: None,
+            extra: ExtraParams::default(),
         }
     }
 
@@ -322,14 +890,16 @@ impl GetCandlesParams {
 }
 
 /// Response from getting candles.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetCandlesResponse {
     /// The candle data.
     pub candles: Vec<Candle>,
 }
 
 /// A market trade.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct Trade {
     /// Trade ID.
     pub trade_id: String,
@@ -346,7 +916,7 @@ pub struct Trade {
 }
 
 /// Request parameters for getting market trades.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct GetMarketTradesParams {
     /// Product ID.
     #[serde(skip_serializing)]
@@ -359,8 +929,13 @@ pub struct GetMarketTradesParams {
     /// End time (Unix timestamp).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end: Option<String>,
+    /// Additional query parameters.
+    #[serde(flatten)]
+    pub extra: ExtraParams,
 }
 
+impl_extra_param!(GetMarketTradesParams);
+
 impl GetMarketTradesParams {
     /// Create new market trades parameters.
     pub fn new(product_id: impl Into<String>, limit: u32) -> Self {
@@ -369,6 +944,7 @@ impl GetMarketTradesParams {
             limit,
             start: None,
             end: None,
+            extra: ExtraParams::default(),
         }
     }
 
@@ -386,7 +962,8 @@ impl GetMarketTradesParams {
 }
 
 /// Response from getting market trades.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct GetMarketTradesResponse {
     /// The trades.
     pub trades: Vec<Trade>,
@@ -395,3 +972,56 @@ pub struct GetMarketTradesResponse {
     /// Best ask.
     pub best_ask: Option<String>,
 }
+
+/// Parameters for a combined product snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductSnapshotParams {
+    /// Product ID.
+    pub product_id: String,
+    /// Number of most recent candles.
+    pub candles: u32,
+    /// Candle granularity.
+    pub granularity: Granularity,
+    /// Number of most recent trades.
+    pub trades: u32,
+}
+
+impl ProductSnapshotParams {
+    /// Create snapshot parameters with the last 60 one-minute candles and
+    /// the last 100 trades.
+    pub fn new(product_id: impl Into<String>) -> Self {
+        Self {
+            product_id: product_id.into(),
+            candles: 60,
+            granularity: Granularity::OneMinute,
+            trades: 100,
+        }
+    }
+
+    /// Set how many of the most recent candles to fetch, and their granularity.
+    pub fn candles(mut self, count: u32, granularity: Granularity) -> Self {
+        self.candles = count;
+        self.granularity = granularity;
+        self
+    }
+
+    /// Set how many of the most recent trades to fetch.
+    pub fn trades(mut self, count: u32) -> Self {
+        self.trades = count;
+        self
+    }
+}
+
+/// Product details, top of book, recent candles and recent trades, fetched
+/// together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductSnapshot {
+    /// Product details.
+    pub product: Product,
+    /// Best bid and ask.
+    pub best_bid_ask: Option<BestBidAsk>,
+    /// Most recent candles, oldest first.
+    pub candles: Vec<Candle>,
+    /// Most recent trades, as returned by the API (newest first).
+    pub trades: Vec<Trade>,
+}
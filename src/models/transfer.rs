@@ -0,0 +1,214 @@
+//! Deposit and withdrawal types.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::models::{PaymentMethod, parse_plain_decimal};
+
+/// The direction of a fiat transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransferKind {
+    /// Moving funds from a payment method into a Coinbase account.
+    Deposit,
+    /// Moving funds from a Coinbase account to a payment method.
+    Withdrawal,
+}
+
+impl TransferKind {
+    /// Get the path segment used by the API for this kind of transfer.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferKind::Deposit => "deposits",
+            TransferKind::Withdrawal => "withdrawals",
+        }
+    }
+}
+
+/// Request to deposit or withdraw fiat funds using a payment method.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransferRequest {
+    /// The amount to transfer.
+    pub amount: String,
+    /// The currency of the amount (e.g., "USD").
+    pub currency: String,
+    /// The ID of the payment method to transfer from or to.
+    pub payment_method: String,
+    /// Whether to commit the transfer immediately.
+    ///
+    /// When false, the transfer is created in a pending state and must be
+    /// committed separately.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<bool>,
+}
+
+impl TransferRequest {
+    /// Create a new transfer request.
+    pub fn new(
+        amount: impl Into<String>,
+        currency: impl Into<String>,
+        payment_method: impl Into<String>,
+    ) -> Self {
+        Self {
+            amount: amount.into(),
+            currency: currency.into(),
+            payment_method: payment_method.into(),
+            commit: None,
+        }
+    }
+
+    /// Set whether to commit the transfer immediately.
+    pub fn commit(mut self, commit: bool) -> Self {
+        self.commit = Some(commit);
+        self
+    }
+
+    /// Check the request against a payment method's capabilities and limits.
+    ///
+    /// Verifies that the payment method matches the request, supports the kind
+    /// of transfer, uses the same currency, and has enough remaining limit
+    /// (when the API reports limits for it).
+    pub fn validate(&self, payment_method: &PaymentMethod, kind: TransferKind) -> Result<()> {
+        if payment_method.id != self.payment_method {
+            return Err(Error::request(format!(
+                "payment method {} does not match request payment method {}",
+                payment_method.id, self.payment_method
+            )));
+        }
+
+        let allowed = match kind {
+            TransferKind::Deposit => payment_method.allow_deposit,
+            TransferKind::Withdrawal => payment_method.allow_withdraw,
+        };
+        if !allowed {
+            return Err(Error::request(format!(
+                "payment method {} does not allow {}",
+                payment_method.id,
+                kind.as_str()
+            )));
+        }
+
+        if !payment_method.currency.eq_ignore_ascii_case(&self.currency) {
+            return Err(Error::request(format!(
+                "currency {} does not match payment method currency {}",
+                self.currency, payment_method.currency
+            )));
+        }
+
+        let amount = parse_plain_decimal(&self.amount)
+            .ok_or_else(|| Error::request(format!("invalid amount: {}", self.amount)))?;
+        if amount <= Decimal::ZERO {
+            return Err(Error::request("amount must be positive"));
+        }
+
+        let limits = payment_method.limits.as_ref().map(|l| match kind {
+            TransferKind::Deposit => &l.deposit,
+            TransferKind::Withdrawal => &l.withdraw,
+        });
+        for limit in limits.into_iter().flatten() {
+            let Some(remaining) = &limit.remaining else {
+                continue;
+            };
+            if let Ok(remaining_value) = remaining.amount.parse::<Decimal>() {
+                if amount > remaining_value {
+                    return Err(Error::request(format!(
+                        "amount {} exceeds remaining {} limit of {} {}",
+                        self.amount,
+                        kind.as_str(),
+                        remaining.amount,
+                        remaining.currency
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A monetary amount as reported by the transfer endpoints.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct MoneyAmount {
+    /// The numeric amount as a string.
+    pub amount: String,
+    /// The currency code.
+    pub currency: String,
+}
+
+/// A reference to another API resource.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ResourceRef {
+    /// The resource ID.
+    pub id: String,
+    /// The resource type.
+    #[serde(default)]
+    pub resource: String,
+    /// The resource path.
+    #[serde(default)]
+    pub resource_path: String,
+}
+
+/// The status of a deposit or withdrawal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    /// The transfer has been created but not committed.
+    Created,
+    /// The transfer has completed.
+    Completed,
+    /// The transfer was canceled.
+    Canceled,
+    /// A status not known to this client.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A deposit or withdrawal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct Transfer {
+    /// The transfer ID.
+    pub id: String,
+    /// The transfer status.
+    pub status: TransferStatus,
+    /// The payment method used.
+    #[serde(default)]
+    pub payment_method: Option<ResourceRef>,
+    /// The associated account transaction.
+    #[serde(default)]
+    pub transaction: Option<ResourceRef>,
+    /// The amount transferred, after fees.
+    #[serde(default)]
+    pub amount: Option<MoneyAmount>,
+    /// The amount before fees.
+    #[serde(default)]
+    pub subtotal: Option<MoneyAmount>,
+    /// The fee charged.
+    #[serde(default)]
+    pub fee: Option<MoneyAmount>,
+    /// Whether the transfer has been committed.
+    #[serde(default)]
+    pub committed: bool,
+    /// Whether the transfer is instant.
+    #[serde(default)]
+    pub instant: bool,
+    /// When the transfer was created.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// When the transfer was last updated.
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// When the funds will arrive.
+    #[serde(default)]
+    pub payout_at: Option<String>,
+}
+
+/// Response wrapping a single transfer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct TransferResponse {
+    /// The transfer.
+    pub data: Transfer,
+}
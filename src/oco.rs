@@ -0,0 +1,845 @@
+//! Take-profit/stop-loss brackets managed client-side.
+//!
+//! Spot markets have no native bracket orders. [`OcoManager`] emulates them:
+//! it places an entry order and waits for the fill on the WebSocket user
+//! channel, then places a stop-loss stop-limit order for the filled size.
+//!
+//! On spot, an open sell order holds the base currency it sells, so a resting
+//! take-profit and a resting stop-loss for the same position cannot both be
+//! accepted. The take-profit is therefore armed locally: when the ticker price
+//! reaches it, the manager cancels the stop-loss and only then places the
+//! take-profit limit order. At no point are both exits open at once.
+//!
+//! The exits are coordinated locally, so the manager must keep observing the
+//! user and ticker channels for as long as a bracket is open. If the process
+//! stops, the stop-loss stays open on the exchange but the take-profit never
+//! triggers. Once the take-profit has been placed, the position is no longer
+//! protected by a stop.
+//!
+//! Order requests that fail are reported as [`OcoEvent::Failed`] alongside
+//! the other events, rather than as errors, so no event is lost.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::oco::{BracketExits, OcoManager};
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//! use coinbase_advanced::{Credentials, RestClient};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let credentials = Credentials::from_env()?;
+//! let rest = RestClient::builder().credentials(credentials.clone()).build()?;
+//! let ws = WebSocketClient::builder().credentials(credentials).build()?;
+//! let stream = ws.connect().await?;
+//! ws.subscribe(&[
+//!     Channel::User,
+//!     Channel::Ticker {
+//!         product_ids: vec!["BTC-USD".to_string()],
+//!     },
+//! ])
+//! .await?;
+//!
+//! let entry = rest
+//!     .limit_order_gtc()
+//!     .buy("BTC-USD")
+//!     .base_size("0.001")
+//!     .limit_price("50000.00")
+//!     .build()?;
+//!
+//! let mut oco = OcoManager::new(rest);
+//! oco.place(entry, BracketExits::new("55000.00", "48000.00", "47900.00"))
+//!     .await?;
+//!
+//! for event in oco.run(stream).await {
+//!     println!("{:?}", event);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use futures::{Stream, StreamExt};
+use rust_decimal::Decimal;
+
+use crate::client::RestClient;
+use crate::error::{Error, Result};
+use crate::models::{
+    CancelOrdersRequest, CreateOrderRequest, CreateOrderResponse, OrderSide, OrderStatus,
+    StopDirection, parse_plain_decimal,
+};
+use crate::ws::{Events, Message, OrderUpdate};
+
+/// Exit prices for a bracket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketExits {
+    /// Limit price of the take-profit order, and the price that triggers it.
+    pub take_profit_price: String,
+    /// Trigger price of the stop-loss order.
+    pub stop_price: String,
+    /// Limit price of the stop-loss order once triggered.
+    pub stop_limit_price: String,
+}
+
+impl BracketExits {
+    /// Create exit prices for a bracket.
+    pub fn new(
+        take_profit_price: impl Into<String>,
+        stop_price: impl Into<String>,
+        stop_limit_price: impl Into<String>,
+    ) -> Self {
+        Self {
+            take_profit_price: take_profit_price.into(),
+            stop_price: stop_price.into(),
+            stop_limit_price: stop_limit_price.into(),
+        }
+    }
+
+    /// Check that the exits make sense for an entry on the given side.
+    ///
+    /// A long position (buy entry) takes profit above the stop, and a short
+    /// position (sell entry) takes profit below it.
+    pub fn validate(&self, entry_side: OrderSide) -> Result<()> {
+        let take_profit = parse_price("take_profit_price", &self.take_profit_price)?;
+        let stop = parse_price("stop_price", &self.stop_price)?;
+        parse_price("stop_limit_price", &self.stop_limit_price)?;
+
+        let ordered = match entry_side {
+            OrderSide::Buy => take_profit > stop,
+            OrderSide::Sell => take_profit < stop,
+        };
+        if !ordered {
+            return Err(Error::request(format!(
+                "take profit {} must be on the profitable side of stop {} for a {:?} entry",
+                self.take_profit_price, self.stop_price, entry_side
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Parse a positive decimal price, rejecting exponents and non-finite values.
+fn parse_price(field: &str, value: &str) -> Result<Decimal> {
+    parse_plain_decimal(value)
+        .filter(|n| n.is_sign_positive() && !n.is_zero())
+        .ok_or_else(|| Error::request(format!("invalid {}: {}", field, value)))
+}
+
+/// One of the two exit orders of a bracket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitLeg {
+    /// The take-profit limit order.
+    TakeProfit,
+    /// The stop-loss stop-limit order.
+    StopLoss,
+}
+
+/// The progress of a bracket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BracketState {
+    /// The entry order is open and has not filled yet.
+    AwaitingEntry,
+    /// The entry filled and the stop-loss is open. The take-profit is armed
+    /// locally and is placed once the price reaches it.
+    Active {
+        /// The base size filled by the entry.
+        size: Decimal,
+        /// The stop-loss order ID.
+        stop_loss_order_id: String,
+        /// The base size the stop-loss has filled so far.
+        stop_loss_filled: Decimal,
+    },
+    /// The take-profit price was reached: the stop-loss was cancelled and the
+    /// take-profit order is open.
+    TakingProfit {
+        /// The base size of the take-profit order.
+        size: Decimal,
+        /// The take-profit order ID.
+        take_profit_order_id: String,
+    },
+}
+
+/// An entry order with its take-profit and stop-loss exits.
+#[derive(Debug, Clone)]
+pub struct Bracket {
+    /// The entry order ID.
+    pub entry_order_id: String,
+    /// The product ID.
+    pub product_id: String,
+    /// The side of the entry order.
+    pub entry_side: OrderSide,
+    /// The exit prices.
+    pub exits: BracketExits,
+    /// The current state.
+    pub state: BracketState,
+}
+
+impl Bracket {
+    /// The side of the exit orders, opposite to the entry.
+    pub fn exit_side(&self) -> OrderSide {
+        match self.entry_side {
+            OrderSide::Buy => OrderSide::Sell,
+            OrderSide::Sell => OrderSide::Buy,
+        }
+    }
+
+    /// The stop direction of the stop-loss, which triggers against the position.
+    fn stop_direction(&self) -> StopDirection {
+        match self.entry_side {
+            OrderSide::Buy => StopDirection::StopDirectionStopDown,
+            OrderSide::Sell => StopDirection::StopDirectionStopUp,
+        }
+    }
+
+    /// Returns true if the order ID belongs to this bracket.
+    fn owns(&self, order_id: &str) -> bool {
+        match &self.state {
+            BracketState::AwaitingEntry => self.entry_order_id == order_id,
+            BracketState::Active {
+                stop_loss_order_id, ..
+            } => stop_loss_order_id == order_id,
+            BracketState::TakingProfit {
+                take_profit_order_id,
+                ..
+            } => take_profit_order_id == order_id,
+        }
+    }
+
+    /// Returns true if the price has reached the take-profit of an active bracket.
+    fn take_profit_reached(&self, price: Decimal) -> bool {
+        if !matches!(self.state, BracketState::Active { .. }) {
+            return false;
+        }
+        let Ok(take_profit) = Decimal::from_str(&self.exits.take_profit_price) else {
+            return false;
+        };
+        match self.entry_side {
+            OrderSide::Buy => price >= take_profit,
+            OrderSide::Sell => price <= take_profit,
+        }
+    }
+
+    /// Decide what to do about an update to one of this bracket's orders.
+    fn next_action(&self, update: &OrderUpdate) -> Option<Action> {
+        let status: OrderStatus = update.status.parse().unwrap_or(OrderStatus::Unknown);
+        let filled = Decimal::from_str(&update.cumulative_quantity).unwrap_or_default();
+        let ended = matches!(
+            status,
+            OrderStatus::Cancelled | OrderStatus::Expired | OrderStatus::Failed
+        );
+        match &self.state {
+            BracketState::AwaitingEntry => match status {
+                // An entry that ends after a partial fill still leaves a
+                // position, which needs protecting like a full fill.
+                OrderStatus::Filled
+                | OrderStatus::Cancelled
+                | OrderStatus::Expired
+                | OrderStatus::Failed
+                    if filled > Decimal::ZERO =>
+                {
+                    Some(Action::PlaceStopLoss {
+                        size: filled,
+                        status,
+                    })
+                }
+                _ if ended => Some(Action::EntryEnded { status }),
+                _ => None,
+            },
+            BracketState::Active {
+                stop_loss_filled, ..
+            } => match status {
+                OrderStatus::Filled => Some(Action::ExitFilled {
+                    leg: ExitLeg::StopLoss,
+                    order_id: update.order_id.clone(),
+                }),
+                _ if ended => Some(Action::ExitEnded {
+                    leg: ExitLeg::StopLoss,
+                    order_id: update.order_id.clone(),
+                    status,
+                }),
+                _ if filled > *stop_loss_filled => Some(Action::StopLossFilling { filled }),
+                _ => None,
+            },
+            BracketState::TakingProfit { .. } => match status {
+                OrderStatus::Filled => Some(Action::ExitFilled {
+                    leg: ExitLeg::TakeProfit,
+                    order_id: update.order_id.clone(),
+                }),
+                _ if ended => Some(Action::ExitEnded {
+                    leg: ExitLeg::TakeProfit,
+                    order_id: update.order_id.clone(),
+                    status,
+                }),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Something that happened to a bracket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcoEvent {
+    /// The entry filled and the stop-loss was placed.
+    ///
+    /// This is also sent when the entry ended after a partial fill, in which
+    /// case `status` is the entry's final status and `size` the partial fill.
+    EntryFilled {
+        /// The entry order ID.
+        entry_order_id: String,
+        /// The final status of the entry.
+        status: OrderStatus,
+        /// The filled base size, used for the exits.
+        size: Decimal,
+        /// The stop-loss order ID.
+        stop_loss_order_id: String,
+    },
+    /// The entry ended without filling. The bracket is no longer managed.
+    EntryEnded {
+        /// The entry order ID.
+        entry_order_id: String,
+        /// The final status of the entry.
+        status: OrderStatus,
+    },
+    /// The price reached the take-profit: the stop-loss was cancelled and the
+    /// take-profit order was placed.
+    TakeProfitTriggered {
+        /// The entry order ID.
+        entry_order_id: String,
+        /// The price that triggered the take-profit.
+        price: Decimal,
+        /// The ID of the stop-loss that was cancelled.
+        cancelled_order_id: String,
+        /// The take-profit order ID.
+        take_profit_order_id: String,
+        /// The base size of the take-profit order.
+        size: Decimal,
+    },
+    /// An exit filled. The bracket is closed.
+    ExitFilled {
+        /// The entry order ID.
+        entry_order_id: String,
+        /// Which exit filled.
+        leg: ExitLeg,
+        /// The ID of the exit that filled.
+        order_id: String,
+    },
+    /// An exit ended without filling, e.g. it was cancelled elsewhere.
+    ///
+    /// The position is left without an exit and the bracket is no longer managed.
+    ExitEnded {
+        /// The entry order ID.
+        entry_order_id: String,
+        /// Which exit ended.
+        leg: ExitLeg,
+        /// The ID of the exit that ended.
+        order_id: String,
+        /// The final status of the exit.
+        status: OrderStatus,
+    },
+    /// An order request for the bracket failed.
+    ///
+    /// If the stop-loss or take-profit could not be placed, the position is
+    /// left without an exit and the bracket is no longer managed. If the
+    /// stop-loss could not be cancelled to take profit, the bracket stays
+    /// active and the take-profit is tried again at the next price.
+    Failed {
+        /// The entry order ID.
+        entry_order_id: String,
+        /// What failed.
+        error: String,
+    },
+}
+
+/// What the manager must do in response to an order update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Action {
+    PlaceStopLoss {
+        size: Decimal,
+        status: OrderStatus,
+    },
+    EntryEnded {
+        status: OrderStatus,
+    },
+    StopLossFilling {
+        filled: Decimal,
+    },
+    ExitFilled {
+        leg: ExitLeg,
+        order_id: String,
+    },
+    ExitEnded {
+        leg: ExitLeg,
+        order_id: String,
+        status: OrderStatus,
+    },
+}
+
+/// Places brackets and coordinates their exits from the user and ticker channels.
+pub struct OcoManager {
+    client: RestClient,
+    brackets: HashMap<String, Bracket>,
+}
+
+impl OcoManager {
+    /// Create a manager that places orders with the given client.
+    pub fn new(client: RestClient) -> Self {
+        Self {
+            client,
+            brackets: HashMap::new(),
+        }
+    }
+
+    /// Place an entry order and manage its exits once it fills.
+    ///
+    /// Returns the entry order ID.
+    pub async fn place(
+        &mut self,
+        entry: CreateOrderRequest,
+        exits: BracketExits,
+    ) -> Result<String> {
+        exits.validate(entry.side)?;
+
+        let product_id = entry.product_id.clone();
+        let entry_side = entry.side;
+        let entry_order_id = self.client.orders().create(entry).await?.into_order_id()?;
+        tracing::debug!(%entry_order_id, %product_id, "placed bracket entry");
+
+        self.brackets.insert(
+            entry_order_id.clone(),
+            Bracket {
+                entry_order_id: entry_order_id.clone(),
+                product_id,
+                entry_side,
+                exits,
+                state: BracketState::AwaitingEntry,
+            },
+        );
+        Ok(entry_order_id)
+    }
+
+    /// Get a managed bracket by entry order ID.
+    pub fn get(&self, entry_order_id: &str) -> Option<&Bracket> {
+        self.brackets.get(entry_order_id)
+    }
+
+    /// Iterate over the managed brackets.
+    pub fn brackets(&self) -> impl Iterator<Item = &Bracket> {
+        self.brackets.values()
+    }
+
+    /// Number of brackets being managed.
+    pub fn len(&self) -> usize {
+        self.brackets.len()
+    }
+
+    /// Returns true if no brackets are being managed.
+    pub fn is_empty(&self) -> bool {
+        self.brackets.is_empty()
+    }
+
+    /// Cancel the open order of a bracket and stop managing it.
+    pub async fn cancel(&mut self, entry_order_id: &str) -> Result<()> {
+        let Some(bracket) = self.brackets.remove(entry_order_id) else {
+            return Ok(());
+        };
+        let order_id = match bracket.state {
+            BracketState::AwaitingEntry => bracket.entry_order_id,
+            BracketState::Active {
+                stop_loss_order_id, ..
+            } => stop_loss_order_id,
+            BracketState::TakingProfit {
+                take_profit_order_id,
+                ..
+            } => take_profit_order_id,
+        };
+        self.client
+            .orders()
+            .cancel(CancelOrdersRequest::single(order_id))
+            .await?;
+        Ok(())
+    }
+
+    /// Handle the order and ticker updates in a WebSocket message.
+    ///
+    /// Messages from other channels are ignored.
+    pub async fn observe(&mut self, message: &Message) -> Vec<OcoEvent> {
+        let mut out = Vec::new();
+        match &message.events {
+            Events::User(events) => {
+                for update in events.iter().flat_map(|e| &e.orders) {
+                    out.extend(self.observe_order(update).await);
+                }
+            }
+            Events::Ticker(events) => {
+                for ticker in events.iter().flat_map(|e| &e.tickers) {
+                    let Ok(price) = Decimal::from_str(&ticker.price) else {
+                        continue;
+                    };
+                    out.extend(self.observe_price(&ticker.product_id, price).await);
+                }
+            }
+            _ => {}
+        }
+        out
+    }
+
+    /// Handle a price for a product from any source.
+    ///
+    /// Every active bracket on the product whose take-profit has been reached
+    /// has its stop-loss cancelled and its take-profit placed. If the
+    /// stop-loss cannot be cancelled, e.g. because it is already filling, the
+    /// bracket is left as it is and the user channel decides its outcome.
+    pub async fn observe_price(&mut self, product_id: &str, price: Decimal) -> Vec<OcoEvent> {
+        let triggered: Vec<String> = self
+            .brackets
+            .values()
+            .filter(|b| b.product_id == product_id && b.take_profit_reached(price))
+            .map(|b| b.entry_order_id.clone())
+            .collect();
+
+        let mut out = Vec::new();
+        for entry_order_id in triggered {
+            out.extend(self.take_profit(&entry_order_id, price).await);
+        }
+        out
+    }
+
+    /// Handle every message from a user and ticker stream until no brackets
+    /// remain or the stream ends.
+    ///
+    /// Returns the events that occurred. Stream errors are logged and skipped.
+    pub async fn run<S>(&mut self, mut stream: S) -> Vec<OcoEvent>
+    where
+        S: Stream<Item = Result<Message>> + Unpin,
+    {
+        let mut events = Vec::new();
+        while !self.is_empty() {
+            let Some(message) = stream.next().await else {
+                break;
+            };
+            match message {
+                Ok(message) => events.extend(self.observe(&message).await),
+                Err(e) => {
+                    tracing::warn!(error = %e, "skipping bad message while managing brackets")
+                }
+            }
+        }
+        events
+    }
+
+    async fn observe_order(&mut self, update: &OrderUpdate) -> Option<OcoEvent> {
+        let bracket = self.brackets.values().find(|b| b.owns(&update.order_id))?;
+        let entry_order_id = bracket.entry_order_id.clone();
+        let action = bracket.next_action(update)?;
+        self.apply(&entry_order_id, action).await
+    }
+
+    async fn apply(&mut self, entry_order_id: &str, action: Action) -> Option<OcoEvent> {
+        let event = match action {
+            Action::PlaceStopLoss { size, status } => {
+                let bracket = self.brackets[entry_order_id].clone();
+                let stop_loss_order_id = match self.place_stop_loss(&bracket, size).await {
+                    Ok(id) => id,
+                    Err(e) => {
+                        self.brackets.remove(entry_order_id);
+                        tracing::warn!(%entry_order_id, %size, error = %e, "failed to place bracket stop-loss, position is unprotected");
+                        return Some(OcoEvent::Failed {
+                            entry_order_id: entry_order_id.to_string(),
+                            error: e.to_string(),
+                        });
+                    }
+                };
+                if let Some(b) = self.brackets.get_mut(entry_order_id) {
+                    b.state = BracketState::Active {
+                        size,
+                        stop_loss_order_id: stop_loss_order_id.clone(),
+                        stop_loss_filled: Decimal::ZERO,
+                    };
+                }
+                OcoEvent::EntryFilled {
+                    entry_order_id: entry_order_id.to_string(),
+                    status,
+                    size,
+                    stop_loss_order_id,
+                }
+            }
+            Action::EntryEnded { status } => {
+                self.brackets.remove(entry_order_id);
+                OcoEvent::EntryEnded {
+                    entry_order_id: entry_order_id.to_string(),
+                    status,
+                }
+            }
+            Action::StopLossFilling { filled } => {
+                let state = self.brackets.get_mut(entry_order_id).map(|b| &mut b.state);
+                if let Some(BracketState::Active {
+                    stop_loss_filled, ..
+                }) = state
+                {
+                    *stop_loss_filled = filled;
+                }
+                return None;
+            }
+            Action::ExitFilled { leg, order_id } => {
+                self.brackets.remove(entry_order_id);
+                OcoEvent::ExitFilled {
+                    entry_order_id: entry_order_id.to_string(),
+                    leg,
+                    order_id,
+                }
+            }
+            Action::ExitEnded {
+                leg,
+                order_id,
+                status,
+            } => {
+                self.brackets.remove(entry_order_id);
+                tracing::warn!(%entry_order_id, %order_id, ?leg, ?status, "bracket exit ended without filling");
+                OcoEvent::ExitEnded {
+                    entry_order_id: entry_order_id.to_string(),
+                    leg,
+                    order_id,
+                    status,
+                }
+            }
+        };
+        Some(event)
+    }
+
+    /// Place the stop-loss for a filled entry, returning its order ID.
+    async fn place_stop_loss(&self, bracket: &Bracket, size: Decimal) -> Result<String> {
+        let product_id = bracket.product_id.as_str();
+        let stop_loss = self.client.stop_limit_order_gtc();
+        let stop_loss = match bracket.exit_side() {
+            OrderSide::Buy => stop_loss.buy(product_id),
+            OrderSide::Sell => stop_loss.sell(product_id),
+        };
+        stop_loss
+            .base_size(size.to_string())
+            .limit_price(&bracket.exits.stop_limit_price)
+            .stop_price(&bracket.exits.stop_price)
+            .stop_direction(bracket.stop_direction())
+            .send()
+            .await
+            .and_then(CreateOrderResponse::into_order_id)
+    }
+
+    /// Swap the stop-loss of an active bracket for its take-profit.
+    ///
+    /// The stop-loss is cancelled first, so the take-profit never competes
+    /// with it for the same funds. The take-profit is sized at what the
+    /// stop-loss had not filled.
+    async fn take_profit(&mut self, entry_order_id: &str, price: Decimal) -> Option<OcoEvent> {
+        let bracket = self.brackets[entry_order_id].clone();
+        let BracketState::Active {
+            size,
+            stop_loss_order_id,
+            stop_loss_filled,
+        } = bracket.state.clone()
+        else {
+            return None;
+        };
+
+        let response = match self
+            .client
+            .orders()
+            .cancel(CancelOrdersRequest::single(stop_loss_order_id.clone()))
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                // The stop-loss may or may not have been cancelled, so the
+                // bracket stays active and the user channel settles it.
+                tracing::warn!(%entry_order_id, %stop_loss_order_id, error = %e, "failed to cancel stop-loss to take profit");
+                return Some(OcoEvent::Failed {
+                    entry_order_id: entry_order_id.to_string(),
+                    error: e.to_string(),
+                });
+            }
+        };
+        if let Some(failed) = response.results.iter().find(|r| !r.success) {
+            tracing::warn!(%entry_order_id, %stop_loss_order_id, reason = ?failed.failure_reason, "could not cancel stop-loss to take profit");
+            return None;
+        }
+
+        let remaining = size - stop_loss_filled;
+        let take_profit = self.client.limit_order_gtc();
+        let take_profit = match bracket.exit_side() {
+            OrderSide::Buy => take_profit.buy(&bracket.product_id),
+            OrderSide::Sell => take_profit.sell(&bracket.product_id),
+        };
+        let take_profit = take_profit
+            .base_size(remaining.to_string())
+            .limit_price(&bracket.exits.take_profit_price)
+            .send()
+            .await
+            .and_then(CreateOrderResponse::into_order_id);
+        let take_profit_order_id = match take_profit {
+            Ok(id) => id,
+            Err(e) => {
+                self.brackets.remove(entry_order_id);
+                tracing::warn!(%entry_order_id, %remaining, error = %e, "failed to place bracket take-profit after cancelling the stop-loss, position is unprotected");
+                return Some(OcoEvent::Failed {
+                    entry_order_id: entry_order_id.to_string(),
+                    error: e.to_string(),
+                });
+            }
+        };
+
+        if let Some(b) = self.brackets.get_mut(entry_order_id) {
+            b.state = BracketState::TakingProfit {
+                size: remaining,
+                take_profit_order_id: take_profit_order_id.clone(),
+            };
+        }
+        Some(OcoEvent::TakeProfitTriggered {
+            entry_order_id: entry_order_id.to_string(),
+            price,
+            cancelled_order_id: stop_loss_order_id,
+            take_profit_order_id,
+            size: remaining,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bracket(state: BracketState) -> Bracket {
+        Bracket {
+            entry_order_id: "entry".to_string(),
+            product_id: "BTC-USD".to_string(),
+            entry_side: OrderSide::Buy,
+            exits: BracketExits::new("55000", "48000", "47900"),
+            state,
+        }
+    }
+
+    fn update(order_id: &str, status: &str, cumulative_quantity: &str) -> OrderUpdate {
+        serde_json::from_value(serde_json::json!({
+            "order_id": order_id,
+            "status": status,
+            "cumulative_quantity": cumulative_quantity,
+            "order_side": "BUY",
+            "order_type": "LIMIT",
+            "product_id": "BTC-USD",
+            "post_only": false
+        }))
+        .unwrap()
+    }
+
+    fn active() -> BracketState {
+        BracketState::Active {
+            size: Decimal::new(1, 3),
+            stop_loss_order_id: "sl".to_string(),
+            stop_loss_filled: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_exits_validation() {
+        let exits = BracketExits::new("55000", "48000", "47900");
+        assert!(exits.validate(OrderSide::Buy).is_ok());
+        assert!(exits.validate(OrderSide::Sell).is_err());
+        for bad in ["abc", "1e5", "inf", "NaN", "0", "-1"] {
+            assert!(
+                BracketExits::new(bad, "48000", "47900")
+                    .validate(OrderSide::Buy)
+                    .is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_entry_fill_places_stop_loss() {
+        let b = bracket(BracketState::AwaitingEntry);
+        assert!(b.owns("entry"));
+        assert_eq!(b.exit_side(), OrderSide::Sell);
+        assert_eq!(b.stop_direction(), StopDirection::StopDirectionStopDown);
+        assert_eq!(b.next_action(&update("entry", "OPEN", "0.0005")), None);
+        assert_eq!(
+            b.next_action(&update("entry", "FILLED", "0.001")),
+            Some(Action::PlaceStopLoss {
+                size: Decimal::new(1, 3),
+                status: OrderStatus::Filled,
+            })
+        );
+        assert_eq!(
+            b.next_action(&update("entry", "CANCELLED", "0")),
+            Some(Action::EntryEnded {
+                status: OrderStatus::Cancelled
+            })
+        );
+    }
+
+    #[test]
+    fn test_partially_filled_entry_places_stop_loss() {
+        let b = bracket(BracketState::AwaitingEntry);
+        assert_eq!(
+            b.next_action(&update("entry", "CANCELLED", "0.0004")),
+            Some(Action::PlaceStopLoss {
+                size: Decimal::new(4, 4),
+                status: OrderStatus::Cancelled,
+            })
+        );
+        assert_eq!(
+            b.next_action(&update("entry", "EXPIRED", "0.0002")),
+            Some(Action::PlaceStopLoss {
+                size: Decimal::new(2, 4),
+                status: OrderStatus::Expired,
+            })
+        );
+    }
+
+    #[test]
+    fn test_take_profit_trigger() {
+        let b = bracket(active());
+        assert!(b.take_profit_reached(Decimal::from(55000)));
+        assert!(b.take_profit_reached(Decimal::from(56000)));
+        assert!(!b.take_profit_reached(Decimal::from(54999)));
+        assert!(!bracket(BracketState::AwaitingEntry).take_profit_reached(Decimal::from(56000)));
+
+        let mut short = bracket(active());
+        short.entry_side = OrderSide::Sell;
+        short.exits = BracketExits::new("45000", "52000", "52100");
+        assert!(short.take_profit_reached(Decimal::from(44000)));
+        assert!(!short.take_profit_reached(Decimal::from(46000)));
+    }
+
+    #[test]
+    fn test_exit_updates() {
+        let b = bracket(active());
+        assert!(!b.owns("entry"));
+        assert!(b.owns("sl"));
+        assert_eq!(
+            b.next_action(&update("sl", "OPEN", "0.0003")),
+            Some(Action::StopLossFilling {
+                filled: Decimal::new(3, 4)
+            })
+        );
+        assert_eq!(
+            b.next_action(&update("sl", "FILLED", "0.001")),
+            Some(Action::ExitFilled {
+                leg: ExitLeg::StopLoss,
+                order_id: "sl".to_string(),
+            })
+        );
+
+        let b = bracket(BracketState::TakingProfit {
+            size: Decimal::new(1, 3),
+            take_profit_order_id: "tp".to_string(),
+        });
+        assert!(b.owns("tp") && !b.owns("sl"));
+        assert_eq!(
+            b.next_action(&update("tp", "EXPIRED", "0")),
+            Some(Action::ExitEnded {
+                leg: ExitLeg::TakeProfit,
+                order_id: "tp".to_string(),
+                status: OrderStatus::Expired,
+            })
+        );
+    }
+}
@@ -0,0 +1,415 @@
+//! Mapping client order IDs to exchange order IDs.
+//!
+//! Orders are placed with a client order ID chosen locally, and the
+//! exchange answers with an order ID of its own. After a crash the client
+//! IDs are often all that is left, so an [`OrderIdStore`] keeps the pairs
+//! as they become known. Set one on the client with
+//! [`RestClientBuilder::order_id_store`](crate::RestClientBuilder::order_id_store)
+//! and every order created through [`OrdersApi`](crate::rest::OrdersApi) is
+//! recorded; pass user channel messages to
+//! [`OrderIdStore::record_message`] to record orders placed elsewhere.
+//!
+//! [`FileOrderIdStore`] appends the pairs to a file and reloads them on
+//! open; [`MemoryOrderIdStore`] keeps them for the life of the process.
+//! Implement [`OrderIdStore`] to use another backend.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use coinbase_advanced::RestClient;
+//! use coinbase_advanced::order_ids::FileOrderIdStore;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder()
+//!     .order_id_store(Arc::new(FileOrderIdStore::open("order-ids.jsonl")?))
+//!     .build()?;
+//!
+//! // After a restart, find the order placed as "my-order-1".
+//! let order = client.orders().get_by_client_order_id("my-order-1").await?;
+//! println!("{:?}", order.status);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::models::CreateOrderResponse;
+use crate::ws::{Events, Message};
+
+/// A store of client order ID to exchange order ID mappings.
+///
+/// Each client order ID maps to at most one order ID and the other way
+/// round; recording a pair replaces any earlier pair for either ID. Stores
+/// must be safe to share between tasks.
+pub trait OrderIdStore: fmt::Debug + Send + Sync {
+    /// Record that `client_order_id` was assigned `order_id`.
+    fn record(&self, client_order_id: &str, order_id: &str) -> Result<()>;
+
+    /// Get the order ID assigned to a client order ID, if known.
+    fn order_id(&self, client_order_id: &str) -> Result<Option<String>>;
+
+    /// Get the client order ID an order was placed with, if known.
+    fn client_order_id(&self, order_id: &str) -> Result<Option<String>>;
+
+    /// Forget the mapping of a client order ID, if any.
+    fn remove(&self, client_order_id: &str) -> Result<()>;
+
+    /// Record the orders reported in a user channel message.
+    ///
+    /// Other messages are ignored, as are updates without both IDs.
+    fn record_message(&self, message: &Message) -> Result<()> {
+        let Events::User(events) = &message.events else {
+            return Ok(());
+        };
+        for order in events.iter().flat_map(|event| &event.orders) {
+            if !order.client_order_id.is_empty() && !order.order_id.is_empty() {
+                self.record(&order.client_order_id, &order.order_id)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Record the order ID of a created order, logging failures.
+///
+/// The order exists whether or not the store could be written, so a store
+/// failure must not fail the request.
+pub(crate) fn record_created(
+    store: &dyn OrderIdStore,
+    client_order_id: &str,
+    response: &CreateOrderResponse,
+) {
+    if !response.success {
+        return;
+    }
+    let order_id = response.order_id.as_deref().or_else(|| {
+        response
+            .success_response
+            .as_ref()
+            .map(|s| s.order_id.as_str())
+    });
+    let Some(order_id) = order_id else {
+        return;
+    };
+    if let Err(e) = store.record(client_order_id, order_id) {
+        tracing::warn!(client_order_id, order_id, error = %e, "failed to record order ID");
+    }
+}
+
+/// Both directions of the mapping.
+#[derive(Debug, Default)]
+struct Mappings {
+    by_client: HashMap<String, String>,
+    by_order: HashMap<String, String>,
+}
+
+impl Mappings {
+    /// Insert a pair, returning whether anything changed.
+    fn insert(&mut self, client_order_id: &str, order_id: &str) -> bool {
+        if self.by_client.get(client_order_id).map(String::as_str) == Some(order_id) {
+            return false;
+        }
+        if let Some(previous) = self.by_client.remove(client_order_id) {
+            self.by_order.remove(&previous);
+        }
+        if let Some(previous) = self.by_order.remove(order_id) {
+            self.by_client.remove(&previous);
+        }
+        self.by_client
+            .insert(client_order_id.to_string(), order_id.to_string());
+        self.by_order
+            .insert(order_id.to_string(), client_order_id.to_string());
+        true
+    }
+
+    /// Remove a pair, returning whether it existed.
+    fn remove(&mut self, client_order_id: &str) -> bool {
+        match self.by_client.remove(client_order_id) {
+            Some(order_id) => {
+                self.by_order.remove(&order_id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A store held in memory, for tests and short-lived processes.
+#[derive(Debug, Default)]
+pub struct MemoryOrderIdStore {
+    mappings: Mutex<Mappings>,
+}
+
+impl MemoryOrderIdStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of mappings.
+    pub fn len(&self) -> usize {
+        self.mappings().by_client.len()
+    }
+
+    /// Returns true if the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.mappings().by_client.is_empty()
+    }
+
+    fn mappings(&self) -> MutexGuard<'_, Mappings> {
+        self.mappings.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl OrderIdStore for MemoryOrderIdStore {
+    fn record(&self, client_order_id: &str, order_id: &str) -> Result<()> {
+        self.mappings().insert(client_order_id, order_id);
+        Ok(())
+    }
+
+    fn order_id(&self, client_order_id: &str) -> Result<Option<String>> {
+        Ok(self.mappings().by_client.get(client_order_id).cloned())
+    }
+
+    fn client_order_id(&self, order_id: &str) -> Result<Option<String>> {
+        Ok(self.mappings().by_order.get(order_id).cloned())
+    }
+
+    fn remove(&self, client_order_id: &str) -> Result<()> {
+        self.mappings().remove(client_order_id);
+        Ok(())
+    }
+}
+
+/// One line of a [`FileOrderIdStore`]; a missing order ID removes the pair.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    client_order_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    order_id: Option<String>,
+}
+
+/// A store appending each change as a JSON line to a file.
+///
+/// The file is read back when the store is opened, so mappings survive
+/// restarts. Lines that cannot be parsed, such as a line cut short by a
+/// crash, are logged and skipped. The file only grows; call
+/// [`compact`](Self::compact) now and then to rewrite it with the current
+/// mappings.
+pub struct FileOrderIdStore {
+    path: PathBuf,
+    state: Mutex<(Mappings, File)>,
+}
+
+impl FileOrderIdStore {
+    /// Open the store at `path`, creating the file if needed.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut mappings = Mappings::default();
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        for (number, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Entry>(line) {
+                Ok(Entry {
+                    client_order_id,
+                    order_id: Some(order_id),
+                }) => {
+                    mappings.insert(&client_order_id, &order_id);
+                }
+                Ok(Entry {
+                    client_order_id,
+                    order_id: None,
+                }) => {
+                    mappings.remove(&client_order_id);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        path = %path.display(),
+                        line = number + 1,
+                        error = %e,
+                        "skipping unreadable order ID entry"
+                    );
+                }
+            }
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        // End a line cut short by a crash so the next entry starts cleanly.
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            file.write_all(b"\n")?;
+        }
+        Ok(Self {
+            path,
+            state: Mutex::new((mappings, file)),
+        })
+    }
+
+    /// Get the path of the store's file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of mappings.
+    pub fn len(&self) -> usize {
+        self.state().0.by_client.len()
+    }
+
+    /// Returns true if the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.state().0.by_client.is_empty()
+    }
+
+    /// Rewrite the file with only the current mappings.
+    pub fn compact(&self) -> Result<()> {
+        let mut state = self.state();
+        let mut contents = String::new();
+        for (client_order_id, order_id) in &state.0.by_client {
+            contents.push_str(&entry_line(client_order_id, Some(order_id))?);
+        }
+        // Write to a temporary file first so a crash never loses the store.
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, contents)?;
+        std::fs::rename(&tmp, &self.path)?;
+        state.1 = OpenOptions::new().append(true).open(&self.path)?;
+        Ok(())
+    }
+
+    fn state(&self) -> MutexGuard<'_, (Mappings, File)> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Serialize one entry as a line of the store's file.
+fn entry_line(client_order_id: &str, order_id: Option<&str>) -> Result<String> {
+    let entry = Entry {
+        client_order_id: client_order_id.to_string(),
+        order_id: order_id.map(str::to_string),
+    };
+    let mut line = serde_json::to_string(&entry)
+        .map_err(|e| Error::parse(format!("Failed to serialize order ID entry: {}", e), None))?;
+    line.push('\n');
+    Ok(line)
+}
+
+impl fmt::Debug for FileOrderIdStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileOrderIdStore")
+            .field("path", &self.path)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl OrderIdStore for FileOrderIdStore {
+    fn record(&self, client_order_id: &str, order_id: &str) -> Result<()> {
+        let mut state = self.state();
+        let (mappings, file) = &mut *state;
+        if mappings.insert(client_order_id, order_id) {
+            file.write_all(entry_line(client_order_id, Some(order_id))?.as_bytes())?;
+            file.flush()?;
+        }
+        Ok(())
+    }
+
+    fn order_id(&self, client_order_id: &str) -> Result<Option<String>> {
+        Ok(self.state().0.by_client.get(client_order_id).cloned())
+    }
+
+    fn client_order_id(&self, order_id: &str) -> Result<Option<String>> {
+        Ok(self.state().0.by_order.get(order_id).cloned())
+    }
+
+    fn remove(&self, client_order_id: &str) -> Result<()> {
+        let mut state = self.state();
+        let (mappings, file) = &mut *state;
+        if mappings.remove(client_order_id) {
+            file.write_all(entry_line(client_order_id, None)?.as_bytes())?;
+            file.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_store() {
+        let store = MemoryOrderIdStore::new();
+        store.record("client-1", "order-1").unwrap();
+        assert_eq!(
+            store.order_id("client-1").unwrap().as_deref(),
+            Some("order-1")
+        );
+        assert_eq!(
+            store.client_order_id("order-1").unwrap().as_deref(),
+            Some("client-1")
+        );
+
+        // Remapping a client ID drops its old order ID.
+        store.record("client-1", "order-2").unwrap();
+        assert_eq!(store.client_order_id("order-1").unwrap(), None);
+        assert_eq!(store.len(), 1);
+
+        store.remove("client-1").unwrap();
+        assert!(store.is_empty());
+        assert_eq!(store.client_order_id("order-2").unwrap(), None);
+    }
+
+    #[test]
+    fn test_file_store_reopens() {
+        let path =
+            std::env::temp_dir().join(format!("coinbase-order-ids-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let store = FileOrderIdStore::open(&path).unwrap();
+        store.record("client-1", "order-1").unwrap();
+        store.record("client-2", "order-2").unwrap();
+        store.record("client-2", "order-2").unwrap();
+        store.remove("client-1").unwrap();
+        drop(store);
+
+        // A torn line from a crash is skipped.
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"client_order_id\":\"cli")
+            .unwrap();
+
+        let store = FileOrderIdStore::open(&path).unwrap();
+        assert_eq!(store.len(), 1);
+        assert_eq!(
+            store.order_id("client-2").unwrap().as_deref(),
+            Some("order-2")
+        );
+        assert_eq!(store.order_id("client-1").unwrap(), None);
+        store.record("client-3", "order-3").unwrap();
+        drop(store);
+
+        let store = FileOrderIdStore::open(&path).unwrap();
+        assert_eq!(store.len(), 2);
+        store.compact().unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+        drop(store);
+        assert_eq!(FileOrderIdStore::open(&path).unwrap().len(), 2);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
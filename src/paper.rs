@@ -0,0 +1,1307 @@
+//! Paper trading against live market data.
+//!
+//! [`PaperTradingClient`] takes the same `orders()` and `accounts()` calls as
+//! [`RestClient`](crate::RestClient) but never sends them. Both clients
+//! implement [`OrdersClient`] and [`AccountsClient`], so a strategy written
+//! against those traits runs unchanged in either mode. Orders are matched
+//! against the market seen on the WebSocket feed, and balances, holds, orders
+//! and fills are kept in memory, so a strategy can run against the live
+//! market before it trades real funds.
+//!
+//! Quotes come from the `level2` channel for products it is subscribed to,
+//! and from the best bid and ask of `ticker` updates otherwise, or their
+//! last price when they carry none. Matching is deliberately simple:
+//!
+//! - Market orders fill in full at the best ask (buys) or bid (sells).
+//! - Limit orders that cross the market fill in full at the best price as
+//!   takers. Others rest, holding funds, and fill at their limit price as
+//!   makers once the market reaches it.
+//! - Limit IOC and FOK orders that do not cross are cancelled, post-only
+//!   orders that would cross are rejected, and GTD orders expire at their
+//!   end time.
+//! - Stop, TWAP and custom configurations are rejected.
+//!
+//! The size shown at each price is not checked, so large orders fill without
+//! slippage.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::paper::PaperTradingClient;
+//! use coinbase_advanced::models::{CreateOrderRequest, OrderConfiguration, OrderSide};
+//! use coinbase_advanced::rest::OrdersClient;
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//! use coinbase_advanced::Decimal;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let paper = PaperTradingClient::new()
+//!     .with_balance("USD", Decimal::from(10_000))
+//!     .with_fees(Decimal::new(4, 3), Decimal::new(6, 3));
+//!
+//! let ws = WebSocketClient::builder().build()?;
+//! let stream = ws.connect().await?;
+//! ws.subscribe(&[Channel::Level2 {
+//!     product_ids: vec!["BTC-USD".to_string()],
+//! }]).await?;
+//! let runner = paper.clone();
+//! tokio::spawn(async move { runner.run(stream).await });
+//!
+//! let request = CreateOrderRequest::new(
+//!     "my-order-1",
+//!     "BTC-USD",
+//!     OrderSide::Buy,
+//!     OrderConfiguration::limit_gtc("0.01", "40000", false),
+//! );
+//! let response = paper.orders().create(request).await?;
+//! println!("paper order {:?}", response.order_id);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{Stream, StreamExt};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::balance::CurrencyBalance;
+use crate::book::OrderBook;
+use crate::error::{Error, Result};
+use crate::models::{
+    Account, Balance, CancelOrderResult, CancelOrdersRequest, CancelOrdersResponse,
+    CreateOrderRequest, CreateOrderResponse, Fill, ListAccountsParams, ListAccountsResponse,
+    ListFillsParams, ListFillsResponse, ListOrdersParams, ListOrdersResponse, Order,
+    OrderConfiguration, OrderSide, OrderStatus, OrderSuccessResponse, parse_decimal_field,
+    parse_optional_decimal_field,
+};
+use crate::rest::{AccountsClient, OrdersClient};
+use crate::ws::{Events, Message};
+
+/// Decimal places kept when converting a quote size into a base size.
+const BASE_SIZE_DP: u32 = 8;
+
+/// The best bid and ask of a product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quote {
+    /// Best bid price.
+    pub bid: Decimal,
+    /// Best ask price.
+    pub ask: Decimal,
+}
+
+/// Simulated trading client that fills orders against live market data.
+///
+/// Clones share the same balances and orders. Feed it WebSocket messages
+/// with [`observe`](Self::observe) or [`run`](Self::run) and place orders
+/// through [`orders`](Self::orders) as with a [`RestClient`](crate::RestClient).
+#[derive(Debug, Clone, Default)]
+pub struct PaperTradingClient {
+    state: Arc<Mutex<State>>,
+}
+
+impl PaperTradingClient {
+    /// Create a paper trading client with no funds and no fees.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with `amount` of `currency` available.
+    pub fn with_balance(self, currency: &str, amount: Decimal) -> Self {
+        self.deposit(currency, amount);
+        self
+    }
+
+    /// Charge fees as fractions of the filled value, e.g. `0.006` for 0.6%.
+    ///
+    /// `maker` applies to resting limit orders, `taker` to orders that fill
+    /// on placement.
+    pub fn with_fees(self, maker: Decimal, taker: Decimal) -> Self {
+        {
+            let mut state = self.lock();
+            state.maker_fee = maker;
+            state.taker_fee = taker;
+        }
+        self
+    }
+
+    /// Add `amount` of `currency` to the available balance.
+    pub fn deposit(&self, currency: &str, amount: Decimal) {
+        self.lock().balance_mut(currency).available += amount;
+    }
+
+    /// Get the balance of a currency, which is zero if it was never funded.
+    pub fn balance(&self, currency: &str) -> CurrencyBalance {
+        self.lock()
+            .balances
+            .get(currency)
+            .cloned()
+            .unwrap_or_else(|| CurrencyBalance::empty(currency))
+    }
+
+    /// Get the current quote of a product.
+    pub fn quote(&self, product_id: &str) -> Option<Quote> {
+        self.lock().quotes.get(product_id).copied()
+    }
+
+    /// Set the quote of a product by hand and fill any orders it reaches.
+    ///
+    /// Useful in tests and backtests; live quotes replace it on the next
+    /// message for the product.
+    pub fn set_quote(&self, product_id: &str, bid: Decimal, ask: Decimal) {
+        let mut state = self.lock();
+        state
+            .quotes
+            .insert(product_id.to_string(), Quote { bid, ask });
+        state.match_resting(product_id);
+    }
+
+    /// Update quotes from a WebSocket message and fill the orders they
+    /// reach.
+    ///
+    /// Reads `level2` and `ticker` events; other messages only expire GTD
+    /// orders that are past their end time.
+    pub fn observe(&self, message: &Message) -> Result<()> {
+        let mut guard = self.lock();
+        let state = &mut *guard;
+        let mut touched = Vec::new();
+        match &message.events {
+            Events::Level2(events) => {
+                for event in events {
+                    let book = state
+                        .books
+                        .entry(event.product_id.clone())
+                        .or_insert_with(|| OrderBook::new(event.product_id.as_str()));
+                    if !book.apply(event)? {
+                        continue;
+                    }
+                    if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+                        let quote = Quote {
+                            bid: bid.price,
+                            ask: ask.price,
+                        };
+                        state.quotes.insert(event.product_id.clone(), quote);
+                        touched.push(event.product_id.clone());
+                    }
+                }
+            }
+            Events::Ticker(events) => {
+                for ticker in events.iter().flat_map(|event| &event.tickers) {
+                    // A book, when there is one, gives the better quote.
+                    if state.books.contains_key(&ticker.product_id) {
+                        continue;
+                    }
+                    let Some(price) = ticker.price()? else {
+                        continue;
+                    };
+                    let quote = Quote {
+                        bid: ticker.best_bid()?.unwrap_or(price),
+                        ask: ticker.best_ask()?.unwrap_or(price),
+                    };
+                    state.quotes.insert(ticker.product_id.clone(), quote);
+                    touched.push(ticker.product_id.clone());
+                }
+            }
+            _ => {}
+        }
+
+        state.expire(Utc::now());
+        for product_id in touched {
+            state.match_resting(&product_id);
+        }
+        Ok(())
+    }
+
+    /// Observe every message of a WebSocket stream until it ends.
+    ///
+    /// Returns the first stream or parse error.
+    pub async fn run<S>(&self, mut stream: S) -> Result<()>
+    where
+        S: Stream<Item = Result<Message>> + Unpin,
+    {
+        while let Some(message) = stream.next().await {
+            self.observe(&message?)?;
+        }
+        Ok(())
+    }
+
+    /// Access the simulated orders API.
+    pub fn orders(&self) -> PaperOrders<'_> {
+        PaperOrders { client: self }
+    }
+
+    /// Access the simulated accounts API.
+    pub fn accounts(&self) -> PaperAccounts<'_> {
+        PaperAccounts { client: self }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Simulated orders API, implementing [`OrdersClient`] like
+/// [`OrdersApi`](crate::rest::OrdersApi).
+#[derive(Debug)]
+pub struct PaperOrders<'a> {
+    client: &'a PaperTradingClient,
+}
+
+impl OrdersClient for PaperOrders<'_> {
+    /// Place an order.
+    ///
+    /// Orders that cannot be placed come back with `success` false and a
+    /// `failure_reason`, as from the exchange: `INSUFFICIENT_FUND`,
+    /// `INVALID_LIMIT_PRICE` for a limit price that is not positive,
+    /// `INVALID_LIMIT_PRICE_POST_ONLY`, `UNSUPPORTED_ORDER_CONFIGURATION`, or
+    /// `UNKNOWN_FAILURE_REASON` when there is no quote for a market order.
+    /// Reusing a client order ID returns the existing order.
+    async fn create(&self, request: CreateOrderRequest) -> Result<CreateOrderResponse> {
+        self.client.lock().create(&request)
+    }
+
+    /// Cancel open orders, releasing their holds.
+    async fn cancel(&self, request: CancelOrdersRequest) -> Result<CancelOrdersResponse> {
+        let mut state = self.client.lock();
+        let results = request
+            .order_ids
+            .iter()
+            .map(|order_id| state.cancel(order_id))
+            .collect();
+        Ok(CancelOrdersResponse { results })
+    }
+
+    /// List orders, newest first.
+    ///
+    /// Filters on product, status, side and creation time.
+    async fn list(&self, params: ListOrdersParams) -> Result<ListOrdersResponse> {
+        let state = self.client.lock();
+        let orders: Vec<Order> = state
+            .orders
+            .iter()
+            .rev()
+            .filter(|order| {
+                (params.product_ids.is_empty() || params.product_ids.contains(&order.product_id))
+                    && (params.order_status.is_empty()
+                        || params.order_status.contains(&order.status))
+                    && params.order_side.is_none_or(|side| order.side == side)
+                    && params
+                        .start_date
+                        .is_none_or(|start| order.created_time >= start)
+                    && params.end_date.is_none_or(|end| order.created_time < end)
+            })
+            .map(PaperOrder::to_order)
+            .collect();
+        let (orders, cursor) = page(orders, params.cursor.as_deref(), params.limit)?;
+        Ok(ListOrdersResponse {
+            orders,
+            sequence: None,
+            has_next: cursor.is_some(),
+            cursor,
+        })
+    }
+
+    /// Get an order by ID.
+    async fn get(&self, order_id: &str) -> Result<Order> {
+        self.client
+            .lock()
+            .orders
+            .iter()
+            .find(|order| order.order_id == order_id)
+            .map(PaperOrder::to_order)
+            .ok_or_else(|| Error::request(format!("Order {} not found", order_id)))
+    }
+
+    /// Get an order by the client order ID it was placed with.
+    async fn get_by_client_order_id(&self, client_order_id: &str) -> Result<Order> {
+        self.client
+            .lock()
+            .orders
+            .iter()
+            .find(|order| order.client_order_id == client_order_id)
+            .map(PaperOrder::to_order)
+            .ok_or_else(|| {
+                Error::request(format!(
+                    "No order recorded for client order ID {}",
+                    client_order_id
+                ))
+            })
+    }
+
+    /// List fills, newest first.
+    ///
+    /// Filters on order, product and sequence timestamp.
+    async fn list_fills(&self, params: ListFillsParams) -> Result<ListFillsResponse> {
+        let since = params
+            .start_sequence_timestamp
+            .as_deref()
+            .map(|start| parse_time(start, "start_sequence_timestamp"))
+            .transpose()?;
+        let until = params
+            .end_sequence_timestamp
+            .as_deref()
+            .map(|end| parse_time(end, "end_sequence_timestamp"))
+            .transpose()?;
+
+        let state = self.client.lock();
+        let fills: Vec<Fill> = state
+            .fills
+            .iter()
+            .rev()
+            .filter(|(time, fill)| {
+                (params.order_ids.is_empty() || params.order_ids.contains(&fill.order_id))
+                    && (params.trade_ids.is_empty() || params.trade_ids.contains(&fill.trade_id))
+                    && (params.product_ids.is_empty()
+                        || params.product_ids.contains(&fill.product_id))
+                    && since.is_none_or(|since| *time >= since)
+                    && until.is_none_or(|until| *time < until)
+            })
+            .map(|(_, fill)| fill.clone())
+            .collect();
+        let (fills, cursor) = page(fills, params.cursor.as_deref(), params.limit)?;
+        Ok(ListFillsResponse { fills, cursor })
+    }
+}
+
+/// Simulated accounts API, implementing [`AccountsClient`] like
+/// [`AccountsApi`](crate::rest::AccountsApi).
+///
+/// Each funded currency has one account, with the ID `paper-<CURRENCY>`.
+#[derive(Debug)]
+pub struct PaperAccounts<'a> {
+    client: &'a PaperTradingClient,
+}
+
+impl AccountsClient for PaperAccounts<'_> {
+    /// List accounts, ordered by currency.
+    async fn list(&self, params: ListAccountsParams) -> Result<ListAccountsResponse> {
+        let accounts = self.client.lock().accounts();
+        let size = accounts.len() as u32;
+        let (accounts, cursor) = page(accounts, params.cursor.as_deref(), params.limit)?;
+        Ok(ListAccountsResponse {
+            accounts,
+            has_next: cursor.is_some(),
+            cursor,
+            size: Some(size),
+        })
+    }
+
+    /// Get an account by UUID.
+    async fn get(&self, account_uuid: &str) -> Result<Account> {
+        self.client
+            .lock()
+            .accounts()
+            .into_iter()
+            .find(|account| account.uuid == account_uuid)
+            .ok_or_else(|| Error::request(format!("Account {} not found", account_uuid)))
+    }
+}
+
+/// Whether a fill added or took liquidity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Liquidity {
+    Maker,
+    Taker,
+}
+
+/// How long an order may rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeInForce {
+    ImmediateOrCancel,
+    FillOrKill,
+    GoodUntilCancelled,
+    GoodUntilDate(DateTime<Utc>),
+}
+
+impl TimeInForce {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::ImmediateOrCancel => "IMMEDIATE_OR_CANCEL",
+            Self::FillOrKill => "FILL_OR_KILL",
+            Self::GoodUntilCancelled => "GOOD_UNTIL_CANCELLED",
+            Self::GoodUntilDate(_) => "GOOD_UNTIL_DATE_TIME",
+        }
+    }
+
+    fn rests(&self) -> bool {
+        matches!(self, Self::GoodUntilCancelled | Self::GoodUntilDate(_))
+    }
+}
+
+/// The parts of an order configuration the simulator acts on.
+enum Terms {
+    Market {
+        base_size: Option<Decimal>,
+        quote_size: Option<Decimal>,
+    },
+    Limit {
+        base_size: Decimal,
+        limit_price: Decimal,
+        post_only: bool,
+        time_in_force: TimeInForce,
+    },
+}
+
+impl Terms {
+    /// Read an order configuration, or `None` if it is not supported.
+    fn parse(configuration: &OrderConfiguration) -> Result<Option<Self>> {
+        let terms = match configuration {
+            OrderConfiguration::MarketIoc { market_market_ioc } => Self::Market {
+                base_size: parse_optional_decimal_field(
+                    "base_size",
+                    market_market_ioc.base_size.as_deref(),
+                )?,
+                quote_size: parse_optional_decimal_field(
+                    "quote_size",
+                    market_market_ioc.quote_size.as_deref(),
+                )?,
+            },
+            OrderConfiguration::LimitGtc { limit_limit_gtc } => Self::Limit {
+                base_size: parse_decimal_field("base_size", &limit_limit_gtc.base_size)?,
+                limit_price: parse_decimal_field("limit_price", &limit_limit_gtc.limit_price)?,
+                post_only: limit_limit_gtc.post_only.unwrap_or(false),
+                time_in_force: TimeInForce::GoodUntilCancelled,
+            },
+            OrderConfiguration::LimitGtd { limit_limit_gtd } => Self::Limit {
+                base_size: parse_decimal_field("base_size", &limit_limit_gtd.base_size)?,
+                limit_price: parse_decimal_field("limit_price", &limit_limit_gtd.limit_price)?,
+                post_only: limit_limit_gtd.post_only.unwrap_or(false),
+                time_in_force: TimeInForce::GoodUntilDate(parse_time(
+                    &limit_limit_gtd.end_time,
+                    "end_time",
+                )?),
+            },
+            OrderConfiguration::LimitFok { limit_limit_fok } => Self::Limit {
+                base_size: parse_decimal_field("base_size", &limit_limit_fok.base_size)?,
+                limit_price: parse_decimal_field("limit_price", &limit_limit_fok.limit_price)?,
+                post_only: false,
+                time_in_force: TimeInForce::FillOrKill,
+            },
+            OrderConfiguration::SorLimitIoc { sor_limit_ioc } => Self::Limit {
+                base_size: parse_decimal_field("base_size", &sor_limit_ioc.base_size)?,
+                limit_price: parse_decimal_field("limit_price", &sor_limit_ioc.limit_price)?,
+                post_only: false,
+                time_in_force: TimeInForce::ImmediateOrCancel,
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(terms))
+    }
+}
+
+/// An order placed with the simulator.
+#[derive(Debug, Clone)]
+struct PaperOrder {
+    order_id: String,
+    client_order_id: String,
+    product_id: String,
+    base_currency: String,
+    quote_currency: String,
+    side: OrderSide,
+    configuration: serde_json::Value,
+    limit_price: Option<Decimal>,
+    base_size: Decimal,
+    size_in_quote: bool,
+    time_in_force: TimeInForce,
+    status: OrderStatus,
+    created_time: DateTime<Utc>,
+    filled_value: Decimal,
+    fees: Decimal,
+    /// Funds held while the order rests, in the currency it pays with.
+    hold: Decimal,
+}
+
+impl PaperOrder {
+    fn to_order(&self) -> Order {
+        let filled = self.status == OrderStatus::Filled;
+        let filled_size = if filled {
+            self.base_size
+        } else {
+            Decimal::ZERO
+        };
+        let average_price = if filled {
+            self.filled_value / self.base_size
+        } else {
+            Decimal::ZERO
+        };
+        let after_fees = match self.side {
+            OrderSide::Buy => self.filled_value + self.fees,
+            OrderSide::Sell => self.filled_value - self.fees,
+        };
+        Order {
+            order_id: self.order_id.clone(),
+            product_id: self.product_id.clone(),
+            user_id: None,
+            order_configuration: Some(self.configuration.clone()),
+            side: side_str(self.side).to_string(),
+            client_order_id: self.client_order_id.clone(),
+            status: status_str(self.status).to_string(),
+            time_in_force: Some(self.time_in_force.as_str().to_string()),
+            created_time: Some(format_time(self.created_time)),
+            completion_percentage: Some(if filled { "100" } else { "0" }.to_string()),
+            filled_size: Some(filled_size.normalize().to_string()),
+            average_filled_price: Some(average_price.normalize().to_string()),
+            fee: None,
+            number_of_fills: Some(if filled { "1" } else { "0" }.to_string()),
+            filled_value: Some(self.filled_value.normalize().to_string()),
+            pending_cancel: Some(false),
+            size_in_quote: Some(self.size_in_quote),
+            total_fees: Some(self.fees.normalize().to_string()),
+            size_inclusive_of_fees: Some(false),
+            total_value_after_fees: Some(after_fees.normalize().to_string()),
+            trigger_status: None,
+            order_type: Some(
+                if self.limit_price.is_some() {
+                    "LIMIT"
+                } else {
+                    "MARKET"
+                }
+                .to_string(),
+            ),
+            reject_reason: None,
+            settled: Some(filled),
+            product_type: Some("SPOT".to_string()),
+            reject_message: None,
+            cancel_message: None,
+            order_placement_source: Some("RETAIL_ADVANCED".to_string()),
+            outstanding_hold_amount: Some(self.hold.normalize().to_string()),
+            #[cfg(feature = "capture-unknown")]
+            extra: Default::default(),
+        }
+    }
+}
+
+/// Simulator state shared by the clones of a client.
+#[derive(Debug, Default)]
+struct State {
+    maker_fee: Decimal,
+    taker_fee: Decimal,
+    balances: HashMap<String, CurrencyBalance>,
+    books: HashMap<String, OrderBook>,
+    quotes: HashMap<String, Quote>,
+    orders: Vec<PaperOrder>,
+    /// Fills in the order they happened, with the time of each.
+    fills: Vec<(DateTime<Utc>, Fill)>,
+    next_id: u64,
+}
+
+impl State {
+    fn next_id(&mut self, kind: &str) -> String {
+        self.next_id += 1;
+        format!("paper-{}-{}", kind, self.next_id)
+    }
+
+    fn balance_mut(&mut self, currency: &str) -> &mut CurrencyBalance {
+        self.balances
+            .entry(currency.to_string())
+            .or_insert_with(|| CurrencyBalance::empty(currency))
+    }
+
+    fn available(&self, currency: &str) -> Decimal {
+        self.balances
+            .get(currency)
+            .map_or(Decimal::ZERO, |balance| balance.available)
+    }
+
+    fn create(&mut self, request: &CreateOrderRequest) -> Result<CreateOrderResponse> {
+        if let Some(order) = self
+            .orders
+            .iter()
+            .find(|order| order.client_order_id == request.client_order_id)
+        {
+            return Ok(accepted(order));
+        }
+
+        let Some(terms) = Terms::parse(&request.order_configuration)? else {
+            return Ok(rejected(
+                "UNSUPPORTED_ORDER_CONFIGURATION",
+                "Paper trading supports market and limit orders only",
+            ));
+        };
+        let (base_currency, quote_currency) = request
+            .product_id
+            .split_once('-')
+            .ok_or_else(|| Error::request(format!("Invalid product ID {}", request.product_id)))?;
+        let side = request.side;
+        let touch = self
+            .quotes
+            .get(&request.product_id)
+            .map(|quote| match side {
+                OrderSide::Buy => quote.ask,
+                OrderSide::Sell => quote.bid,
+            })
+            .filter(|price| *price > Decimal::ZERO);
+
+        let (base_size, limit_price, size_in_quote, post_only, time_in_force) = match terms {
+            Terms::Market {
+                base_size,
+                quote_size,
+            } => {
+                let Some(price) = touch else {
+                    return Ok(rejected(
+                        "UNKNOWN_FAILURE_REASON",
+                        &format!("No market data for {}", request.product_id),
+                    ));
+                };
+                let (base_size, size_in_quote) = match (base_size, quote_size) {
+                    (Some(base_size), _) => (base_size, false),
+                    (None, Some(quote_size)) => (
+                        (quote_size / price)
+                            .round_dp_with_strategy(BASE_SIZE_DP, RoundingStrategy::ToZero),
+                        true,
+                    ),
+                    (None, None) => (Decimal::ZERO, false),
+                };
+                (
+                    base_size,
+                    None,
+                    size_in_quote,
+                    false,
+                    TimeInForce::ImmediateOrCancel,
+                )
+            }
+            Terms::Limit {
+                base_size,
+                limit_price,
+                post_only,
+                time_in_force,
+            } => (
+                base_size,
+                Some(limit_price),
+                false,
+                post_only,
+                time_in_force,
+            ),
+        };
+        if base_size <= Decimal::ZERO {
+            return Ok(rejected(
+                "UNKNOWN_FAILURE_REASON",
+                "Order size must be positive",
+            ));
+        }
+        if limit_price.is_some_and(|limit| limit <= Decimal::ZERO) {
+            return Ok(rejected(
+                "INVALID_LIMIT_PRICE",
+                "Limit price must be positive",
+            ));
+        }
+
+        let crosses = match (touch, limit_price) {
+            (Some(price), Some(limit)) => match side {
+                OrderSide::Buy => price <= limit,
+                OrderSide::Sell => price >= limit,
+            },
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if post_only && crosses {
+            return Ok(rejected(
+                "INVALID_LIMIT_PRICE_POST_ONLY",
+                "Post-only order would cross the market",
+            ));
+        }
+
+        // Buys are checked against the worst price they can fill at, with
+        // the taker fee; sells against the size.
+        let (pays_with, required) = match side {
+            OrderSide::Buy => {
+                let price = limit_price.or(touch).unwrap_or_default();
+                (
+                    quote_currency,
+                    base_size * price * (Decimal::ONE + self.taker_fee),
+                )
+            }
+            OrderSide::Sell => (base_currency, base_size),
+        };
+        if self.available(pays_with) < required {
+            return Ok(rejected(
+                "INSUFFICIENT_FUND",
+                &format!("Insufficient {} for order", pays_with),
+            ));
+        }
+
+        let order_id = self.next_id("order");
+        self.orders.push(PaperOrder {
+            order_id,
+            client_order_id: request.client_order_id.clone(),
+            product_id: request.product_id.clone(),
+            base_currency: base_currency.to_string(),
+            quote_currency: quote_currency.to_string(),
+            side,
+            configuration: serde_json::to_value(&request.order_configuration).unwrap_or_default(),
+            limit_price,
+            base_size,
+            size_in_quote,
+            time_in_force,
+            status: OrderStatus::Open,
+            created_time: Utc::now(),
+            filled_value: Decimal::ZERO,
+            fees: Decimal::ZERO,
+            hold: Decimal::ZERO,
+        });
+        let index = self.orders.len() - 1;
+
+        match touch {
+            Some(price) if crosses => self.fill(index, price, Liquidity::Taker),
+            _ if time_in_force.rests() => {
+                self.orders[index].hold = required;
+                let balance = self.balance_mut(pays_with);
+                balance.available -= required;
+                balance.hold += required;
+            }
+            _ => self.orders[index].status = OrderStatus::Cancelled,
+        }
+        Ok(accepted(&self.orders[index]))
+    }
+
+    /// Fill an order in full at `price`.
+    fn fill(&mut self, index: usize, price: Decimal, liquidity: Liquidity) {
+        let fee_rate = match liquidity {
+            Liquidity::Maker => self.maker_fee,
+            Liquidity::Taker => self.taker_fee,
+        };
+        let order = &mut self.orders[index];
+        let size = order.base_size;
+        let value = size * price;
+        let fee = value * fee_rate;
+        let hold = std::mem::take(&mut order.hold);
+        order.status = OrderStatus::Filled;
+        order.filled_value = value;
+        order.fees = fee;
+        let order = order.clone();
+
+        match order.side {
+            OrderSide::Buy => {
+                let quote = self.balance_mut(&order.quote_currency);
+                quote.hold -= hold;
+                quote.available += hold - value - fee;
+                self.balance_mut(&order.base_currency).available += size;
+            }
+            OrderSide::Sell => {
+                let base = self.balance_mut(&order.base_currency);
+                base.hold -= hold;
+                base.available += hold - size;
+                self.balance_mut(&order.quote_currency).available += value - fee;
+            }
+        }
+
+        let now = Utc::now();
+        let trade_id = self.next_id("trade");
+        tracing::debug!(
+            order_id = %order.order_id,
+            product_id = %order.product_id,
+            %size,
+            %price,
+            "paper order filled"
+        );
+        self.fills.push((
+            now,
+            Fill {
+                entry_id: trade_id.clone(),
+                trade_id,
+                order_id: order.order_id,
+                trade_time: format_time(now),
+                trade_type: "FILL".to_string(),
+                price: price.normalize().to_string(),
+                size: size.normalize().to_string(),
+                commission: fee.normalize().to_string(),
+                product_id: order.product_id,
+                sequence_timestamp: Some(format_time(now)),
+                liquidity_indicator: Some(
+                    match liquidity {
+                        Liquidity::Maker => "MAKER",
+                        Liquidity::Taker => "TAKER",
+                    }
+                    .to_string(),
+                ),
+                size_in_quote: Some(false),
+                user_id: None,
+                side: Some(side_str(order.side).to_string()),
+                #[cfg(feature = "capture-unknown")]
+                extra: Default::default(),
+            },
+        ));
+    }
+
+    /// Fill the resting orders of a product that its quote has reached.
+    fn match_resting(&mut self, product_id: &str) {
+        let Some(quote) = self.quotes.get(product_id).copied() else {
+            return;
+        };
+        for index in 0..self.orders.len() {
+            let order = &self.orders[index];
+            if order.status != OrderStatus::Open || order.product_id != product_id {
+                continue;
+            }
+            let Some(limit) = order.limit_price else {
+                continue;
+            };
+            let reached = match order.side {
+                OrderSide::Buy => quote.ask <= limit,
+                OrderSide::Sell => quote.bid >= limit,
+            };
+            if reached {
+                self.fill(index, limit, Liquidity::Maker);
+            }
+        }
+    }
+
+    /// Expire GTD orders whose end time has passed.
+    fn expire(&mut self, now: DateTime<Utc>) {
+        for index in 0..self.orders.len() {
+            let order = &self.orders[index];
+            if order.status == OrderStatus::Open
+                && matches!(order.time_in_force, TimeInForce::GoodUntilDate(end) if end <= now)
+            {
+                self.close(index, OrderStatus::Expired);
+            }
+        }
+    }
+
+    /// Cancel an order by ID.
+    fn cancel(&mut self, order_id: &str) -> CancelOrderResult {
+        let failure_reason = match self
+            .orders
+            .iter()
+            .position(|order| order.order_id == order_id)
+        {
+            Some(index) if self.orders[index].status == OrderStatus::Open => {
+                self.close(index, OrderStatus::Cancelled);
+                None
+            }
+            Some(_) => Some("INVALID_CANCEL_REQUEST"),
+            None => Some("UNKNOWN_CANCEL_ORDER"),
+        };
+        CancelOrderResult {
+            success: failure_reason.is_none(),
+            failure_reason: failure_reason.map(str::to_string),
+            order_id: order_id.to_string(),
+        }
+    }
+
+    /// End an open order without filling it, releasing its hold.
+    fn close(&mut self, index: usize, status: OrderStatus) {
+        let order = &mut self.orders[index];
+        order.status = status;
+        let hold = std::mem::take(&mut order.hold);
+        let currency = match order.side {
+            OrderSide::Buy => order.quote_currency.clone(),
+            OrderSide::Sell => order.base_currency.clone(),
+        };
+        let balance = self.balance_mut(&currency);
+        balance.hold -= hold;
+        balance.available += hold;
+    }
+
+    /// One account per currency, ordered by currency.
+    fn accounts(&self) -> Vec<Account> {
+        let mut balances: Vec<&CurrencyBalance> = self.balances.values().collect();
+        balances.sort_by(|a, b| a.currency.cmp(&b.currency));
+        let now = format_time(Utc::now());
+        balances
+            .into_iter()
+            .map(|balance| Account {
+                uuid: format!("paper-{}", balance.currency),
+                name: format!("{} Wallet", balance.currency),
+                currency: balance.currency.clone(),
+                available_balance: Balance {
+                    value: balance.available.normalize().to_string(),
+                    currency: balance.currency.clone(),
+                },
+                default: true,
+                active: true,
+                created_at: now.clone(),
+                updated_at: now.clone(),
+                deleted_at: None,
+                account_type: "ACCOUNT_TYPE_CRYPTO".to_string(),
+                ready: true,
+                hold: Balance {
+                    value: balance.hold.normalize().to_string(),
+                    currency: balance.currency.clone(),
+                },
+                retail_portfolio_id: None,
+            })
+            .collect()
+    }
+}
+
+/// Response for a placed order.
+fn accepted(order: &PaperOrder) -> CreateOrderResponse {
+    CreateOrderResponse {
+        success: true,
+        failure_reason: None,
+        order_id: Some(order.order_id.clone()),
+        success_response: Some(OrderSuccessResponse {
+            order_id: order.order_id.clone(),
+            product_id: Some(order.product_id.clone()),
+            side: Some(side_str(order.side).to_string()),
+            client_order_id: Some(order.client_order_id.clone()),
+        }),
+        error_response: None,
+    }
+}
+
+/// Response for an order that was not placed.
+fn rejected(reason: &str, message: &str) -> CreateOrderResponse {
+    CreateOrderResponse {
+        success: false,
+        failure_reason: Some(reason.to_string()),
+        order_id: None,
+        success_response: None,
+        error_response: Some(serde_json::json!({
+            "error": reason,
+            "message": message,
+        })),
+    }
+}
+
+/// Take the page of `items` a cursor points to.
+///
+/// Cursors are offsets into the full list.
+fn page<T>(
+    items: Vec<T>,
+    cursor: Option<&str>,
+    limit: Option<u32>,
+) -> Result<(Vec<T>, Option<String>)> {
+    let start = match cursor {
+        Some(cursor) if !cursor.is_empty() => cursor
+            .parse::<usize>()
+            .map_err(|_| Error::request(format!("Invalid cursor {:?}", cursor)))?,
+        _ => 0,
+    };
+    let total = items.len();
+    let end = limit.map_or(total, |limit| total.min(start + limit as usize));
+    let next = (end < total).then(|| end.to_string());
+    Ok((
+        items
+            .into_iter()
+            .skip(start)
+            .take(end.saturating_sub(start))
+            .collect(),
+        next,
+    ))
+}
+
+fn side_str(side: OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "BUY",
+        OrderSide::Sell => "SELL",
+    }
+}
+
+fn status_str(status: OrderStatus) -> &'static str {
+    match status {
+        OrderStatus::Pending => "PENDING",
+        OrderStatus::Open => "OPEN",
+        OrderStatus::Filled => "FILLED",
+        OrderStatus::Cancelled => "CANCELLED",
+        OrderStatus::Expired => "EXPIRED",
+        OrderStatus::Failed => "FAILED",
+        OrderStatus::Unknown => "UNKNOWN",
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn parse_time(value: &str, field: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| Error::request(format!("Invalid {} {:?}: {}", field, value, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    fn level2_snapshot(bid: &str, ask: &str) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "channel": "l2_data",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": 1,
+            "events": [{
+                "type": "snapshot",
+                "product_id": "BTC-USD",
+                "updates": [
+                    {"side": "bid", "event_time": "2024-01-15T12:00:00Z", "price_level": bid, "new_quantity": "1"},
+                    {"side": "offer", "event_time": "2024-01-15T12:00:00Z", "price_level": ask, "new_quantity": "1"}
+                ]
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn limit_buy(client_order_id: &str, size: &str, price: &str) -> CreateOrderRequest {
+        CreateOrderRequest::new(
+            client_order_id,
+            "BTC-USD",
+            OrderSide::Buy,
+            OrderConfiguration::limit_gtc(size, price, false),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_market_order_fills_at_touch() {
+        let paper = PaperTradingClient::new()
+            .with_balance("USD", dec("10000"))
+            .with_fees(Decimal::ZERO, dec("0.01"));
+        paper.observe(&level2_snapshot("49990", "50000")).unwrap();
+        assert_eq!(
+            paper.quote("BTC-USD"),
+            Some(Quote {
+                bid: dec("49990"),
+                ask: dec("50000")
+            })
+        );
+
+        let request = CreateOrderRequest::new(
+            "market-1",
+            "BTC-USD",
+            OrderSide::Buy,
+            OrderConfiguration::market_buy_base("0.1"),
+        );
+        let response = paper.orders().create(request).await.unwrap();
+        assert!(response.success);
+
+        let order = paper
+            .orders()
+            .get_by_client_order_id("market-1")
+            .await
+            .unwrap();
+        assert_eq!(order.status, "FILLED");
+        assert_eq!(order.average_filled_price.as_deref(), Some("50000"));
+        // 5000 for the coins plus a 1% taker fee.
+        assert_eq!(paper.balance("USD").available, dec("4950"));
+        assert_eq!(paper.balance("BTC").available, dec("0.1"));
+
+        let fills = paper
+            .orders()
+            .list_fills(ListFillsParams::new())
+            .await
+            .unwrap();
+        assert_eq!(fills.fills.len(), 1);
+        assert_eq!(fills.fills[0].commission, "50");
+        assert_eq!(fills.fills[0].liquidity_indicator.as_deref(), Some("TAKER"));
+    }
+
+    #[tokio::test]
+    async fn test_resting_limit_order() {
+        let paper = PaperTradingClient::new().with_balance("USD", dec("1000"));
+        paper.set_quote("BTC-USD", dec("49990"), dec("50000"));
+
+        let response = paper
+            .orders()
+            .create(limit_buy("limit-1", "0.01", "49000"))
+            .await
+            .unwrap();
+        let order_id = response.order_id.unwrap();
+        let usd = paper.balance("USD");
+        assert_eq!(usd.available, dec("510"));
+        assert_eq!(usd.hold, dec("490"));
+        let open = paper
+            .orders()
+            .list(ListOrdersParams::new().status(OrderStatus::Open))
+            .await
+            .unwrap();
+        assert_eq!(open.orders.len(), 1);
+
+        // The ask reaching the limit fills it at the limit price.
+        paper.set_quote("BTC-USD", dec("48900"), dec("48950"));
+        let order = paper.orders().get(&order_id).await.unwrap();
+        assert_eq!(order.status, "FILLED");
+        assert_eq!(order.average_filled_price.as_deref(), Some("49000"));
+        let usd = paper.balance("USD");
+        assert_eq!(usd.available, dec("510"));
+        assert_eq!(usd.hold, Decimal::ZERO);
+        assert_eq!(paper.balance("BTC").available, dec("0.01"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_releases_hold() {
+        let paper = PaperTradingClient::new().with_balance("USD", dec("1000"));
+        let response = paper
+            .orders()
+            .create(limit_buy("limit-1", "0.01", "49000"))
+            .await
+            .unwrap();
+        let order_id = response.order_id.unwrap();
+
+        let cancelled = paper
+            .orders()
+            .cancel(CancelOrdersRequest::single(order_id.as_str()))
+            .await
+            .unwrap();
+        assert!(cancelled.results[0].success);
+        assert_eq!(paper.balance("USD").available, dec("1000"));
+        assert_eq!(paper.balance("USD").hold, Decimal::ZERO);
+
+        let again = paper
+            .orders()
+            .cancel(CancelOrdersRequest::new(vec![
+                order_id,
+                "missing".to_string(),
+            ]))
+            .await
+            .unwrap();
+        assert_eq!(
+            again.results[0].failure_reason.as_deref(),
+            Some("INVALID_CANCEL_REQUEST")
+        );
+        assert_eq!(
+            again.results[1].failure_reason.as_deref(),
+            Some("UNKNOWN_CANCEL_ORDER")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejections() {
+        let paper = PaperTradingClient::new().with_balance("USD", dec("100"));
+        paper.set_quote("BTC-USD", dec("49990"), dec("50000"));
+
+        let response = paper
+            .orders()
+            .create(limit_buy("too-big", "1", "50000"))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.failure_reason.as_deref(),
+            Some("INSUFFICIENT_FUND")
+        );
+
+        let post_only = CreateOrderRequest::new(
+            "post-only",
+            "BTC-USD",
+            OrderSide::Buy,
+            OrderConfiguration::limit_gtc("0.001", "50000", true),
+        );
+        let response = paper.orders().create(post_only).await.unwrap();
+        assert_eq!(
+            response.failure_reason.as_deref(),
+            Some("INVALID_LIMIT_PRICE_POST_ONLY")
+        );
+
+        let stop = CreateOrderRequest::new(
+            "stop",
+            "BTC-USD",
+            OrderSide::Buy,
+            OrderConfiguration::stop_limit_gtc(
+                "0.001",
+                "51000",
+                "50500",
+                crate::models::StopDirection::StopDirectionStopUp,
+            ),
+        );
+        let response = paper.orders().create(stop).await.unwrap();
+        assert_eq!(
+            response.failure_reason.as_deref(),
+            Some("UNSUPPORTED_ORDER_CONFIGURATION")
+        );
+
+        let ioc = CreateOrderRequest::new(
+            "ioc",
+            "BTC-USD",
+            OrderSide::Buy,
+            OrderConfiguration::limit_ioc("0.001", "49000"),
+        );
+        let response = paper.orders().create(ioc).await.unwrap();
+        assert!(response.success);
+        let order = paper.orders().get_by_client_order_id("ioc").await.unwrap();
+        assert_eq!(order.status, "CANCELLED");
+        assert_eq!(paper.balance("USD").available, dec("100"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_non_positive_limit_price() {
+        let paper = PaperTradingClient::new().with_balance("USD", dec("100"));
+        paper.set_quote("BTC-USD", dec("49990"), dec("50000"));
+
+        for (client_order_id, price) in [("negative", "-50000"), ("zero", "0")] {
+            let response = paper
+                .orders()
+                .create(limit_buy(client_order_id, "1", price))
+                .await
+                .unwrap();
+            assert_eq!(
+                response.failure_reason.as_deref(),
+                Some("INVALID_LIMIT_PRICE")
+            );
+        }
+        let usd = paper.balance("USD");
+        assert_eq!(usd.available, dec("100"));
+        assert_eq!(usd.hold, Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_accounts() {
+        let paper = PaperTradingClient::new()
+            .with_balance("USD", dec("100"))
+            .with_balance("BTC", dec("0.5"));
+        let accounts = paper
+            .accounts()
+            .list(ListAccountsParams::new().limit(1))
+            .await
+            .unwrap();
+        assert_eq!(accounts.accounts[0].currency, "BTC");
+        assert!(accounts.has_next);
+
+        let next = paper
+            .accounts()
+            .list(ListAccountsParams::new().cursor(accounts.cursor.unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(next.accounts.len(), 1);
+        assert!(!next.has_next);
+
+        let usd = paper.accounts().get("paper-USD").await.unwrap();
+        assert_eq!(usd.available_balance.value, "100");
+    }
+
+    /// Spend an account's available balance on a limit buy.
+    async fn buy_with_balance(
+        orders: &impl OrdersClient,
+        accounts: &impl AccountsClient,
+        account_uuid: &str,
+        price: Decimal,
+    ) -> Result<CreateOrderResponse> {
+        let account = accounts.get(account_uuid).await?;
+        let available: Decimal = account.available_balance.value.parse().unwrap();
+        let size = (available / price).round_dp_with_strategy(2, RoundingStrategy::ToZero);
+        orders
+            .create(limit_buy(
+                "strategy-1",
+                &size.to_string(),
+                &price.to_string(),
+            ))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_strategy_runs_against_shared_traits() {
+        fn assert_live_clients<O: OrdersClient, A: AccountsClient>() {}
+        assert_live_clients::<crate::rest::OrdersApi<'_>, crate::rest::AccountsApi<'_>>();
+
+        let paper = PaperTradingClient::new().with_balance("USD", dec("1000"));
+        let response = buy_with_balance(
+            &paper.orders(),
+            &paper.accounts(),
+            "paper-USD",
+            dec("40000"),
+        )
+        .await
+        .unwrap();
+        assert!(response.success);
+        let order = paper
+            .orders()
+            .get_by_client_order_id("strategy-1")
+            .await
+            .unwrap();
+        assert_eq!(order.status, "OPEN");
+        assert_eq!(paper.balance("USD").hold, dec("800"));
+    }
+}
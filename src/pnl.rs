@@ -0,0 +1,605 @@
+//! Realized profit and loss from fills.
+//!
+//! [`PnlCalculator`] matches fills against the open position of each product
+//! and books the realized profit or loss of every closing fill, net of
+//! commissions. Lots are matched first-in-first-out, last-in-first-out or
+//! against the average cost, see [`LotMethod`].
+//!
+//! Fills are loaded from the fills endpoint with
+//! [`backfill`](PnlCalculator::backfill) and kept up to date from the `user`
+//! WebSocket channel with [`observe`](PnlCalculator::observe). The user
+//! channel reports cumulative order progress rather than individual fills, so
+//! live fills are recorded at the average price of what was filled since the
+//! previous update.
+//!
+//! Fills from the endpoint are deduplicated by trade ID. Live updates are
+//! deduplicated against them by cumulative quantity: only the part of an
+//! order's filled quantity beyond what was already applied for that order is
+//! booked. Live fills get no exchange trade ID, so run the backfill before
+//! observing the user channel; a backfill that starts after live updates
+//! were observed books their executions a second time.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::models::ListFillsParams;
+//! use coinbase_advanced::pnl::{LotMethod, PnlCalculator};
+//! use coinbase_advanced::{Credentials, RestClient};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder()
+//!     .credentials(Credentials::from_env()?)
+//!     .build()?;
+//!
+//! let mut pnl = PnlCalculator::new(LotMethod::Fifo);
+//! pnl.backfill(&client, ListFillsParams::new().product_id("BTC-USD"))
+//!     .await?;
+//!
+//! for product in pnl.report() {
+//!     println!(
+//!         "{}: realized {} (fees {}), open {}",
+//!         product.product_id, product.realized_pnl, product.commissions, product.position
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+use crate::client::RestClient;
+use crate::error::{Error, Result};
+use crate::models::{Fill, ListFillsParams, OrderSide, parse_decimal_field};
+use crate::ws::{EventType, Events, Message};
+
+/// How closing fills are matched against open lots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LotMethod {
+    /// Close the oldest lots first.
+    #[default]
+    Fifo,
+    /// Close the newest lots first.
+    Lifo,
+    /// Close against the average cost of the open position.
+    AverageCost,
+}
+
+/// A single execution, parsed into decimal amounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeFill {
+    /// Trade ID, used to skip fills that were already applied.
+    pub trade_id: String,
+    /// Order ID.
+    pub order_id: String,
+    /// Product ID.
+    pub product_id: String,
+    /// Side of the order that was filled.
+    pub side: OrderSide,
+    /// Execution price.
+    pub price: Decimal,
+    /// Size in the base currency.
+    pub size: Decimal,
+    /// Commission in the quote currency.
+    pub commission: Decimal,
+    /// Trade time, if known.
+    pub time: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<&Fill> for TradeFill {
+    type Error = Error;
+
+    fn try_from(fill: &Fill) -> Result<Self> {
+        let side = match fill.side.as_deref() {
+            Some("BUY") => OrderSide::Buy,
+            Some("SELL") => OrderSide::Sell,
+            other => {
+                return Err(Error::parse(
+                    format!("invalid side for fill {}: {:?}", fill.trade_id, other),
+                    None,
+                ));
+            }
+        };
+        let price = parse_decimal_field("price", &fill.price)?;
+        let mut size = parse_decimal_field("size", &fill.size)?;
+        if fill.size_in_quote == Some(true) && !price.is_zero() {
+            size /= price;
+        }
+        Ok(Self {
+            trade_id: fill.trade_id.clone(),
+            order_id: fill.order_id.clone(),
+            product_id: fill.product_id.clone(),
+            side,
+            price,
+            size,
+            commission: parse_decimal_field("commission", &fill.commission)?,
+            time: DateTime::parse_from_rfc3339(&fill.trade_time)
+                .ok()
+                .map(|t| t.with_timezone(&Utc)),
+        })
+    }
+}
+
+/// Realized profit and loss of a product.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductPnl {
+    /// Product ID.
+    pub product_id: String,
+    /// Realized profit and loss after commissions, in the quote currency.
+    pub realized_pnl: Decimal,
+    /// Realized profit and loss before commissions.
+    pub gross_pnl: Decimal,
+    /// Commissions paid on every fill, including fills that opened positions.
+    pub commissions: Decimal,
+    /// Open position in the base currency; negative when short.
+    pub position: Decimal,
+    /// Average cost of the open position, if any.
+    pub average_cost: Option<Decimal>,
+    /// Number of fills applied.
+    pub fills: usize,
+}
+
+/// An open lot.
+#[derive(Debug, Clone, Copy)]
+struct Lot {
+    size: Decimal,
+    price: Decimal,
+}
+
+/// Lots and totals of one product.
+#[derive(Debug, Default)]
+struct Book {
+    /// Direction of the open lots.
+    side: Option<OrderSide>,
+    lots: VecDeque<Lot>,
+    gross_pnl: Decimal,
+    commissions: Decimal,
+    fills: usize,
+}
+
+impl Book {
+    fn apply(&mut self, method: LotMethod, fill: &TradeFill) {
+        self.fills += 1;
+        self.commissions += fill.commission;
+
+        let mut remaining = fill.size;
+        if self.side.is_some_and(|side| side != fill.side) {
+            while remaining > Decimal::ZERO {
+                let lot = match method {
+                    LotMethod::Lifo => self.lots.back_mut(),
+                    LotMethod::Fifo | LotMethod::AverageCost => self.lots.front_mut(),
+                };
+                let Some(lot) = lot else {
+                    break;
+                };
+                let closed = remaining.min(lot.size);
+                let gain = match fill.side {
+                    // Selling closes a long position.
+                    OrderSide::Sell => fill.price - lot.price,
+                    OrderSide::Buy => lot.price - fill.price,
+                };
+                self.gross_pnl += gain * closed;
+                lot.size -= closed;
+                remaining -= closed;
+                if lot.size.is_zero() {
+                    match method {
+                        LotMethod::Lifo => self.lots.pop_back(),
+                        LotMethod::Fifo | LotMethod::AverageCost => self.lots.pop_front(),
+                    };
+                }
+            }
+            if self.lots.is_empty() {
+                self.side = None;
+            }
+        }
+
+        if remaining > Decimal::ZERO {
+            self.side = Some(fill.side);
+            let lot = Lot {
+                size: remaining,
+                price: fill.price,
+            };
+            if method == LotMethod::AverageCost {
+                if let Some(open) = self.lots.front_mut() {
+                    let size = open.size + lot.size;
+                    open.price = (open.price * open.size + lot.price * lot.size) / size;
+                    open.size = size;
+                    return;
+                }
+            }
+            self.lots.push_back(lot);
+        }
+    }
+
+    fn position(&self) -> Decimal {
+        let size: Decimal = self.lots.iter().map(|lot| lot.size).sum();
+        match self.side {
+            Some(OrderSide::Sell) => -size,
+            _ => size,
+        }
+    }
+
+    fn average_cost(&self) -> Option<Decimal> {
+        let size: Decimal = self.lots.iter().map(|lot| lot.size).sum();
+        if size.is_zero() {
+            return None;
+        }
+        let cost: Decimal = self.lots.iter().map(|lot| lot.size * lot.price).sum();
+        Some(cost / size)
+    }
+}
+
+/// How much of an order had been filled at its last user channel update.
+#[derive(Debug, Clone, Copy, Default)]
+struct OrderProgress {
+    quantity: Decimal,
+    value: Decimal,
+    fees: Decimal,
+}
+
+/// Computes realized profit and loss per product from fills.
+#[derive(Debug, Default)]
+pub struct PnlCalculator {
+    method: LotMethod,
+    books: BTreeMap<String, Book>,
+    seen: HashSet<String>,
+    orders: HashMap<String, OrderProgress>,
+    /// Base quantity applied per order, from any source.
+    applied: HashMap<String, Decimal>,
+}
+
+impl PnlCalculator {
+    /// Create a calculator that matches lots with `method`.
+    pub fn new(method: LotMethod) -> Self {
+        Self {
+            method,
+            ..Self::default()
+        }
+    }
+
+    /// Get the lot matching method.
+    pub fn method(&self) -> LotMethod {
+        self.method
+    }
+
+    /// Apply a fill.
+    ///
+    /// Returns false if a fill with the same trade ID was already applied.
+    /// Fills must be applied in the order they happened.
+    pub fn add_fill(&mut self, fill: &TradeFill) -> bool {
+        if !self.seen.insert(fill.trade_id.clone()) {
+            return false;
+        }
+        *self.applied.entry(fill.order_id.clone()).or_default() += fill.size;
+        self.books
+            .entry(fill.product_id.clone())
+            .or_default()
+            .apply(self.method, fill);
+        true
+    }
+
+    /// Apply fills from the fills endpoint, oldest first.
+    ///
+    /// The endpoint lists the newest fills first, so the fills are sorted by
+    /// trade time before they are applied.
+    pub fn add_fills<'a>(&mut self, fills: impl IntoIterator<Item = &'a Fill>) -> Result<()> {
+        let mut parsed = fills
+            .into_iter()
+            .map(TradeFill::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        parsed.sort_by_key(|fill| fill.time);
+        for fill in &parsed {
+            self.add_fill(fill);
+        }
+        Ok(())
+    }
+
+    /// Load every fill matching `params` and apply them.
+    pub async fn backfill(
+        &mut self,
+        client: &RestClient,
+        mut params: ListFillsParams,
+    ) -> Result<()> {
+        let mut fills = Vec::new();
+        loop {
+            let response = client.orders().list_fills(params.clone()).await?;
+            fills.extend(response.fills);
+            match response.cursor.filter(|c| !c.is_empty()) {
+                Some(cursor) if params.cursor.as_deref() != Some(cursor.as_str()) => {
+                    params = params.cursor(cursor)
+                }
+                _ => break,
+            }
+        }
+        self.add_fills(&fills)
+    }
+
+    /// Apply the fills reported in a user channel message.
+    ///
+    /// The snapshot sent when subscribing only records how far each open
+    /// order had been filled, as those fills are expected to come from a
+    /// backfill. Quantity already applied for an order, e.g. by a backfill
+    /// that overlaps the subscription, is not booked again. Messages from
+    /// other channels are ignored.
+    ///
+    /// Every update is parsed before any is applied, so a message with an
+    /// invalid update returns an error and changes nothing.
+    pub fn observe(&mut self, message: &Message) -> Result<()> {
+        let Events::User(events) = &message.events else {
+            return Ok(());
+        };
+        let updates = events
+            .iter()
+            .flat_map(|event| {
+                let live = event.r#type == EventType::Update;
+                event
+                    .orders
+                    .iter()
+                    .map(move |update| Ok((live, update.typed()?)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let time = DateTime::parse_from_rfc3339(&message.timestamp)
+            .ok()
+            .map(|t| t.with_timezone(&Utc));
+
+        for (live, update) in updates {
+            let current = OrderProgress {
+                quantity: update.cumulative_quantity.unwrap_or_default(),
+                value: update.filled_value.unwrap_or_default(),
+                fees: update.total_fees.unwrap_or_default(),
+            };
+            let previous = self
+                .orders
+                .insert(update.order_id.clone(), current)
+                .unwrap_or_default();
+            let Some(side) = update.order_side.filter(|_| live) else {
+                continue;
+            };
+            let filled = current.quantity - previous.quantity;
+            let applied = self
+                .applied
+                .get(&update.order_id)
+                .copied()
+                .unwrap_or_default()
+                .max(previous.quantity);
+            let quantity = current.quantity - applied;
+            if filled <= Decimal::ZERO || quantity <= Decimal::ZERO {
+                continue;
+            }
+            // Only part of the window may be new; book that part at the
+            // window's average price and a matching share of its fees.
+            self.add_fill(&TradeFill {
+                trade_id: format!("{}:{}", update.order_id, current.quantity),
+                order_id: update.order_id.clone(),
+                product_id: update.product_id.clone(),
+                side,
+                price: (current.value - previous.value) / filled,
+                size: quantity,
+                commission: (current.fees - previous.fees) * quantity / filled,
+                time,
+            });
+        }
+        Ok(())
+    }
+
+    /// Get the profit and loss of a product.
+    pub fn product(&self, product_id: &str) -> Option<ProductPnl> {
+        self.books
+            .get(product_id)
+            .map(|book| summarize(product_id, book))
+    }
+
+    /// Get the profit and loss of every product, sorted by product ID.
+    pub fn report(&self) -> Vec<ProductPnl> {
+        self.books
+            .iter()
+            .map(|(product_id, book)| summarize(product_id, book))
+            .collect()
+    }
+
+    /// Total realized profit and loss after commissions over all products.
+    ///
+    /// Only meaningful when every product has the same quote currency.
+    pub fn total_realized(&self) -> Decimal {
+        self.books
+            .values()
+            .map(|book| book.gross_pnl - book.commissions)
+            .sum()
+    }
+}
+
+fn summarize(product_id: &str, book: &Book) -> ProductPnl {
+    ProductPnl {
+        product_id: product_id.to_string(),
+        realized_pnl: book.gross_pnl - book.commissions,
+        gross_pnl: book.gross_pnl,
+        commissions: book.commissions,
+        position: book.position(),
+        average_cost: book.average_cost(),
+        fills: book.fills,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(trade_id: &str, side: OrderSide, price: i64, size: i64) -> TradeFill {
+        TradeFill {
+            trade_id: trade_id.to_string(),
+            order_id: format!("order-{}", trade_id),
+            product_id: "BTC-USD".to_string(),
+            side,
+            price: Decimal::from(price),
+            size: Decimal::from(size),
+            commission: Decimal::ONE,
+            time: None,
+        }
+    }
+
+    fn run(method: LotMethod) -> ProductPnl {
+        let mut pnl = PnlCalculator::new(method);
+        pnl.add_fill(&fill("1", OrderSide::Buy, 100, 2));
+        pnl.add_fill(&fill("2", OrderSide::Buy, 200, 2));
+        pnl.add_fill(&fill("3", OrderSide::Sell, 300, 3));
+        assert!(!pnl.add_fill(&fill("3", OrderSide::Sell, 300, 3)));
+        pnl.product("BTC-USD").unwrap()
+    }
+
+    #[test]
+    fn test_lot_methods() {
+        // FIFO closes 2 @ 100 and 1 @ 200.
+        let fifo = run(LotMethod::Fifo);
+        assert_eq!(fifo.gross_pnl, Decimal::from(500));
+        assert_eq!(fifo.realized_pnl, Decimal::from(497));
+        assert_eq!(fifo.position, Decimal::ONE);
+        assert_eq!(fifo.average_cost, Some(Decimal::from(200)));
+        assert_eq!(fifo.fills, 3);
+
+        // LIFO closes 2 @ 200 and 1 @ 100.
+        let lifo = run(LotMethod::Lifo);
+        assert_eq!(lifo.gross_pnl, Decimal::from(400));
+        assert_eq!(lifo.average_cost, Some(Decimal::from(100)));
+
+        // Average cost closes 3 @ 150.
+        let average = run(LotMethod::AverageCost);
+        assert_eq!(average.gross_pnl, Decimal::from(450));
+        assert_eq!(average.average_cost, Some(Decimal::from(150)));
+    }
+
+    #[test]
+    fn test_flip_to_short() {
+        let mut pnl = PnlCalculator::new(LotMethod::Fifo);
+        pnl.add_fill(&fill("1", OrderSide::Buy, 100, 1));
+        pnl.add_fill(&fill("2", OrderSide::Sell, 120, 3));
+        pnl.add_fill(&fill("3", OrderSide::Buy, 90, 2));
+
+        let btc = pnl.product("BTC-USD").unwrap();
+        // 20 on the long, then 2 * 30 covering the short.
+        assert_eq!(btc.gross_pnl, Decimal::from(80));
+        assert_eq!(btc.position, Decimal::ZERO);
+        assert_eq!(btc.average_cost, None);
+        assert_eq!(pnl.total_realized(), Decimal::from(77));
+    }
+
+    #[test]
+    fn test_add_rest_fills_sorted() {
+        let rest_fill = |trade_id: &str, side: &str, price: &str, time: &str| -> Fill {
+            serde_json::from_value(serde_json::json!({
+                "entry_id": trade_id,
+                "trade_id": trade_id,
+                "order_id": "order",
+                "trade_time": time,
+                "trade_type": "FILL",
+                "price": price,
+                "size": "1",
+                "commission": "0.5",
+                "product_id": "ETH-USD",
+                "side": side
+            }))
+            .unwrap()
+        };
+        // Newest first, as returned by the endpoint.
+        let fills = [
+            rest_fill("2", "SELL", "2500", "2024-01-02T00:00:00Z"),
+            rest_fill("1", "BUY", "2000", "2024-01-01T00:00:00Z"),
+        ];
+
+        let mut pnl = PnlCalculator::new(LotMethod::Fifo);
+        pnl.add_fills(&fills).unwrap();
+        let eth = pnl.product("ETH-USD").unwrap();
+        assert_eq!(eth.realized_pnl, Decimal::from(499));
+        assert_eq!(eth.position, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_rest_fill_amounts_are_plain_decimals() {
+        let rest_fill = |commission: &str| -> Fill {
+            serde_json::from_value(serde_json::json!({
+                "entry_id": "1",
+                "trade_id": "1",
+                "order_id": "order",
+                "trade_time": "2024-01-01T00:00:00Z",
+                "trade_type": "FILL",
+                "price": "2000",
+                "size": "1",
+                "commission": commission,
+                "product_id": "ETH-USD",
+                "side": "BUY"
+            }))
+            .unwrap()
+        };
+
+        let fill = TradeFill::try_from(&rest_fill(" 0.5 ")).unwrap();
+        assert_eq!(fill.commission, Decimal::new(5, 1));
+        for bad in ["", "1e2", "NaN"] {
+            let err = TradeFill::try_from(&rest_fill(bad)).unwrap_err();
+            assert!(err.to_string().contains("commission"), "{err}");
+        }
+    }
+
+    fn user_update(cumulative: &str, value: &str, fees: &str) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "channel": "user",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": 1,
+            "events": [{
+                "type": "update",
+                "orders": [{
+                    "order_id": "order-1",
+                    "product_id": "BTC-USD",
+                    "order_side": "BUY",
+                    "order_type": "LIMIT",
+                    "status": "OPEN",
+                    "cumulative_quantity": cumulative,
+                    "filled_value": value,
+                    "total_fees": fees
+                }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_observe_skips_backfilled_quantity() {
+        let mut pnl = PnlCalculator::new(LotMethod::Fifo);
+        // The backfill already holds the first 2 of the order's fills.
+        let mut backfilled = fill("1", OrderSide::Buy, 100, 2);
+        backfilled.order_id = "order-1".to_string();
+        pnl.add_fill(&backfilled);
+
+        // The first live update overlaps the backfill entirely.
+        pnl.observe(&user_update("2", "200", "2")).unwrap();
+        assert_eq!(pnl.product("BTC-USD").unwrap().position, Decimal::from(2));
+
+        // Only the quantity beyond the backfill is booked.
+        pnl.observe(&user_update("3", "320", "3")).unwrap();
+        let btc = pnl.product("BTC-USD").unwrap();
+        assert_eq!(btc.position, Decimal::from(3));
+        assert_eq!(btc.fills, 2);
+        assert_eq!(
+            btc.average_cost,
+            Some("106.666666666666666666666666667".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_observe_invalid_update() {
+        let mut pnl = PnlCalculator::new(LotMethod::Fifo);
+        let mut message = user_update("1", "100", "0");
+        let Events::User(events) = &mut message.events else {
+            unreachable!()
+        };
+        let mut invalid = events[0].orders[0].clone();
+        invalid.order_id = "order-2".to_string();
+        invalid.filled_value = "not a number".to_string();
+        events[0].orders.push(invalid);
+
+        assert!(pnl.observe(&message).is_err());
+        assert!(pnl.product("BTC-USD").is_none());
+        assert!(pnl.orders.is_empty());
+    }
+}
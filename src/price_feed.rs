@@ -0,0 +1,294 @@
+//! Best-effort latest prices.
+//!
+//! [`PriceFeed`] answers "what is the current price of X" from the freshest
+//! source available: ticker updates from a WebSocket stream when they are
+//! recent enough, otherwise the REST best bid/ask endpoint.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::price_feed::PriceFeed;
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//! use coinbase_advanced::{Credentials, RestClient};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let rest = RestClient::builder()
+//!     .credentials(Credentials::from_env()?)
+//!     .build()?;
+//! let feed = PriceFeed::new(rest);
+//!
+//! let ws = WebSocketClient::builder().build()?;
+//! let stream = ws.connect().await?;
+//! ws.subscribe(&[Channel::Ticker {
+//!     product_ids: vec!["BTC-USD".to_string()],
+//! }]).await?;
+//!
+//! let tracker = feed.clone();
+//! tokio::spawn(async move { tracker.run(stream).await });
+//!
+//! let price = feed.price("BTC-USD").await?;
+//! println!("BTC-USD {} ({:?}, {:?} old)", price.price, price.source, price.age());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+use rust_decimal::Decimal;
+
+use crate::client::RestClient;
+use crate::error::{Error, Result};
+use crate::models::{BestBidAsk, GetBestBidAskParams};
+use crate::runtime::Instant;
+use crate::ws::{Events, Message};
+
+/// Default maximum age of a WebSocket price before falling back to REST.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(5);
+
+/// Where a price came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    /// A ticker update from a WebSocket stream.
+    WebSocket,
+    /// The midpoint of the REST best bid/ask.
+    Rest,
+}
+
+/// A price observation for a product.
+#[derive(Debug, Clone)]
+pub struct Price {
+    /// The product ID.
+    pub product_id: String,
+    /// The price.
+    pub price: Decimal,
+    /// Where the price came from.
+    pub source: PriceSource,
+    /// When the price was observed.
+    pub observed_at: Instant,
+}
+
+impl Price {
+    /// Time since the price was observed.
+    pub fn age(&self) -> Duration {
+        self.observed_at.elapsed()
+    }
+
+    /// Returns true if the price is older than `max_age`.
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+}
+
+/// Latest prices from WebSocket tickers, with REST fallback.
+///
+/// Cloning a feed is cheap; clones share the same price cache.
+#[derive(Clone)]
+pub struct PriceFeed {
+    client: RestClient,
+    max_age: Duration,
+    prices: Arc<RwLock<HashMap<String, Price>>>,
+}
+
+impl PriceFeed {
+    /// Create a price feed that falls back to the given REST client.
+    pub fn new(client: RestClient) -> Self {
+        Self {
+            client,
+            max_age: DEFAULT_MAX_AGE,
+            prices: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Set how old a WebSocket price may be before REST is used instead.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Get the maximum age of a WebSocket price.
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    /// Record the ticker updates in a WebSocket message.
+    ///
+    /// Messages from other channels and tickers without a valid price are
+    /// ignored.
+    pub fn observe(&self, message: &Message) {
+        let Events::Ticker(events) = &message.events else {
+            return;
+        };
+
+        let now = Instant::now();
+        let mut prices = self.prices.write().unwrap_or_else(|e| e.into_inner());
+        for ticker in events.iter().flat_map(|e| &e.tickers) {
+            let Ok(Some(price)) = ticker.price() else {
+                continue;
+            };
+            prices.insert(
+                ticker.product_id.clone(),
+                Price {
+                    product_id: ticker.product_id.clone(),
+                    price,
+                    source: PriceSource::WebSocket,
+                    observed_at: now,
+                },
+            );
+        }
+    }
+
+    /// Record every message from a stream until it ends.
+    ///
+    /// Subscribe the stream to the `ticker` or `ticker_batch` channel for the
+    /// products of interest. Returns the first stream error.
+    pub async fn run<S>(&self, mut stream: S) -> Result<()>
+    where
+        S: Stream<Item = Result<Message>> + Unpin,
+    {
+        while let Some(message) = stream.next().await {
+            self.observe(&message?);
+        }
+        Ok(())
+    }
+
+    /// Get the last observed price for a product, however old.
+    pub fn cached(&self, product_id: &str) -> Option<Price> {
+        self.prices
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(product_id)
+            .cloned()
+    }
+
+    /// Get the latest price for a product.
+    ///
+    /// Returns the WebSocket price if it is no older than the maximum age,
+    /// otherwise fetches the best bid/ask over REST and returns its midpoint.
+    pub async fn price(&self, product_id: &str) -> Result<Price> {
+        if let Some(price) = self
+            .cached(product_id)
+            .filter(|p| p.source == PriceSource::WebSocket && !p.is_stale(self.max_age))
+        {
+            return Ok(price);
+        }
+
+        let response = self
+            .client
+            .products()
+            .get_best_bid_ask(GetBestBidAskParams::new().product_ids([product_id]))
+            .await?;
+
+        let price = response
+            .pricebooks
+            .iter()
+            .find(|b| b.product_id == product_id)
+            .and_then(midpoint)
+            .ok_or_else(|| Error::request(format!("No price available for {}", product_id)))?;
+
+        Ok(Price {
+            product_id: product_id.to_string(),
+            price,
+            source: PriceSource::Rest,
+            observed_at: Instant::now(),
+        })
+    }
+}
+
+/// Midpoint of the best bid and ask, falling back to whichever side exists.
+fn midpoint(book: &BestBidAsk) -> Option<Decimal> {
+    let bid = book
+        .bids
+        .first()
+        .and_then(|l| l.price.parse::<Decimal>().ok());
+    let ask = book
+        .asks
+        .first()
+        .and_then(|l| l.price.parse::<Decimal>().ok());
+
+    match (bid, ask) {
+        (Some(bid), Some(ask)) => Some((bid + ask) / Decimal::TWO),
+        (Some(price), None) | (None, Some(price)) => Some(price),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BookLevel;
+
+    fn ticker_message(product_id: &str, price: &str) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "channel": "ticker",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": 1,
+            "events": [{
+                "type": "update",
+                "tickers": [{
+                    "type": "ticker",
+                    "product_id": product_id,
+                    "price": price,
+                    "volume_24_h": "0",
+                    "low_24_h": "0",
+                    "high_24_h": "0",
+                    "low_52_w": "0",
+                    "high_52_w": "0",
+                    "price_percent_chg_24_h": "0"
+                }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_observe_ticker() {
+        let feed = PriceFeed::new(RestClient::builder().build().unwrap());
+        feed.observe(&ticker_message("BTC-USD", "50000.00"));
+
+        let cached = feed.cached("BTC-USD").unwrap();
+        assert_eq!(cached.price, Decimal::from(50000));
+        assert_eq!(cached.source, PriceSource::WebSocket);
+
+        // Fresh WebSocket prices are served without a REST call.
+        let price = feed.price("BTC-USD").await.unwrap();
+        assert_eq!(price.price, Decimal::from(50000));
+
+        // Tickers without a price are skipped.
+        feed.observe(&ticker_message("ETH-USD", ""));
+        assert!(feed.cached("ETH-USD").is_none());
+    }
+
+    #[test]
+    fn test_staleness() {
+        let price = Price {
+            product_id: "BTC-USD".to_string(),
+            price: Decimal::ONE,
+            source: PriceSource::WebSocket,
+            observed_at: Instant::now() - Duration::from_secs(10),
+        };
+        assert!(price.is_stale(Duration::from_secs(5)));
+        assert!(!price.is_stale(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_midpoint() {
+        let level = |price: &str| BookLevel {
+            price: price.to_string(),
+            size: "1".to_string(),
+        };
+        let book = BestBidAsk {
+            product_id: "BTC-USD".to_string(),
+            bids: vec![level("100.00")],
+            asks: vec![level("100.10")],
+            time: None,
+        };
+        assert_eq!(
+            midpoint(&book).unwrap(),
+            "100.05".parse::<Decimal>().unwrap()
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Publishing WebSocket events to message queues.
+//!
+//! This module provides adapters that forward typed [`Message`]s from a
+//! WebSocket stream into existing event-driven infrastructure.
+//!
+//! # Publishers
+//!
+//! - [`ChannelPublisher`] - bridges messages into a `tokio::sync::mpsc` channel.
+//! - `NatsPublisher` - publishes JSON-encoded messages to NATS subjects
+//!   (requires the `nats` feature).
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::publish::{self, ChannelPublisher};
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = WebSocketClient::builder().build()?;
+//! let stream = client.connect().await?;
+//! client.subscribe(&[Channel::Ticker {
+//!     product_ids: vec!["BTC-USD".to_string()],
+//! }]).await?;
+//!
+//! let (publisher, mut receiver) = ChannelPublisher::channel(1024);
+//! tokio::spawn(async move { publish::forward(stream, &publisher).await });
+//!
+//! while let Some(message) = receiver.recv().await {
+//!     println!("{:?}", message.channel);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+
+use futures::{Stream, StreamExt};
+use tokio::sync::mpsc;
+
+use crate::error::{Error, Result};
+use crate::ws::Message;
+
+/// A destination that typed WebSocket messages can be published to.
+pub trait EventPublisher: Send + Sync {
+    /// Publish a single message.
+    fn publish(&self, message: &Message) -> impl Future<Output = Result<()>> + Send;
+}
+
+/// Publisher that bridges messages into a `tokio::sync::mpsc` channel.
+#[derive(Debug, Clone)]
+pub struct ChannelPublisher {
+    sender: mpsc::Sender<Message>,
+}
+
+impl ChannelPublisher {
+    /// Create a publisher from an existing sender.
+    pub fn new(sender: mpsc::Sender<Message>) -> Self {
+        Self { sender }
+    }
+
+    /// Create a publisher along with the receiving end of a new bounded channel.
+    pub fn channel(buffer: usize) -> (Self, mpsc::Receiver<Message>) {
+        let (sender, receiver) = mpsc::channel(buffer);
+        (Self::new(sender), receiver)
+    }
+}
+
+impl EventPublisher for ChannelPublisher {
+    async fn publish(&self, message: &Message) -> Result<()> {
+        self.sender
+            .send(message.clone())
+            .await
+            .map_err(|_| Error::publish("Receiver has been dropped"))
+    }
+}
+
+/// Publisher that sends JSON-encoded messages to NATS.
+///
+/// Messages are published to `{prefix}.{channel}`, e.g. `coinbase.ticker`.
+#[cfg(feature = "nats")]
+#[derive(Debug, Clone)]
+pub struct NatsPublisher {
+    client: async_nats::Client,
+    prefix: String,
+}
+
+#[cfg(feature = "nats")]
+impl NatsPublisher {
+    /// Create a new NATS publisher.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A connected NATS client.
+    /// * `prefix` - Subject prefix prepended to the channel name.
+    pub fn new(client: async_nats::Client, prefix: impl Into<String>) -> Self {
+        Self {
+            client,
+            prefix: prefix.into(),
+        }
+    }
+
+    /// Get the subject a message will be published to.
+    pub fn subject(&self, message: &Message) -> String {
+        format!("{}.{}", self.prefix, message.channel.as_str())
+    }
+}
+
+#[cfg(feature = "nats")]
+impl EventPublisher for NatsPublisher {
+    async fn publish(&self, message: &Message) -> Result<()> {
+        let payload = serde_json::to_vec(message)
+            .map_err(|e| Error::publish(format!("Failed to serialize message: {}", e)))?;
+
+        self.client
+            .publish(self.subject(message), payload.into())
+            .await
+            .map_err(|e| Error::publish(format!("Failed to publish to NATS: {}", e)))
+    }
+}
+
+/// Forward every message from a stream to a publisher.
+///
+/// Returns when the stream ends, or with the first stream or publish error.
+pub async fn forward<S, P>(mut stream: S, publisher: &P) -> Result<()>
+where
+    S: Stream<Item = Result<Message>> + Unpin,
+    P: EventPublisher,
+{
+    while let Some(message) = stream.next().await {
+        publisher.publish(&message?).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticker_message(sequence_num: u64) -> Message {
+        Message::raw(serde_json::json!({
+            "channel": "ticker",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": sequence_num,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_channel_publisher() {
+        let (publisher, mut receiver) = ChannelPublisher::channel(4);
+
+        publisher.publish(&ticker_message(1)).await.unwrap();
+        let received = receiver.recv().await.unwrap();
+        assert_eq!(received.sequence_num, 1);
+    }
+
+    #[tokio::test]
+    async fn test_channel_publisher_closed() {
+        let (publisher, receiver) = ChannelPublisher::channel(1);
+        drop(receiver);
+
+        assert!(publisher.publish(&ticker_message(1)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_forward() {
+        let (publisher, mut receiver) = ChannelPublisher::channel(4);
+        let stream = futures::stream::iter(vec![Ok(ticker_message(1)), Ok(ticker_message(2))]);
+
+        forward(stream, &publisher).await.unwrap();
+        assert_eq!(receiver.recv().await.unwrap().sequence_num, 1);
+        assert_eq!(receiver.recv().await.unwrap().sequence_num, 2);
+    }
+}
@@ -0,0 +1,281 @@
+//! Latest top of book for many products.
+//!
+//! [`QuoteBoard`] keeps the best bid, best ask and last price of a set of
+//! products from the `ticker_batch` channel. Reads never lock or await:
+//! updates swap in a new copy of the whole board, so
+//! [`get`](QuoteBoard::get) can be called from hot-path strategy code,
+//! including synchronous callbacks, while the feed keeps writing.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::quote_board::QuoteBoard;
+//! use coinbase_advanced::ws::WebSocketClient;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let board = QuoteBoard::new(["BTC-USD", "ETH-USD"]);
+//!
+//! let ws = WebSocketClient::builder().build()?;
+//! let stream = ws.connect().await?;
+//! board.subscribe(&ws).await?;
+//!
+//! let feed = board.clone();
+//! tokio::spawn(async move { feed.run(stream).await });
+//!
+//! if let Some(quote) = board.get("BTC-USD") {
+//!     println!("BTC-USD {:?} / {:?}", quote.bid, quote.ask);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use futures::{Stream, StreamExt};
+use rust_decimal::Decimal;
+
+use crate::error::Result;
+use crate::runtime::Instant;
+#[cfg(feature = "tokio")]
+use crate::ws::WebSocketClient;
+use crate::ws::{Channel, Events, Message};
+
+/// The latest top of book of one product.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopOfBook {
+    /// The product ID.
+    pub product_id: String,
+    /// Best bid price, if the ticker reported one.
+    pub bid: Option<Decimal>,
+    /// Best ask price, if the ticker reported one.
+    pub ask: Option<Decimal>,
+    /// Last trade price.
+    pub last: Option<Decimal>,
+    /// When the update was received.
+    pub updated_at: Instant,
+}
+
+impl TopOfBook {
+    /// Get the midpoint between the best bid and ask.
+    pub fn mid(&self) -> Option<Decimal> {
+        Some((self.bid? + self.ask?) / Decimal::TWO)
+    }
+
+    /// Get the difference between the best ask and bid.
+    pub fn spread(&self) -> Option<Decimal> {
+        Some(self.ask? - self.bid?)
+    }
+
+    /// Time since the update was received.
+    pub fn age(&self) -> Duration {
+        self.updated_at.elapsed()
+    }
+}
+
+/// Best bid, ask and last price of a set of products, readable without
+/// locking.
+///
+/// Cloning a board is cheap; clones share the same quotes.
+#[derive(Debug, Clone)]
+pub struct QuoteBoard {
+    product_ids: Arc<[String]>,
+    quotes: Arc<ArcSwap<HashMap<String, TopOfBook>>>,
+}
+
+impl QuoteBoard {
+    /// Create an empty board for the given products.
+    pub fn new<I, S>(product_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            product_ids: product_ids.into_iter().map(Into::into).collect(),
+            quotes: Arc::new(ArcSwap::from_pointee(HashMap::new())),
+        }
+    }
+
+    /// Get the products on the board.
+    pub fn product_ids(&self) -> &[String] {
+        &self.product_ids
+    }
+
+    /// Get the `ticker_batch` subscription for the board's products.
+    pub fn channel(&self) -> Channel {
+        Channel::TickerBatch {
+            product_ids: self.product_ids.to_vec(),
+        }
+    }
+
+    /// Subscribe a connected client to the board's products.
+    ///
+    /// Pass the client's message stream to [`run`](Self::run) to keep the
+    /// board up to date.
+    #[cfg(feature = "tokio")]
+    pub async fn subscribe(&self, client: &WebSocketClient) -> Result<()> {
+        client.subscribe(&[self.channel()]).await
+    }
+
+    /// Record the ticker updates in a WebSocket message.
+    ///
+    /// Updates for products not on the board and messages from other
+    /// channels are ignored. Returns true if the board changed.
+    pub fn observe(&self, message: &Message) -> Result<bool> {
+        let Events::Ticker(events) = &message.events else {
+            return Ok(false);
+        };
+
+        let now = Instant::now();
+        let mut updates = Vec::new();
+        for ticker in events.iter().flat_map(|event| &event.tickers) {
+            if !self.product_ids.contains(&ticker.product_id) {
+                continue;
+            }
+            updates.push(TopOfBook {
+                product_id: ticker.product_id.clone(),
+                bid: ticker.best_bid()?,
+                ask: ticker.best_ask()?,
+                last: ticker.price()?,
+                updated_at: now,
+            });
+        }
+        if updates.is_empty() {
+            return Ok(false);
+        }
+
+        self.quotes.rcu(|current| {
+            let mut next = HashMap::clone(current);
+            for update in &updates {
+                next.insert(update.product_id.clone(), update.clone());
+            }
+            next
+        });
+        Ok(true)
+    }
+
+    /// Record every message from a stream until it ends.
+    ///
+    /// Returns the first stream or parse error.
+    pub async fn run<S>(&self, mut stream: S) -> Result<()>
+    where
+        S: Stream<Item = Result<Message>> + Unpin,
+    {
+        while let Some(message) = stream.next().await {
+            self.observe(&message?)?;
+        }
+        Ok(())
+    }
+
+    /// Get the latest top of book of a product.
+    ///
+    /// Never blocks; returns `None` until the first update for the product.
+    pub fn get(&self, product_id: &str) -> Option<TopOfBook> {
+        self.quotes.load().get(product_id).cloned()
+    }
+
+    /// Get a consistent view of every product on the board.
+    pub fn snapshot(&self) -> Arc<HashMap<String, TopOfBook>> {
+        self.quotes.load_full()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticker_batch(product_id: &str, bid: &str, ask: &str, price: &str) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "channel": "ticker_batch",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": 1,
+            "events": [{
+                "type": "update",
+                "tickers": [{
+                    "type": "ticker",
+                    "product_id": product_id,
+                    "price": price,
+                    "volume_24_h": "1000",
+                    "low_24_h": "1",
+                    "high_24_h": "2",
+                    "low_52_w": "1",
+                    "high_52_w": "2",
+                    "price_percent_chg_24_h": "0",
+                    "best_bid": bid,
+                    "best_bid_quantity": "0.5",
+                    "best_ask": ask,
+                    "best_ask_quantity": "0.25"
+                }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_observe() {
+        let board = QuoteBoard::new(["BTC-USD", "ETH-USD"]);
+        assert!(board.get("BTC-USD").is_none());
+
+        assert!(
+            board
+                .observe(&ticker_batch("BTC-USD", "49990", "50010", "50000"))
+                .unwrap()
+        );
+        assert!(
+            board
+                .observe(&ticker_batch("ETH-USD", "2999", "3001", "3000"))
+                .unwrap()
+        );
+        // Products not on the board are ignored.
+        assert!(
+            !board
+                .observe(&ticker_batch("SOL-USD", "99", "101", "100"))
+                .unwrap()
+        );
+
+        let btc = board.get("BTC-USD").unwrap();
+        assert_eq!(btc.bid, Some(Decimal::from(49990)));
+        assert_eq!(btc.ask, Some(Decimal::from(50010)));
+        assert_eq!(btc.last, Some(Decimal::from(50000)));
+        assert_eq!(btc.mid(), Some(Decimal::from(50000)));
+        assert_eq!(btc.spread(), Some(Decimal::from(20)));
+        assert!(board.get("SOL-USD").is_none());
+        assert_eq!(board.snapshot().len(), 2);
+
+        // A clone reads the same board.
+        let clone = board.clone();
+        board
+            .observe(&ticker_batch("BTC-USD", "50100", "50110", "50105"))
+            .unwrap();
+        assert_eq!(
+            clone.get("BTC-USD").unwrap().bid,
+            Some(Decimal::from(50100))
+        );
+    }
+
+    #[test]
+    fn test_missing_best_bid_ask() {
+        let board = QuoteBoard::new(["BTC-USD"]);
+        board
+            .observe(&ticker_batch("BTC-USD", "", "", "50000"))
+            .unwrap();
+        let btc = board.get("BTC-USD").unwrap();
+        assert_eq!(btc.bid, None);
+        assert_eq!(btc.mid(), None);
+        assert_eq!(btc.last, Some(Decimal::from(50000)));
+    }
+
+    #[test]
+    fn test_channel() {
+        let board = QuoteBoard::new(vec!["BTC-USD".to_string()]);
+        assert_eq!(
+            board.channel(),
+            Channel::TickerBatch {
+                product_ids: vec!["BTC-USD".to_string()]
+            }
+        );
+    }
+}
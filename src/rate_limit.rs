@@ -1,7 +1,9 @@
 //! Rate limiting implementation using a token bucket algorithm.
 //!
 //! This module provides client-side rate limiting to avoid hitting Coinbase API limits
-//! and to gracefully handle rate limit responses.
+//! and to gracefully handle rate limit responses, either with
+//! [`retry_rate_limited`] or by letting the client retry once on its own
+//! (see [`RestClientBuilder::retry_on_rate_limit`](crate::RestClientBuilder::retry_on_rate_limit)).
 //!
 //! # Rate Limits
 //!
@@ -22,11 +24,27 @@
 //!     .build()
 //!     .unwrap();
 //! ```
+//!
+//! Requests that still get rate limited by the server can be retried with
+//! [`retry_rate_limited`]:
+//! ```no_run
+//! use coinbase_advanced::rate_limit::{RateLimitConfig, retry_rate_limited};
+//! use coinbase_advanced::RestClient;
+//!
+//! # async fn example(client: RestClient) -> coinbase_advanced::Result<()> {
+//! let policy = RateLimitConfig::new().with_max_retries(5);
+//! let products = retry_rate_limited(&policy, || async { client.products().list_all().await }).await?;
+//! # Ok(())
+//! # }
+//! ```
 
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use tokio::sync::Mutex;
 
+use crate::error::Result;
+use crate::runtime::{self, Instant};
+
 /// Default rate limits based on Coinbase API documentation.
 pub mod limits {
     /// Public REST API refresh rate (requests per second).
@@ -125,7 +143,7 @@ impl TokenBucket {
     pub async fn wait_and_consume(&mut self) {
         while !self.try_consume() {
             let wait_time = self.time_until_available();
-            tokio::time::sleep(wait_time).await;
+            crate::runtime::sleep(wait_time).await;
         }
     }
 
@@ -167,8 +185,10 @@ impl RateLimiter {
 
     /// Wait until a token is available and acquire it.
     pub async fn acquire(&self) {
+        let started = Instant::now();
         let mut bucket = self.bucket.lock().await;
         bucket.wait_and_consume().await;
+        crate::telemetry::record_rate_limit_wait(started.elapsed());
     }
 
     /// Get the current number of available tokens.
@@ -234,6 +254,48 @@ impl RateLimitConfig {
         self.max_backoff = duration;
         self
     }
+
+    /// Get the delay before retry number `attempt`, counting from zero.
+    ///
+    /// Uses the server's `Retry-After` when given, otherwise doubles the
+    /// initial backoff on every attempt. Both are capped at the maximum
+    /// backoff.
+    pub fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let delay = retry_after.unwrap_or_else(|| {
+            self.initial_backoff
+                .saturating_mul(2u32.saturating_pow(attempt))
+        });
+        delay.min(self.max_backoff)
+    }
+}
+
+/// Run a request, waiting and retrying while the server rate limits it.
+///
+/// `op` is called again after each [`Error::RateLimited`](crate::Error::RateLimited),
+/// up to `policy.max_retries` times, waiting as long as the server asked or
+/// as [`RateLimitConfig::backoff`] computes. Other errors are returned
+/// immediately. With a disabled policy the request is made once.
+pub async fn retry_rate_limited<F, Fut, T>(policy: &RateLimitConfig, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match op().await {
+            Err(e) if e.is_rate_limited() && policy.enabled && attempt < policy.max_retries => {
+                let delay = policy.backoff(attempt, e.retry_after());
+                tracing::debug!(
+                    attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    "rate limited, retrying"
+                );
+                runtime::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
 }
 
 /// Parsed rate limit information from response headers.
@@ -351,4 +413,51 @@ mod tests {
         // Third should fail immediately
         assert!(!limiter.try_acquire().await);
     }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RateLimitConfig::new()
+            .with_initial_backoff(Duration::from_secs(1))
+            .with_max_backoff(Duration::from_secs(5));
+        assert_eq!(policy.backoff(0, None), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2, None), Duration::from_secs(4));
+        assert_eq!(policy.backoff(10, None), Duration::from_secs(5));
+        assert_eq!(
+            policy.backoff(0, Some(Duration::from_secs(3))),
+            Duration::from_secs(3)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_rate_limited() {
+        use crate::error::Error;
+
+        let policy = RateLimitConfig::new()
+            .with_max_retries(2)
+            .with_initial_backoff(Duration::from_millis(1));
+
+        let mut calls = 0;
+        let result = retry_rate_limited(&policy, || {
+            calls += 1;
+            let n = calls;
+            async move {
+                if n < 3 {
+                    Err(Error::RateLimited { retry_after: None })
+                } else {
+                    Ok(n)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 3);
+
+        let mut calls = 0;
+        let result: Result<()> = retry_rate_limited(&policy, || {
+            calls += 1;
+            async { Err(Error::RateLimited { retry_after: None }) }
+        })
+        .await;
+        assert!(result.unwrap_err().is_rate_limited());
+        assert_eq!(calls, 3);
+    }
 }
@@ -0,0 +1,294 @@
+//! Checking the user channel snapshot against REST open orders.
+//!
+//! When the `user` channel is subscribed, Coinbase first sends a snapshot of
+//! the open orders. [`RestClient::reconcile_open_orders`] fetches the open
+//! orders over REST and reports every difference between the two, so a
+//! trading system can notice on startup that its local state is out of
+//! sync before acting on it.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::ws::{Channel, EventType, Events, WebSocketClient};
+//! use coinbase_advanced::{Credentials, RestClient};
+//! use futures::StreamExt;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let credentials = Credentials::from_env()?;
+//! let rest = RestClient::builder().credentials(credentials.clone()).build()?;
+//! let ws = WebSocketClient::builder().credentials(credentials).build()?;
+//!
+//! let mut stream = ws.connect().await?;
+//! ws.subscribe(&[Channel::User]).await?;
+//! while let Some(message) = stream.next().await {
+//!     let message = message?;
+//!     let is_snapshot = matches!(
+//!         &message.events,
+//!         Events::User(events) if events.iter().any(|e| e.r#type == EventType::Snapshot)
+//!     );
+//!     if is_snapshot {
+//!         let report = rest.reconcile_open_orders([&message]).await?;
+//!         for discrepancy in &report.discrepancies {
+//!             eprintln!("out of sync: {}", discrepancy);
+//!         }
+//!         break;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use futures::TryStreamExt;
+use rust_decimal::Decimal;
+
+use crate::client::RestClient;
+use crate::error::{Error, Result};
+use crate::models::{ListOrdersParams, Order, OrderStatus};
+use crate::ws::{EventType, Events, Message, OrderUpdate};
+
+/// A difference between the user channel snapshot and the REST open orders.
+#[derive(Debug, Clone)]
+pub enum OrderDiscrepancy {
+    /// The order is open according to REST but missing from the snapshot.
+    MissingFromSnapshot(Order),
+    /// The order is in the snapshot but not open according to REST.
+    MissingFromRest(OrderUpdate),
+    /// The order is in both, but their states differ.
+    Stale {
+        /// Order ID.
+        order_id: String,
+        /// Status reported over REST.
+        rest_status: String,
+        /// Status in the snapshot.
+        snapshot_status: String,
+        /// Filled size reported over REST.
+        rest_filled_size: Option<Decimal>,
+        /// Filled size in the snapshot.
+        snapshot_filled_size: Option<Decimal>,
+    },
+}
+
+impl OrderDiscrepancy {
+    /// Get the ID of the order concerned.
+    pub fn order_id(&self) -> &str {
+        match self {
+            Self::MissingFromSnapshot(order) => &order.order_id,
+            Self::MissingFromRest(update) => &update.order_id,
+            Self::Stale { order_id, .. } => order_id,
+        }
+    }
+}
+
+impl fmt::Display for OrderDiscrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFromSnapshot(order) => write!(
+                f,
+                "order {} ({}) is open but missing from the snapshot",
+                order.order_id, order.product_id
+            ),
+            Self::MissingFromRest(update) => write!(
+                f,
+                "order {} ({}) is in the snapshot but not open",
+                update.order_id, update.product_id
+            ),
+            Self::Stale {
+                order_id,
+                rest_status,
+                snapshot_status,
+                rest_filled_size,
+                snapshot_filled_size,
+            } => write!(
+                f,
+                "order {} is {} filled {:?} but the snapshot has {} filled {:?}",
+                order_id, rest_status, rest_filled_size, snapshot_status, snapshot_filled_size
+            ),
+        }
+    }
+}
+
+/// Result of comparing the user channel snapshot with the REST open orders.
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileReport {
+    /// Orders found in both with the same state.
+    pub matched: usize,
+    /// Every difference found, ordered by order ID.
+    pub discrepancies: Vec<OrderDiscrepancy>,
+}
+
+impl ReconcileReport {
+    /// Returns true if the snapshot and the REST open orders agree.
+    pub fn is_consistent(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// Compare the orders of a user channel snapshot with open orders from REST.
+///
+/// Orders are matched by ID. Matched orders are stale when their statuses
+/// or filled sizes differ.
+pub fn reconcile(snapshot: &[OrderUpdate], open_orders: &[Order]) -> ReconcileReport {
+    let mut rest: HashMap<&str, &Order> = open_orders
+        .iter()
+        .map(|order| (order.order_id.as_str(), order))
+        .collect();
+
+    let mut report = ReconcileReport::default();
+    for update in snapshot {
+        let Some(order) = rest.remove(update.order_id.as_str()) else {
+            report
+                .discrepancies
+                .push(OrderDiscrepancy::MissingFromRest(update.clone()));
+            continue;
+        };
+
+        let rest_filled_size = order.filled_size.as_deref().and_then(parse_size);
+        let snapshot_filled_size = parse_size(&update.cumulative_quantity);
+        let same_status =
+            order.status.parse::<OrderStatus>().ok() == update.status.parse::<OrderStatus>().ok();
+        let same_fill =
+            rest_filled_size.unwrap_or_default() == snapshot_filled_size.unwrap_or_default();
+        if same_status && same_fill {
+            report.matched += 1;
+        } else {
+            report.discrepancies.push(OrderDiscrepancy::Stale {
+                order_id: order.order_id.clone(),
+                rest_status: order.status.clone(),
+                snapshot_status: update.status.clone(),
+                rest_filled_size,
+                snapshot_filled_size,
+            });
+        }
+    }
+    report.discrepancies.extend(
+        rest.into_values()
+            .map(|order| OrderDiscrepancy::MissingFromSnapshot(order.clone())),
+    );
+    report
+        .discrepancies
+        .sort_by(|a, b| a.order_id().cmp(b.order_id()));
+    report
+}
+
+fn parse_size(value: &str) -> Option<Decimal> {
+    value.trim().parse().ok()
+}
+
+impl RestClient {
+    /// Compare a user channel snapshot with the open orders over REST.
+    ///
+    /// Pass every message of the snapshot; Coinbase splits large snapshots
+    /// over several messages. Messages that are not user channel snapshots
+    /// are ignored, but at least one must be given.
+    pub async fn reconcile_open_orders<'m, I>(&self, snapshot: I) -> Result<ReconcileReport>
+    where
+        I: IntoIterator<Item = &'m Message>,
+    {
+        let mut found = false;
+        let mut updates = Vec::new();
+        for message in snapshot {
+            let Events::User(events) = &message.events else {
+                continue;
+            };
+            for event in events.iter().filter(|e| e.r#type == EventType::Snapshot) {
+                found = true;
+                updates.extend(event.orders.iter().cloned());
+            }
+        }
+        if !found {
+            return Err(Error::request("no user channel snapshot to reconcile"));
+        }
+
+        let open_orders: Vec<Order> = self
+            .orders()
+            .stream(ListOrdersParams::new().status(OrderStatus::Open))
+            .try_collect()
+            .await?;
+        let report = reconcile(&updates, &open_orders);
+        if !report.is_consistent() {
+            tracing::warn!(
+                discrepancies = report.discrepancies.len(),
+                "user channel snapshot does not match open orders"
+            );
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(order_id: &str, status: &str, filled: &str) -> OrderUpdate {
+        serde_json::from_value(serde_json::json!({
+            "order_id": order_id,
+            "status": status,
+            "cumulative_quantity": filled,
+            "order_side": "BUY",
+            "order_type": "LIMIT",
+            "product_id": "BTC-USD"
+        }))
+        .unwrap()
+    }
+
+    fn order(order_id: &str, status: &str, filled: &str) -> Order {
+        serde_json::from_value(serde_json::json!({
+            "order_id": order_id,
+            "product_id": "BTC-USD",
+            "side": "BUY",
+            "client_order_id": "",
+            "status": status,
+            "filled_size": filled
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_reconcile() {
+        let snapshot = [
+            update("a", "OPEN", "0"),
+            update("b", "OPEN", "0.5"),
+            update("c", "OPEN", "0"),
+        ];
+        let rest = [
+            order("a", "OPEN", "0.000"),
+            order("b", "OPEN", "0.7"),
+            order("d", "OPEN", "0"),
+        ];
+
+        let report = reconcile(&snapshot, &rest);
+        assert_eq!(report.matched, 1);
+        let ids: Vec<&str> = report
+            .discrepancies
+            .iter()
+            .map(OrderDiscrepancy::order_id)
+            .collect();
+        assert_eq!(ids, ["b", "c", "d"]);
+        assert!(matches!(
+            &report.discrepancies[0],
+            OrderDiscrepancy::Stale { rest_filled_size: Some(size), .. } if *size == Decimal::new(7, 1)
+        ));
+        assert!(matches!(
+            report.discrepancies[1],
+            OrderDiscrepancy::MissingFromRest(_)
+        ));
+        assert!(matches!(
+            report.discrepancies[2],
+            OrderDiscrepancy::MissingFromSnapshot(_)
+        ));
+        assert!(reconcile(&snapshot[..1], &rest[..1]).is_consistent());
+    }
+
+    #[tokio::test]
+    async fn test_requires_snapshot() {
+        let client = RestClient::builder().build().unwrap();
+        let err = client
+            .reconcile_open_orders(Vec::<&Message>::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Request(_)));
+    }
+}
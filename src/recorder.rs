@@ -0,0 +1,356 @@
+//! Recording WebSocket market data to disk.
+//!
+//! [`Recorder`] appends messages to newline-delimited JSON files, one message
+//! per line, starting a new file once the current one reaches a size or age
+//! limit. Each line is the serialized [`Message`], so recordings can be read
+//! back with `serde_json` into the same types the live stream produces.
+//!
+//! Frames that do not parse are the ones most worth keeping. Build the client
+//! with [`raw_fallback`](crate::ws::WebSocketClientBuilder::raw_fallback) so
+//! they reach the recorder as [`Message::raw`], which is written as the
+//! original payload, or pass frames from elsewhere to
+//! [`write_raw`](Recorder::write_raw).
+//!
+//! To write somewhere other than local files, pass any [`futures::Sink`] of
+//! messages to [`record_to_sink`].
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use coinbase_advanced::recorder::{self, Recorder};
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = WebSocketClient::builder().raw_fallback(true).build()?;
+//! let stream = recorder::connect(
+//!     &client,
+//!     &[Channel::Level2 {
+//!         product_ids: vec!["BTC-USD".to_string()],
+//!     }],
+//! )
+//! .await?;
+//!
+//! let mut recorder = Recorder::new("ticks")
+//!     .prefix("btc-l2")
+//!     .max_bytes(256 * 1024 * 1024)
+//!     .max_age(Duration::from_secs(3600));
+//! recorder.record(stream).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use tokio::fs::{self, File};
+use tokio::io::{AsyncWriteExt, BufWriter};
+
+use crate::error::{Error, Result};
+use crate::ws::{Channel, Events, Message, MessageStream, WebSocketClient};
+
+/// File extension used for recordings.
+pub const EXTENSION: &str = "ndjson";
+
+/// Connect a WebSocket client and subscribe to the channels to record.
+pub async fn connect(client: &WebSocketClient, channels: &[Channel]) -> Result<MessageStream> {
+    let stream = client.connect().await?;
+    client.subscribe(channels).await?;
+    Ok(stream)
+}
+
+/// Writes messages to rotating newline-delimited JSON files.
+///
+/// Files are created in the configured directory and named
+/// `{prefix}-{timestamp}-{n}.ndjson`, so sorting the names orders the
+/// recordings chronologically.
+#[derive(Debug)]
+pub struct Recorder {
+    dir: PathBuf,
+    prefix: String,
+    max_bytes: Option<u64>,
+    max_age: Option<Duration>,
+    segments: u64,
+    current: Option<Segment>,
+}
+
+/// The file currently being written.
+#[derive(Debug)]
+struct Segment {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    bytes: u64,
+    opened: Instant,
+}
+
+impl Recorder {
+    /// Create a recorder that writes into `dir`.
+    ///
+    /// The directory is created on the first write if it does not exist. By
+    /// default files are never rotated.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            prefix: "coinbase".to_string(),
+            max_bytes: None,
+            max_age: None,
+            segments: 0,
+            current: None,
+        }
+    }
+
+    /// Set the file name prefix.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Start a new file once the current one reaches this many bytes.
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Start a new file once the current one has been open this long.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Get the directory recordings are written to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the path of the file currently being written, if any.
+    pub fn current_path(&self) -> Option<&Path> {
+        self.current.as_ref().map(|s| s.path.as_path())
+    }
+
+    /// Append a message, rotating to a new file first if a limit was reached.
+    ///
+    /// A [`Message::raw`] is written as the payload it was built from.
+    pub async fn write(&mut self, message: &Message) -> Result<()> {
+        let line = match &message.events {
+            Events::Unknown(payload) if payload.is_object() => serde_json::to_vec(payload),
+            _ => serde_json::to_vec(message),
+        }
+        .map_err(|e| Error::parse(format!("Failed to serialize message: {}", e), None))?;
+        self.append(line).await
+    }
+
+    /// Append a frame as it was received, whether or not it parses.
+    ///
+    /// JSON frames spread over several lines are written compactly so each
+    /// line still holds one frame. Other frames containing line breaks are
+    /// rejected.
+    pub async fn write_raw(&mut self, frame: &str) -> Result<()> {
+        let frame = frame.trim_end();
+        let line = if frame.contains(['\n', '\r']) {
+            let value: serde_json::Value = serde_json::from_str(frame).map_err(|e| {
+                Error::parse(
+                    format!("Cannot record multi-line frame that is not JSON: {}", e),
+                    Some(frame.to_string()),
+                )
+            })?;
+            serde_json::to_vec(&value)
+                .map_err(|e| Error::parse(format!("Failed to serialize frame: {}", e), None))?
+        } else {
+            frame.as_bytes().to_vec()
+        };
+        self.append(line).await
+    }
+
+    async fn append(&mut self, mut line: Vec<u8>) -> Result<()> {
+        line.push(b'\n');
+
+        if self.should_rotate() {
+            self.rotate().await?;
+        }
+        if self.current.is_none() {
+            self.current = Some(self.open_segment().await?);
+        }
+        if let Some(segment) = self.current.as_mut() {
+            segment.writer.write_all(&line).await?;
+            segment.bytes += line.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Flush buffered messages to the current file.
+    pub async fn flush(&mut self) -> Result<()> {
+        if let Some(segment) = self.current.as_mut() {
+            segment.writer.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Write every message from a stream until it ends.
+    ///
+    /// Buffered messages are flushed before returning, including when the
+    /// stream yields an error. A frame that fails to parse ends the stream
+    /// with an error unless the client was built with
+    /// [`raw_fallback`](crate::ws::WebSocketClientBuilder::raw_fallback).
+    pub async fn record<S>(&mut self, mut stream: S) -> Result<()>
+    where
+        S: Stream<Item = Result<Message>> + Unpin,
+    {
+        let result = async {
+            while let Some(message) = stream.next().await {
+                self.write(&message?).await?;
+            }
+            Ok(())
+        }
+        .await;
+        self.flush().await?;
+        result
+    }
+
+    fn should_rotate(&self) -> bool {
+        let Some(segment) = &self.current else {
+            return false;
+        };
+        self.max_bytes.is_some_and(|max| segment.bytes >= max)
+            || self
+                .max_age
+                .is_some_and(|max| segment.opened.elapsed() >= max)
+    }
+
+    async fn rotate(&mut self) -> Result<()> {
+        if let Some(mut segment) = self.current.take() {
+            segment.writer.flush().await?;
+            tracing::debug!(path = %segment.path.display(), bytes = segment.bytes, "rotated recording");
+        }
+        Ok(())
+    }
+
+    async fn open_segment(&mut self) -> Result<Segment> {
+        fs::create_dir_all(&self.dir).await?;
+        let path = self.dir.join(format!(
+            "{}-{}-{:04}.{}",
+            self.prefix,
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%.6fZ"),
+            self.segments,
+            EXTENSION
+        ));
+        self.segments += 1;
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+        Ok(Segment {
+            path,
+            writer: BufWriter::new(file),
+            bytes: 0,
+            opened: Instant::now(),
+        })
+    }
+}
+
+/// Forward every message from a stream into a sink, then close the sink.
+///
+/// Use this to record into storage other than local files, such as an object
+/// store or database writer that implements [`Sink`].
+pub async fn record_to_sink<S, K>(mut stream: S, mut sink: K) -> Result<()>
+where
+    S: Stream<Item = Result<Message>> + Unpin,
+    K: Sink<Message> + Unpin,
+    K::Error: Display,
+{
+    let sink_error = |e: K::Error| Error::publish(format!("Failed to record message: {}", e));
+    let result = async {
+        while let Some(message) = stream.next().await {
+            sink.feed(message?).await.map_err(sink_error)?;
+        }
+        Ok(())
+    }
+    .await;
+    sink.close().await.map_err(sink_error)?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heartbeat(sequence_num: u64) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "channel": "heartbeats",
+            "client_id": "",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "sequence_num": sequence_num,
+            "events": [{"current_time": "2024-01-01T00:00:00Z", "heartbeat_counter": sequence_num}]
+        }))
+        .unwrap()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("coinbase-recorder-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_record_rotates_by_size() {
+        let dir = temp_dir("rotate");
+        let mut recorder = Recorder::new(&dir).prefix("test").max_bytes(1);
+
+        let messages = futures::stream::iter((1..=3).map(|n| Ok(heartbeat(n))));
+        recorder.record(messages).await.unwrap();
+
+        let mut files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        files.sort();
+        assert_eq!(files.len(), 3);
+
+        let last = std::fs::read_to_string(&files[2]).unwrap();
+        let message: Message = serde_json::from_str(last.trim_end()).unwrap();
+        assert_eq!(message.sequence_num, 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_record_raw_frames() {
+        let dir = temp_dir("raw");
+        let mut recorder = Recorder::new(&dir);
+
+        let payload = serde_json::json!({"channel": "new_channel", "events": [{"x": 1}]});
+        recorder
+            .write(&Message::raw(payload.clone()))
+            .await
+            .unwrap();
+        recorder.write_raw("not json").await.unwrap();
+        recorder.write_raw("{\n  \"a\": 1\n}").await.unwrap();
+        assert!(recorder.write_raw("not\njson").await.is_err());
+        recorder.flush().await.unwrap();
+
+        let contents = std::fs::read_to_string(recorder.current_path().unwrap()).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        let recorded: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(recorded, payload);
+        assert_eq!(lines[1], "not json");
+        assert_eq!(lines[2], r#"{"a":1}"#);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_record_to_sink() {
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let messages = futures::stream::iter((1..=2).map(|n| Ok(heartbeat(n))));
+        record_to_sink(messages, tx).await.unwrap();
+
+        let recorded: Vec<Message> = rx.collect().await;
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[1].sequence_num, 2);
+    }
+}
@@ -0,0 +1,265 @@
+//! Replaying recorded WebSocket market data.
+//!
+//! [`Replayer`] reads newline-delimited JSON files written by
+//! [`Recorder`](crate::recorder::Recorder) and yields the messages through a
+//! stream of the same `Result<Message>` items as a live
+//! [`MessageStream`](crate::ws::MessageStream). Strategies written against the
+//! live stream can therefore be run against captured data unchanged.
+//!
+//! By default messages are replayed as fast as they can be read. Use
+//! [`Replayer::real_time`] or [`Replayer::speed`] to space them out according
+//! to their recorded timestamps.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::replay::Replayer;
+//! use futures::StreamExt;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let mut stream = Replayer::from_dir("ticks").await?.speed(10.0).stream();
+//!
+//! while let Some(message) = stream.next().await {
+//!     let message = message?;
+//!     println!("{:?} {}", message.channel, message.sequence_num);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use futures::stream::{self, BoxStream};
+use tokio::fs::{self, File};
+use tokio::io::{AsyncBufReadExt, BufReader, Lines};
+
+use crate::error::{Error, Result};
+use crate::recorder::EXTENSION;
+use crate::ws::Message;
+
+/// Replays recorded messages as a stream.
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    files: Vec<PathBuf>,
+    speed: Option<f64>,
+}
+
+impl Replayer {
+    /// Create a replayer over the given files, read in order.
+    pub fn new<I, P>(files: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        Self {
+            files: files.into_iter().map(Into::into).collect(),
+            speed: None,
+        }
+    }
+
+    /// Create a replayer over every recording in a directory.
+    ///
+    /// Files with the `.ndjson` extension are replayed in name order, which is
+    /// the order the recorder wrote them in.
+    pub async fn from_dir(dir: impl AsRef<Path>) -> Result<Self> {
+        let mut entries = fs::read_dir(dir).await?;
+        let mut files = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == EXTENSION) {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(Self::new(files))
+    }
+
+    /// Replay messages with the gaps between them as recorded.
+    pub fn real_time(self) -> Self {
+        self.speed(1.0)
+    }
+
+    /// Replay messages `speed` times faster than recorded.
+    ///
+    /// For example `10.0` turns a one second gap into 100ms. Values that are
+    /// not positive replay as fast as possible.
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = (speed > 0.0).then_some(speed);
+        self
+    }
+
+    /// Get the files that will be replayed.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Start replaying.
+    ///
+    /// Lines that cannot be parsed are yielded as errors without ending the
+    /// stream. The stream ends after the last line of the last file.
+    pub fn stream(self) -> BoxStream<'static, Result<Message>> {
+        let state = ReplayState {
+            files: self.files.into(),
+            lines: None,
+            speed: self.speed,
+            last_time: None,
+        };
+        stream::unfold(state, |mut state| async move {
+            let item = state.next().await?;
+            Some((item, state))
+        })
+        .boxed()
+    }
+}
+
+struct ReplayState {
+    files: VecDeque<PathBuf>,
+    lines: Option<(PathBuf, Lines<BufReader<File>>)>,
+    speed: Option<f64>,
+    last_time: Option<DateTime<Utc>>,
+}
+
+impl ReplayState {
+    async fn next(&mut self) -> Option<Result<Message>> {
+        loop {
+            let Some((path, lines)) = self.lines.as_mut() else {
+                let path = self.files.pop_front()?;
+                match File::open(&path).await {
+                    Ok(file) => self.lines = Some((path, BufReader::new(file).lines())),
+                    Err(e) => return Some(Err(e.into())),
+                }
+                continue;
+            };
+
+            let line = match lines.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => {
+                    self.lines = None;
+                    continue;
+                }
+                Err(e) => return Some(Err(e.into())),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let message = serde_json::from_str::<Message>(&line).map_err(|e| {
+                Error::parse(
+                    format!("Failed to parse {}: {}", path.display(), e),
+                    Some(line),
+                )
+            });
+            if let Ok(message) = &message {
+                self.pace(message).await;
+            }
+            return Some(message);
+        }
+    }
+
+    /// Wait out the recorded gap since the previous message.
+    async fn pace(&mut self, message: &Message) {
+        let Some(speed) = self.speed else {
+            return;
+        };
+        let Ok(time) = DateTime::parse_from_rfc3339(&message.timestamp) else {
+            return;
+        };
+        let time = time.with_timezone(&Utc);
+        if let Some(gap) = self
+            .last_time
+            .and_then(|last| (time - last).to_std().ok())
+            .filter(|gap| !gap.is_zero())
+        {
+            tokio::time::sleep(Duration::from_secs_f64(gap.as_secs_f64() / speed)).await;
+        }
+        self.last_time = Some(time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heartbeat_line(sequence_num: u64, timestamp: &str) -> String {
+        serde_json::json!({
+            "channel": "heartbeats",
+            "client_id": "",
+            "timestamp": timestamp,
+            "sequence_num": sequence_num,
+            "events": [{"current_time": timestamp, "heartbeat_counter": sequence_num}]
+        })
+        .to_string()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("coinbase-replay-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_replay_dir_in_order() {
+        let dir = temp_dir("order");
+        std::fs::write(
+            dir.join("rec-0001.ndjson"),
+            format!("{}\nnot json\n", heartbeat_line(3, "2024-01-01T00:00:02Z")),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("rec-0000.ndjson"),
+            format!(
+                "{}\n\n{}\n",
+                heartbeat_line(1, "2024-01-01T00:00:00Z"),
+                heartbeat_line(2, "2024-01-01T00:00:01Z")
+            ),
+        )
+        .unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let items: Vec<_> = Replayer::from_dir(&dir)
+            .await
+            .unwrap()
+            .stream()
+            .collect()
+            .await;
+
+        assert_eq!(items.len(), 4);
+        let sequence: Vec<_> = items[..3]
+            .iter()
+            .map(|m| m.as_ref().unwrap().sequence_num)
+            .collect();
+        assert_eq!(sequence, vec![1, 2, 3]);
+        assert!(items[3].is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_paced() {
+        let dir = temp_dir("paced");
+        let path = dir.join("rec.ndjson");
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n{}\n",
+                heartbeat_line(1, "2024-01-01T00:00:00Z"),
+                heartbeat_line(2, "2024-01-01T00:00:10Z")
+            ),
+        )
+        .unwrap();
+
+        let started = std::time::Instant::now();
+        let items: Vec<_> = Replayer::new([path]).speed(200.0).stream().collect().await;
+
+        assert_eq!(items.len(), 2);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,8 +1,66 @@
 //! Accounts API endpoints.
 
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
 use crate::client::RestClient;
-use crate::error::Result;
-use crate::models::{Account, GetAccountResponse, ListAccountsParams, ListAccountsResponse};
+use crate::constants::paths;
+use crate::error::{Error, Result};
+use crate::models::{
+    Account, Cursor, GetAccountResponse, HoldExplanation, ListAccountsParams, ListAccountsResponse,
+    ListOrdersParams, OrderStatus,
+};
+use crate::runtime::Instant;
+
+/// Default time before [`AccountsApi::resolve`] fetches accounts again.
+pub const DEFAULT_ACCOUNT_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Accounts fetched at a point in time.
+#[derive(Debug)]
+struct AccountCacheState {
+    accounts: Vec<Account>,
+    fetched_at: Instant,
+}
+
+/// Every account of a client, for resolving currencies to account UUIDs.
+///
+/// Shared by clones of the client.
+#[derive(Debug, Default)]
+pub(crate) struct AccountCache {
+    state: Mutex<Option<AccountCacheState>>,
+}
+
+impl AccountCache {
+    /// Look up accounts if they were fetched less than `ttl` ago.
+    fn lookup<T>(&self, ttl: Duration, f: impl FnOnce(&[Account]) -> T) -> Option<T> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .as_ref()
+            .filter(|state| state.fetched_at.elapsed() < ttl)
+            .map(|state| f(&state.accounts))
+    }
+
+    fn replace(&self, accounts: Vec<Account>) {
+        *self.state.lock().unwrap_or_else(|e| e.into_inner()) = Some(AccountCacheState {
+            accounts,
+            fetched_at: Instant::now(),
+        });
+    }
+
+    fn clear(&self) {
+        *self.state.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Pick the account for a currency: the default one if there are several.
+fn account_for<'a>(accounts: &'a [Account], currency: &str) -> Option<&'a Account> {
+    let mut matching = accounts
+        .iter()
+        .filter(|a| a.active && a.currency.eq_ignore_ascii_case(currency));
+    let first = matching.clone().next();
+    matching.find(|a| a.default).or(first)
+}
 
 /// API for managing accounts.
 ///
@@ -43,8 +101,9 @@ impl<'a> AccountsApi<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list(&self, params: ListAccountsParams) -> Result<ListAccountsResponse> {
-        self.client.get_with_query("/accounts", &params).await
+    pub async fn list(&self, mut params: ListAccountsParams) -> Result<ListAccountsResponse> {
+        self.client.apply_portfolio(&mut params.retail_portfolio_id);
+        self.client.get_with_query(paths::ACCOUNTS, &params).await
     }
 
     /// List all accounts with default parameters.
@@ -52,6 +111,18 @@ impl<'a> AccountsApi<'a> {
         self.list(ListAccountsParams::default()).await
     }
 
+    /// Fetch the page of accounts a persisted cursor points to.
+    ///
+    /// `params` must be the parameters the cursor was returned for. See
+    /// [`Cursor`].
+    pub async fn resume(
+        &self,
+        params: ListAccountsParams,
+        cursor: &Cursor<ListAccountsParams>,
+    ) -> Result<ListAccountsResponse> {
+        self.list(cursor.apply(params)?).await
+    }
+
     /// Get a single account by UUID.
     ///
     /// # Arguments
@@ -76,8 +147,173 @@ impl<'a> AccountsApi<'a> {
     /// # }
     /// ```
     pub async fn get(&self, account_uuid: &str) -> Result<Account> {
-        let endpoint = format!("/accounts/{}", account_uuid);
+        let endpoint = paths::account(account_uuid);
         let response: GetAccountResponse = self.client.get(&endpoint).await?;
         Ok(response.account)
     }
+
+    /// Explain what is holding an account's balance.
+    ///
+    /// Fetches the account and all open orders, then attributes the account
+    /// hold to orders by their outstanding hold amount. Any remainder is
+    /// reported as unexplained.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let explanation = client.accounts().explain_hold("account-uuid-here").await?;
+    /// for order in &explanation.orders {
+    ///     println!("{} holds {} {}", order.order_id, order.amount, explanation.currency);
+    /// }
+    /// println!("Unexplained: {} {}", explanation.unexplained, explanation.currency);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn explain_hold(&self, account_uuid: &str) -> Result<HoldExplanation> {
+        let account = self.get(account_uuid).await?;
+
+        let mut open_orders = Vec::new();
+        let mut params = ListOrdersParams::new().status(OrderStatus::Open);
+        params.retail_portfolio_id = account.retail_portfolio_id.clone();
+        loop {
+            let response = self.client.orders().list(params.clone()).await?;
+            open_orders.extend(response.orders);
+            match response.cursor {
+                Some(cursor) if response.has_next => params = params.cursor(cursor),
+                _ => break,
+            }
+        }
+
+        Ok(HoldExplanation::explain(&account, &open_orders))
+    }
+
+    /// Get the account holding a currency (e.g. "USD").
+    ///
+    /// Accounts are fetched once and cached on the client for the
+    /// [account cache TTL](crate::RestClientBuilder::account_cache_ttl). When
+    /// a currency has several accounts, the default one is returned. Call
+    /// [`invalidate_cache`](Self::invalidate_cache) after creating accounts.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let usdc = client.accounts().resolve("USDC").await?;
+    /// println!("USDC account: {}", usdc.uuid);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resolve(&self, currency: &str) -> Result<Account> {
+        let account = self
+            .with_accounts(|accounts| account_for(accounts, currency).cloned())
+            .await?;
+        account.ok_or_else(|| Error::request(format!("no active {} account", currency)))
+    }
+
+    /// Get the currency of an account by UUID, from the same cache as
+    /// [`resolve`](Self::resolve).
+    pub async fn currency_of(&self, account_uuid: &str) -> Result<String> {
+        let currency = self
+            .with_accounts(|accounts| {
+                accounts
+                    .iter()
+                    .find(|a| a.uuid == account_uuid)
+                    .map(|a| a.currency.clone())
+            })
+            .await?;
+        currency.ok_or_else(|| Error::request(format!("no account {}", account_uuid)))
+    }
+
+    /// Fetch every account now, replacing the cache used by
+    /// [`resolve`](Self::resolve).
+    pub async fn refresh_cache(&self) -> Result<()> {
+        let accounts = self.fetch_all().await?;
+        self.client.account_cache().replace(accounts);
+        Ok(())
+    }
+
+    /// Discard the cached accounts so the next lookup fetches them again.
+    pub fn invalidate_cache(&self) {
+        self.client.account_cache().clear();
+    }
+
+    /// Run `f` against cached accounts, fetching them first if needed.
+    async fn with_accounts<T>(&self, f: impl Fn(&[Account]) -> T) -> Result<T> {
+        let cache = self.client.account_cache();
+        if let Some(value) = cache.lookup(self.client.account_cache_ttl(), &f) {
+            return Ok(value);
+        }
+        let accounts = self.fetch_all().await?;
+        let value = f(&accounts);
+        cache.replace(accounts);
+        Ok(value)
+    }
+
+    /// Fetch every page of accounts.
+    async fn fetch_all(&self) -> Result<Vec<Account>> {
+        let mut accounts = Vec::new();
+        let mut params = ListAccountsParams::new();
+        loop {
+            let response = self.list(params.clone()).await?;
+            accounts.extend(response.accounts);
+            match response
+                .cursor
+                .filter(|c| response.has_next && !c.is_empty())
+            {
+                Some(cursor) => params = params.cursor(cursor),
+                None => break,
+            }
+        }
+        tracing::debug!(
+            count = accounts.len(),
+            "fetched accounts for the account cache"
+        );
+        Ok(accounts)
+    }
+}
+
+/// The account calls shared by the live and paper trading clients.
+///
+/// Implemented by [`AccountsApi`] and, with the `paper` feature, by
+/// `PaperAccounts`, so strategy code written against this trait runs
+/// unchanged in either mode.
+///
+/// The live implementation is not available on wasm32, where requests are not
+/// `Send`.
+pub trait AccountsClient {
+    /// List accounts.
+    fn list(
+        &self,
+        params: ListAccountsParams,
+    ) -> impl Future<Output = Result<ListAccountsResponse>> + Send;
+
+    /// List all accounts with default parameters.
+    fn list_all(&self) -> impl Future<Output = Result<ListAccountsResponse>> + Send {
+        self.list(ListAccountsParams::default())
+    }
+
+    /// Get an account by UUID.
+    fn get(&self, account_uuid: &str) -> impl Future<Output = Result<Account>> + Send;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AccountsClient for AccountsApi<'_> {
+    async fn list(&self, params: ListAccountsParams) -> Result<ListAccountsResponse> {
+        AccountsApi::list(self, params).await
+    }
+
+    async fn get(&self, account_uuid: &str) -> Result<Account> {
+        AccountsApi::get(self, account_uuid).await
+    }
 }
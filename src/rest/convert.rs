@@ -1,6 +1,7 @@
 //! Convert API endpoints.
 
 use crate::client::RestClient;
+use crate::constants::paths;
 use crate::error::Result;
 use crate::models::{
     CommitConvertTradeRequest, ConvertTrade, ConvertTradeResponse, CreateConvertQuoteRequest,
@@ -47,7 +48,8 @@ impl<'a> ConvertApi<'a> {
     /// # }
     /// ```
     pub async fn create_quote(&self, request: CreateConvertQuoteRequest) -> Result<ConvertTrade> {
-        let response: ConvertTradeResponse = self.client.post("/convert/quote", &request).await?;
+        let response: ConvertTradeResponse =
+            self.client.post(paths::CONVERT_QUOTE, &request).await?;
         Ok(response.trade)
     }
 
@@ -79,7 +81,7 @@ impl<'a> ConvertApi<'a> {
         trade_id: &str,
         request: CommitConvertTradeRequest,
     ) -> Result<ConvertTrade> {
-        let endpoint = format!("/convert/trade/{}", trade_id);
+        let endpoint = paths::convert_trade(trade_id);
         let response: ConvertTradeResponse = self.client.post(&endpoint, &request).await?;
         Ok(response.trade)
     }
@@ -110,8 +112,45 @@ impl<'a> ConvertApi<'a> {
         trade_id: &str,
         params: GetConvertTradeParams,
     ) -> Result<ConvertTrade> {
-        let endpoint = format!("/convert/trade/{}", trade_id);
+        let endpoint = paths::convert_trade(trade_id);
         let response: ConvertTradeResponse = self.client.get_with_query(&endpoint, &params).await?;
         Ok(response.trade)
     }
+
+    /// Convert between two currencies by name, quoting and committing the
+    /// trade.
+    ///
+    /// The accounts are looked up with
+    /// [`AccountsApi::resolve`](crate::rest::AccountsApi::resolve). Returns
+    /// the committed trade.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let trade = client.convert().convert_by_currency("USD", "USDC", "100.00").await?;
+    /// println!("Trade {}: {:?}", trade.id, trade.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn convert_by_currency(
+        &self,
+        from_currency: &str,
+        to_currency: &str,
+        amount: impl Into<String>,
+    ) -> Result<ConvertTrade> {
+        let accounts = self.client.accounts();
+        let from = accounts.resolve(from_currency).await?;
+        let to = accounts.resolve(to_currency).await?;
+
+        let request = CreateConvertQuoteRequest::new(&from.uuid, &to.uuid, amount);
+        let quote = self.create_quote(request).await?;
+        let request = CommitConvertTradeRequest::new(&from.uuid, &to.uuid);
+        self.commit_trade(&quote.id, request).await
+    }
 }
@@ -1,6 +1,7 @@
 //! Data API endpoints.
 
 use crate::client::RestClient;
+use crate::constants::paths;
 use crate::error::Result;
 use crate::models::ApiKeyPermissions;
 
@@ -38,6 +39,6 @@ impl<'a> DataApi<'a> {
     /// # }
     /// ```
     pub async fn get_key_permissions(&self) -> Result<ApiKeyPermissions> {
-        self.client.get("/key_permissions").await
+        self.client.get(paths::KEY_PERMISSIONS).await
     }
 }
@@ -1,6 +1,7 @@
 //! Fees API endpoints.
 
 use crate::client::RestClient;
+use crate::constants::paths;
 use crate::error::Result;
 use crate::models::{TransactionSummary, TransactionSummaryParams};
 
@@ -65,7 +66,7 @@ impl<'a> FeesApi<'a> {
         params: TransactionSummaryParams,
     ) -> Result<TransactionSummary> {
         self.client
-            .get_with_query("/transaction_summary", &params)
+            .get_with_query(paths::TRANSACTION_SUMMARY, &params)
             .await
     }
 }
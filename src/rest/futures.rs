@@ -1,6 +1,7 @@
 //! Futures/CFM API endpoints.
 
 use crate::client::RestClient;
+use crate::constants::paths;
 use crate::error::Result;
 use crate::models::{
     FuturesBalanceSummary, FuturesPosition, FuturesSweep, GetCurrentMarginWindowParams,
@@ -13,6 +14,18 @@ use crate::models::{
 /// API for futures (CFM) trading.
 ///
 /// This API provides endpoints for managing futures positions, balances, margins, and sweeps.
+///
+/// # Margin state
+///
+/// The Advanced Trade API has no spot margin endpoints; margin
+/// applies to futures here and to perpetuals in
+/// [`PerpetualsApi`](crate::PerpetualsApi). For futures:
+///
+/// - [`get_balance_summary`](Self::get_balance_summary) reports buying power,
+///   initial and available margin, and the liquidation threshold and buffer.
+/// - [`get_intraday_margin_setting`](Self::get_intraday_margin_setting) and
+///   [`get_current_margin_window`](Self::get_current_margin_window) report
+///   whether intraday or overnight margin rates apply.
 pub struct FuturesApi<'a> {
     client: &'a RestClient,
 }
@@ -42,7 +55,8 @@ impl<'a> FuturesApi<'a> {
     /// # }
     /// ```
     pub async fn list_positions(&self) -> Result<Vec<FuturesPosition>> {
-        let response: ListFuturesPositionsResponse = self.client.get("/cfm/positions").await?;
+        let response: ListFuturesPositionsResponse =
+            self.client.get(paths::FUTURES_POSITIONS).await?;
         Ok(response.positions)
     }
 
@@ -63,7 +77,7 @@ impl<'a> FuturesApi<'a> {
     /// # }
     /// ```
     pub async fn get_position(&self, product_id: &str) -> Result<FuturesPosition> {
-        let endpoint = format!("/cfm/positions/{}", product_id);
+        let endpoint = paths::futures_position(product_id);
         let response: GetFuturesPositionResponse = self.client.get(&endpoint).await?;
         Ok(response.position)
     }
@@ -86,7 +100,7 @@ impl<'a> FuturesApi<'a> {
     /// ```
     pub async fn get_balance_summary(&self) -> Result<FuturesBalanceSummary> {
         let response: GetFuturesBalanceSummaryResponse =
-            self.client.get("/cfm/balance_summary").await?;
+            self.client.get(paths::FUTURES_BALANCE_SUMMARY).await?;
         Ok(response.balance_summary)
     }
 
@@ -107,7 +121,7 @@ impl<'a> FuturesApi<'a> {
     /// # }
     /// ```
     pub async fn get_intraday_margin_setting(&self) -> Result<GetIntradayMarginSettingResponse> {
-        self.client.get("/cfm/intraday/margin_setting").await
+        self.client.get(paths::FUTURES_MARGIN_SETTING).await
     }
 
     /// Set the intraday margin setting.
@@ -132,7 +146,7 @@ impl<'a> FuturesApi<'a> {
     ) -> Result<()> {
         let _response: serde_json::Value = self
             .client
-            .post("/cfm/intraday/margin_setting", &request)
+            .post(paths::FUTURES_MARGIN_SETTING, &request)
             .await?;
         Ok(())
     }
@@ -161,7 +175,7 @@ impl<'a> FuturesApi<'a> {
     ) -> Result<MarginWindow> {
         let response: GetCurrentMarginWindowResponse = self
             .client
-            .get_with_query("/cfm/intraday/current_margin_window", &params)
+            .get_with_query(paths::FUTURES_MARGIN_WINDOW, &params)
             .await?;
         Ok(response.margin_window)
     }
@@ -185,7 +199,7 @@ impl<'a> FuturesApi<'a> {
     /// # }
     /// ```
     pub async fn list_sweeps(&self) -> Result<Vec<FuturesSweep>> {
-        let response: ListFuturesSweepsResponse = self.client.get("/cfm/sweeps").await?;
+        let response: ListFuturesSweepsResponse = self.client.get(paths::FUTURES_SWEEPS).await?;
         Ok(response.sweeps)
     }
 
@@ -209,7 +223,9 @@ impl<'a> FuturesApi<'a> {
         &self,
         request: ScheduleFuturesSweepRequest,
     ) -> Result<ScheduleFuturesSweepResponse> {
-        self.client.post("/cfm/sweeps/schedule", &request).await
+        self.client
+            .post(paths::FUTURES_SWEEPS_SCHEDULE, &request)
+            .await
     }
 
     /// Cancel a pending futures sweep.
@@ -228,7 +244,7 @@ impl<'a> FuturesApi<'a> {
     /// # }
     /// ```
     pub async fn cancel_pending_sweep(&self) -> Result<()> {
-        let _response: serde_json::Value = self.client.delete("/cfm/sweeps").await?;
+        let _response: serde_json::Value = self.client.delete(paths::FUTURES_SWEEPS).await?;
         Ok(())
     }
 }
@@ -13,15 +13,18 @@ mod portfolios;
 mod products;
 mod public;
 
-pub use accounts::AccountsApi;
+pub(crate) use accounts::AccountCache;
+pub use accounts::{AccountsApi, AccountsClient, DEFAULT_ACCOUNT_CACHE_TTL};
 pub use convert::ConvertApi;
 pub use data::DataApi;
 pub use fees::FeesApi;
 pub use futures::FuturesApi;
 pub use order_builder::{
-    LimitOrderGtcBuilder, LimitOrderGtdBuilder, MarketOrderBuilder, StopLimitOrderGtcBuilder,
+    FuturesOrderBuilder, LimitOrderFokBuilder, LimitOrderGtcBuilder, LimitOrderGtdBuilder,
+    LimitOrderIocBuilder, MarketOrderBuilder, OrderBuilder, StopLimitOrderGtcBuilder,
+    StopLimitOrderGtdBuilder,
 };
-pub use orders::OrdersApi;
+pub use orders::{FillSummary, OrdersApi, OrdersClient, PollPolicy};
 pub use payment_methods::PaymentMethodsApi;
 pub use perpetuals::PerpetualsApi;
 pub use portfolios::PortfoliosApi;
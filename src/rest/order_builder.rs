@@ -3,11 +3,17 @@
 //! These builders provide a more convenient way to construct orders
 //! compared to manually creating `CreateOrderRequest` objects.
 
+use chrono::{DateTime, SecondsFormat, Utc};
+use rust_decimal::Decimal;
+
 use crate::client::RestClient;
-use crate::error::{Error, Result};
+use crate::error::{Error, Result, ValidationError, Violation};
+use crate::latency::OrderLatencyReport;
 use crate::models::{
-    CreateOrderRequest, CreateOrderResponse, OrderConfiguration, OrderSide, StopDirection,
+    CreateOrderRequest, CreateOrderResponse, FutureProductDetails, OrderConfiguration, OrderSide,
+    PreviewOrderResponse, Product, StopDirection, Tif, impl_order_options, parse_plain_decimal,
 };
+use crate::runtime::Instant;
 
 /// Builder for market orders.
 pub struct MarketOrderBuilder<'a> {
@@ -17,6 +23,7 @@ pub struct MarketOrderBuilder<'a> {
     quote_size: Option<String>,
     base_size: Option<String>,
     client_order_id: Option<String>,
+    flags: OrderFlags,
 }
 
 impl<'a> MarketOrderBuilder<'a> {
@@ -29,6 +36,7 @@ impl<'a> MarketOrderBuilder<'a> {
             quote_size: None,
             base_size: None,
             client_order_id: None,
+            flags: OrderFlags::default(),
         }
     }
 
@@ -72,29 +80,78 @@ impl<'a> MarketOrderBuilder<'a> {
 
     /// Build and send the order.
     pub async fn send(self) -> Result<CreateOrderResponse> {
-        let product_id = self
-            .product_id
-            .ok_or_else(|| Error::request("product_id is required"))?;
-        let side = self
-            .side
-            .ok_or_else(|| Error::request("side is required (use .buy() or .sell())"))?;
-
-        let config = if let Some(quote_size) = self.quote_size {
-            OrderConfiguration::market_buy_quote(quote_size)
-        } else if let Some(base_size) = self.base_size {
-            if side == OrderSide::Buy {
-                OrderConfiguration::market_buy_base(base_size)
-            } else {
-                OrderConfiguration::market_sell(base_size)
+        let (client, request) = self.into_request()?;
+        client.orders().create(request).await
+    }
+
+    /// Build and send the order, reporting where the time was spent.
+    ///
+    /// See [`OrderLatencyReport`] for the stages that are measured.
+    pub async fn send_with_latency(self) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        let started = Instant::now();
+        let (client, request) = self.into_request()?;
+        client.orders().create_timed(request, started).await
+    }
+
+    /// Preview the order without sending it.
+    ///
+    /// Returns the expected fees, total and slippage for the order.
+    pub async fn preview(self) -> Result<PreviewOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().preview(request).await
+    }
+
+    /// Build the order request without sending it.
+    pub fn build(self) -> Result<CreateOrderRequest> {
+        self.into_request().map(|(_, request)| request)
+    }
+
+    /// Validate the builder and produce the order request.
+    fn into_request(self) -> Result<(&'a RestClient, CreateOrderRequest)> {
+        let mut v = Validator::default();
+        let product_id = v.required("product_id", self.product_id);
+        let side = v.required_with("side", self.side, "is required (use .buy() or .sell())");
+
+        let size = match (self.quote_size, self.base_size) {
+            (Some(_), Some(_)) => {
+                v.push(
+                    "quote_size",
+                    "and base_size are mutually exclusive (set only one)",
+                );
+                None
             }
+            (Some(quote_size), None) => {
+                v.positive("quote_size", &quote_size);
+                Some((quote_size, true))
+            }
+            (None, Some(base_size)) => {
+                v.positive("base_size", &base_size);
+                Some((base_size, false))
+            }
+            (None, None) => {
+                v.push("quote_size", "or base_size is required");
+                None
+            }
+        };
+        self.flags.validate(&mut v);
+        v.finish()?;
+
+        let (Some(product_id), Some(side), Some((size, is_quote))) = (product_id, side, size)
+        else {
+            return Err(incomplete_order());
+        };
+        let config = if is_quote {
+            OrderConfiguration::market_buy_quote(size)
+        } else if side == OrderSide::Buy {
+            OrderConfiguration::market_buy_base(size)
         } else {
-            return Err(Error::request("either quote_size or base_size is required"));
+            OrderConfiguration::market_sell(size)
         };
 
         let client_order_id = self.client_order_id.unwrap_or_else(uuid_v4);
 
         let request = CreateOrderRequest::new(client_order_id, product_id, side, config);
-        self.client.orders().create(request).await
+        Ok((self.client, self.flags.apply(request)))
     }
 }
 
@@ -107,6 +164,7 @@ pub struct LimitOrderGtcBuilder<'a> {
     limit_price: Option<String>,
     post_only: bool,
     client_order_id: Option<String>,
+    flags: OrderFlags,
 }
 
 impl<'a> LimitOrderGtcBuilder<'a> {
@@ -120,6 +178,7 @@ impl<'a> LimitOrderGtcBuilder<'a> {
             limit_price: None,
             post_only: false,
             client_order_id: None,
+            flags: OrderFlags::default(),
         }
     }
 
@@ -161,26 +220,93 @@ impl<'a> LimitOrderGtcBuilder<'a> {
         self
     }
 
+    /// Convert into a GTD order builder that expires at `end_time`.
+    ///
+    /// All fields that have already been set are preserved.
+    pub fn into_gtd(self, end_time: impl Into<String>) -> LimitOrderGtdBuilder<'a> {
+        LimitOrderGtdBuilder {
+            client: self.client,
+            product_id: self.product_id,
+            side: self.side,
+            base_size: self.base_size,
+            limit_price: self.limit_price,
+            end_time: Some(end_time.into()),
+            post_only: self.post_only,
+            client_order_id: self.client_order_id,
+            flags: self.flags,
+        }
+    }
+
+    /// Convert into a stop-limit order builder triggered at `stop_price`.
+    ///
+    /// All other fields that have already been set are preserved. Stop-limit
+    /// orders do not support post-only, so it is cleared.
+    pub fn into_stop_limit(
+        self,
+        stop_price: impl Into<String>,
+        stop_direction: StopDirection,
+    ) -> StopLimitOrderGtcBuilder<'a> {
+        StopLimitOrderGtcBuilder {
+            client: self.client,
+            product_id: self.product_id,
+            side: self.side,
+            base_size: self.base_size,
+            limit_price: self.limit_price,
+            stop_price: Some(stop_price.into()),
+            stop_direction: Some(stop_direction),
+            client_order_id: self.client_order_id,
+            flags: self.flags,
+        }
+    }
+
     /// Build and send the order.
     pub async fn send(self) -> Result<CreateOrderResponse> {
-        let product_id = self
-            .product_id
-            .ok_or_else(|| Error::request("product_id is required"))?;
-        let side = self
-            .side
-            .ok_or_else(|| Error::request("side is required (use .buy() or .sell())"))?;
-        let base_size = self
-            .base_size
-            .ok_or_else(|| Error::request("base_size is required"))?;
-        let limit_price = self
-            .limit_price
-            .ok_or_else(|| Error::request("limit_price is required"))?;
+        let (client, request) = self.into_request()?;
+        client.orders().create(request).await
+    }
+
+    /// Build and send the order, reporting where the time was spent.
+    ///
+    /// See [`OrderLatencyReport`] for the stages that are measured.
+    pub async fn send_with_latency(self) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        let started = Instant::now();
+        let (client, request) = self.into_request()?;
+        client.orders().create_timed(request, started).await
+    }
+
+    /// Preview the order without sending it.
+    ///
+    /// Returns the expected fees, total and slippage for the order.
+    pub async fn preview(self) -> Result<PreviewOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().preview(request).await
+    }
+
+    /// Build the order request without sending it.
+    pub fn build(self) -> Result<CreateOrderRequest> {
+        self.into_request().map(|(_, request)| request)
+    }
+
+    /// Validate the builder and produce the order request.
+    fn into_request(self) -> Result<(&'a RestClient, CreateOrderRequest)> {
+        let mut v = Validator::default();
+        let product_id = v.required("product_id", self.product_id);
+        let side = v.required_with("side", self.side, "is required (use .buy() or .sell())");
+        let base_size = v.required_positive("base_size", self.base_size);
+        let limit_price = v.required_positive("limit_price", self.limit_price);
+        self.flags.validate(&mut v);
+        v.finish()?;
+        let (Some(product_id), Some(side), Some(base_size), Some(limit_price)) =
+            (product_id, side, base_size, limit_price)
+        else {
+            return Err(incomplete_order());
+        };
 
         let config = OrderConfiguration::limit_gtc(base_size, limit_price, self.post_only);
         let client_order_id = self.client_order_id.unwrap_or_else(uuid_v4);
 
         let request = CreateOrderRequest::new(client_order_id, product_id, side, config);
-        self.client.orders().create(request).await
+        Ok((self.client, self.flags.apply(request)))
     }
 }
 
@@ -194,6 +320,7 @@ pub struct LimitOrderGtdBuilder<'a> {
     end_time: Option<String>,
     post_only: bool,
     client_order_id: Option<String>,
+    flags: OrderFlags,
 }
 
 impl<'a> LimitOrderGtdBuilder<'a> {
@@ -208,6 +335,7 @@ impl<'a> LimitOrderGtdBuilder<'a> {
             end_time: None,
             post_only: false,
             client_order_id: None,
+            flags: OrderFlags::default(),
         }
     }
 
@@ -255,30 +383,75 @@ impl<'a> LimitOrderGtdBuilder<'a> {
         self
     }
 
+    /// Convert into a GTC order builder, discarding the end time.
+    ///
+    /// All other fields that have already been set are preserved.
+    pub fn into_gtc(self) -> LimitOrderGtcBuilder<'a> {
+        LimitOrderGtcBuilder {
+            client: self.client,
+            product_id: self.product_id,
+            side: self.side,
+            base_size: self.base_size,
+            limit_price: self.limit_price,
+            post_only: self.post_only,
+            client_order_id: self.client_order_id,
+            flags: self.flags,
+        }
+    }
+
     /// Build and send the order.
     pub async fn send(self) -> Result<CreateOrderResponse> {
-        let product_id = self
-            .product_id
-            .ok_or_else(|| Error::request("product_id is required"))?;
-        let side = self
-            .side
-            .ok_or_else(|| Error::request("side is required (use .buy() or .sell())"))?;
-        let base_size = self
-            .base_size
-            .ok_or_else(|| Error::request("base_size is required"))?;
-        let limit_price = self
-            .limit_price
-            .ok_or_else(|| Error::request("limit_price is required"))?;
-        let end_time = self
-            .end_time
-            .ok_or_else(|| Error::request("end_time is required"))?;
+        let (client, request) = self.into_request()?;
+        client.orders().create(request).await
+    }
+
+    /// Build and send the order, reporting where the time was spent.
+    ///
+    /// See [`OrderLatencyReport`] for the stages that are measured.
+    pub async fn send_with_latency(self) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        let started = Instant::now();
+        let (client, request) = self.into_request()?;
+        client.orders().create_timed(request, started).await
+    }
+
+    /// Preview the order without sending it.
+    ///
+    /// Returns the expected fees, total and slippage for the order.
+    pub async fn preview(self) -> Result<PreviewOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().preview(request).await
+    }
+
+    /// Build the order request without sending it.
+    pub fn build(self) -> Result<CreateOrderRequest> {
+        self.into_request().map(|(_, request)| request)
+    }
+
+    /// Validate the builder and produce the order request.
+    fn into_request(self) -> Result<(&'a RestClient, CreateOrderRequest)> {
+        let mut v = Validator::default();
+        let product_id = v.required("product_id", self.product_id);
+        let side = v.required_with("side", self.side, "is required (use .buy() or .sell())");
+        let base_size = v.required_positive("base_size", self.base_size);
+        let limit_price = v.required_positive("limit_price", self.limit_price);
+        let end_time = v.required("end_time", self.end_time);
+        if let Some(end_time) = &end_time {
+            v.future_time("end_time", end_time);
+        }
+        self.flags.validate(&mut v);
+        v.finish()?;
+        let (Some(product_id), Some(side), Some(base_size), Some(limit_price), Some(end_time)) =
+            (product_id, side, base_size, limit_price, end_time)
+        else {
+            return Err(incomplete_order());
+        };
 
         let config =
             OrderConfiguration::limit_gtd(base_size, limit_price, end_time, self.post_only);
         let client_order_id = self.client_order_id.unwrap_or_else(uuid_v4);
 
         let request = CreateOrderRequest::new(client_order_id, product_id, side, config);
-        self.client.orders().create(request).await
+        Ok((self.client, self.flags.apply(request)))
     }
 }
 
@@ -292,6 +465,7 @@ pub struct StopLimitOrderGtcBuilder<'a> {
     stop_price: Option<String>,
     stop_direction: Option<StopDirection>,
     client_order_id: Option<String>,
+    flags: OrderFlags,
 }
 
 impl<'a> StopLimitOrderGtcBuilder<'a> {
@@ -306,6 +480,7 @@ impl<'a> StopLimitOrderGtcBuilder<'a> {
             stop_price: None,
             stop_direction: None,
             client_order_id: None,
+            flags: OrderFlags::default(),
         }
     }
 
@@ -353,155 +528,1412 @@ impl<'a> StopLimitOrderGtcBuilder<'a> {
         self
     }
 
+    /// Convert into a stop-limit GTD order builder that expires at `end_time`.
+    ///
+    /// All fields that have already been set are preserved.
+    pub fn into_gtd(self, end_time: impl Into<String>) -> StopLimitOrderGtdBuilder<'a> {
+        StopLimitOrderGtdBuilder {
+            client: self.client,
+            product_id: self.product_id,
+            side: self.side,
+            base_size: self.base_size,
+            limit_price: self.limit_price,
+            stop_price: self.stop_price,
+            stop_direction: self.stop_direction,
+            end_time: Some(end_time.into()),
+            client_order_id: self.client_order_id,
+            flags: self.flags,
+        }
+    }
+
+    /// Convert into a plain limit GTC order builder, discarding the stop.
+    ///
+    /// All other fields that have already been set are preserved.
+    pub fn into_limit_gtc(self) -> LimitOrderGtcBuilder<'a> {
+        LimitOrderGtcBuilder {
+            client: self.client,
+            product_id: self.product_id,
+            side: self.side,
+            base_size: self.base_size,
+            limit_price: self.limit_price,
+            post_only: false,
+            client_order_id: self.client_order_id,
+            flags: self.flags,
+        }
+    }
+
     /// Build and send the order.
     pub async fn send(self) -> Result<CreateOrderResponse> {
-        let product_id = self
-            .product_id
-            .ok_or_else(|| Error::request("product_id is required"))?;
-        let side = self
-            .side
-            .ok_or_else(|| Error::request("side is required (use .buy() or .sell())"))?;
-        let base_size = self
-            .base_size
-            .ok_or_else(|| Error::request("base_size is required"))?;
-        let limit_price = self
-            .limit_price
-            .ok_or_else(|| Error::request("limit_price is required"))?;
-        let stop_price = self
-            .stop_price
-            .ok_or_else(|| Error::request("stop_price is required"))?;
-        let stop_direction = self
-            .stop_direction
-            .ok_or_else(|| Error::request("stop_direction is required"))?;
+        let (client, request) = self.into_request()?;
+        client.orders().create(request).await
+    }
+
+    /// Build and send the order, reporting where the time was spent.
+    ///
+    /// See [`OrderLatencyReport`] for the stages that are measured.
+    pub async fn send_with_latency(self) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        let started = Instant::now();
+        let (client, request) = self.into_request()?;
+        client.orders().create_timed(request, started).await
+    }
+
+    /// Preview the order without sending it.
+    ///
+    /// Returns the expected fees, total and slippage for the order.
+    pub async fn preview(self) -> Result<PreviewOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().preview(request).await
+    }
+
+    /// Build the order request without sending it.
+    pub fn build(self) -> Result<CreateOrderRequest> {
+        self.into_request().map(|(_, request)| request)
+    }
+
+    /// Validate the builder and produce the order request.
+    fn into_request(self) -> Result<(&'a RestClient, CreateOrderRequest)> {
+        let mut v = Validator::default();
+        let product_id = v.required("product_id", self.product_id);
+        let side = v.required_with("side", self.side, "is required (use .buy() or .sell())");
+        let base_size = v.required_positive("base_size", self.base_size);
+        let limit_price = v.required_positive("limit_price", self.limit_price);
+        let stop_price = v.required_positive("stop_price", self.stop_price);
+        let stop_direction = v.required("stop_direction", self.stop_direction);
+        if let (Some(limit), Some(stop), Some(direction)) =
+            (&limit_price, &stop_price, stop_direction)
+        {
+            v.stop_side(limit, stop, direction);
+        }
+        self.flags.validate(&mut v);
+        v.finish()?;
+        let (
+            Some(product_id),
+            Some(side),
+            Some(base_size),
+            Some(limit_price),
+            Some(stop_price),
+            Some(stop_direction),
+        ) = (
+            product_id,
+            side,
+            base_size,
+            limit_price,
+            stop_price,
+            stop_direction,
+        )
+        else {
+            return Err(incomplete_order());
+        };
 
         let config =
             OrderConfiguration::stop_limit_gtc(base_size, limit_price, stop_price, stop_direction);
         let client_order_id = self.client_order_id.unwrap_or_else(uuid_v4);
 
         let request = CreateOrderRequest::new(client_order_id, product_id, side, config);
-        self.client.orders().create(request).await
+        Ok((self.client, self.flags.apply(request)))
     }
 }
 
-/// Generate a simple UUID v4 string.
-fn uuid_v4() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
+/// Builder for stop-limit GTD orders.
+pub struct StopLimitOrderGtdBuilder<'a> {
+    client: &'a RestClient,
+    product_id: Option<String>,
+    side: Option<OrderSide>,
+    base_size: Option<String>,
+    limit_price: Option<String>,
+    stop_price: Option<String>,
+    stop_direction: Option<StopDirection>,
+    end_time: Option<String>,
+    client_order_id: Option<String>,
+    flags: OrderFlags,
+}
 
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
+impl<'a> StopLimitOrderGtdBuilder<'a> {
+    /// Create a new stop-limit order GTD builder.
+    pub(crate) fn new(client: &'a RestClient) -> Self {
+        Self {
+            client,
+            product_id: None,
+            side: None,
+            base_size: None,
+            limit_price: None,
+            stop_price: None,
+            stop_direction: None,
+            end_time: None,
+            client_order_id: None,
+            flags: OrderFlags::default(),
+        }
+    }
 
-    // Simple UUID-like format using timestamp and random bits.
-    format!(
-        "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
-        (now >> 96) as u32,
-        (now >> 80) as u16,
-        (now >> 68) as u16 & 0x0fff,
-        ((now >> 52) as u16 & 0x3fff) | 0x8000,
-        now as u64 & 0xffffffffffff
-    )
-}
+    /// Set as a buy order.
+    pub fn buy(mut self, product_id: impl Into<String>) -> Self {
+        self.product_id = Some(product_id.into());
+        self.side = Some(OrderSide::Buy);
+        self
+    }
 
-// Add builder methods to RestClient.
-impl RestClient {
-    /// Create a market order builder.
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// # use coinbase_advanced::{RestClient, Credentials};
-    /// # async fn example() -> coinbase_advanced::Result<()> {
-    /// let client = RestClient::builder()
-    ///     .credentials(Credentials::from_env()?)
-    ///     .build()?;
-    ///
-    /// // Buy $100 of BTC
-    /// let response = client.market_order()
-    ///     .buy("BTC-USD")
-    ///     .quote_size("100.00")
-    ///     .send()
-    ///     .await?;
-    ///
-    /// // Sell 0.001 BTC
-    /// let response = client.market_order()
-    ///     .sell("BTC-USD")
-    ///     .base_size("0.001")
-    ///     .send()
-    ///     .await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn market_order(&self) -> MarketOrderBuilder<'_> {
-        MarketOrderBuilder::new(self)
+    /// Set as a sell order.
+    pub fn sell(mut self, product_id: impl Into<String>) -> Self {
+        self.product_id = Some(product_id.into());
+        self.side = Some(OrderSide::Sell);
+        self
     }
 
-    /// Create a limit order (GTC) builder.
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// # use coinbase_advanced::{RestClient, Credentials};
-    /// # async fn example() -> coinbase_advanced::Result<()> {
-    /// let client = RestClient::builder()
-    ///     .credentials(Credentials::from_env()?)
-    ///     .build()?;
-    ///
-    /// let response = client.limit_order_gtc()
-    ///     .buy("BTC-USD")
-    ///     .base_size("0.001")
-    ///     .limit_price("50000.00")
-    ///     .post_only(true)
-    ///     .send()
-    ///     .await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn limit_order_gtc(&self) -> LimitOrderGtcBuilder<'_> {
-        LimitOrderGtcBuilder::new(self)
+    /// Set the base size.
+    pub fn base_size(mut self, base_size: impl Into<String>) -> Self {
+        self.base_size = Some(base_size.into());
+        self
     }
 
-    /// Create a limit order (GTD) builder.
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// # use coinbase_advanced::{RestClient, Credentials};
-    /// # async fn example() -> coinbase_advanced::Result<()> {
-    /// let client = RestClient::builder()
-    ///     .credentials(Credentials::from_env()?)
-    ///     .build()?;
-    ///
-    /// let response = client.limit_order_gtd()
-    ///     .buy("BTC-USD")
-    ///     .base_size("0.001")
-    ///     .limit_price("50000.00")
-    ///     .end_time("2024-12-31T23:59:59Z")
-    ///     .send()
-    ///     .await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn limit_order_gtd(&self) -> LimitOrderGtdBuilder<'_> {
-        LimitOrderGtdBuilder::new(self)
+    /// Set the limit price.
+    pub fn limit_price(mut self, limit_price: impl Into<String>) -> Self {
+        self.limit_price = Some(limit_price.into());
+        self
     }
 
-    /// Create a stop-limit order (GTC) builder.
+    /// Set the stop price.
+    pub fn stop_price(mut self, stop_price: impl Into<String>) -> Self {
+        self.stop_price = Some(stop_price.into());
+        self
+    }
+
+    /// Set the stop direction.
+    pub fn stop_direction(mut self, stop_direction: StopDirection) -> Self {
+        self.stop_direction = Some(stop_direction);
+        self
+    }
+
+    /// Set the end time (ISO 8601 format).
+    pub fn end_time(mut self, end_time: impl Into<String>) -> Self {
+        self.end_time = Some(end_time.into());
+        self
+    }
+
+    /// Set a custom client order ID.
+    pub fn client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    /// Convert into a stop-limit GTC order builder, discarding the end time.
     ///
-    /// # Example
+    /// All other fields that have already been set are preserved.
+    pub fn into_gtc(self) -> StopLimitOrderGtcBuilder<'a> {
+        StopLimitOrderGtcBuilder {
+            client: self.client,
+            product_id: self.product_id,
+            side: self.side,
+            base_size: self.base_size,
+            limit_price: self.limit_price,
+            stop_price: self.stop_price,
+            stop_direction: self.stop_direction,
+            client_order_id: self.client_order_id,
+            flags: self.flags,
+        }
+    }
+
+    /// Build and send the order.
+    pub async fn send(self) -> Result<CreateOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().create(request).await
+    }
+
+    /// Build and send the order, reporting where the time was spent.
     ///
-    /// ```no_run
-    /// # use coinbase_advanced::{RestClient, Credentials, models::StopDirection};
-    /// # async fn example() -> coinbase_advanced::Result<()> {
-    /// let client = RestClient::builder()
-    ///     .credentials(Credentials::from_env()?)
-    ///     .build()?;
+    /// See [`OrderLatencyReport`] for the stages that are measured.
+    pub async fn send_with_latency(self) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        let started = Instant::now();
+        let (client, request) = self.into_request()?;
+        client.orders().create_timed(request, started).await
+    }
+
+    /// Preview the order without sending it.
     ///
-    /// let response = client.stop_limit_order_gtc()
-    ///     .sell("BTC-USD")
-    ///     .base_size("0.001")
-    ///     .limit_price("49000.00")
-    ///     .stop_price("50000.00")
+    /// Returns the expected fees, total and slippage for the order.
+    pub async fn preview(self) -> Result<PreviewOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().preview(request).await
+    }
+
+    /// Build the order request without sending it.
+    pub fn build(self) -> Result<CreateOrderRequest> {
+        self.into_request().map(|(_, request)| request)
+    }
+
+    /// Validate the builder and produce the order request.
+    fn into_request(self) -> Result<(&'a RestClient, CreateOrderRequest)> {
+        let mut v = Validator::default();
+        let product_id = v.required("product_id", self.product_id);
+        let side = v.required_with("side", self.side, "is required (use .buy() or .sell())");
+        let base_size = v.required_positive("base_size", self.base_size);
+        let limit_price = v.required_positive("limit_price", self.limit_price);
+        let stop_price = v.required_positive("stop_price", self.stop_price);
+        let stop_direction = v.required("stop_direction", self.stop_direction);
+        if let (Some(limit), Some(stop), Some(direction)) =
+            (&limit_price, &stop_price, stop_direction)
+        {
+            v.stop_side(limit, stop, direction);
+        }
+        let end_time = v.required("end_time", self.end_time);
+        if let Some(end_time) = &end_time {
+            v.future_time("end_time", end_time);
+        }
+        self.flags.validate(&mut v);
+        v.finish()?;
+        let (
+            Some(product_id),
+            Some(side),
+            Some(base_size),
+            Some(limit_price),
+            Some(stop_price),
+            Some(end_time),
+            Some(stop_direction),
+        ) = (
+            product_id,
+            side,
+            base_size,
+            limit_price,
+            stop_price,
+            end_time,
+            stop_direction,
+        )
+        else {
+            return Err(incomplete_order());
+        };
+
+        let config = OrderConfiguration::stop_limit_gtd(
+            base_size,
+            limit_price,
+            stop_price,
+            end_time,
+            stop_direction,
+        );
+        let client_order_id = self.client_order_id.unwrap_or_else(uuid_v4);
+
+        let request = CreateOrderRequest::new(client_order_id, product_id, side, config);
+        Ok((self.client, self.flags.apply(request)))
+    }
+}
+
+/// Builder for limit FOK (fill-or-kill) orders.
+pub struct LimitOrderFokBuilder<'a> {
+    client: &'a RestClient,
+    product_id: Option<String>,
+    side: Option<OrderSide>,
+    base_size: Option<String>,
+    limit_price: Option<String>,
+    client_order_id: Option<String>,
+    flags: OrderFlags,
+}
+
+impl<'a> LimitOrderFokBuilder<'a> {
+    /// Create a new limit order FOK builder.
+    pub(crate) fn new(client: &'a RestClient) -> Self {
+        Self {
+            client,
+            product_id: None,
+            side: None,
+            base_size: None,
+            limit_price: None,
+            client_order_id: None,
+            flags: OrderFlags::default(),
+        }
+    }
+
+    /// Set as a buy order.
+    pub fn buy(mut self, product_id: impl Into<String>) -> Self {
+        self.product_id = Some(product_id.into());
+        self.side = Some(OrderSide::Buy);
+        self
+    }
+
+    /// Set as a sell order.
+    pub fn sell(mut self, product_id: impl Into<String>) -> Self {
+        self.product_id = Some(product_id.into());
+        self.side = Some(OrderSide::Sell);
+        self
+    }
+
+    /// Set the base size.
+    pub fn base_size(mut self, base_size: impl Into<String>) -> Self {
+        self.base_size = Some(base_size.into());
+        self
+    }
+
+    /// Set the limit price.
+    pub fn limit_price(mut self, limit_price: impl Into<String>) -> Self {
+        self.limit_price = Some(limit_price.into());
+        self
+    }
+
+    /// Set a custom client order ID.
+    pub fn client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    /// Build and send the order.
+    pub async fn send(self) -> Result<CreateOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().create(request).await
+    }
+
+    /// Build and send the order, reporting where the time was spent.
+    ///
+    /// See [`OrderLatencyReport`] for the stages that are measured.
+    pub async fn send_with_latency(self) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        let started = Instant::now();
+        let (client, request) = self.into_request()?;
+        client.orders().create_timed(request, started).await
+    }
+
+    /// Preview the order without sending it.
+    ///
+    /// Returns the expected fees, total and slippage for the order.
+    pub async fn preview(self) -> Result<PreviewOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().preview(request).await
+    }
+
+    /// Build the order request without sending it.
+    pub fn build(self) -> Result<CreateOrderRequest> {
+        self.into_request().map(|(_, request)| request)
+    }
+
+    /// Validate the builder and produce the order request.
+    fn into_request(self) -> Result<(&'a RestClient, CreateOrderRequest)> {
+        let mut v = Validator::default();
+        let product_id = v.required("product_id", self.product_id);
+        let side = v.required_with("side", self.side, "is required (use .buy() or .sell())");
+        let base_size = v.required_positive("base_size", self.base_size);
+        let limit_price = v.required_positive("limit_price", self.limit_price);
+        self.flags.validate(&mut v);
+        v.finish()?;
+        let (Some(product_id), Some(side), Some(base_size), Some(limit_price)) =
+            (product_id, side, base_size, limit_price)
+        else {
+            return Err(incomplete_order());
+        };
+
+        let config = OrderConfiguration::limit_fok(base_size, limit_price);
+        let client_order_id = self.client_order_id.unwrap_or_else(uuid_v4);
+
+        let request = CreateOrderRequest::new(client_order_id, product_id, side, config);
+        Ok((self.client, self.flags.apply(request)))
+    }
+}
+
+/// Builder for limit IOC (immediate-or-cancel) orders.
+pub struct LimitOrderIocBuilder<'a> {
+    client: &'a RestClient,
+    product_id: Option<String>,
+    side: Option<OrderSide>,
+    base_size: Option<String>,
+    limit_price: Option<String>,
+    client_order_id: Option<String>,
+    flags: OrderFlags,
+}
+
+impl<'a> LimitOrderIocBuilder<'a> {
+    /// Create a new limit order IOC builder.
+    pub(crate) fn new(client: &'a RestClient) -> Self {
+        Self {
+            client,
+            product_id: None,
+            side: None,
+            base_size: None,
+            limit_price: None,
+            client_order_id: None,
+            flags: OrderFlags::default(),
+        }
+    }
+
+    /// Set as a buy order.
+    pub fn buy(mut self, product_id: impl Into<String>) -> Self {
+        self.product_id = Some(product_id.into());
+        self.side = Some(OrderSide::Buy);
+        self
+    }
+
+    /// Set as a sell order.
+    pub fn sell(mut self, product_id: impl Into<String>) -> Self {
+        self.product_id = Some(product_id.into());
+        self.side = Some(OrderSide::Sell);
+        self
+    }
+
+    /// Set the base size.
+    pub fn base_size(mut self, base_size: impl Into<String>) -> Self {
+        self.base_size = Some(base_size.into());
+        self
+    }
+
+    /// Set the limit price.
+    pub fn limit_price(mut self, limit_price: impl Into<String>) -> Self {
+        self.limit_price = Some(limit_price.into());
+        self
+    }
+
+    /// Set a custom client order ID.
+    pub fn client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    /// Build and send the order.
+    pub async fn send(self) -> Result<CreateOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().create(request).await
+    }
+
+    /// Build and send the order, reporting where the time was spent.
+    ///
+    /// See [`OrderLatencyReport`] for the stages that are measured.
+    pub async fn send_with_latency(self) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        let started = Instant::now();
+        let (client, request) = self.into_request()?;
+        client.orders().create_timed(request, started).await
+    }
+
+    /// Preview the order without sending it.
+    ///
+    /// Returns the expected fees, total and slippage for the order.
+    pub async fn preview(self) -> Result<PreviewOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().preview(request).await
+    }
+
+    /// Build the order request without sending it.
+    pub fn build(self) -> Result<CreateOrderRequest> {
+        self.into_request().map(|(_, request)| request)
+    }
+
+    /// Validate the builder and produce the order request.
+    fn into_request(self) -> Result<(&'a RestClient, CreateOrderRequest)> {
+        let mut v = Validator::default();
+        let product_id = v.required("product_id", self.product_id);
+        let side = v.required_with("side", self.side, "is required (use .buy() or .sell())");
+        let base_size = v.required_positive("base_size", self.base_size);
+        let limit_price = v.required_positive("limit_price", self.limit_price);
+        self.flags.validate(&mut v);
+        v.finish()?;
+        let (Some(product_id), Some(side), Some(base_size), Some(limit_price)) =
+            (product_id, side, base_size, limit_price)
+        else {
+            return Err(incomplete_order());
+        };
+
+        let config = OrderConfiguration::limit_ioc(base_size, limit_price);
+        let client_order_id = self.client_order_id.unwrap_or_else(uuid_v4);
+
+        let request = CreateOrderRequest::new(client_order_id, product_id, side, config);
+        Ok((self.client, self.flags.apply(request)))
+    }
+}
+
+/// Price type of an order built with [`OrderBuilder`].
+#[derive(Debug, Clone)]
+enum OrderKind {
+    Market,
+    Limit {
+        limit_price: String,
+    },
+    StopLimit {
+        stop_price: String,
+        limit_price: String,
+    },
+}
+
+/// Builder for any order type, chosen with combinators.
+///
+/// The price type is set with [`market`](Self::market),
+/// [`limit`](Self::limit) or [`stop_limit`](Self::stop_limit), and the time
+/// in force with [`tif`](Self::tif), defaulting to IOC for market orders
+/// and GTC otherwise. Combinations the API does not offer, such as a FOK
+/// stop-limit order, fail validation.
+pub struct OrderBuilder<'a> {
+    client: &'a RestClient,
+    product_id: Option<String>,
+    side: Option<OrderSide>,
+    kind: Option<OrderKind>,
+    tif: Option<Tif>,
+    base_size: Option<String>,
+    quote_size: Option<String>,
+    post_only: bool,
+    stop_direction: Option<StopDirection>,
+    client_order_id: Option<String>,
+    flags: OrderFlags,
+}
+
+impl<'a> OrderBuilder<'a> {
+    /// Create a new order builder.
+    pub(crate) fn new(client: &'a RestClient) -> Self {
+        Self {
+            client,
+            product_id: None,
+            side: None,
+            kind: None,
+            tif: None,
+            base_size: None,
+            quote_size: None,
+            post_only: false,
+            stop_direction: None,
+            client_order_id: None,
+            flags: OrderFlags::default(),
+        }
+    }
+
+    /// Set as a buy order.
+    pub fn buy(mut self, product_id: impl Into<String>) -> Self {
+        self.product_id = Some(product_id.into());
+        self.side = Some(OrderSide::Buy);
+        self
+    }
+
+    /// Set as a sell order.
+    pub fn sell(mut self, product_id: impl Into<String>) -> Self {
+        self.product_id = Some(product_id.into());
+        self.side = Some(OrderSide::Sell);
+        self
+    }
+
+    /// Make this a market order.
+    pub fn market(mut self) -> Self {
+        self.kind = Some(OrderKind::Market);
+        self
+    }
+
+    /// Make this a limit order at `limit_price`.
+    pub fn limit(mut self, limit_price: impl Into<String>) -> Self {
+        self.kind = Some(OrderKind::Limit {
+            limit_price: limit_price.into(),
+        });
+        self
+    }
+
+    /// Make this a stop-limit order triggered at `stop_price`.
+    ///
+    /// The stop direction defaults to down for sells and up for buys; set
+    /// it with [`stop_direction`](Self::stop_direction).
+    pub fn stop_limit(
+        mut self,
+        stop_price: impl Into<String>,
+        limit_price: impl Into<String>,
+    ) -> Self {
+        self.kind = Some(OrderKind::StopLimit {
+            stop_price: stop_price.into(),
+            limit_price: limit_price.into(),
+        });
+        self
+    }
+
+    /// Set the time in force.
+    pub fn tif(mut self, tif: Tif) -> Self {
+        self.tif = Some(tif);
+        self
+    }
+
+    /// Set the base size.
+    pub fn base_size(mut self, base_size: impl Into<String>) -> Self {
+        self.base_size = Some(base_size.into());
+        self
+    }
+
+    /// Set the quote size (market orders only).
+    pub fn quote_size(mut self, quote_size: impl Into<String>) -> Self {
+        self.quote_size = Some(quote_size.into());
+        self
+    }
+
+    /// Set post-only mode (GTC and GTD limit orders only).
+    pub fn post_only(mut self, post_only: bool) -> Self {
+        self.post_only = post_only;
+        self
+    }
+
+    /// Set the stop direction of a stop-limit order.
+    pub fn stop_direction(mut self, stop_direction: StopDirection) -> Self {
+        self.stop_direction = Some(stop_direction);
+        self
+    }
+
+    /// Set a custom client order ID.
+    pub fn client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    /// Build and send the order.
+    pub async fn send(self) -> Result<CreateOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().create(request).await
+    }
+
+    /// Build and send the order, reporting where the time was spent.
+    ///
+    /// See [`OrderLatencyReport`] for the stages that are measured.
+    pub async fn send_with_latency(self) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        let started = Instant::now();
+        let (client, request) = self.into_request()?;
+        client.orders().create_timed(request, started).await
+    }
+
+    /// Preview the order without sending it.
+    ///
+    /// Returns the expected fees, total and slippage for the order.
+    pub async fn preview(self) -> Result<PreviewOrderResponse> {
+        let (client, request) = self.into_request()?;
+        client.orders().preview(request).await
+    }
+
+    /// Build the order request without sending it.
+    pub fn build(self) -> Result<CreateOrderRequest> {
+        self.into_request().map(|(_, request)| request)
+    }
+
+    /// Validate the builder and produce the order request.
+    fn into_request(self) -> Result<(&'a RestClient, CreateOrderRequest)> {
+        let mut v = Validator::default();
+        let built = self.validate(&mut v);
+        v.finish()?;
+        built.ok_or_else(incomplete_order)
+    }
+
+    /// Validate the builder, recording violations in `v`.
+    ///
+    /// Returns the order request if every required field was usable.
+    fn validate(self, v: &mut Validator) -> Option<(&'a RestClient, CreateOrderRequest)> {
+        let product_id = v.required("product_id", self.product_id);
+        let side = v.required_with("side", self.side, "is required (use .buy() or .sell())");
+        let kind = v.required_with(
+            "order_type",
+            self.kind,
+            "is required (use .market(), .limit() or .stop_limit())",
+        );
+        let end_time = match self.tif {
+            Some(Tif::Gtd(end)) => {
+                let end_time = end.to_rfc3339_opts(SecondsFormat::Secs, true);
+                v.future_time("end_time", &end_time);
+                Some(end_time)
+            }
+            _ => None,
+        };
+
+        let config = match kind {
+            Some(OrderKind::Market) => {
+                if self.tif.is_some_and(|tif| tif != Tif::Ioc) {
+                    v.push("tif", "must be IOC for market orders");
+                }
+                if self.post_only {
+                    v.push("post_only", "is not supported for market orders");
+                }
+                match (self.quote_size, self.base_size) {
+                    (Some(_), Some(_)) => {
+                        v.push(
+                            "quote_size",
+                            "and base_size are mutually exclusive (set only one)",
+                        );
+                        None
+                    }
+                    (Some(quote_size), None) => {
+                        v.positive("quote_size", &quote_size);
+                        Some(OrderConfiguration::market_buy_quote(quote_size))
+                    }
+                    (None, Some(base_size)) => {
+                        v.positive("base_size", &base_size);
+                        Some(match side {
+                            Some(OrderSide::Sell) => OrderConfiguration::market_sell(base_size),
+                            _ => OrderConfiguration::market_buy_base(base_size),
+                        })
+                    }
+                    (None, None) => {
+                        v.push("quote_size", "or base_size is required");
+                        None
+                    }
+                }
+            }
+            Some(OrderKind::Limit { limit_price }) => {
+                v.positive("limit_price", &limit_price);
+                if self.quote_size.is_some() {
+                    v.push("quote_size", "is only supported for market orders");
+                }
+                let base_size = v.required_positive("base_size", self.base_size);
+                match (self.tif.unwrap_or(Tif::Gtc), base_size) {
+                    (Tif::Gtc, Some(base_size)) => Some(OrderConfiguration::limit_gtc(
+                        base_size,
+                        limit_price,
+                        self.post_only,
+                    )),
+                    (Tif::Gtd(_), Some(base_size)) => end_time.map(|end_time| {
+                        OrderConfiguration::limit_gtd(
+                            base_size,
+                            limit_price,
+                            end_time,
+                            self.post_only,
+                        )
+                    }),
+                    (Tif::Fok, base_size) => {
+                        if self.post_only {
+                            v.push("post_only", "is not supported for FOK orders");
+                        }
+                        base_size
+                            .map(|base_size| OrderConfiguration::limit_fok(base_size, limit_price))
+                    }
+                    (Tif::Ioc, base_size) => {
+                        if self.post_only {
+                            v.push("post_only", "is not supported for IOC orders");
+                        }
+                        base_size
+                            .map(|base_size| OrderConfiguration::limit_ioc(base_size, limit_price))
+                    }
+                    (_, None) => None,
+                }
+            }
+            Some(OrderKind::StopLimit {
+                stop_price,
+                limit_price,
+            }) => {
+                v.positive("stop_price", &stop_price);
+                v.positive("limit_price", &limit_price);
+                if self.quote_size.is_some() {
+                    v.push("quote_size", "is only supported for market orders");
+                }
+                if self.post_only {
+                    v.push("post_only", "is not supported for stop-limit orders");
+                }
+                let direction = self.stop_direction.unwrap_or(match side {
+                    Some(OrderSide::Sell) => StopDirection::StopDirectionStopDown,
+                    _ => StopDirection::StopDirectionStopUp,
+                });
+                v.stop_side(&limit_price, &stop_price, direction);
+                let base_size = v.required_positive("base_size", self.base_size);
+                match (self.tif.unwrap_or(Tif::Gtc), base_size) {
+                    (Tif::Gtc, Some(base_size)) => Some(OrderConfiguration::stop_limit_gtc(
+                        base_size,
+                        limit_price,
+                        stop_price,
+                        direction,
+                    )),
+                    (Tif::Gtd(_), Some(base_size)) => end_time.map(|end_time| {
+                        OrderConfiguration::stop_limit_gtd(
+                            base_size,
+                            limit_price,
+                            stop_price,
+                            end_time,
+                            direction,
+                        )
+                    }),
+                    (tif @ (Tif::Ioc | Tif::Fok), _) => {
+                        v.push(
+                            "tif",
+                            format!("{} is not supported for stop-limit orders", tif),
+                        );
+                        None
+                    }
+                    (_, None) => None,
+                }
+            }
+            None => None,
+        };
+        self.flags.validate(v);
+
+        let (Some(product_id), Some(side), Some(config)) = (product_id, side, config) else {
+            return None;
+        };
+        let client_order_id = self.client_order_id.unwrap_or_else(uuid_v4);
+        let request = CreateOrderRequest::new(client_order_id, product_id, side, config);
+        Some((self.client, self.flags.apply(request)))
+    }
+}
+
+/// Builder for futures and perpetuals orders, sized in contracts.
+///
+/// Works like [`OrderBuilder`], but the size is a number of contracts and
+/// the order is checked against the product's contract metadata before it
+/// is sent: the product must be a futures product that is open for
+/// trading and not expired, the contracts must fit the product's size
+/// increment and limits, and the leverage must not exceed the maximum of a
+/// perpetual. [`send`](Self::send) and [`preview`](Self::preview) fetch the
+/// product unless it was given with [`product`](Self::product);
+/// [`build`](Self::build) only checks the metadata when it was given.
+pub struct FuturesOrderBuilder<'a> {
+    order: OrderBuilder<'a>,
+    product: Option<Product>,
+}
+
+impl<'a> FuturesOrderBuilder<'a> {
+    /// Create a new futures order builder.
+    pub(crate) fn new(client: &'a RestClient) -> Self {
+        Self {
+            order: OrderBuilder::new(client),
+            product: None,
+        }
+    }
+
+    /// Set as a buy (long) order.
+    pub fn buy(mut self, product_id: impl Into<String>) -> Self {
+        self.order = self.order.buy(product_id);
+        self
+    }
+
+    /// Set as a sell (short) order.
+    pub fn sell(mut self, product_id: impl Into<String>) -> Self {
+        self.order = self.order.sell(product_id);
+        self
+    }
+
+    /// Set the number of contracts.
+    pub fn contracts(mut self, number_of_contracts: impl Into<String>) -> Self {
+        self.order = self.order.base_size(number_of_contracts);
+        self
+    }
+
+    /// Make this a market order.
+    pub fn market(mut self) -> Self {
+        self.order = self.order.market();
+        self
+    }
+
+    /// Make this a limit order at `limit_price`.
+    pub fn limit(mut self, limit_price: impl Into<String>) -> Self {
+        self.order = self.order.limit(limit_price);
+        self
+    }
+
+    /// Make this a stop-limit order triggered at `stop_price`.
+    ///
+    /// See [`OrderBuilder::stop_limit`].
+    pub fn stop_limit(
+        mut self,
+        stop_price: impl Into<String>,
+        limit_price: impl Into<String>,
+    ) -> Self {
+        self.order = self.order.stop_limit(stop_price, limit_price);
+        self
+    }
+
+    /// Set the time in force.
+    pub fn tif(mut self, tif: Tif) -> Self {
+        self.order = self.order.tif(tif);
+        self
+    }
+
+    /// Set post-only mode (GTC and GTD limit orders only).
+    pub fn post_only(mut self, post_only: bool) -> Self {
+        self.order = self.order.post_only(post_only);
+        self
+    }
+
+    /// Set the stop direction of a stop-limit order.
+    pub fn stop_direction(mut self, stop_direction: StopDirection) -> Self {
+        self.order = self.order.stop_direction(stop_direction);
+        self
+    }
+
+    /// Set a custom client order ID.
+    pub fn client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.order = self.order.client_order_id(client_order_id);
+        self
+    }
+
+    /// Validate against the given product instead of fetching it.
+    pub fn product(mut self, product: Product) -> Self {
+        self.product = Some(product);
+        self
+    }
+
+    /// Check the order against the product, then send it.
+    pub async fn send(self) -> Result<CreateOrderResponse> {
+        let (client, request) = self.fetch_product().await?.into_request()?;
+        client.orders().create(request).await
+    }
+
+    /// Check the order against the product, then send it, reporting where
+    /// the time was spent.
+    ///
+    /// The product lookup counts towards the validation stage. See
+    /// [`OrderLatencyReport`] for the stages that are measured.
+    pub async fn send_with_latency(self) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        let started = Instant::now();
+        let (client, request) = self.fetch_product().await?.into_request()?;
+        client.orders().create_timed(request, started).await
+    }
+
+    /// Check the order against the product, then preview it without sending it.
+    pub async fn preview(self) -> Result<PreviewOrderResponse> {
+        let (client, request) = self.fetch_product().await?.into_request()?;
+        client.orders().preview(request).await
+    }
+
+    /// Build the order request without sending it.
+    pub fn build(self) -> Result<CreateOrderRequest> {
+        self.into_request().map(|(_, request)| request)
+    }
+
+    /// Fetch the product unless it was given.
+    async fn fetch_product(mut self) -> Result<Self> {
+        if self.product.is_none() {
+            if let Some(product_id) = &self.order.product_id {
+                self.product = Some(self.order.client.products().get(product_id).await?);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Validate the builder and produce the order request.
+    fn into_request(self) -> Result<(&'a RestClient, CreateOrderRequest)> {
+        let mut v = Validator::default();
+        let contracts = v.required("number_of_contracts", self.order.base_size.clone());
+        if let Some(product) = &self.product {
+            v.contract(
+                product,
+                self.order.product_id.as_deref(),
+                contracts.as_deref(),
+                self.order.flags.leverage.as_deref(),
+            );
+        }
+        let built = self.order.validate(&mut v);
+        if contracts.is_none() {
+            // Already reported; the order builder reports it as a missing base or quote size.
+            v.violations
+                .retain(|violation| !matches!(violation.field, "base_size" | "quote_size"));
+        }
+        v.rename("base_size", "number_of_contracts");
+        v.finish()?;
+        built.ok_or_else(incomplete_order)
+    }
+}
+
+impl_order_options!(
+    MarketOrderBuilder<'_> => flags,
+    LimitOrderGtcBuilder<'_> => flags,
+    LimitOrderGtdBuilder<'_> => flags,
+    StopLimitOrderGtcBuilder<'_> => flags,
+    StopLimitOrderGtdBuilder<'_> => flags,
+    LimitOrderFokBuilder<'_> => flags,
+    LimitOrderIocBuilder<'_> => flags,
+    OrderBuilder<'_> => flags,
+    FuturesOrderBuilder<'_> => order.flags,
+);
+
+/// Margin and self-trade prevention settings shared by all order builders.
+#[derive(Debug, Clone, Default)]
+struct OrderFlags {
+    leverage: Option<String>,
+    margin_type: Option<String>,
+    self_trade_prevention_id: Option<String>,
+}
+
+impl OrderFlags {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(leverage) = &self.leverage {
+            v.positive("leverage", leverage);
+        }
+        if self
+            .self_trade_prevention_id
+            .as_ref()
+            .is_some_and(|id| id.trim().is_empty())
+        {
+            v.push("self_trade_prevention_id", "must not be empty");
+        }
+    }
+
+    fn apply(self, mut request: CreateOrderRequest) -> CreateOrderRequest {
+        request.leverage = self.leverage;
+        request.margin_type = self.margin_type;
+        request.self_trade_prevention_id = self.self_trade_prevention_id;
+        request
+    }
+}
+
+/// Collects violations while validating builder fields.
+#[derive(Default)]
+struct Validator {
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    fn push(&mut self, field: &'static str, message: impl Into<String>) {
+        self.violations.push(Violation {
+            field,
+            message: message.into(),
+        });
+    }
+
+    fn required<T>(&mut self, field: &'static str, value: Option<T>) -> Option<T> {
+        self.required_with(field, value, "is required")
+    }
+
+    fn required_with<T>(
+        &mut self,
+        field: &'static str,
+        value: Option<T>,
+        message: &str,
+    ) -> Option<T> {
+        if value.is_none() {
+            self.push(field, message);
+        }
+        value
+    }
+
+    fn required_positive(&mut self, field: &'static str, value: Option<String>) -> Option<String> {
+        let value = self.required(field, value)?;
+        self.positive(field, &value);
+        Some(value)
+    }
+
+    /// Check that a value is a positive decimal number.
+    fn positive(&mut self, field: &'static str, value: &str) {
+        match parse_plain_decimal(value) {
+            Some(n) if n > Decimal::ZERO => {}
+            Some(_) => self.push(field, format!("must be positive (got {})", value)),
+            None => self.push(field, format!("must be a decimal number (got {:?})", value)),
+        }
+    }
+
+    /// Check that an RFC 3339 timestamp is in the future.
+    fn future_time(&mut self, field: &'static str, value: &str) {
+        match DateTime::parse_from_rfc3339(value) {
+            Ok(time) if time > Utc::now() => {}
+            Ok(_) => self.push(field, format!("must be in the future (got {})", value)),
+            Err(_) => self.push(
+                field,
+                format!("must be an RFC 3339 timestamp (got {:?})", value),
+            ),
+        }
+    }
+
+    /// Check that the limit price is on the correct side of the stop price.
+    ///
+    /// A stop-up order triggers as the price rises, so its limit must be at or
+    /// above the stop; a stop-down order needs a limit at or below the stop.
+    fn stop_side(&mut self, limit_price: &str, stop_price: &str, direction: StopDirection) {
+        let (Some(limit), Some(stop)) = (
+            parse_plain_decimal(limit_price),
+            parse_plain_decimal(stop_price),
+        ) else {
+            return;
+        };
+        match direction {
+            StopDirection::StopDirectionStopUp if limit < stop => self.push(
+                "limit_price",
+                format!("must be at or above stop_price {} for STOP_UP", stop_price),
+            ),
+            StopDirection::StopDirectionStopDown if limit > stop => self.push(
+                "limit_price",
+                format!(
+                    "must be at or below stop_price {} for STOP_DOWN",
+                    stop_price
+                ),
+            ),
+            _ => {}
+        }
+    }
+
+    /// Check an order of `contracts` against a futures product's metadata.
+    fn contract(
+        &mut self,
+        product: &Product,
+        product_id: Option<&str>,
+        contracts: Option<&str>,
+        leverage: Option<&str>,
+    ) {
+        if product_id.is_some_and(|id| id != product.product_id) {
+            self.push(
+                "product_id",
+                format!("does not match the product {}", product.product_id),
+            );
+            return;
+        }
+        if product.product_type.as_deref() != Some("FUTURE") {
+            self.push(
+                "product_id",
+                format!("{} is not a futures product", product.product_id),
+            );
+            return;
+        }
+        if product.trading_disabled || product.is_disabled || product.cancel_only {
+            self.push(
+                "product_id",
+                format!("{} is not open for new orders", product.product_id),
+            );
+        }
+        let session_closed = product
+            .fcm_trading_session_details
+            .as_ref()
+            .is_some_and(|s| !s.is_session_open && s.after_hours_order_entry_disabled);
+        if session_closed {
+            self.push(
+                "product_id",
+                format!("{} is outside its trading session", product.product_id),
+            );
+        }
+        let details = product.future_product_details.as_ref();
+        if let Some(expiry) = details.and_then(FutureProductDetails::expiry) {
+            if expiry <= Utc::now() {
+                self.push(
+                    "product_id",
+                    format!("{} expired at {}", product.product_id, expiry),
+                );
+            }
+        }
+
+        if let Some(contracts) = contracts.and_then(|c| c.trim().parse::<Decimal>().ok()) {
+            let increment = product.base_increment.parse::<Decimal>().ok();
+            if let Some(increment) = increment.filter(|i| !i.is_zero()) {
+                if !(contracts % increment).is_zero() {
+                    self.push(
+                        "base_size",
+                        format!("must be a multiple of {}", product.base_increment),
+                    );
+                }
+            }
+            if let Ok(min) = product.base_min_size.parse::<Decimal>() {
+                if contracts < min {
+                    self.push(
+                        "base_size",
+                        format!("must be at least {}", product.base_min_size),
+                    );
+                }
+            }
+            if let Ok(max) = product.base_max_size.parse::<Decimal>() {
+                if !max.is_zero() && contracts > max {
+                    self.push(
+                        "base_size",
+                        format!("must be at most {}", product.base_max_size),
+                    );
+                }
+            }
+        }
+
+        let max_leverage = details
+            .and_then(|d| d.perpetual_details.as_ref())
+            .and_then(|p| p.max_leverage.as_deref())
+            .and_then(|max| max.parse::<Decimal>().ok().map(|parsed| (max, parsed)));
+        let leverage = leverage.and_then(|l| l.trim().parse::<Decimal>().ok());
+        if let (Some(leverage), Some((max, parsed))) = (leverage, max_leverage) {
+            if leverage > parsed {
+                self.push("leverage", format!("must be at most {}", max));
+            }
+        }
+    }
+
+    /// Report violations of `from` under the name `to`.
+    fn rename(&mut self, from: &'static str, to: &'static str) {
+        for violation in &mut self.violations {
+            if violation.field == from {
+                violation.field = to;
+            }
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        if self.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(ValidationError {
+                violations: self.violations,
+            }))
+        }
+    }
+}
+
+/// The error for a required field that validation let through unset.
+///
+/// Validation reports every missing field, so this is not expected to happen,
+/// but a missed report must surface as an error rather than a panic.
+fn incomplete_order() -> Error {
+    Error::request("order builder is missing a required field")
+}
+
+/// Generate a simple UUID v4 string.
+fn uuid_v4() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+
+    // Simple UUID-like format using timestamp and random bits.
+    format!(
+        "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+        (now >> 96) as u32,
+        (now >> 80) as u16,
+        (now >> 68) as u16 & 0x0fff,
+        ((now >> 52) as u16 & 0x3fff) | 0x8000,
+        now as u64 & 0xffffffffffff
+    )
+}
+
+// Add builder methods to RestClient.
+impl RestClient {
+    /// Create an order builder for any order type.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials, models::Tif};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// // Fill 0.001 BTC at 50000 or better right away, or not at all
+    /// let response = client.order()
+    ///     .buy("BTC-USD")
+    ///     .base_size("0.001")
+    ///     .limit("50000.00")
+    ///     .tif(Tif::Fok)
+    ///     .send()
+    ///     .await?;
+    ///
+    /// // Stop loss at 48000, selling no lower than 47900
+    /// let response = client.order()
+    ///     .sell("BTC-USD")
+    ///     .base_size("0.001")
+    ///     .stop_limit("48000.00", "47900.00")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn order(&self) -> OrderBuilder<'_> {
+        OrderBuilder::new(self)
+    }
+
+    /// Create an order builder for futures and perpetuals.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials, models::MarginType};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// // Go long 2 contracts of the January BTC future at 98000
+    /// let response = client.futures_order()
+    ///     .buy("BIT-31JAN25-CDE")
+    ///     .contracts("2")
+    ///     .limit("98000")
+    ///     .send()
+    ///     .await?;
+    ///
+    /// // Short 0.01 BTC perpetual at 5x isolated margin
+    /// let response = client.futures_order()
+    ///     .sell("BTC-PERP-INTX")
+    ///     .contracts("0.01")
+    ///     .market()
+    ///     .leverage("5")
+    ///     .margin_type(MarginType::Isolated)
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn futures_order(&self) -> FuturesOrderBuilder<'_> {
+        FuturesOrderBuilder::new(self)
+    }
+
+    /// Create a market order builder.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// // Buy $100 of BTC
+    /// let response = client.market_order()
+    ///     .buy("BTC-USD")
+    ///     .quote_size("100.00")
+    ///     .send()
+    ///     .await?;
+    ///
+    /// // Sell 0.001 BTC
+    /// let response = client.market_order()
+    ///     .sell("BTC-USD")
+    ///     .base_size("0.001")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn market_order(&self) -> MarketOrderBuilder<'_> {
+        MarketOrderBuilder::new(self)
+    }
+
+    /// Create a limit order (GTC) builder.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let response = client.limit_order_gtc()
+    ///     .buy("BTC-USD")
+    ///     .base_size("0.001")
+    ///     .limit_price("50000.00")
+    ///     .post_only(true)
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit_order_gtc(&self) -> LimitOrderGtcBuilder<'_> {
+        LimitOrderGtcBuilder::new(self)
+    }
+
+    /// Create a limit order (GTD) builder.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let response = client.limit_order_gtd()
+    ///     .buy("BTC-USD")
+    ///     .base_size("0.001")
+    ///     .limit_price("50000.00")
+    ///     .end_time("2024-12-31T23:59:59Z")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit_order_gtd(&self) -> LimitOrderGtdBuilder<'_> {
+        LimitOrderGtdBuilder::new(self)
+    }
+
+    /// Create a stop-limit order (GTC) builder.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials, models::StopDirection};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let response = client.stop_limit_order_gtc()
+    ///     .sell("BTC-USD")
+    ///     .base_size("0.001")
+    ///     .limit_price("49000.00")
+    ///     .stop_price("50000.00")
     ///     .stop_direction(StopDirection::StopDirectionStopDown)
     ///     .send()
     ///     .await?;
@@ -511,4 +1943,548 @@ impl RestClient {
     pub fn stop_limit_order_gtc(&self) -> StopLimitOrderGtcBuilder<'_> {
         StopLimitOrderGtcBuilder::new(self)
     }
+
+    /// Create a stop-limit order (GTD) builder.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials, models::StopDirection};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let response = client.stop_limit_order_gtd()
+    ///     .sell("BTC-USD")
+    ///     .base_size("0.001")
+    ///     .limit_price("49000.00")
+    ///     .stop_price("50000.00")
+    ///     .stop_direction(StopDirection::StopDirectionStopDown)
+    ///     .end_time("2024-12-31T23:59:59Z")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stop_limit_order_gtd(&self) -> StopLimitOrderGtdBuilder<'_> {
+        StopLimitOrderGtdBuilder::new(self)
+    }
+
+    /// Create a limit order (FOK) builder.
+    ///
+    /// Fill-or-kill orders are filled completely at the limit price or better
+    /// immediately, or cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let response = client.limit_order_fok()
+    ///     .buy("BTC-USD")
+    ///     .base_size("0.001")
+    ///     .limit_price("50000.00")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit_order_fok(&self) -> LimitOrderFokBuilder<'_> {
+        LimitOrderFokBuilder::new(self)
+    }
+
+    /// Create a limit order (IOC) builder.
+    ///
+    /// Immediate-or-cancel orders fill as much as possible at the limit price
+    /// or better immediately, and the rest is cancelled. Coinbase routes
+    /// them across venues for the best price (`sor_limit_ioc`).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let response = client.limit_order_ioc()
+    ///     .buy("BTC-USD")
+    ///     .base_size("0.001")
+    ///     .limit_price("50000.00")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit_order_ioc(&self) -> LimitOrderIocBuilder<'_> {
+        LimitOrderIocBuilder::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MarginType;
+
+    #[test]
+    fn test_gtc_into_gtd_preserves_fields() {
+        let client = RestClient::builder().build().unwrap();
+        let request = client
+            .limit_order_gtc()
+            .buy("BTC-USD")
+            .base_size("0.001")
+            .limit_price("50000.00")
+            .post_only(true)
+            .client_order_id("my-order")
+            .into_gtd("2099-12-31T23:59:59Z")
+            .build()
+            .unwrap();
+
+        assert_eq!(request.client_order_id, "my-order");
+        assert_eq!(request.product_id, "BTC-USD");
+        assert_eq!(request.side, OrderSide::Buy);
+        let json = serde_json::to_value(&request.order_configuration).unwrap();
+        let gtd = &json["limit_limit_gtd"];
+        assert_eq!(gtd["end_time"], "2099-12-31T23:59:59Z");
+        assert_eq!(gtd["limit_price"], "50000.00");
+        assert_eq!(gtd["post_only"], true);
+    }
+
+    #[test]
+    fn test_order_flags() {
+        let client = RestClient::builder().build().unwrap();
+        let request = client
+            .limit_order_gtc()
+            .buy("BTC-USD")
+            .base_size("0.001")
+            .limit_price("50000.00")
+            .leverage("5")
+            .margin_type(MarginType::Cross)
+            .stp("bot-1")
+            .into_gtd("2099-12-31T23:59:59Z")
+            .build()
+            .unwrap();
+
+        assert_eq!(request.leverage.as_deref(), Some("5"));
+        assert_eq!(request.margin_type.as_deref(), Some("CROSS"));
+        assert_eq!(request.self_trade_prevention_id.as_deref(), Some("bot-1"));
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["margin_type"], "CROSS");
+
+        let err = client
+            .market_order()
+            .buy("BTC-USD")
+            .quote_size("100")
+            .leverage("0")
+            .build()
+            .unwrap_err();
+        assert!(err.validation_error().unwrap().has_field("leverage"));
+    }
+
+    #[test]
+    fn test_gtd_into_gtc_round_trip() {
+        let client = RestClient::builder().build().unwrap();
+        let request = client
+            .limit_order_gtc()
+            .sell("ETH-USD")
+            .base_size("1.5")
+            .limit_price("3000.00")
+            .into_gtd("2099-12-31T23:59:59Z")
+            .into_gtc()
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request.order_configuration).unwrap();
+        assert_eq!(json["limit_limit_gtc"]["base_size"], "1.5");
+    }
+
+    #[test]
+    fn test_gtc_into_stop_limit() {
+        let client = RestClient::builder().build().unwrap();
+        let request = client
+            .limit_order_gtc()
+            .sell("BTC-USD")
+            .base_size("0.001")
+            .limit_price("49000.00")
+            .into_stop_limit("50000.00", StopDirection::StopDirectionStopDown)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request.order_configuration).unwrap();
+        assert_eq!(json["stop_limit_stop_limit_gtc"]["stop_price"], "50000.00");
+    }
+
+    #[test]
+    fn test_gtc_into_stop_limit_clears_post_only() {
+        let client = RestClient::builder().build().unwrap();
+        let request = client
+            .limit_order_gtc()
+            .sell("BTC-USD")
+            .base_size("0.001")
+            .limit_price("49000.00")
+            .post_only(true)
+            .into_stop_limit("50000.00", StopDirection::StopDirectionStopDown)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request.order_configuration).unwrap();
+        assert!(json["stop_limit_stop_limit_gtc"].get("post_only").is_none());
+    }
+
+    #[test]
+    fn test_validation_reports_all_violations() {
+        let client = RestClient::builder().build().unwrap();
+        let err = client
+            .limit_order_gtd()
+            .base_size("-1")
+            .limit_price("abc")
+            .end_time("2000-01-01T00:00:00Z")
+            .build()
+            .unwrap_err();
+
+        let validation = err.validation_error().unwrap();
+        assert_eq!(validation.violations.len(), 5);
+        for field in ["product_id", "side", "base_size", "limit_price", "end_time"] {
+            assert!(
+                validation.has_field(field),
+                "missing violation for {}",
+                field
+            );
+        }
+    }
+
+    #[test]
+    fn test_validation_rejects_non_plain_decimals() {
+        let client = RestClient::builder().build().unwrap();
+        for value in ["1e5", "inf", "NaN", " 1"] {
+            let err = client
+                .limit_order_gtc()
+                .buy("BTC-USD")
+                .base_size(value)
+                .limit_price(value)
+                .build()
+                .unwrap_err();
+            let validation = err.validation_error().unwrap();
+            assert!(validation.has_field("base_size"), "{value}");
+            assert!(validation.has_field("limit_price"), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_validation_market_sizes_exclusive() {
+        let client = RestClient::builder().build().unwrap();
+        let err = client
+            .market_order()
+            .buy("BTC-USD")
+            .quote_size("100")
+            .base_size("0.001")
+            .build()
+            .unwrap_err();
+        assert!(err.validation_error().unwrap().has_field("quote_size"));
+
+        assert!(
+            client
+                .market_order()
+                .buy("BTC-USD")
+                .quote_size("100")
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_validation_stop_side() {
+        let client = RestClient::builder().build().unwrap();
+        let err = client
+            .stop_limit_order_gtc()
+            .sell("BTC-USD")
+            .base_size("0.001")
+            .limit_price("51000.00")
+            .stop_price("50000.00")
+            .stop_direction(StopDirection::StopDirectionStopDown)
+            .build()
+            .unwrap_err();
+        assert!(err.validation_error().unwrap().has_field("limit_price"));
+    }
+
+    #[test]
+    fn test_stop_limit_gtd_builder() {
+        let client = RestClient::builder().build().unwrap();
+        let request = client
+            .stop_limit_order_gtd()
+            .buy("BTC-USD")
+            .base_size("0.001")
+            .limit_price("51000.00")
+            .stop_price("50000.00")
+            .stop_direction(StopDirection::StopDirectionStopUp)
+            .end_time("2099-12-31T23:59:59Z")
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request.order_configuration).unwrap();
+        let gtd = &json["stop_limit_stop_limit_gtd"];
+        assert_eq!(gtd["stop_price"], "50000.00");
+        assert_eq!(gtd["end_time"], "2099-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn test_limit_fok_builder() {
+        let client = RestClient::builder().build().unwrap();
+        let request = client
+            .limit_order_fok()
+            .sell("ETH-USD")
+            .base_size("1")
+            .limit_price("3000.00")
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request.order_configuration).unwrap();
+        assert_eq!(json["limit_limit_fok"]["limit_price"], "3000.00");
+        assert!(client.limit_order_fok().buy("ETH-USD").build().is_err());
+    }
+
+    #[test]
+    fn test_limit_ioc_builder() {
+        let client = RestClient::builder().build().unwrap();
+        let request = client
+            .limit_order_ioc()
+            .buy("BTC-USD")
+            .base_size("0.5")
+            .limit_price("50000.00")
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request.order_configuration).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "sor_limit_ioc": { "base_size": "0.5", "limit_price": "50000.00" }
+            })
+        );
+        assert!(client.limit_order_ioc().buy("BTC-USD").build().is_err());
+    }
+
+    #[test]
+    fn test_order_builder_configurations() {
+        let client = RestClient::builder().build().unwrap();
+        let config = |builder: OrderBuilder<'_>| {
+            serde_json::to_value(builder.build().unwrap().order_configuration).unwrap()
+        };
+
+        let json = config(client.order().sell("BTC-USD").base_size("0.1").market());
+        assert_eq!(json["market_market_ioc"]["base_size"], "0.1");
+
+        let json = config(
+            client
+                .order()
+                .buy("BTC-USD")
+                .base_size("0.1")
+                .limit("50000")
+                .post_only(true),
+        );
+        assert_eq!(json["limit_limit_gtc"]["post_only"], true);
+
+        let end = "2099-12-31T23:59:59Z".parse().unwrap();
+        let json = config(
+            client
+                .order()
+                .buy("BTC-USD")
+                .base_size("0.1")
+                .limit("50000")
+                .tif(Tif::Gtd(end)),
+        );
+        assert_eq!(json["limit_limit_gtd"]["end_time"], "2099-12-31T23:59:59Z");
+
+        let json = config(
+            client
+                .order()
+                .buy("BTC-USD")
+                .base_size("0.1")
+                .limit("50000")
+                .tif(Tif::Fok),
+        );
+        assert_eq!(json["limit_limit_fok"]["limit_price"], "50000");
+
+        let json = config(
+            client
+                .order()
+                .buy("BTC-USD")
+                .base_size("0.1")
+                .limit("50000")
+                .tif(Tif::Ioc),
+        );
+        assert_eq!(json["sor_limit_ioc"]["limit_price"], "50000");
+
+        let json = config(
+            client
+                .order()
+                .sell("BTC-USD")
+                .base_size("0.1")
+                .stop_limit("48000", "47900"),
+        );
+        let stop = &json["stop_limit_stop_limit_gtc"];
+        assert_eq!(stop["stop_price"], "48000");
+        assert_eq!(stop["stop_direction"], "STOP_DIRECTION_STOP_DOWN");
+    }
+
+    #[test]
+    fn test_order_builder_rejects_unsupported_tif() {
+        let client = RestClient::builder().build().unwrap();
+        let fields = |builder: OrderBuilder<'_>| {
+            builder
+                .build()
+                .unwrap_err()
+                .validation_error()
+                .unwrap()
+                .violations
+                .iter()
+                .map(|v| v.field)
+                .collect::<Vec<_>>()
+        };
+
+        let base = || client.order().buy("BTC-USD").base_size("0.1");
+        assert_eq!(fields(base().market().tif(Tif::Gtc)), ["tif"]);
+        assert_eq!(
+            fields(base().limit("50000").tif(Tif::Ioc).post_only(true)),
+            ["post_only"]
+        );
+        assert_eq!(
+            fields(base().stop_limit("51000", "51100").tif(Tif::Fok)),
+            ["tif"]
+        );
+        assert_eq!(
+            fields(base().limit("50000").quote_size("10")),
+            ["quote_size"]
+        );
+        assert_eq!(fields(client.order().buy("BTC-USD")), ["order_type"]);
+    }
+
+    fn futures_product(max_leverage: &str) -> Product {
+        serde_json::from_value(serde_json::json!({
+            "product_id": "BTC-PERP-INTX",
+            "price": "98000",
+            "price_percentage_change_24h": "1.2",
+            "volume_24h": "5000",
+            "volume_percentage_change_24h": "3.4",
+            "base_increment": "0.0001",
+            "quote_increment": "0.1",
+            "quote_min_size": "10",
+            "quote_max_size": "50000000",
+            "base_min_size": "0.0001",
+            "base_max_size": "50",
+            "base_name": "",
+            "quote_name": "USDC",
+            "watched": false,
+            "is_disabled": false,
+            "new": false,
+            "status": "",
+            "cancel_only": false,
+            "limit_only": false,
+            "post_only": false,
+            "trading_disabled": false,
+            "auction_mode": false,
+            "product_type": "FUTURE",
+            "quote_currency_id": "USDC",
+            "base_currency_id": "",
+            "future_product_details": {
+                "venue": "INTX",
+                "contract_code": "BTC",
+                "contract_expiry": "",
+                "contract_size": "1",
+                "contract_root_unit": "BTC",
+                "contract_expiry_type": "PERPETUAL",
+                "perpetual_details": { "max_leverage": max_leverage, "funding_rate": "0.0001" }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_futures_order_builder() {
+        let client = RestClient::builder().build().unwrap();
+        let request = client
+            .futures_order()
+            .sell("BTC-PERP-INTX")
+            .contracts("0.5")
+            .limit("99000")
+            .leverage("5")
+            .margin_type(MarginType::Isolated)
+            .product(futures_product("10"))
+            .build()
+            .unwrap();
+        assert_eq!(request.side, OrderSide::Sell);
+        assert_eq!(request.leverage.as_deref(), Some("5"));
+        assert_eq!(request.margin_type.as_deref(), Some("ISOLATED"));
+        let json = serde_json::to_value(&request.order_configuration).unwrap();
+        assert_eq!(json["limit_limit_gtc"]["base_size"], "0.5");
+
+        let err = client
+            .futures_order()
+            .buy("BTC-PERP-INTX")
+            .contracts("0.00005")
+            .market()
+            .leverage("20")
+            .product(futures_product("10"))
+            .build()
+            .unwrap_err();
+        let fields: Vec<_> = err
+            .validation_error()
+            .unwrap()
+            .violations
+            .iter()
+            .map(|v| v.field)
+            .collect();
+        assert_eq!(
+            fields,
+            ["number_of_contracts", "number_of_contracts", "leverage"]
+        );
+
+        let err = client
+            .futures_order()
+            .buy("BTC-PERP-INTX")
+            .limit("99000")
+            .build()
+            .unwrap_err();
+        let violations = &err.validation_error().unwrap().violations;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].field, "number_of_contracts");
+    }
+
+    #[test]
+    fn test_futures_order_rejects_spot_and_expired() {
+        let client = RestClient::builder().build().unwrap();
+
+        let mut spot = futures_product("10");
+        spot.product_type = Some("SPOT".to_string());
+        let err = client
+            .futures_order()
+            .buy("BTC-PERP-INTX")
+            .contracts("1")
+            .market()
+            .product(spot)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("not a futures product"));
+
+        let mut expired = futures_product("10");
+        let details = expired.future_product_details.as_mut().unwrap();
+        details.contract_expiry = Some("2020-01-31T16:00:00Z".to_string());
+        details.contract_expiry_type = Some("EXPIRING".to_string());
+        assert!(!details.is_perpetual());
+        let err = client
+            .futures_order()
+            .buy("BTC-PERP-INTX")
+            .contracts("1")
+            .market()
+            .product(expired)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("expired"));
+    }
 }
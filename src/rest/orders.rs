@@ -1,12 +1,90 @@
 //! Orders API endpoints.
 
-use crate::client::RestClient;
-use crate::error::Result;
+use std::future::Future;
+use std::time::Duration;
+
+use futures::{Stream, StreamExt, TryStreamExt, stream};
+use rust_decimal::Decimal;
+use tracing::Instrument;
+
+use crate::batch::Batcher;
+use crate::client::{RequestOptions, RequestTimings, RestClient};
+use crate::constants::{MAX_CANCEL_ORDER_IDS, paths};
+use crate::error::{Error, Result};
+use crate::latency::OrderLatencyReport;
 use crate::models::{
     CancelOrdersRequest, CancelOrdersResponse, ClosePositionRequest, CreateOrderRequest,
-    CreateOrderResponse, EditOrderRequest, EditOrderResponse, ListFillsParams, ListFillsResponse,
-    ListOrdersParams, ListOrdersResponse, Order,
+    CreateOrderResponse, Cursor, EditOrderRequest, EditOrderResponse, ListFillsParams,
+    ListFillsResponse, ListOrdersParams, ListOrdersResponse, Order, OrderStatus,
+    PreviewOrderResponse, PreviewedOrder, parse_optional_decimal_field,
 };
+use crate::order_ids;
+use crate::runtime::{self, Instant};
+use crate::ws::{Events, Message};
+
+/// How often [`OrdersApi::place_and_wait`] polls the order's status.
+const FILL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How [`OrdersApi::wait_for_status`] polls an order.
+///
+/// Polls start `initial_interval` apart. While the order's status stays
+/// the same the interval doubles, up to `max_interval`; a status change
+/// starts over at `initial_interval`. When the server rate limits the
+/// polls, the next one waits for its `Retry-After`, or twice the current
+/// interval if it sends none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PollPolicy {
+    /// Interval before the second poll.
+    pub initial_interval: Duration,
+    /// Longest interval between polls.
+    pub max_interval: Duration,
+    /// How long to wait in total, or `None` to wait indefinitely.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for PollPolicy {
+    fn default() -> Self {
+        Self {
+            initial_interval: FILL_POLL_INTERVAL,
+            max_interval: Duration::from_secs(10),
+            timeout: None,
+        }
+    }
+}
+
+impl PollPolicy {
+    /// Create a new poll policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Poll at a fixed interval.
+    pub fn fixed(interval: Duration) -> Self {
+        Self {
+            initial_interval: interval,
+            max_interval: interval,
+            timeout: None,
+        }
+    }
+
+    /// Set the initial interval.
+    pub fn with_initial_interval(mut self, interval: Duration) -> Self {
+        self.initial_interval = interval;
+        self
+    }
+
+    /// Set the maximum interval.
+    pub fn with_max_interval(mut self, interval: Duration) -> Self {
+        self.max_interval = interval;
+        self
+    }
+
+    /// Give up with [`Error::DeadlineExceeded`] after `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
 
 /// Response from getting a single order.
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -15,6 +93,49 @@ pub struct GetOrderResponse {
     pub order: Order,
 }
 
+/// Outcome of [`OrdersApi::place_and_wait`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillSummary {
+    /// The order ID.
+    pub order_id: String,
+    /// Status when last checked.
+    ///
+    /// Right after a cancel this can still be reported as open.
+    pub status: OrderStatus,
+    /// Quantity filled, in base currency.
+    pub filled_size: Decimal,
+    /// Average price of the fills, if any.
+    pub average_filled_price: Option<Decimal>,
+    /// Fees paid on the fills.
+    pub total_fees: Decimal,
+    /// Whether the remainder was cancelled because the timeout passed.
+    pub cancelled: bool,
+}
+
+impl FillSummary {
+    fn from_order(order: &Order, cancelled: bool) -> Result<Self> {
+        Ok(Self {
+            order_id: order.order_id.clone(),
+            status: order.status.parse().unwrap_or(OrderStatus::Unknown),
+            filled_size: parse_optional_decimal_field("filled_size", order.filled_size.as_deref())?
+                .unwrap_or_default(),
+            average_filled_price: parse_optional_decimal_field(
+                "average_filled_price",
+                order.average_filled_price.as_deref(),
+            )?
+            .filter(|price| !price.is_zero()),
+            total_fees: parse_optional_decimal_field("total_fees", order.total_fees.as_deref())?
+                .unwrap_or_default(),
+            cancelled,
+        })
+    }
+
+    /// Returns true if the whole order was filled.
+    pub fn is_filled(&self) -> bool {
+        self.status == OrderStatus::Filled
+    }
+}
+
 /// API for managing orders.
 ///
 /// This API provides endpoints for creating, editing, cancelling,
@@ -31,6 +152,9 @@ impl<'a> OrdersApi<'a> {
 
     /// Create a new order.
     ///
+    /// Use [`create_with_latency`](Self::create_with_latency) to also time
+    /// the request.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -55,31 +179,192 @@ impl<'a> OrdersApi<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn create(&self, request: CreateOrderRequest) -> Result<CreateOrderResponse> {
-        self.client.post("/orders", &request).await
+    pub async fn create(&self, mut request: CreateOrderRequest) -> Result<CreateOrderResponse> {
+        self.client
+            .apply_portfolio(&mut request.retail_portfolio_id);
+        if let Some(response) = self.client.guard_order(&request).await? {
+            return Ok(response);
+        }
+        let response = self.client.post(paths::ORDERS, &request).await?;
+        self.record_order_id(&request.client_order_id, &response);
+        Ok(response)
+    }
+
+    /// Record a created order's ID, if the client has an order ID store.
+    fn record_order_id(&self, client_order_id: &str, response: &CreateOrderResponse) {
+        if let Some(store) = self.client.order_id_store() {
+            order_ids::record_created(store, client_order_id, response);
+        }
+    }
+
+    /// Create a new order, failing if it is not acknowledged by `deadline`.
+    ///
+    /// Equivalent to running [`create`](Self::create) inside a
+    /// [`RequestOptions`] scope with the deadline set. On
+    /// [`Error::DeadlineExceeded`](crate::Error::DeadlineExceeded) the order may
+    /// still have been accepted, so look it up by client order ID before
+    /// retrying.
+    pub async fn create_with_deadline(
+        &self,
+        request: CreateOrderRequest,
+        deadline: Instant,
+    ) -> Result<CreateOrderResponse> {
+        RequestOptions::new()
+            .deadline(deadline)
+            .scope(self.create(request))
+            .await
+    }
+
+    /// Create a new order, reporting where the time was spent.
+    ///
+    /// The report's [`latency`](OrderLatencyReport::latency) holds the round
+    /// trip and Coinbase's processing time. The request is sent inside an
+    /// `order_placement` span tagged with the client order ID. Pass the
+    /// returned report to an
+    /// [`OrderLatencyTracker`](crate::latency::OrderLatencyTracker) to also
+    /// measure the time until the order is confirmed on the user channel.
+    pub async fn create_with_latency(
+        &self,
+        request: CreateOrderRequest,
+    ) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        self.create_timed(request, Instant::now()).await
+    }
+
+    /// Create a new order, treating the time since `started` as validation.
+    pub(crate) async fn create_timed(
+        &self,
+        mut request: CreateOrderRequest,
+        started: Instant,
+    ) -> Result<(CreateOrderResponse, OrderLatencyReport)> {
+        let validation = started.elapsed();
+        self.client
+            .apply_portfolio(&mut request.retail_portfolio_id);
+        let span = tracing::info_span!(
+            "order_placement",
+            client_order_id = %request.client_order_id,
+            product_id = %request.product_id,
+            validation_us = validation.as_micros() as u64,
+        );
+
+        let mut timings = RequestTimings::default();
+        let response: CreateOrderResponse = match self.client.guard_order(&request).await? {
+            Some(response) => response,
+            None => {
+                let response = self
+                    .client
+                    .post_timed(paths::ORDERS, &request, &mut timings)
+                    .instrument(span.clone())
+                    .await?;
+                self.record_order_id(&request.client_order_id, &response);
+                response
+            }
+        };
+
+        let report = OrderLatencyReport {
+            client_order_id: request.client_order_id,
+            started_at: started,
+            validation,
+            signing: timings.signing,
+            transport: timings.transport,
+            parse: timings.parse,
+            latency: timings.latency,
+            confirmation: None,
+        };
+        span.in_scope(|| tracing::debug!(%report, "order placed"));
+
+        Ok((response, report))
     }
 
     /// Preview an order without executing it.
     ///
     /// Returns the expected fees and total for the order.
-    pub async fn preview(&self, request: CreateOrderRequest) -> Result<serde_json::Value> {
-        self.client.post("/orders/preview", &request).await
+    pub async fn preview(&self, mut request: CreateOrderRequest) -> Result<PreviewOrderResponse> {
+        self.client
+            .apply_portfolio(&mut request.retail_portfolio_id);
+        self.client.post(paths::ORDERS_PREVIEW, &request).await
+    }
+
+    /// Preview an order and create it only if `accept` approves the preview.
+    ///
+    /// Previews that report errors are not passed to `accept`. Only the
+    /// create step goes through the order guard, as with [`create`](Self::create).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # use coinbase_advanced::models::{CreateOrderRequest, OrderConfiguration, OrderSide, PreviewedOrder};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let request = CreateOrderRequest::new(
+    ///     "my-order-id",
+    ///     "BTC-USD",
+    ///     OrderSide::Buy,
+    ///     OrderConfiguration::market_buy_quote("100"),
+    /// );
+    /// // Only buy if the expected slippage is under 0.5%.
+    /// let outcome = client
+    ///     .orders()
+    ///     .create_if_preview_ok(request, |preview| {
+    ///         preview
+    ///             .slippage
+    ///             .as_deref()
+    ///             .and_then(|s| s.parse::<f64>().ok())
+    ///             .is_some_and(|s| s < 0.005)
+    ///     })
+    ///     .await?;
+    /// match outcome {
+    ///     PreviewedOrder::Placed { response, .. } => println!("placed {:?}", response.order_id),
+    ///     other => println!("not placed: {:?}", other.preview().errs),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_if_preview_ok<F>(
+        &self,
+        request: CreateOrderRequest,
+        accept: F,
+    ) -> Result<PreviewedOrder>
+    where
+        F: FnOnce(&PreviewOrderResponse) -> bool,
+    {
+        let preview = self.preview(request.clone()).await?;
+        if !preview.is_ok() {
+            tracing::debug!(client_order_id = %request.client_order_id, errs = ?preview.errs, "preview failed, not placing order");
+            return Ok(PreviewedOrder::PreviewFailed(preview));
+        }
+        if !accept(&preview) {
+            tracing::debug!(client_order_id = %request.client_order_id, "preview rejected, not placing order");
+            return Ok(PreviewedOrder::Rejected(preview));
+        }
+        let response = self.create(request).await?;
+        Ok(PreviewedOrder::Placed {
+            preview,
+            response: Box::new(response),
+        })
     }
 
     /// Edit an existing order.
     ///
     /// Only the price and/or size can be modified.
     pub async fn edit(&self, request: EditOrderRequest) -> Result<EditOrderResponse> {
-        self.client.post("/orders/edit", &request).await
+        self.client.post(paths::ORDERS_EDIT, &request).await
     }
 
     /// Preview an order edit.
     pub async fn preview_edit(&self, request: EditOrderRequest) -> Result<serde_json::Value> {
-        self.client.post("/orders/edit_preview", &request).await
+        self.client.post(paths::ORDERS_EDIT_PREVIEW, &request).await
     }
 
     /// Cancel one or more orders.
     ///
+    /// Requests with more than [`MAX_CANCEL_ORDER_IDS`] order IDs are sent
+    /// as several batch requests, one after another; see
+    /// [`cancel_concurrent`](Self::cancel_concurrent).
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -105,7 +390,47 @@ impl<'a> OrdersApi<'a> {
     /// # }
     /// ```
     pub async fn cancel(&self, request: CancelOrdersRequest) -> Result<CancelOrdersResponse> {
-        self.client.post("/orders/batch_cancel", &request).await
+        self.cancel_concurrent(request, 1).await
+    }
+
+    /// Cancel orders, sending up to `concurrency` batch requests at once.
+    ///
+    /// Requests with more than [`MAX_CANCEL_ORDER_IDS`] order IDs are split
+    /// into chunks of that size and the results are merged in input order.
+    /// Every chunk is sent even if one fails; the first error is returned.
+    /// With [`rate_limiting`](crate::RestClientBuilder::rate_limiting)
+    /// enabled, the chunks are paced by the rate limiter.
+    pub async fn cancel_concurrent(
+        &self,
+        request: CancelOrdersRequest,
+        concurrency: usize,
+    ) -> Result<CancelOrdersResponse> {
+        if request.order_ids.len() <= MAX_CANCEL_ORDER_IDS {
+            return self.client.post(paths::ORDERS_BATCH_CANCEL, &request).await;
+        }
+
+        let chunks: Vec<CancelOrdersRequest> = request
+            .order_ids
+            .chunks(MAX_CANCEL_ORDER_IDS)
+            .map(|ids| CancelOrdersRequest::new(ids.to_vec()))
+            .collect();
+        tracing::debug!(
+            order_ids = request.order_ids.len(),
+            "splitting batch cancel into chunks"
+        );
+        let responses = Batcher::new(concurrency)
+            .run(chunks.into_iter().map(|chunk| async move {
+                self.client
+                    .post::<_, CancelOrdersResponse>(paths::ORDERS_BATCH_CANCEL, &chunk)
+                    .await
+            }))
+            .await;
+
+        let mut results = Vec::with_capacity(request.order_ids.len());
+        for response in responses {
+            results.extend(response?.results);
+        }
+        Ok(CancelOrdersResponse { results })
     }
 
     /// List orders.
@@ -113,7 +438,7 @@ impl<'a> OrdersApi<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use coinbase_advanced::{RestClient, Credentials, models::ListOrdersParams};
+    /// # use coinbase_advanced::{RestClient, Credentials, models::{ListOrdersParams, OrderStatus}};
     /// # async fn example() -> coinbase_advanced::Result<()> {
     /// let client = RestClient::builder()
     ///     .credentials(Credentials::from_env()?)
@@ -123,7 +448,7 @@ impl<'a> OrdersApi<'a> {
     /// let orders = client.orders()
     ///     .list(ListOrdersParams::new()
     ///         .product_id("BTC-USD")
-    ///         .status("OPEN")
+    ///         .status(OrderStatus::Open)
     ///         .limit(10))
     ///     .await?;
     ///
@@ -133,9 +458,10 @@ impl<'a> OrdersApi<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list(&self, params: ListOrdersParams) -> Result<ListOrdersResponse> {
+    pub async fn list(&self, mut params: ListOrdersParams) -> Result<ListOrdersResponse> {
+        self.client.apply_portfolio(&mut params.retail_portfolio_id);
         self.client
-            .get_with_query("/orders/historical/batch", &params)
+            .get_with_query_streamed(paths::ORDERS_HISTORICAL, &params)
             .await
     }
 
@@ -144,6 +470,102 @@ impl<'a> OrdersApi<'a> {
         self.list(ListOrdersParams::default()).await
     }
 
+    /// Fetch the page of orders a persisted cursor points to.
+    ///
+    /// `params` must be the parameters the cursor was returned for. See
+    /// [`Cursor`].
+    pub async fn resume(
+        &self,
+        params: ListOrdersParams,
+        cursor: &Cursor<ListOrdersParams>,
+    ) -> Result<ListOrdersResponse> {
+        self.list(cursor.apply(params)?).await
+    }
+
+    /// Stream every order matching `params`, fetching pages as they are needed.
+    ///
+    /// The stream is not `Unpin`; pin it before polling it with
+    /// `StreamExt::next` or `TryStreamExt::try_next`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials, models::{ListOrdersParams, OrderStatus}};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let orders = client.orders();
+    /// let mut stream = std::pin::pin!(orders.stream(ListOrdersParams::new().status(OrderStatus::Open)));
+    /// while let Some(order) = stream.try_next().await? {
+    ///     println!("{}: {}", order.order_id, order.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(&self, params: ListOrdersParams) -> impl Stream<Item = Result<Order>> + 'a {
+        let client = self.client;
+        stream::try_unfold(Some(params), move |params| async move {
+            let Some(params) = params else {
+                return Ok(None);
+            };
+            let page = client.orders().list(params.clone()).await?;
+            let next = match &page.cursor {
+                Some(cursor) if page.has_next_page() => Some(params.cursor(cursor.clone())),
+                _ => None,
+            };
+            Ok::<_, Error>(Some((stream::iter(page.orders.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
+
+    /// Count the orders matching `params` by walking every page.
+    pub async fn count(&self, params: ListOrdersParams) -> Result<usize> {
+        let mut count = 0;
+        let mut params = params;
+        loop {
+            let page = self.list(params.clone()).await?;
+            count += page.orders.len();
+            let has_next = page.has_next_page();
+            match page.cursor {
+                Some(cursor) if has_next => params = params.cursor(cursor),
+                _ => return Ok(count),
+            }
+        }
+    }
+
+    /// Collect up to `max_items` orders matching `params`.
+    ///
+    /// Stops fetching once enough orders have been collected, and never asks
+    /// for a page larger than the orders still needed or the `limit` in
+    /// `params`.
+    pub async fn collect_all(
+        &self,
+        params: ListOrdersParams,
+        max_items: usize,
+    ) -> Result<Vec<Order>> {
+        let page_limit = params.limit;
+        let mut params = params;
+        let mut orders = Vec::new();
+        while orders.len() < max_items {
+            let remaining = u32::try_from(max_items - orders.len()).unwrap_or(u32::MAX);
+            params.limit = Some(page_limit.map_or(remaining, |limit| limit.min(remaining)));
+
+            let page = self.list(params.clone()).await?;
+            let has_next = page.has_next_page();
+            orders.extend(page.orders);
+            match page.cursor {
+                Some(cursor) if has_next => params = params.cursor(cursor),
+                _ => break,
+            }
+        }
+        orders.truncate(max_items);
+        Ok(orders)
+    }
+
     /// Get a single order by ID.
     ///
     /// # Example
@@ -161,11 +583,186 @@ impl<'a> OrdersApi<'a> {
     /// # }
     /// ```
     pub async fn get(&self, order_id: &str) -> Result<Order> {
-        let endpoint = format!("/orders/historical/{}", order_id);
+        let endpoint = paths::order(order_id);
         let response: GetOrderResponse = self.client.get(&endpoint).await?;
         Ok(response.order)
     }
 
+    /// Get an order by the client order ID it was placed with.
+    ///
+    /// The order ID is looked up in the client's
+    /// [`order_id_store`](crate::RestClientBuilder::order_id_store); fails
+    /// if no store is set or it has no order for `client_order_id`.
+    pub async fn get_by_client_order_id(&self, client_order_id: &str) -> Result<Order> {
+        let store = self
+            .client
+            .order_id_store()
+            .ok_or_else(|| Error::config("No order ID store configured"))?;
+        let order_id = store.order_id(client_order_id)?.ok_or_else(|| {
+            Error::request(format!(
+                "No order ID recorded for client order ID {}",
+                client_order_id
+            ))
+        })?;
+        self.get(&order_id).await
+    }
+
+    /// Place an order and wait up to `timeout` for it to finish, cancelling
+    /// whatever is left after that.
+    ///
+    /// The order's status is polled with [`get`](Self::get), backing off
+    /// when the server rate limits the polls. Use
+    /// [`place_and_wait_on`](Self::place_and_wait_on) to watch the user
+    /// channel instead. Returns how much was filled, and at what price.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use coinbase_advanced::{RestClient, Credentials, models::{CreateOrderRequest, OrderSide, OrderConfiguration}};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let request = CreateOrderRequest::new(
+    ///     "my-order-id",
+    ///     "BTC-USD",
+    ///     OrderSide::Buy,
+    ///     OrderConfiguration::limit_gtc("0.001", "50000", false),
+    /// );
+    /// let summary = client
+    ///     .orders()
+    ///     .place_and_wait(request, Duration::from_secs(30))
+    ///     .await?;
+    /// println!("filled {} at {:?}", summary.filled_size, summary.average_filled_price);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn place_and_wait(
+        &self,
+        request: CreateOrderRequest,
+        timeout: Duration,
+    ) -> Result<FillSummary> {
+        self.place_and_wait_on(request, timeout, stream::empty())
+            .await
+    }
+
+    /// Place an order and wait up to `timeout` for it to finish, watching a
+    /// user channel stream for its status.
+    ///
+    /// `updates` should be subscribed to [`Channel::User`](crate::ws::Channel::User).
+    /// If it ends or fails, the order's status is polled instead, as in
+    /// [`place_and_wait`](Self::place_and_wait). Messages for other orders
+    /// are consumed and dropped.
+    pub async fn place_and_wait_on<S>(
+        &self,
+        request: CreateOrderRequest,
+        timeout: Duration,
+        mut updates: S,
+    ) -> Result<FillSummary>
+    where
+        S: Stream<Item = Result<Message>> + Unpin,
+    {
+        let deadline = Instant::now() + timeout;
+        let order_id = self.create(request).await?.into_order_id()?;
+
+        let finished = runtime::timeout_at(deadline, async {
+            if !watch_updates(&order_id, &mut updates).await {
+                self.poll_until_done(&order_id).await?;
+            }
+            Ok::<_, Error>(())
+        })
+        .await;
+        match finished {
+            Ok(result) => {
+                result?;
+                FillSummary::from_order(&self.get(&order_id).await?, false)
+            }
+            Err(_) => {
+                tracing::debug!(%order_id, "order not done before timeout, cancelling");
+                let response = self.cancel(CancelOrdersRequest::single(&order_id)).await?;
+                let cancelled = response.results.iter().any(|r| r.success);
+                FillSummary::from_order(&self.get(&order_id).await?, cancelled)
+            }
+        }
+    }
+
+    /// Poll an order until it reaches one of `statuses` or a terminal
+    /// status, and return it.
+    ///
+    /// For environments without WebSocket access; with it, watching the
+    /// user channel is faster and cheaper. Pass no statuses to wait for the
+    /// order to finish. See [`PollPolicy`] for how often the order is
+    /// polled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use coinbase_advanced::{RestClient, Credentials, models::OrderStatus, rest::PollPolicy};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let policy = PollPolicy::new().with_timeout(Duration::from_secs(60));
+    /// let order = client
+    ///     .orders()
+    ///     .wait_for_status("order-id", [OrderStatus::Open], policy)
+    ///     .await?;
+    /// println!("{} is {}", order.order_id, order.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_status(
+        &self,
+        order_id: &str,
+        statuses: impl IntoIterator<Item = OrderStatus>,
+        policy: PollPolicy,
+    ) -> Result<Order> {
+        let statuses: Vec<OrderStatus> = statuses.into_iter().collect();
+        let poll = async {
+            let mut interval = policy.initial_interval;
+            let mut last_status = None;
+            loop {
+                let wait = match self.get(order_id).await {
+                    Ok(order) => {
+                        let status = order.status.parse().unwrap_or(OrderStatus::Unknown);
+                        if status.is_terminal() || statuses.contains(&status) {
+                            return Ok(order);
+                        }
+                        if last_status.replace(status) == Some(status) {
+                            interval = (interval * 2).min(policy.max_interval);
+                        } else {
+                            interval = policy.initial_interval;
+                        }
+                        interval
+                    }
+                    Err(e) if e.is_rate_limited() => {
+                        interval = (interval * 2).min(policy.max_interval);
+                        e.retry_after().unwrap_or(interval)
+                    }
+                    Err(e) => return Err(e),
+                };
+                runtime::sleep(wait).await;
+            }
+        };
+        match policy.timeout {
+            Some(timeout) => runtime::timeout_at(Instant::now() + timeout, poll)
+                .await
+                .unwrap_or(Err(Error::DeadlineExceeded)),
+            None => poll.await,
+        }
+    }
+
+    /// Poll an order's status until it is terminal.
+    async fn poll_until_done(&self, order_id: &str) -> Result<()> {
+        self.wait_for_status(order_id, [], PollPolicy::fixed(FILL_POLL_INTERVAL))
+            .await
+            .map(|_| ())
+    }
+
     /// List order fills (executions).
     ///
     /// # Example
@@ -188,12 +785,25 @@ impl<'a> OrdersApi<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list_fills(&self, params: ListFillsParams) -> Result<ListFillsResponse> {
+    pub async fn list_fills(&self, mut params: ListFillsParams) -> Result<ListFillsResponse> {
+        self.client.apply_portfolio(&mut params.retail_portfolio_id);
         self.client
-            .get_with_query("/orders/historical/fills", &params)
+            .get_with_query_streamed(paths::FILLS, &params)
             .await
     }
 
+    /// Fetch the page of fills a persisted cursor points to.
+    ///
+    /// `params` must be the parameters the cursor was returned for. See
+    /// [`Cursor`].
+    pub async fn resume_fills(
+        &self,
+        params: ListFillsParams,
+        cursor: &Cursor<ListFillsParams>,
+    ) -> Result<ListFillsResponse> {
+        self.list_fills(cursor.apply(params)?).await
+    }
+
     /// Close a position.
     ///
     /// This creates a market order to close an existing position.
@@ -201,6 +811,116 @@ impl<'a> OrdersApi<'a> {
         &self,
         request: ClosePositionRequest,
     ) -> Result<CreateOrderResponse> {
-        self.client.post("/orders/close_position", &request).await
+        self.client.post(paths::CLOSE_POSITION, &request).await
+    }
+}
+
+/// The order calls shared by the live and paper trading clients.
+///
+/// Implemented by [`OrdersApi`] and, with the `paper` feature, by
+/// `PaperOrders`, so strategy code written against this trait runs unchanged
+/// in either mode.
+///
+/// The live implementation is not available on wasm32, where requests are not
+/// `Send`.
+pub trait OrdersClient {
+    /// Place an order.
+    fn create(
+        &self,
+        request: CreateOrderRequest,
+    ) -> impl Future<Output = Result<CreateOrderResponse>> + Send;
+
+    /// Cancel one or more orders.
+    fn cancel(
+        &self,
+        request: CancelOrdersRequest,
+    ) -> impl Future<Output = Result<CancelOrdersResponse>> + Send;
+
+    /// List orders.
+    fn list(
+        &self,
+        params: ListOrdersParams,
+    ) -> impl Future<Output = Result<ListOrdersResponse>> + Send;
+
+    /// List all orders with default parameters.
+    fn list_all(&self) -> impl Future<Output = Result<ListOrdersResponse>> + Send {
+        self.list(ListOrdersParams::default())
+    }
+
+    /// Get an order by ID.
+    fn get(&self, order_id: &str) -> impl Future<Output = Result<Order>> + Send;
+
+    /// Get an order by the client order ID it was placed with.
+    fn get_by_client_order_id(
+        &self,
+        client_order_id: &str,
+    ) -> impl Future<Output = Result<Order>> + Send;
+
+    /// List fills.
+    fn list_fills(
+        &self,
+        params: ListFillsParams,
+    ) -> impl Future<Output = Result<ListFillsResponse>> + Send;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl OrdersClient for OrdersApi<'_> {
+    async fn create(&self, request: CreateOrderRequest) -> Result<CreateOrderResponse> {
+        OrdersApi::create(self, request).await
+    }
+
+    async fn cancel(&self, request: CancelOrdersRequest) -> Result<CancelOrdersResponse> {
+        OrdersApi::cancel(self, request).await
+    }
+
+    async fn list(&self, params: ListOrdersParams) -> Result<ListOrdersResponse> {
+        OrdersApi::list(self, params).await
+    }
+
+    async fn get(&self, order_id: &str) -> Result<Order> {
+        OrdersApi::get(self, order_id).await
+    }
+
+    async fn get_by_client_order_id(&self, client_order_id: &str) -> Result<Order> {
+        OrdersApi::get_by_client_order_id(self, client_order_id).await
+    }
+
+    async fn list_fills(&self, params: ListFillsParams) -> Result<ListFillsResponse> {
+        OrdersApi::list_fills(self, params).await
+    }
+}
+
+/// Wait for a user channel update saying the order is done.
+///
+/// Returns false if the stream ended or failed first.
+async fn watch_updates<S>(order_id: &str, updates: &mut S) -> bool
+where
+    S: Stream<Item = Result<Message>> + Unpin,
+{
+    while let Some(message) = updates.next().await {
+        let message = match message {
+            Ok(message) => message,
+            Err(e) => {
+                tracing::warn!(%order_id, error = %e, "user channel failed, polling order status");
+                return false;
+            }
+        };
+        let Events::User(events) = &message.events else {
+            continue;
+        };
+        let done = events
+            .iter()
+            .flat_map(|event| &event.orders)
+            .filter(|order| order.order_id == order_id)
+            .any(|order| {
+                order
+                    .status
+                    .parse()
+                    .is_ok_and(|status: OrderStatus| status.is_terminal())
+            });
+        if done {
+            return true;
+        }
     }
+    false
 }
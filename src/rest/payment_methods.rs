@@ -1,12 +1,17 @@
 //! Payment Methods API endpoints.
 
 use crate::client::RestClient;
+use crate::constants::paths;
 use crate::error::Result;
-use crate::models::{GetPaymentMethodResponse, ListPaymentMethodsResponse, PaymentMethod};
+use crate::models::{
+    GetPaymentMethodResponse, ListPaymentMethodsResponse, PaymentMethod, Transfer, TransferKind,
+    TransferRequest, TransferResponse,
+};
 
 /// API for managing payment methods.
 ///
-/// This API provides endpoints for listing and retrieving payment methods.
+/// This API provides endpoints for listing and retrieving payment methods,
+/// and for depositing and withdrawing fiat funds with them.
 pub struct PaymentMethodsApi<'a> {
     client: &'a RestClient,
 }
@@ -36,7 +41,7 @@ impl<'a> PaymentMethodsApi<'a> {
     /// # }
     /// ```
     pub async fn list(&self) -> Result<Vec<PaymentMethod>> {
-        let response: ListPaymentMethodsResponse = self.client.get("/payment_methods").await?;
+        let response: ListPaymentMethodsResponse = self.client.get(paths::PAYMENT_METHODS).await?;
         Ok(response.payment_methods)
     }
 
@@ -57,8 +62,90 @@ impl<'a> PaymentMethodsApi<'a> {
     /// # }
     /// ```
     pub async fn get(&self, payment_method_id: &str) -> Result<PaymentMethod> {
-        let endpoint = format!("/payment_methods/{}", payment_method_id);
+        let endpoint = paths::payment_method(payment_method_id);
         let response: GetPaymentMethodResponse = self.client.get(&endpoint).await?;
         Ok(response.payment_method)
     }
+
+    /// Deposit fiat funds from a payment method into an account.
+    ///
+    /// Deposits use the Coinbase App (v2) API.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials, models::TransferRequest};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let request = TransferRequest::new("100.00", "USD", "payment-method-id").commit(true);
+    /// let deposit = client.payment_methods().deposit("usd-account-id", &request).await?;
+    /// println!("Deposit {}: {:?}", deposit.id, deposit.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn deposit(&self, account_id: &str, request: &TransferRequest) -> Result<Transfer> {
+        self.create_transfer(account_id, TransferKind::Deposit, request)
+            .await
+    }
+
+    /// Withdraw fiat funds from an account to a payment method.
+    ///
+    /// Withdrawals use the Coinbase App (v2) API.
+    pub async fn withdraw(&self, account_id: &str, request: &TransferRequest) -> Result<Transfer> {
+        self.create_transfer(account_id, TransferKind::Withdrawal, request)
+            .await
+    }
+
+    /// Get a deposit or withdrawal by ID.
+    pub async fn get_transfer(
+        &self,
+        account_id: &str,
+        kind: TransferKind,
+        transfer_id: &str,
+    ) -> Result<Transfer> {
+        let endpoint = paths::account_transfer(account_id, kind, transfer_id);
+        let response: TransferResponse = self.client.get(&endpoint).await?;
+        Ok(response.data)
+    }
+
+    /// Commit a deposit or withdrawal that was created without `commit`.
+    pub async fn commit_transfer(
+        &self,
+        account_id: &str,
+        kind: TransferKind,
+        transfer_id: &str,
+    ) -> Result<Transfer> {
+        let endpoint = paths::commit_transfer(account_id, kind, transfer_id);
+        let response: TransferResponse =
+            self.client.post(&endpoint, &serde_json::json!({})).await?;
+        Ok(response.data)
+    }
+
+    /// Fetch the request's payment method and validate the request against it.
+    ///
+    /// Returns the payment method if the transfer is allowed and within the
+    /// reported limits. See [`TransferRequest::validate`].
+    pub async fn validate_transfer(
+        &self,
+        request: &TransferRequest,
+        kind: TransferKind,
+    ) -> Result<PaymentMethod> {
+        let payment_method = self.get(&request.payment_method).await?;
+        request.validate(&payment_method, kind)?;
+        Ok(payment_method)
+    }
+
+    async fn create_transfer(
+        &self,
+        account_id: &str,
+        kind: TransferKind,
+        request: &TransferRequest,
+    ) -> Result<Transfer> {
+        let endpoint = paths::account_transfers(account_id, kind);
+        let response: TransferResponse = self.client.post(&endpoint, request).await?;
+        Ok(response.data)
+    }
 }
@@ -1,16 +1,29 @@
 //! Perpetuals/INTX API endpoints.
 
 use crate::client::RestClient;
+use crate::constants::paths;
 use crate::error::Result;
 use crate::models::{
     AllocatePortfolioRequest, GetPerpetualsPortfolioSummaryResponse, GetPerpetualsPositionResponse,
-    GetPortfolioBalancesResponse, IntxPortfolioSummary, IntxPosition,
-    ListPerpetualsPositionsResponse, SetMultiAssetCollateralRequest,
+    GetPortfolioBalancesResponse, IntxPortfolioMargin, IntxPortfolioSummary, IntxPosition,
+    ListPerpetualsPositionsResponse, ListPortfoliosParams, MultiAssetCollateral,
+    MultiAssetCollateralQuery, Portfolio, SetMultiAssetCollateralRequest,
 };
 
 /// API for perpetuals (INTX) trading.
 ///
 /// This API provides endpoints for managing perpetual futures positions and portfolios.
+///
+/// Margin state for a portfolio is reported by
+/// [`get_portfolio_margin`](Self::get_portfolio_margin) (margin requirements,
+/// borrowing and liquidation status),
+/// [`get_portfolio_summary`](Self::get_portfolio_summary) (buying power) and,
+/// per asset, [`get_portfolio_balances`](Self::get_portfolio_balances)
+/// (collateral value).
+///
+/// The Advanced Trade API documents no endpoints for INTX funding history or
+/// asset details, so they are not covered here. They are only available
+/// through the Coinbase International Exchange API.
 pub struct PerpetualsApi<'a> {
     client: &'a RestClient,
 }
@@ -43,7 +56,7 @@ impl<'a> PerpetualsApi<'a> {
         &self,
         portfolio_uuid: &str,
     ) -> Result<ListPerpetualsPositionsResponse> {
-        let endpoint = format!("/intx/positions/{}", portfolio_uuid);
+        let endpoint = paths::perpetuals_positions(portfolio_uuid);
         self.client.get(&endpoint).await
     }
 
@@ -66,7 +79,7 @@ impl<'a> PerpetualsApi<'a> {
     /// # }
     /// ```
     pub async fn get_position(&self, portfolio_uuid: &str, symbol: &str) -> Result<IntxPosition> {
-        let endpoint = format!("/intx/positions/{}/{}", portfolio_uuid, symbol);
+        let endpoint = paths::perpetuals_position(portfolio_uuid, symbol);
         let response: GetPerpetualsPositionResponse = self.client.get(&endpoint).await?;
         Ok(response.position)
     }
@@ -95,7 +108,7 @@ impl<'a> PerpetualsApi<'a> {
         &self,
         portfolio_uuid: &str,
     ) -> Result<GetPortfolioBalancesResponse> {
-        let endpoint = format!("/intx/balances/{}", portfolio_uuid);
+        let endpoint = paths::perpetuals_balances(portfolio_uuid);
         self.client.get(&endpoint).await
     }
 
@@ -121,11 +134,52 @@ impl<'a> PerpetualsApi<'a> {
         &self,
         portfolio_uuid: &str,
     ) -> Result<IntxPortfolioSummary> {
-        let endpoint = format!("/intx/portfolio/{}", portfolio_uuid);
+        let endpoint = paths::perpetuals_portfolio(portfolio_uuid);
         let response: GetPerpetualsPortfolioSummaryResponse = self.client.get(&endpoint).await?;
         Ok(response.summary)
     }
 
+    /// Get the margin and borrowing state of a perpetuals portfolio.
+    ///
+    /// Returns `None` if the API reports no margin details for the portfolio.
+    /// A single entry without a portfolio UUID is taken to be the requested
+    /// portfolio; an entry for any other portfolio never is.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// if let Some(margin) = client.perpetuals()
+    ///     .get_portfolio_margin("portfolio-uuid")
+    ///     .await?
+    /// {
+    ///     println!("Borrowed: {:?}, status: {:?}", margin.borrow, margin.liquidation_status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_portfolio_margin(
+        &self,
+        portfolio_uuid: &str,
+    ) -> Result<Option<IntxPortfolioMargin>> {
+        let endpoint = paths::perpetuals_portfolio(portfolio_uuid);
+        let response: GetPerpetualsPortfolioSummaryResponse = self.client.get(&endpoint).await?;
+        let mut portfolios = response.portfolios;
+        if let [portfolio] = portfolios.as_slice() {
+            if portfolio.portfolio_uuid.is_none() {
+                return Ok(portfolios.pop());
+            }
+        }
+        Ok(portfolios
+            .into_iter()
+            .find(|p| p.portfolio_uuid.as_deref() == Some(portfolio_uuid)))
+    }
+
     /// Allocate funds to a perpetuals portfolio.
     ///
     /// # Example
@@ -149,11 +203,16 @@ impl<'a> PerpetualsApi<'a> {
     /// # }
     /// ```
     pub async fn allocate(&self, request: AllocatePortfolioRequest) -> Result<()> {
-        let _response: serde_json::Value = self.client.post("/intx/allocate", &request).await?;
+        let _response: serde_json::Value = self
+            .client
+            .post(paths::PERPETUALS_ALLOCATE, &request)
+            .await?;
         Ok(())
     }
 
-    /// Set multi-asset collateral for a portfolio.
+    /// Opt a portfolio in or out of multi-asset collateral.
+    ///
+    /// Returns the collateral state after the change.
     ///
     /// # Example
     ///
@@ -165,19 +224,77 @@ impl<'a> PerpetualsApi<'a> {
     ///     .build()?;
     ///
     /// let request = SetMultiAssetCollateralRequest::new(true);
-    /// client.perpetuals()
+    /// let state = client.perpetuals()
     ///     .set_multi_asset_collateral("portfolio-uuid", request)
     ///     .await?;
+    /// assert!(state.multi_asset_collateral_enabled);
     /// # Ok(())
     /// # }
     /// ```
     pub async fn set_multi_asset_collateral(
         &self,
         portfolio_uuid: &str,
-        request: SetMultiAssetCollateralRequest,
-    ) -> Result<()> {
-        let endpoint = format!("/intx/balances/{}", portfolio_uuid);
-        let _response: serde_json::Value = self.client.post(&endpoint, &request).await?;
-        Ok(())
+        mut request: SetMultiAssetCollateralRequest,
+    ) -> Result<MultiAssetCollateral> {
+        if request.portfolio_uuid.is_none() {
+            request.portfolio_uuid = Some(portfolio_uuid.to_string());
+        }
+        self.client
+            .post(paths::PERPETUALS_MULTI_ASSET_COLLATERAL, &request)
+            .await
+    }
+
+    /// Get whether multi-asset collateral is enabled for a portfolio.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let state = client.perpetuals()
+    ///     .get_multi_asset_collateral("portfolio-uuid")
+    ///     .await?;
+    /// println!("Multi-asset collateral: {}", state.multi_asset_collateral_enabled);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_multi_asset_collateral(
+        &self,
+        portfolio_uuid: &str,
+    ) -> Result<MultiAssetCollateral> {
+        self.client
+            .get_with_query(
+                paths::PERPETUALS_MULTI_ASSET_COLLATERAL,
+                &MultiAssetCollateralQuery { portfolio_uuid },
+            )
+            .await
+    }
+
+    /// List the INTX (perpetuals) portfolios.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// for portfolio in client.perpetuals().list_portfolios().await? {
+    ///     println!("{}: {}", portfolio.uuid, portfolio.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_portfolios(&self) -> Result<Vec<Portfolio>> {
+        self.client
+            .portfolios()
+            .list_with_params(ListPortfoliosParams::new().portfolio_type("INTX"))
+            .await
     }
 }
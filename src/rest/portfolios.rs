@@ -1,6 +1,7 @@
 //! Portfolios API endpoints.
 
 use crate::client::RestClient;
+use crate::constants::paths;
 use crate::error::Result;
 use crate::models::{
     CreatePortfolioRequest, EditPortfolioRequest, GetPortfolioBreakdownResponse,
@@ -60,8 +61,10 @@ impl<'a> PortfoliosApi<'a> {
     /// # }
     /// ```
     pub async fn list_with_params(&self, params: ListPortfoliosParams) -> Result<Vec<Portfolio>> {
-        let response: ListPortfoliosResponse =
-            self.client.get_with_query("/portfolios", &params).await?;
+        let response: ListPortfoliosResponse = self
+            .client
+            .get_with_query(paths::PORTFOLIOS, &params)
+            .await?;
         Ok(response.portfolios)
     }
 
@@ -82,7 +85,7 @@ impl<'a> PortfoliosApi<'a> {
     /// # }
     /// ```
     pub async fn get_breakdown(&self, portfolio_uuid: &str) -> Result<PortfolioBreakdown> {
-        let endpoint = format!("/portfolios/{}", portfolio_uuid);
+        let endpoint = paths::portfolio(portfolio_uuid);
         let response: GetPortfolioBreakdownResponse = self.client.get(&endpoint).await?;
         Ok(response.breakdown)
     }
@@ -106,7 +109,7 @@ impl<'a> PortfoliosApi<'a> {
     /// # }
     /// ```
     pub async fn create(&self, request: CreatePortfolioRequest) -> Result<Portfolio> {
-        let response: PortfolioResponse = self.client.post("/portfolios", &request).await?;
+        let response: PortfolioResponse = self.client.post(paths::PORTFOLIOS, &request).await?;
         Ok(response.portfolio)
     }
 
@@ -133,7 +136,7 @@ impl<'a> PortfoliosApi<'a> {
         portfolio_uuid: &str,
         request: EditPortfolioRequest,
     ) -> Result<Portfolio> {
-        let endpoint = format!("/portfolios/{}", portfolio_uuid);
+        let endpoint = paths::portfolio(portfolio_uuid);
         let response: PortfolioResponse = self.client.put(&endpoint, &request).await?;
         Ok(response.portfolio)
     }
@@ -154,7 +157,7 @@ impl<'a> PortfoliosApi<'a> {
     /// # }
     /// ```
     pub async fn delete(&self, portfolio_uuid: &str) -> Result<()> {
-        let endpoint = format!("/portfolios/{}", portfolio_uuid);
+        let endpoint = paths::portfolio(portfolio_uuid);
         let _response: serde_json::Value = self.client.delete(&endpoint).await?;
         Ok(())
     }
@@ -181,6 +184,8 @@ impl<'a> PortfoliosApi<'a> {
     /// # }
     /// ```
     pub async fn move_funds(&self, request: MoveFundsRequest) -> Result<MoveFundsResponse> {
-        self.client.post("/portfolios/move_funds", &request).await
+        self.client
+            .post(paths::PORTFOLIOS_MOVE_FUNDS, &request)
+            .await
     }
 }
@@ -1,13 +1,19 @@
 //! Products API endpoints.
 
 use crate::client::RestClient;
+use crate::constants::paths;
 use crate::error::Result;
 use crate::models::{
     Candle, GetBestBidAskParams, GetBestBidAskResponse, GetCandlesParams, GetCandlesResponse,
     GetMarketTradesParams, GetMarketTradesResponse, GetProductBookParams, GetProductBookResponse,
-    ListProductsParams, ListProductsResponse, Product, ProductBook,
+    Granularity, ListProductsParams, ListProductsResponse, Product, ProductBook, ProductSnapshot,
+    ProductSnapshotParams,
 };
 
+/// Number of candles requested per page by
+/// [`ProductsApi::get_candles_range`]. The API returns at most 350.
+const CANDLES_PER_PAGE: u64 = 300;
+
 /// API for accessing product and market data.
 ///
 /// Products represent trading pairs (e.g., BTC-USD).
@@ -44,7 +50,9 @@ impl<'a> ProductsApi<'a> {
     /// # }
     /// ```
     pub async fn list(&self, params: ListProductsParams) -> Result<ListProductsResponse> {
-        self.client.get_with_query("/products", &params).await
+        self.client
+            .get_with_query_streamed(paths::PRODUCTS, &params)
+            .await
     }
 
     /// List all products with default parameters.
@@ -73,7 +81,7 @@ impl<'a> ProductsApi<'a> {
     /// # }
     /// ```
     pub async fn get(&self, product_id: &str) -> Result<Product> {
-        let endpoint = format!("/products/{}", product_id);
+        let endpoint = paths::product(product_id);
         self.client.get(&endpoint).await
     }
 
@@ -99,8 +107,10 @@ impl<'a> ProductsApi<'a> {
     /// # }
     /// ```
     pub async fn get_book(&self, params: GetProductBookParams) -> Result<ProductBook> {
-        let response: GetProductBookResponse =
-            self.client.get_with_query("/product_book", &params).await?;
+        let response: GetProductBookResponse = self
+            .client
+            .get_with_query(paths::PRODUCT_BOOK, &params)
+            .await?;
         Ok(response.pricebook)
     }
 
@@ -125,7 +135,9 @@ impl<'a> ProductsApi<'a> {
         &self,
         params: GetBestBidAskParams,
     ) -> Result<GetBestBidAskResponse> {
-        self.client.get_with_query("/best_bid_ask", &params).await
+        self.client
+            .get_with_query(paths::BEST_BID_ASK, &params)
+            .await
     }
 
     /// Get candlestick (OHLCV) data for a product.
@@ -151,11 +163,103 @@ impl<'a> ProductsApi<'a> {
     /// # }
     /// ```
     pub async fn get_candles(&self, params: GetCandlesParams) -> Result<Vec<Candle>> {
-        let endpoint = format!("/products/{}/candles", params.product_id);
+        let endpoint = paths::product_candles(&params.product_id);
         let response: GetCandlesResponse = self.client.get_with_query(&endpoint, &params).await?;
         Ok(response.candles)
     }
 
+    /// Get every candle starting in `[start, end)` (Unix seconds), oldest first.
+    ///
+    /// Ranges longer than a single request allows are fetched in pages of
+    /// 300 candles, aligned to multiples of the page length. With the `cache`
+    /// feature and a [cache](crate::RestClientBuilder::cache) set on the
+    /// client, pages whose candles have all closed are stored and read back
+    /// instead of being downloaded again.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, models::Granularity};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder().build()?;
+    ///
+    /// // All of January 2024 in hourly candles.
+    /// let candles = client.products()
+    ///     .get_candles_range("BTC-USD", 1704067200, 1706745600, Granularity::OneHour)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_candles_range(
+        &self,
+        product_id: &str,
+        start: u64,
+        end: u64,
+        granularity: Granularity,
+    ) -> Result<Vec<Candle>> {
+        let span = granularity.as_seconds() * CANDLES_PER_PAGE;
+
+        let mut candles = Vec::new();
+        let mut page_start = start - start % span;
+        while page_start < end {
+            let page = self
+                .candles_page(product_id, page_start, page_start + span, granularity)
+                .await?;
+            candles.extend(
+                page.into_iter()
+                    .filter(|c| candle_start(c).is_some_and(|t| t >= start && t < end)),
+            );
+            page_start += span;
+        }
+        candles.sort_by_key(candle_start);
+        Ok(candles)
+    }
+
+    /// Get the candles starting in `[page_start, page_end)`, from the cache if
+    /// the page has closed and is stored there.
+    async fn candles_page(
+        &self,
+        product_id: &str,
+        page_start: u64,
+        page_end: u64,
+        granularity: Granularity,
+    ) -> Result<Vec<Candle>> {
+        #[cfg(feature = "cache")]
+        let cached = {
+            let now = crate::runtime::SystemTime::now()
+                .duration_since(crate::runtime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            // Leave a candle's grace period for the exchange to finalize the last one.
+            let closed = page_end + granularity.as_seconds() <= now;
+            self.client.cache().filter(|_| closed).map(|cache| {
+                let key = format!("candles/{}/{}/{}", product_id, granularity, page_start);
+                (cache, key)
+            })
+        };
+        #[cfg(feature = "cache")]
+        if let Some((cache, key)) = &cached {
+            if let Some(candles) = crate::cache::get_json(*cache, key) {
+                return Ok(candles);
+            }
+        }
+
+        let params = GetCandlesParams::new(
+            product_id,
+            page_start.to_string(),
+            page_end.to_string(),
+            granularity,
+        );
+        let mut candles = self.get_candles(params).await?;
+        candles.retain(|c| candle_start(c).is_some_and(|t| t < page_end));
+
+        #[cfg(feature = "cache")]
+        if let Some((cache, key)) = &cached {
+            crate::cache::put_json(*cache, key, &candles);
+        }
+        Ok(candles)
+    }
+
     /// Get recent trades for a product.
     ///
     /// # Example
@@ -181,7 +285,78 @@ impl<'a> ProductsApi<'a> {
         &self,
         params: GetMarketTradesParams,
     ) -> Result<GetMarketTradesResponse> {
-        let endpoint = format!("/products/{}/ticker", params.product_id);
+        let endpoint = paths::product_ticker(&params.product_id);
         self.client.get_with_query(&endpoint, &params).await
     }
+
+    /// Get a product's details, best bid/ask, last 60 one-minute candles and
+    /// last 100 trades in one call.
+    ///
+    /// See [`snapshot_with`](Self::snapshot_with) to choose the candles and
+    /// trades.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, Credentials};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder()
+    ///     .credentials(Credentials::from_env()?)
+    ///     .build()?;
+    ///
+    /// let snapshot = client.products().snapshot("BTC-USD").await?;
+    /// println!("{} @ {}", snapshot.product.product_id, snapshot.product.price);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn snapshot(&self, product_id: &str) -> Result<ProductSnapshot> {
+        self.snapshot_with(ProductSnapshotParams::new(product_id))
+            .await
+    }
+
+    /// Get a product's details, best bid/ask, recent candles and recent
+    /// trades in one call.
+    ///
+    /// The four requests are made concurrently and share the client's rate
+    /// limiter. The first error fails the whole snapshot.
+    pub async fn snapshot_with(&self, params: ProductSnapshotParams) -> Result<ProductSnapshot> {
+        let end = crate::runtime::SystemTime::now()
+            .duration_since(crate::runtime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let start = end.saturating_sub(params.granularity.as_seconds() * u64::from(params.candles));
+        let candle_params = GetCandlesParams::new(
+            &params.product_id,
+            start.to_string(),
+            end.to_string(),
+            params.granularity,
+        )
+        .limit(params.candles);
+
+        let (product, best_bid_ask, mut candles, trades) = futures::try_join!(
+            self.get(&params.product_id),
+            self.get_best_bid_ask(GetBestBidAskParams::new().product_ids([&params.product_id])),
+            self.get_candles(candle_params),
+            self.get_market_trades(GetMarketTradesParams::new(
+                &params.product_id,
+                params.trades
+            )),
+        )?;
+        candles.sort_by_key(candle_start);
+
+        Ok(ProductSnapshot {
+            product,
+            best_bid_ask: best_bid_ask
+                .pricebooks
+                .into_iter()
+                .find(|b| b.product_id == params.product_id),
+            candles,
+            trades: trades.trades,
+        })
+    }
+}
+
+/// Start of a candle in Unix seconds.
+fn candle_start(candle: &Candle) -> Option<u64> {
+    candle.start.parse().ok()
 }
@@ -1,17 +1,19 @@
 //! Public (unauthenticated) API endpoints.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::client::RestClient;
+use crate::constants::paths;
 use crate::error::Result;
 use crate::models::{
-    Candle, GetCandlesParams, GetCandlesResponse, GetMarketTradesParams, GetMarketTradesResponse,
-    GetProductBookParams, GetProductBookResponse, ListProductsParams, ListProductsResponse,
-    Product, ProductBook,
+    Candle, GetBestBidAskParams, GetBestBidAskResponse, GetCandlesParams, GetCandlesResponse,
+    GetMarketTradesParams, GetMarketTradesResponse, GetProductBookParams, GetProductBookResponse,
+    ListProductsParams, ListProductsResponse, Product, ProductBook,
 };
 
 /// Server time response.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct ServerTime {
     /// ISO 8601 formatted time.
     pub iso: String,
@@ -55,7 +57,7 @@ impl<'a> PublicApi<'a> {
     /// # }
     /// ```
     pub async fn get_time(&self) -> Result<ServerTime> {
-        self.client.public_get("/time").await
+        self.client.public_get(paths::TIME).await
     }
 
     /// List all public products.
@@ -64,7 +66,7 @@ impl<'a> PublicApi<'a> {
     /// require credentials.
     pub async fn list_products(&self, params: ListProductsParams) -> Result<ListProductsResponse> {
         self.client
-            .public_get_with_query("/market/products", &params)
+            .public_get_with_query(paths::MARKET_PRODUCTS, &params)
             .await
     }
 
@@ -75,7 +77,7 @@ impl<'a> PublicApi<'a> {
 
     /// Get a single product by ID.
     pub async fn get_product(&self, product_id: &str) -> Result<Product> {
-        let endpoint = format!("/market/products/{}", product_id);
+        let endpoint = paths::market_product(product_id);
         self.client.public_get(&endpoint).await
     }
 
@@ -83,14 +85,38 @@ impl<'a> PublicApi<'a> {
     pub async fn get_product_book(&self, params: GetProductBookParams) -> Result<ProductBook> {
         let response: GetProductBookResponse = self
             .client
-            .public_get_with_query("/market/product_book", &params)
+            .public_get_with_query(paths::MARKET_PRODUCT_BOOK, &params)
             .await?;
         Ok(response.pricebook)
     }
 
+    /// Get the best bid and ask for one or more products.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use coinbase_advanced::{RestClient, models::GetBestBidAskParams};
+    /// # async fn example() -> coinbase_advanced::Result<()> {
+    /// let client = RestClient::builder().build()?;
+    ///
+    /// let response = client.public()
+    ///     .get_best_bid_ask(GetBestBidAskParams::new().product_ids(&["BTC-USD", "ETH-USD"]))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_best_bid_ask(
+        &self,
+        params: GetBestBidAskParams,
+    ) -> Result<GetBestBidAskResponse> {
+        self.client
+            .public_get_with_query(paths::MARKET_BEST_BID_ASK, &params)
+            .await
+    }
+
     /// Get candlestick (OHLCV) data for a product.
     pub async fn get_candles(&self, params: GetCandlesParams) -> Result<Vec<Candle>> {
-        let endpoint = format!("/market/products/{}/candles", params.product_id);
+        let endpoint = paths::market_product_candles(&params.product_id);
         let response: GetCandlesResponse = self
             .client
             .public_get_with_query(&endpoint, &params)
@@ -103,7 +129,7 @@ impl<'a> PublicApi<'a> {
         &self,
         params: GetMarketTradesParams,
     ) -> Result<GetMarketTradesResponse> {
-        let endpoint = format!("/market/products/{}/ticker", params.product_id);
+        let endpoint = paths::market_product_ticker(&params.product_id);
         self.client.public_get_with_query(&endpoint, &params).await
     }
 }
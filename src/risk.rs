@@ -0,0 +1,738 @@
+//! Client-side risk limits for order placement.
+//!
+//! A [`RiskEngine`] holds a set of [`RiskLimits`] and the state needed to
+//! enforce them: the open orders of each product and the fills of the
+//! current UTC day. Orders placed through [`RiskCheckedOrders`] are checked
+//! against the limits first and rejected with [`Error::Risk`] carrying a
+//! [`RiskViolation`] before anything is sent.
+//!
+//! Checks are made under a lock and reserve a slot for the order until the
+//! exchange answers, so concurrent tasks cannot all pass the open order limit.
+//!
+//! The engine only sees orders placed through it. Call
+//! [`RiskCheckedOrders::sync`] at startup to load orders that are already
+//! open, and feed it the user channel with [`RiskEngine::observe`] so fills
+//! and orders closed elsewhere are counted.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::models::{CreateOrderRequest, OrderConfiguration, OrderSide};
+//! use coinbase_advanced::risk::{RiskEngine, RiskLimits};
+//! use coinbase_advanced::{Credentials, Error, RestClient};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder()
+//!     .credentials(Credentials::from_env()?)
+//!     .build()?;
+//! let engine = RiskEngine::new(
+//!     RiskLimits::new()
+//!         .max_open_orders_per_product(5)
+//!         .max_order_notional("1000".parse().unwrap())
+//!         .max_daily_loss("250".parse().unwrap()),
+//! );
+//!
+//! let orders = client.risk_checked(&engine);
+//! orders.sync().await?;
+//!
+//! let request = CreateOrderRequest::new(
+//!     "my-order-id",
+//!     "BTC-USD",
+//!     OrderSide::Buy,
+//!     OrderConfiguration::limit_gtc("0.01", "50000", false),
+//! );
+//! match orders.create(request).await {
+//!     Ok(response) => println!("placed {:?}", response.order_id),
+//!     Err(Error::Risk(violation)) => println!("rejected: {}", violation),
+//!     Err(e) => return Err(e),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+
+use crate::client::RestClient;
+use crate::error::{Error, Result};
+use crate::models::{
+    CancelOrdersRequest, CancelOrdersResponse, CreateOrderRequest, CreateOrderResponse,
+    ListOrdersParams, OrderStatus,
+};
+use crate::pnl::{LotMethod, PnlCalculator, TradeFill};
+use crate::ws::{Events, Message};
+
+/// Limits enforced by a [`RiskEngine`].
+///
+/// The default has no limits.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RiskLimits {
+    max_open_orders_per_product: Option<usize>,
+    max_order_notional: Option<Decimal>,
+    max_daily_loss: Option<Decimal>,
+}
+
+impl RiskLimits {
+    /// Create limits that allow every order.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject orders for products that already have `max` open orders.
+    pub fn max_open_orders_per_product(mut self, max: usize) -> Self {
+        self.max_open_orders_per_product = Some(max);
+        self
+    }
+
+    /// Reject orders worth more than `max` in the product's quote currency.
+    ///
+    /// Orders are valued as by
+    /// [`OrderGuard::max_order_notional`](crate::guard::OrderGuard::max_order_notional).
+    pub fn max_order_notional(mut self, max: Decimal) -> Self {
+        self.max_order_notional = Some(max);
+        self
+    }
+
+    /// Reject every order once the realized loss of the current UTC day,
+    /// after commissions, reaches `max`.
+    pub fn max_daily_loss(mut self, max: Decimal) -> Self {
+        self.max_daily_loss = Some(max);
+        self
+    }
+}
+
+/// Why an order was rejected by a [`RiskEngine`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RiskViolation {
+    /// The product already has the most open orders allowed.
+    TooManyOpenOrders {
+        /// Product ID.
+        product_id: String,
+        /// Orders currently open.
+        open: usize,
+        /// The limit.
+        limit: usize,
+    },
+    /// The order is worth more than allowed.
+    OrderNotional {
+        /// Product ID.
+        product_id: String,
+        /// Value of the order in the quote currency.
+        notional: Decimal,
+        /// The limit.
+        limit: Decimal,
+    },
+    /// The loss for the day has reached the limit.
+    DailyLoss {
+        /// Realized loss so far today.
+        loss: Decimal,
+        /// The limit.
+        limit: Decimal,
+    },
+}
+
+impl fmt::Display for RiskViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyOpenOrders {
+                product_id,
+                open,
+                limit,
+            } => write!(
+                f,
+                "{} has {} open orders, the limit is {}",
+                product_id, open, limit
+            ),
+            Self::OrderNotional {
+                product_id,
+                notional,
+                limit,
+            } => write!(
+                f,
+                "{} order notional {} exceeds the limit of {}",
+                product_id, notional, limit
+            ),
+            Self::DailyLoss { loss, limit } => {
+                write!(f, "daily loss {} has reached the limit of {}", loss, limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RiskViolation {}
+
+/// Tracked state of a [`RiskEngine`].
+#[derive(Debug)]
+struct RiskState {
+    /// Open order IDs by product.
+    open_orders: HashMap<String, HashSet<String>>,
+    /// Client order IDs of orders being placed, by product.
+    reserved: HashMap<String, HashSet<String>>,
+    pnl: PnlCalculator,
+    /// The UTC day being tracked.
+    day: NaiveDate,
+    /// Realized PnL when the day started.
+    day_start_realized: Decimal,
+}
+
+impl RiskState {
+    /// Start a new day if the date has changed.
+    fn roll_day(&mut self) {
+        let today = Utc::now().date_naive();
+        if today != self.day {
+            self.day = today;
+            self.day_start_realized = self.pnl.total_realized();
+        }
+    }
+
+    fn daily_pnl(&mut self) -> Decimal {
+        self.roll_day();
+        self.pnl.total_realized() - self.day_start_realized
+    }
+
+    fn close(&mut self, order_id: &str) {
+        for orders in self.open_orders.values_mut() {
+            orders.remove(order_id);
+        }
+    }
+
+    /// Open and reserved orders for a product.
+    fn open(&self, product_id: &str) -> usize {
+        let count = |orders: &HashMap<String, HashSet<String>>| {
+            orders.get(product_id).map_or(0, HashSet::len)
+        };
+        count(&self.open_orders) + count(&self.reserved)
+    }
+
+    /// Free a reserved slot, returning whether it was still reserved.
+    fn release(&mut self, client_order_id: &str) -> bool {
+        let mut released = false;
+        for orders in self.reserved.values_mut() {
+            released |= orders.remove(client_order_id);
+        }
+        released
+    }
+}
+
+/// Enforces [`RiskLimits`] using the orders and fills it has seen.
+///
+/// The engine is shared by reference and safe to use from several tasks.
+#[derive(Debug)]
+pub struct RiskEngine {
+    limits: RiskLimits,
+    state: Mutex<RiskState>,
+}
+
+impl RiskEngine {
+    /// Create an engine with no open orders and no fills.
+    pub fn new(limits: RiskLimits) -> Self {
+        Self {
+            limits,
+            state: Mutex::new(RiskState {
+                open_orders: HashMap::new(),
+                reserved: HashMap::new(),
+                pnl: PnlCalculator::new(LotMethod::Fifo),
+                day: Utc::now().date_naive(),
+                day_start_realized: Decimal::ZERO,
+            }),
+        }
+    }
+
+    /// Get the limits.
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Number of tracked open orders for a product, including reserved ones.
+    pub fn open_orders(&self, product_id: &str) -> usize {
+        self.state().open(product_id)
+    }
+
+    /// Realized profit and loss of the current UTC day, after commissions.
+    pub fn daily_pnl(&self) -> Decimal {
+        self.state().daily_pnl()
+    }
+
+    /// Check an order for a product worth `notional` against the limits.
+    ///
+    /// `notional` is only needed when a notional limit is set. Nothing is
+    /// reserved, so use [`reserve`](Self::reserve) before placing an order.
+    pub fn check(
+        &self,
+        product_id: &str,
+        notional: Option<Decimal>,
+    ) -> std::result::Result<(), RiskViolation> {
+        self.check_locked(&mut self.state(), product_id, notional)
+    }
+
+    /// Check an order against the limits and reserve an open order slot for it.
+    ///
+    /// The slot counts towards the open order limit until it is passed to
+    /// [`confirm`](Self::confirm) once the order is placed, or to
+    /// [`release`](Self::release) if it is not.
+    pub fn reserve(
+        &self,
+        product_id: &str,
+        client_order_id: &str,
+        notional: Option<Decimal>,
+    ) -> std::result::Result<(), RiskViolation> {
+        let mut state = self.state();
+        self.check_locked(&mut state, product_id, notional)?;
+        state
+            .reserved
+            .entry(product_id.to_string())
+            .or_default()
+            .insert(client_order_id.to_string());
+        Ok(())
+    }
+
+    /// Turn a reserved slot into an open order.
+    ///
+    /// Does nothing if the slot was already freed, because the user channel
+    /// reported the order (possibly as finished) before it was confirmed.
+    pub fn confirm(&self, product_id: &str, client_order_id: &str, order_id: &str) {
+        let mut state = self.state();
+        if !state.release(client_order_id) {
+            return;
+        }
+        state
+            .open_orders
+            .entry(product_id.to_string())
+            .or_default()
+            .insert(order_id.to_string());
+    }
+
+    /// Free a reserved slot for an order that was not placed.
+    pub fn release(&self, client_order_id: &str) {
+        self.state().release(client_order_id);
+    }
+
+    fn check_locked(
+        &self,
+        state: &mut RiskState,
+        product_id: &str,
+        notional: Option<Decimal>,
+    ) -> std::result::Result<(), RiskViolation> {
+        if let Some(limit) = self.limits.max_daily_loss {
+            let loss = -state.daily_pnl();
+            if loss >= limit {
+                return Err(RiskViolation::DailyLoss { loss, limit });
+            }
+        }
+
+        if let Some(limit) = self.limits.max_open_orders_per_product {
+            let open = state.open(product_id);
+            if open >= limit {
+                return Err(RiskViolation::TooManyOpenOrders {
+                    product_id: product_id.to_string(),
+                    open,
+                    limit,
+                });
+            }
+        }
+
+        if let (Some(limit), Some(notional)) = (self.limits.max_order_notional, notional) {
+            if notional > limit {
+                return Err(RiskViolation::OrderNotional {
+                    product_id: product_id.to_string(),
+                    notional,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Track an order as open.
+    pub fn order_opened(&self, product_id: &str, order_id: &str) {
+        self.state()
+            .open_orders
+            .entry(product_id.to_string())
+            .or_default()
+            .insert(order_id.to_string());
+    }
+
+    /// Stop tracking an order as open.
+    pub fn order_closed(&self, order_id: &str) {
+        self.state().close(order_id);
+    }
+
+    /// Apply a fill to the daily loss.
+    ///
+    /// Returns false if a fill with the same trade ID was already applied.
+    pub fn record_fill(&self, fill: &TradeFill) -> bool {
+        let mut state = self.state();
+        state.roll_day();
+        state.pnl.add_fill(fill)
+    }
+
+    /// Apply the order updates and fills in a user channel message.
+    ///
+    /// Open and pending orders are tracked, finished ones are dropped.
+    /// Messages from other channels are ignored.
+    pub fn observe(&self, message: &Message) -> Result<()> {
+        let Events::User(events) = &message.events else {
+            return Ok(());
+        };
+        let mut state = self.state();
+        state.roll_day();
+        state.pnl.observe(message)?;
+
+        for event in events {
+            for update in &event.orders {
+                // The order may be reported before its create call returns.
+                state.release(&update.client_order_id);
+                match update.status.parse::<OrderStatus>() {
+                    Ok(OrderStatus::Open | OrderStatus::Pending) => {
+                        state
+                            .open_orders
+                            .entry(update.product_id.clone())
+                            .or_default()
+                            .insert(update.order_id.clone());
+                    }
+                    _ => state.close(&update.order_id),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn state(&self) -> MutexGuard<'_, RiskState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Order placement checked against a [`RiskEngine`].
+///
+/// Created by [`RestClient::risk_checked`].
+pub struct RiskCheckedOrders<'a> {
+    client: &'a RestClient,
+    engine: &'a RiskEngine,
+}
+
+impl<'a> RiskCheckedOrders<'a> {
+    /// Get the engine checking the orders.
+    pub fn engine(&self) -> &RiskEngine {
+        self.engine
+    }
+
+    /// Check an order against the limits and create it.
+    ///
+    /// Fails with [`Error::Risk`] if the order violates a limit. The order
+    /// holds a reserved slot while it is sent, and is tracked as open once
+    /// created.
+    pub async fn create(&self, request: CreateOrderRequest) -> Result<CreateOrderResponse> {
+        let notional = match self.engine.limits.max_order_notional {
+            Some(_) => Some(self.client.order_notional(&request).await?),
+            None => None,
+        };
+        let client_order_id = request.client_order_id.clone();
+        let product_id = request.product_id.clone();
+        if let Err(violation) = self.engine.reserve(&product_id, &client_order_id, notional) {
+            tracing::warn!(%client_order_id, %violation, "order rejected by risk limits");
+            return Err(Error::Risk(violation));
+        }
+
+        // Releases the slot on every path that does not confirm it,
+        // including this future being dropped mid-request.
+        let reservation = Reservation {
+            engine: self.engine,
+            client_order_id: &client_order_id,
+        };
+        let response = self.client.orders().create(request).await?;
+        if response.success {
+            if let Some(order_id) = &response.order_id {
+                self.engine.confirm(&product_id, &client_order_id, order_id);
+            }
+        }
+        drop(reservation);
+        Ok(response)
+    }
+
+    /// Cancel orders, no longer tracking those cancelled.
+    pub async fn cancel(&self, request: CancelOrdersRequest) -> Result<CancelOrdersResponse> {
+        let response = self.client.orders().cancel(request).await?;
+        for result in response.results.iter().filter(|r| r.success) {
+            self.engine.order_closed(&result.order_id);
+        }
+        Ok(response)
+    }
+
+    /// Replace the tracked open orders with those currently open.
+    pub async fn sync(&self) -> Result<()> {
+        let mut open_orders: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut params = ListOrdersParams::new().status(OrderStatus::Open);
+        loop {
+            let response = self.client.orders().list(params.clone()).await?;
+            for order in response.orders {
+                open_orders
+                    .entry(order.product_id)
+                    .or_default()
+                    .insert(order.order_id);
+            }
+            match response.cursor {
+                Some(cursor) if response.has_next && !cursor.is_empty() => {
+                    params = params.cursor(cursor)
+                }
+                _ => break,
+            }
+        }
+        self.engine.state().open_orders = open_orders;
+        Ok(())
+    }
+}
+
+/// A reserved open order slot, released when dropped.
+struct Reservation<'a> {
+    engine: &'a RiskEngine,
+    client_order_id: &'a str,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        self.engine.release(self.client_order_id);
+    }
+}
+
+impl RestClient {
+    /// Place orders through a [`RiskEngine`].
+    pub fn risk_checked<'a>(&'a self, engine: &'a RiskEngine) -> RiskCheckedOrders<'a> {
+        RiskCheckedOrders {
+            client: self,
+            engine,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::Environment;
+    use crate::models::{OrderConfiguration, OrderSide};
+
+    fn fill(trade_id: &str, side: OrderSide, price: i64) -> TradeFill {
+        TradeFill {
+            trade_id: trade_id.to_string(),
+            order_id: "order".to_string(),
+            product_id: "BTC-USD".to_string(),
+            side,
+            price: Decimal::from(price),
+            size: Decimal::ONE,
+            commission: Decimal::ZERO,
+            time: None,
+        }
+    }
+
+    #[test]
+    fn test_open_order_limit() {
+        let engine = RiskEngine::new(RiskLimits::new().max_open_orders_per_product(1));
+        assert!(engine.check("BTC-USD", None).is_ok());
+
+        engine.order_opened("BTC-USD", "a");
+        assert_eq!(
+            engine.check("BTC-USD", None),
+            Err(RiskViolation::TooManyOpenOrders {
+                product_id: "BTC-USD".to_string(),
+                open: 1,
+                limit: 1,
+            })
+        );
+        assert!(engine.check("ETH-USD", None).is_ok());
+
+        engine.order_closed("a");
+        assert_eq!(engine.open_orders("BTC-USD"), 0);
+        assert!(engine.check("BTC-USD", None).is_ok());
+    }
+
+    #[test]
+    fn test_daily_loss_limit() {
+        let engine = RiskEngine::new(RiskLimits::new().max_daily_loss(Decimal::from(100)));
+        engine.record_fill(&fill("1", OrderSide::Buy, 50_000));
+        engine.record_fill(&fill("2", OrderSide::Sell, 49_950));
+        assert_eq!(engine.daily_pnl(), Decimal::from(-50));
+        assert!(engine.check("BTC-USD", None).is_ok());
+
+        engine.record_fill(&fill("3", OrderSide::Buy, 50_000));
+        engine.record_fill(&fill("4", OrderSide::Sell, 49_900));
+        assert!(matches!(
+            engine.check("ETH-USD", None),
+            Err(RiskViolation::DailyLoss { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_notional() {
+        let client = RestClient::builder().build().unwrap();
+        let engine = RiskEngine::new(RiskLimits::new().max_order_notional(Decimal::from(500)));
+
+        let request = CreateOrderRequest::new(
+            "large",
+            "BTC-USD",
+            OrderSide::Buy,
+            OrderConfiguration::limit_gtc("0.1", "50000", false),
+        );
+        let err = client
+            .risk_checked(&engine)
+            .create(request)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Risk(RiskViolation::OrderNotional { notional, .. }) if notional == Decimal::from(5000)
+        ));
+    }
+
+    #[test]
+    fn test_reservations_count_as_open() {
+        let engine = RiskEngine::new(RiskLimits::new().max_open_orders_per_product(1));
+        engine.reserve("BTC-USD", "client-1", None).unwrap();
+        assert_eq!(engine.open_orders("BTC-USD"), 1);
+        assert!(matches!(
+            engine.reserve("BTC-USD", "client-2", None),
+            Err(RiskViolation::TooManyOpenOrders { open: 1, .. })
+        ));
+
+        engine.release("client-1");
+        assert_eq!(engine.open_orders("BTC-USD"), 0);
+
+        engine.reserve("BTC-USD", "client-2", None).unwrap();
+        engine.confirm("BTC-USD", "client-2", "order-2");
+        assert_eq!(engine.open_orders("BTC-USD"), 1);
+        engine.order_closed("order-2");
+        assert_eq!(engine.open_orders("BTC-USD"), 0);
+    }
+
+    fn order_update(client_order_id: &str, order_id: &str, status: &str) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "channel": "user",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": 1,
+            "events": [{
+                "type": "update",
+                "orders": [{
+                    "order_id": order_id,
+                    "client_order_id": client_order_id,
+                    "product_id": "BTC-USD",
+                    "order_side": "BUY",
+                    "order_type": "MARKET",
+                    "status": status,
+                    "cumulative_quantity": "0",
+                    "filled_value": "0",
+                    "total_fees": "0"
+                }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_finished_before_confirm_frees_the_slot() {
+        let engine = RiskEngine::new(RiskLimits::new().max_open_orders_per_product(1));
+        engine.reserve("BTC-USD", "client-1", None).unwrap();
+
+        // A market order fills before its create call returns.
+        engine
+            .observe(&order_update("client-1", "order-1", "FILLED"))
+            .unwrap();
+        engine.confirm("BTC-USD", "client-1", "order-1");
+        assert_eq!(engine.open_orders("BTC-USD"), 0);
+        assert!(engine.check("BTC-USD", None).is_ok());
+
+        // An order reported open first stays tracked once, until it finishes.
+        engine.reserve("BTC-USD", "client-2", None).unwrap();
+        engine
+            .observe(&order_update("client-2", "order-2", "OPEN"))
+            .unwrap();
+        engine.confirm("BTC-USD", "client-2", "order-2");
+        assert_eq!(engine.open_orders("BTC-USD"), 1);
+        engine
+            .observe(&order_update("client-2", "order-2", "FILLED"))
+            .unwrap();
+        assert_eq!(engine.open_orders("BTC-USD"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_creates_share_the_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/brokerage/orders"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "success": true, "order_id": "order-1" }))
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RestClient::builder()
+            .environment(Environment::custom(server.uri(), "ws://localhost"))
+            .build()
+            .unwrap();
+        let engine = RiskEngine::new(RiskLimits::new().max_open_orders_per_product(1));
+        let orders = client.risk_checked(&engine);
+        let request = |client_order_id: &str| {
+            CreateOrderRequest::new(
+                client_order_id,
+                "BTC-USD",
+                OrderSide::Buy,
+                OrderConfiguration::limit_gtc("0.01", "50000", false),
+            )
+        };
+
+        let (first, second) =
+            tokio::join!(orders.create(request("a")), orders.create(request("b")));
+        let results = [first, second];
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        assert!(results.iter().any(|r| matches!(
+            r,
+            Err(Error::Risk(RiskViolation::TooManyOpenOrders {
+                open: 1,
+                limit: 1,
+                ..
+            }))
+        )));
+        assert_eq!(engine.open_orders("BTC-USD"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_create_releases_reservation() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/brokerage/orders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": false,
+                "failure_reason": "INSUFFICIENT_FUND"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = RestClient::builder()
+            .environment(Environment::custom(server.uri(), "ws://localhost"))
+            .build()
+            .unwrap();
+        let engine = RiskEngine::new(RiskLimits::new().max_open_orders_per_product(1));
+        let response = client
+            .risk_checked(&engine)
+            .create(CreateOrderRequest::new(
+                "rejected",
+                "BTC-USD",
+                OrderSide::Buy,
+                OrderConfiguration::limit_gtc("0.01", "50000", false),
+            ))
+            .await
+            .unwrap();
+        assert!(!response.success);
+        assert_eq!(engine.open_orders("BTC-USD"), 0);
+    }
+}
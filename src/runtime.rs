@@ -0,0 +1,78 @@
+//! Clocks and timers used by the REST client, independent of the async
+//! runtime.
+//!
+//! With the `tokio` feature (the default) the timers are Tokio's. Without it
+//! they fall back to `futures-timer`, which runs its own timer thread, or the
+//! browser's timers with the `wasm` feature, so the REST client can be polled
+//! from any executor.
+//!
+//! `std::time::Instant` and `SystemTime` panic in the browser, so on `wasm32`
+//! the `web-time` equivalents are used instead. They are the `std` types on
+//! every other target.
+
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Returned by [`timeout_at`] when the deadline passes first.
+#[derive(Debug)]
+pub(crate) struct Elapsed;
+
+/// Wait for `duration` to pass.
+#[cfg(feature = "tokio")]
+pub(crate) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// Wait for `duration` to pass.
+#[cfg(not(feature = "tokio"))]
+pub(crate) async fn sleep(duration: Duration) {
+    futures_timer::Delay::new(duration).await;
+}
+
+/// Run a future, giving up once `deadline` passes.
+#[cfg(feature = "tokio")]
+pub(crate) async fn timeout_at<F: Future>(
+    deadline: Instant,
+    future: F,
+) -> Result<F::Output, Elapsed> {
+    tokio::time::timeout_at(tokio::time::Instant::from_std(deadline), future)
+        .await
+        .map_err(|_| Elapsed)
+}
+
+/// Run a future, giving up once `deadline` passes.
+#[cfg(not(feature = "tokio"))]
+pub(crate) async fn timeout_at<F: Future>(
+    deadline: Instant,
+    future: F,
+) -> Result<F::Output, Elapsed> {
+    use futures::future::{Either, select};
+
+    let delay = futures_timer::Delay::new(deadline.saturating_duration_since(Instant::now()));
+    match select(std::pin::pin!(future), delay).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(Elapsed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timeout_at() {
+        let soon = Instant::now() + Duration::from_millis(10);
+        assert!(
+            timeout_at(soon, sleep(Duration::from_secs(5)))
+                .await
+                .is_err()
+        );
+
+        let later = Instant::now() + Duration::from_secs(5);
+        assert_eq!(timeout_at(later, async { 7 }).await.unwrap(), 7);
+    }
+}
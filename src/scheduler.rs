@@ -0,0 +1,825 @@
+//! Recurring purchases (dollar-cost averaging).
+//!
+//! A [`Scheduler`] places [`RecurringBuy`] plans on a [`Schedule`]: every
+//! fixed interval, daily at a set time, or weekly on a set day, all in UTC.
+//! Each purchase spends a fixed quote amount with a market order, or with a
+//! limit order at a set price.
+//!
+//! Purchases are idempotent. The client order ID of a purchase is derived
+//! from the plan ID and the time it was scheduled for, so retrying a failed
+//! request, or repeating a purchase after a restart, cannot buy twice.
+//! Failed requests are retried with a [`BackoffPolicy`]; rejected orders are
+//! not.
+//!
+//! The next run of each plan is saved to a [`ScheduleStore`] after every
+//! purchase, so a restarted scheduler picks up where it left off. A run
+//! missed while the process was down is made once on start-up; further
+//! missed runs are skipped.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::scheduler::{FileStore, RecurringBuy, Scheduler};
+//! use coinbase_advanced::{Credentials, RestClient};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = RestClient::builder()
+//!     .credentials(Credentials::from_env()?)
+//!     .build()?;
+//!
+//! let mut scheduler = Scheduler::new(client)
+//!     .store(FileStore::open("dca.json")?)
+//!     .on_purchase(|record| println!("{:?}", record));
+//! scheduler.add(RecurringBuy::market(
+//!     "btc-weekly",
+//!     "BTC-USD",
+//!     "25",
+//!     "weekly mon 09:00".parse()?,
+//! ))?;
+//! scheduler.add(RecurringBuy::limit(
+//!     "eth-daily",
+//!     "ETH-USD",
+//!     "10",
+//!     "2500.00",
+//!     "daily 12:30".parse()?,
+//! ))?;
+//!
+//! scheduler.run().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Utc, Weekday};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::client::RestClient;
+use crate::error::{Error, Result};
+use crate::models::{CreateOrderRequest, OrderConfiguration, OrderSide};
+use crate::ws::BackoffPolicy;
+
+/// Longest the scheduler sleeps before checking the clock again.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// When a plan runs. All times are UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    /// Every fixed interval, starting one interval after the plan is added.
+    Every(Duration),
+    /// Every day at a set time.
+    Daily(NaiveTime),
+    /// Every week on a set day and time.
+    Weekly(Weekday, NaiveTime),
+}
+
+impl Schedule {
+    /// Run every fixed interval.
+    pub fn every(interval: Duration) -> Self {
+        Self::Every(interval)
+    }
+
+    /// Run every day at `hour:minute`.
+    pub fn daily(hour: u32, minute: u32) -> Result<Self> {
+        Ok(Self::Daily(time_of_day(hour, minute)?))
+    }
+
+    /// Run every week on `weekday` at `hour:minute`.
+    pub fn weekly(weekday: Weekday, hour: u32, minute: u32) -> Result<Self> {
+        Ok(Self::Weekly(weekday, time_of_day(hour, minute)?))
+    }
+
+    /// Get the first run strictly after `time`.
+    pub fn next_after(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Self::Every(interval) => {
+                let interval = chrono::Duration::from_std(*interval)
+                    .unwrap_or(chrono::Duration::MAX)
+                    .max(chrono::Duration::seconds(1));
+                time.checked_add_signed(interval)
+                    .unwrap_or(DateTime::<Utc>::MAX_UTC)
+            }
+            Self::Daily(at) => {
+                let today = time.date_naive().and_time(*at).and_utc();
+                if today > time {
+                    today
+                } else {
+                    today + chrono::Duration::days(1)
+                }
+            }
+            Self::Weekly(weekday, at) => {
+                let days_ahead = (7 + weekday.num_days_from_monday()
+                    - time.weekday().num_days_from_monday())
+                    % 7;
+                let candidate = time.date_naive().and_time(*at).and_utc()
+                    + chrono::Duration::days(days_ahead.into());
+                if candidate > time {
+                    candidate
+                } else {
+                    candidate + chrono::Duration::weeks(1)
+                }
+            }
+        }
+    }
+}
+
+fn time_of_day(hour: u32, minute: u32) -> Result<NaiveTime> {
+    NaiveTime::from_hms_opt(hour, minute, 0)
+        .ok_or_else(|| Error::config(format!("Invalid time of day {:02}:{:02}", hour, minute)))
+}
+
+impl FromStr for Schedule {
+    type Err = Error;
+
+    /// Parse a schedule such as "every 4h", "daily 09:30" or
+    /// "weekly mon 09:30".
+    ///
+    /// Intervals take a unit of `s`, `m`, `h`, `d` or `w`.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::config(format!("Invalid schedule '{}'", s));
+        let parse_time =
+            |time: &str| NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| invalid());
+
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        match parts.as_slice() {
+            ["every", interval] => {
+                let split = interval
+                    .find(|c: char| !c.is_ascii_digit())
+                    .ok_or_else(invalid)?;
+                let count: u64 = interval[..split].parse().map_err(|_| invalid())?;
+                let unit = match &interval[split..] {
+                    "s" => 1,
+                    "m" => 60,
+                    "h" => 3600,
+                    "d" => 86_400,
+                    "w" => 604_800,
+                    _ => return Err(invalid()),
+                };
+                if count == 0 {
+                    return Err(invalid());
+                }
+                Ok(Self::Every(Duration::from_secs(count * unit)))
+            }
+            ["daily", time] => Ok(Self::Daily(parse_time(time)?)),
+            ["weekly", day, time] => Ok(Self::Weekly(
+                day.parse().map_err(|_| invalid())?,
+                parse_time(time)?,
+            )),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Every(interval) => {
+                let secs = interval.as_secs();
+                let (count, unit) = [(604_800, "w"), (86_400, "d"), (3600, "h"), (60, "m")]
+                    .into_iter()
+                    .find(|(unit, _)| secs >= *unit && secs % unit == 0)
+                    .map(|(unit, name)| (secs / unit, name))
+                    .unwrap_or((secs, "s"));
+                write!(f, "every {}{}", count, unit)
+            }
+            Self::Daily(at) => write!(f, "daily {:02}:{:02}", at.hour(), at.minute()),
+            Self::Weekly(weekday, at) => write!(
+                f,
+                "weekly {} {:02}:{:02}",
+                weekday.to_string().to_lowercase(),
+                at.hour(),
+                at.minute()
+            ),
+        }
+    }
+}
+
+/// How a purchase is placed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PurchaseOrder {
+    /// A market order for the quote amount.
+    Market,
+    /// A good-til-cancelled limit order. The base size is the quote amount
+    /// divided by the price, rounded down to the product's base increment.
+    Limit {
+        /// Limit price.
+        limit_price: String,
+        /// Whether to only add liquidity.
+        post_only: bool,
+    },
+}
+
+/// A recurring purchase of a fixed quote amount.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurringBuy {
+    /// Plan ID, unique within a scheduler and stable across restarts.
+    pub id: String,
+    /// Product to buy.
+    pub product_id: String,
+    /// Amount to spend each run, in quote currency.
+    pub quote_amount: String,
+    /// When to buy.
+    pub schedule: Schedule,
+    /// How to place the order.
+    pub order: PurchaseOrder,
+}
+
+impl RecurringBuy {
+    /// Buy with a market order on each run.
+    pub fn market(
+        id: impl Into<String>,
+        product_id: impl Into<String>,
+        quote_amount: impl Into<String>,
+        schedule: Schedule,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            product_id: product_id.into(),
+            quote_amount: quote_amount.into(),
+            schedule,
+            order: PurchaseOrder::Market,
+        }
+    }
+
+    /// Buy with a limit order at `limit_price` on each run.
+    pub fn limit(
+        id: impl Into<String>,
+        product_id: impl Into<String>,
+        quote_amount: impl Into<String>,
+        limit_price: impl Into<String>,
+        schedule: Schedule,
+    ) -> Self {
+        Self {
+            order: PurchaseOrder::Limit {
+                limit_price: limit_price.into(),
+                post_only: false,
+            },
+            ..Self::market(id, product_id, quote_amount, schedule)
+        }
+    }
+
+    /// Get the client order ID of the run scheduled for `time`.
+    pub fn client_order_id(&self, time: DateTime<Utc>) -> String {
+        format!("{}-{}", self.id, time.timestamp())
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.id.is_empty() {
+            return Err(Error::config("Recurring buy ID must not be empty"));
+        }
+        let limit_price = match &self.order {
+            PurchaseOrder::Market => None,
+            PurchaseOrder::Limit { limit_price, .. } => Some(limit_price),
+        };
+        for (field, value) in [
+            ("quote amount", Some(&self.quote_amount)),
+            ("limit price", limit_price),
+        ] {
+            let Some(value) = value else { continue };
+            if !value.parse::<Decimal>().is_ok_and(|v| v > Decimal::ZERO) {
+                return Err(Error::config(format!(
+                    "Invalid {} '{}' for recurring buy '{}'",
+                    field, value, self.id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The saved progress of a plan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanState {
+    /// Plan ID.
+    pub plan_id: String,
+    /// When the plan next runs.
+    pub next_run: DateTime<Utc>,
+    /// When the last run was scheduled for, if any.
+    pub last_run: Option<DateTime<Utc>>,
+    /// Order ID placed by the last run, if it placed one.
+    pub last_order_id: Option<String>,
+}
+
+/// Where plan progress is saved between restarts.
+pub trait ScheduleStore: Send + Sync {
+    /// Load the saved state of a plan.
+    fn load(&self, plan_id: &str) -> Result<Option<PlanState>>;
+
+    /// Save the state of a plan.
+    fn save(&self, state: &PlanState) -> Result<()>;
+}
+
+/// Store that keeps plan progress in memory only.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    states: Mutex<HashMap<String, PlanState>>,
+}
+
+impl MemoryStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ScheduleStore for MemoryStore {
+    fn load(&self, plan_id: &str) -> Result<Option<PlanState>> {
+        let states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(states.get(plan_id).cloned())
+    }
+
+    fn save(&self, state: &PlanState) -> Result<()> {
+        let mut states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        states.insert(state.plan_id.clone(), state.clone());
+        Ok(())
+    }
+}
+
+/// Store that keeps the progress of every plan in a single JSON file.
+///
+/// The file is rewritten on each save, through a temporary file so a crash
+/// mid-write leaves the previous version in place.
+#[derive(Debug)]
+pub struct FileStore {
+    path: PathBuf,
+    states: Mutex<HashMap<String, PlanState>>,
+}
+
+impl FileStore {
+    /// Open a store, reading the file if it exists.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let states = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                Error::parse(
+                    format!("Invalid schedule store {}: {}", path.display(), e),
+                    None,
+                )
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path,
+            states: Mutex::new(states),
+        })
+    }
+}
+
+impl ScheduleStore for FileStore {
+    fn load(&self, plan_id: &str) -> Result<Option<PlanState>> {
+        let states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(states.get(plan_id).cloned())
+    }
+
+    fn save(&self, state: &PlanState) -> Result<()> {
+        let mut states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        states.insert(state.plan_id.clone(), state.clone());
+        let json = serde_json::to_vec_pretty(&*states)
+            .map_err(|e| Error::parse(format!("Failed to encode schedule store: {}", e), None))?;
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// How a purchase ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PurchaseOutcome {
+    /// The order was placed.
+    Placed {
+        /// The order ID.
+        order_id: String,
+    },
+    /// The exchange rejected the order.
+    Rejected {
+        /// Why the order was rejected.
+        reason: String,
+    },
+    /// Every attempt failed before the exchange answered.
+    Failed {
+        /// The last error.
+        error: String,
+    },
+}
+
+/// A single run of a plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PurchaseRecord {
+    /// Plan ID.
+    pub plan_id: String,
+    /// Product bought.
+    pub product_id: String,
+    /// When the run was scheduled for.
+    pub scheduled_for: DateTime<Utc>,
+    /// Client order ID used by every attempt.
+    pub client_order_id: String,
+    /// Number of attempts made.
+    pub attempts: u32,
+    /// How the purchase ended.
+    pub outcome: PurchaseOutcome,
+}
+
+type PurchaseCallback = Box<dyn Fn(&PurchaseRecord) + Send + Sync>;
+
+/// A plan and when it next runs.
+#[derive(Debug)]
+struct Plan {
+    buy: RecurringBuy,
+    state: PlanState,
+}
+
+/// Runs recurring purchases on their schedules.
+pub struct Scheduler {
+    client: RestClient,
+    plans: Vec<Plan>,
+    store: Box<dyn ScheduleStore>,
+    max_attempts: u32,
+    backoff: BackoffPolicy,
+    callbacks: Vec<PurchaseCallback>,
+}
+
+impl Scheduler {
+    /// Create a scheduler that places orders through `client`.
+    ///
+    /// Progress is kept in memory until a [`store`](Self::store) is set.
+    /// Failed requests are attempted up to 3 times.
+    pub fn new(client: RestClient) -> Self {
+        Self {
+            client,
+            plans: Vec::new(),
+            store: Box::new(MemoryStore::new()),
+            max_attempts: 3,
+            backoff: BackoffPolicy::exponential(Duration::from_secs(1), Duration::from_secs(30)),
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Save plan progress to `store`.
+    ///
+    /// Set the store before adding plans, so their progress is loaded from
+    /// it.
+    pub fn store(mut self, store: impl ScheduleStore + 'static) -> Self {
+        self.store = Box::new(store);
+        self
+    }
+
+    /// Attempt each failed request up to `max_attempts` times, waiting
+    /// according to `backoff` between attempts.
+    ///
+    /// Rate limited requests wait as long as the server asks instead.
+    pub fn retry(mut self, max_attempts: u32, backoff: BackoffPolicy) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.backoff = backoff;
+        self
+    }
+
+    /// Register a callback for every purchase.
+    pub fn on_purchase(
+        mut self,
+        callback: impl Fn(&PurchaseRecord) + Send + Sync + 'static,
+    ) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Add a plan.
+    ///
+    /// A plan with saved progress resumes from it; otherwise it first runs
+    /// at the next time its schedule allows.
+    pub fn add(&mut self, buy: RecurringBuy) -> Result<()> {
+        buy.validate()?;
+        if self.plans.iter().any(|plan| plan.buy.id == buy.id) {
+            return Err(Error::config(format!(
+                "Duplicate recurring buy ID '{}'",
+                buy.id
+            )));
+        }
+
+        let state = match self.store.load(&buy.id)? {
+            Some(state) => state,
+            None => PlanState {
+                plan_id: buy.id.clone(),
+                next_run: buy.schedule.next_after(Utc::now()),
+                last_run: None,
+                last_order_id: None,
+            },
+        };
+        self.plans.push(Plan { buy, state });
+        Ok(())
+    }
+
+    /// Remove a plan. Its saved progress is kept.
+    pub fn remove(&mut self, plan_id: &str) -> Option<RecurringBuy> {
+        let index = self.plans.iter().position(|plan| plan.buy.id == plan_id)?;
+        Some(self.plans.remove(index).buy)
+    }
+
+    /// Get the progress of a plan.
+    pub fn state(&self, plan_id: &str) -> Option<&PlanState> {
+        self.plans
+            .iter()
+            .find(|plan| plan.buy.id == plan_id)
+            .map(|plan| &plan.state)
+    }
+
+    /// Get the time of the next run of any plan.
+    pub fn next_run(&self) -> Option<DateTime<Utc>> {
+        self.plans.iter().map(|plan| plan.state.next_run).min()
+    }
+
+    /// Make every run due at `now`, returning what happened.
+    ///
+    /// Each plan is run at most once, then scheduled for its first run
+    /// after `now`. Fails only if progress cannot be saved.
+    pub async fn run_due(&mut self, now: DateTime<Utc>) -> Result<Vec<PurchaseRecord>> {
+        let mut records = Vec::new();
+        for index in 0..self.plans.len() {
+            let scheduled_for = self.plans[index].state.next_run;
+            if scheduled_for > now {
+                continue;
+            }
+
+            let record = self.purchase(&self.plans[index].buy, scheduled_for).await;
+            let plan = &mut self.plans[index];
+            let mut next_run = plan.buy.schedule.next_after(scheduled_for);
+            while next_run <= now {
+                next_run = plan.buy.schedule.next_after(next_run);
+            }
+            plan.state.next_run = next_run;
+            plan.state.last_run = Some(scheduled_for);
+            plan.state.last_order_id = match &record.outcome {
+                PurchaseOutcome::Placed { order_id } => Some(order_id.clone()),
+                _ => None,
+            };
+            self.store.save(&plan.state)?;
+
+            for callback in &self.callbacks {
+                callback(&record);
+            }
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// Run the plans until none are left, or progress cannot be saved.
+    pub async fn run(&mut self) -> Result<()> {
+        loop {
+            self.run_due(Utc::now()).await?;
+            let Some(next_run) = self.next_run() else {
+                return Ok(());
+            };
+            // Wake up periodically so a changed system clock is noticed.
+            let wait = (next_run - Utc::now()).to_std().unwrap_or_default();
+            tokio::time::sleep(wait.min(MAX_SLEEP)).await;
+        }
+    }
+
+    /// Place a single purchase, retrying failed requests.
+    async fn purchase(&self, buy: &RecurringBuy, scheduled_for: DateTime<Utc>) -> PurchaseRecord {
+        let client_order_id = buy.client_order_id(scheduled_for);
+        let mut attempts = 0;
+        let outcome = loop {
+            attempts += 1;
+            let result = match self.order_request(buy, &client_order_id).await {
+                Ok(request) => self.client.orders().create(request).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(response) if response.success => {
+                    break PurchaseOutcome::Placed {
+                        order_id: response.order_id.unwrap_or_default(),
+                    };
+                }
+                Ok(response) => {
+                    break PurchaseOutcome::Rejected {
+                        reason: response
+                            .failure_reason
+                            .or_else(|| response.error_response.map(|e| e.to_string()))
+                            .unwrap_or_default(),
+                    };
+                }
+                Err(e) if attempts < self.max_attempts => {
+                    let delay = e
+                        .retry_after()
+                        .unwrap_or_else(|| self.backoff.delay(attempts));
+                    tracing::warn!(
+                        plan_id = %buy.id,
+                        attempt = attempts,
+                        error = %e,
+                        "recurring buy failed, retrying in {:?}",
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    break PurchaseOutcome::Failed {
+                        error: e.to_string(),
+                    };
+                }
+            }
+        };
+
+        PurchaseRecord {
+            plan_id: buy.id.clone(),
+            product_id: buy.product_id.clone(),
+            scheduled_for,
+            client_order_id,
+            attempts,
+            outcome,
+        }
+    }
+
+    async fn order_request(
+        &self,
+        buy: &RecurringBuy,
+        client_order_id: &str,
+    ) -> Result<CreateOrderRequest> {
+        let configuration = match &buy.order {
+            PurchaseOrder::Market => OrderConfiguration::market_buy_quote(&buy.quote_amount),
+            PurchaseOrder::Limit {
+                limit_price,
+                post_only,
+            } => {
+                let increments = self
+                    .client
+                    .products()
+                    .get(&buy.product_id)
+                    .await?
+                    .increments();
+                // Both were validated when the plan was added.
+                let quote: Decimal = buy.quote_amount.parse().unwrap_or_default();
+                let price: Decimal = limit_price.parse().unwrap_or_default();
+                OrderConfiguration::limit_gtc(
+                    increments.quantize_base(quote.checked_div(price).unwrap_or_default()),
+                    limit_price,
+                    *post_only,
+                )
+            }
+        };
+        Ok(CreateOrderRequest::new(
+            client_order_id,
+            &buy.product_id,
+            OrderSide::Buy,
+            configuration,
+        ))
+    }
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("plans", &self.plans)
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::Environment;
+
+    fn utc(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // 2024-01-01 is a Monday.
+        Utc.with_ymd_and_hms(2024, 1, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_next_after() {
+        let every: Schedule = "every 4h".parse().unwrap();
+        assert_eq!(every, Schedule::every(Duration::from_secs(4 * 3600)));
+        assert_eq!(every.next_after(utc(1, 10, 0)), utc(1, 14, 0));
+
+        let daily: Schedule = "daily 09:30".parse().unwrap();
+        assert_eq!(daily.next_after(utc(1, 9, 0)), utc(1, 9, 30));
+        assert_eq!(daily.next_after(utc(1, 9, 30)), utc(2, 9, 30));
+
+        let weekly: Schedule = "weekly wed 12:00".parse().unwrap();
+        assert_eq!(weekly, Schedule::weekly(Weekday::Wed, 12, 0).unwrap());
+        assert_eq!(weekly.next_after(utc(1, 0, 0)), utc(3, 12, 0));
+        assert_eq!(weekly.next_after(utc(3, 12, 0)), utc(10, 12, 0));
+
+        for schedule in [every, daily, weekly] {
+            assert_eq!(schedule.to_string().parse::<Schedule>().unwrap(), schedule);
+        }
+        for invalid in ["every 0h", "every 4x", "daily 25:00", "monthly 1", ""] {
+            assert!(invalid.parse::<Schedule>().is_err(), "{}", invalid);
+        }
+        assert!(Schedule::daily(24, 0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_due() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/brokerage/orders"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/brokerage/orders"))
+            .respond_with(|request: &wiremock::Request| {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                assert_eq!(body["client_order_id"], "btc-daily-1704101400");
+                assert_eq!(
+                    body["order_configuration"]["market_market_ioc"]["quote_size"],
+                    "25"
+                );
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "success": true,
+                    "order_id": "order-1"
+                }))
+            })
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RestClient::builder()
+            .environment(Environment::custom(server.uri(), "ws://localhost"))
+            .build()
+            .unwrap();
+        let store = MemoryStore::new();
+        store
+            .save(&PlanState {
+                plan_id: "btc-daily".to_string(),
+                next_run: utc(1, 9, 30),
+                last_run: None,
+                last_order_id: None,
+            })
+            .unwrap();
+        let mut scheduler = Scheduler::new(client)
+            .store(store)
+            .retry(2, BackoffPolicy::fixed(Duration::ZERO));
+        scheduler
+            .add(RecurringBuy::market(
+                "btc-daily",
+                "BTC-USD",
+                "25",
+                Schedule::daily(9, 30).unwrap(),
+            ))
+            .unwrap();
+        assert!(
+            scheduler
+                .add(RecurringBuy::market(
+                    "btc-daily",
+                    "BTC-USD",
+                    "5",
+                    Schedule::daily(1, 0).unwrap()
+                ))
+                .is_err()
+        );
+
+        // Not due yet.
+        assert!(scheduler.run_due(utc(1, 9, 0)).await.unwrap().is_empty());
+
+        // Three days late: one purchase, then back on schedule.
+        let records = scheduler.run_due(utc(4, 8, 0)).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].attempts, 2);
+        assert_eq!(
+            records[0].outcome,
+            PurchaseOutcome::Placed {
+                order_id: "order-1".to_string()
+            }
+        );
+        let state = scheduler.state("btc-daily").unwrap();
+        assert_eq!(state.next_run, utc(4, 9, 30));
+        assert_eq!(state.last_run, Some(utc(1, 9, 30)));
+        assert_eq!(state.last_order_id.as_deref(), Some("order-1"));
+        assert_eq!(
+            scheduler.store.load("btc-daily").unwrap().as_ref(),
+            Some(state)
+        );
+    }
+
+    #[test]
+    fn test_file_store() {
+        let path = std::env::temp_dir().join(format!("coinbase-dca-{}.json", std::process::id()));
+        let state = PlanState {
+            plan_id: "plan".to_string(),
+            next_run: utc(1, 0, 0),
+            last_run: Some(utc(1, 0, 0)),
+            last_order_id: Some("order-1".to_string()),
+        };
+        FileStore::open(&path).unwrap().save(&state).unwrap();
+        assert_eq!(
+            FileStore::open(&path).unwrap().load("plan").unwrap(),
+            Some(state)
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,87 @@
+//! Optional instrumentation using the [`metrics`](https://docs.rs/metrics) facade.
+//!
+//! When the `metrics` feature is enabled, the REST and WebSocket clients record
+//! the metrics below through whatever recorder the application installs (for
+//! example `metrics-exporter-prometheus`). Without the feature, recording is a no-op.
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `coinbase_rest_requests_total` | counter | `method`, `status` |
+//! | `coinbase_rest_request_duration_seconds` | histogram | `method` |
+//! | `coinbase_rate_limiter_wait_seconds` | histogram | |
+//! | `coinbase_ws_messages_received_total` | counter | `channel` |
+//! | `coinbase_ws_reconnects_total` | counter | |
+//!
+//! The `status` label is the HTTP status code, or `error` when no response was received.
+
+use std::time::Duration;
+
+use reqwest::Method;
+
+/// Total number of REST requests.
+pub const REST_REQUESTS_TOTAL: &str = "coinbase_rest_requests_total";
+
+/// REST request latency in seconds.
+pub const REST_REQUEST_DURATION_SECONDS: &str = "coinbase_rest_request_duration_seconds";
+
+/// Time spent waiting for the client-side rate limiter, in seconds.
+pub const RATE_LIMITER_WAIT_SECONDS: &str = "coinbase_rate_limiter_wait_seconds";
+
+/// Total number of WebSocket messages received.
+pub const WS_MESSAGES_RECEIVED_TOTAL: &str = "coinbase_ws_messages_received_total";
+
+/// Total number of WebSocket reconnection attempts.
+pub const WS_RECONNECTS_TOTAL: &str = "coinbase_ws_reconnects_total";
+
+/// Record a completed REST request.
+pub(crate) fn record_request(method: &Method, status: Option<u16>, elapsed: Duration) {
+    #[cfg(feature = "metrics")]
+    {
+        let status = status
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "error".to_string());
+        metrics::counter!(
+            REST_REQUESTS_TOTAL,
+            "method" => method.as_str().to_string(),
+            "status" => status
+        )
+        .increment(1);
+        metrics::histogram!(
+            REST_REQUEST_DURATION_SECONDS,
+            "method" => method.as_str().to_string()
+        )
+        .record(elapsed.as_secs_f64());
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (method, status, elapsed);
+}
+
+/// Record time spent waiting for a rate limiter token.
+pub(crate) fn record_rate_limit_wait(elapsed: Duration) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::histogram!(RATE_LIMITER_WAIT_SECONDS).record(elapsed.as_secs_f64());
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = elapsed;
+}
+
+/// Record a WebSocket message received on a channel.
+#[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+pub(crate) fn record_ws_message(channel: &'static str) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(WS_MESSAGES_RECEIVED_TOTAL, "channel" => channel).increment(1);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = channel;
+}
+
+/// Record a WebSocket reconnection attempt.
+#[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+pub(crate) fn record_ws_reconnect() {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(WS_RECONNECTS_TOTAL).increment(1);
+    }
+}
@@ -0,0 +1,736 @@
+//! Canned JSON responses for every REST endpoint.
+//!
+//! Each function returns the body the API sends for one response model, with
+//! realistic values that deserialize into the matching type in
+//! [`models`](crate::models). Edit the returned value to cover other cases:
+//!
+//! ```
+//! use coinbase_advanced::test_utils::fixtures;
+//!
+//! let mut body = fixtures::list_orders();
+//! body["orders"][0]["status"] = "FILLED".into();
+//! ```
+
+use serde_json::{Value, json};
+
+/// A USD-quoted BTC product, as returned inside [`list_products`].
+pub fn product() -> Value {
+    json!({
+        "product_id": "BTC-USD",
+        "price": "50000.00",
+        "price_percentage_change_24h": "1.25",
+        "volume_24h": "12345.67",
+        "volume_percentage_change_24h": "-3.5",
+        "base_increment": "0.00000001",
+        "quote_increment": "0.01",
+        "quote_min_size": "1",
+        "quote_max_size": "150000000",
+        "base_min_size": "0.00000001",
+        "base_max_size": "3400",
+        "base_name": "Bitcoin",
+        "quote_name": "US Dollar",
+        "watched": false,
+        "is_disabled": false,
+        "new": false,
+        "status": "online",
+        "cancel_only": false,
+        "limit_only": false,
+        "post_only": false,
+        "trading_disabled": false,
+        "auction_mode": false,
+        "product_type": "SPOT",
+        "quote_currency_id": "USD",
+        "base_currency_id": "BTC",
+        "base_display_symbol": "BTC",
+        "quote_display_symbol": "USD",
+        "price_increment": "0.01"
+    })
+}
+
+/// Body of `GET /products` ([`ListProductsResponse`](crate::models::ListProductsResponse)).
+pub fn list_products() -> Value {
+    json!({ "products": [product()], "num_products": 1 })
+}
+
+/// Body of `GET /product_book` ([`GetProductBookResponse`](crate::models::GetProductBookResponse)).
+pub fn get_product_book() -> Value {
+    json!({
+        "pricebook": {
+            "product_id": "BTC-USD",
+            "bids": [
+                { "price": "49999.99", "size": "0.5" },
+                { "price": "49999.50", "size": "1.2" }
+            ],
+            "asks": [
+                { "price": "50000.01", "size": "0.3" },
+                { "price": "50000.50", "size": "2.0" }
+            ],
+            "time": "2024-01-15T12:00:00Z"
+        }
+    })
+}
+
+/// Body of `GET /best_bid_ask` ([`GetBestBidAskResponse`](crate::models::GetBestBidAskResponse)).
+pub fn get_best_bid_ask() -> Value {
+    json!({
+        "pricebooks": [{
+            "product_id": "BTC-USD",
+            "bids": [{ "price": "49999.99", "size": "0.5" }],
+            "asks": [{ "price": "50000.01", "size": "0.3" }],
+            "time": "2024-01-15T12:00:00Z"
+        }]
+    })
+}
+
+/// Body of `GET /products/{id}/candles` ([`GetCandlesResponse`](crate::models::GetCandlesResponse)).
+pub fn get_candles() -> Value {
+    json!({
+        "candles": [
+            {
+                "start": "1705320000",
+                "low": "49800.00",
+                "high": "50200.00",
+                "open": "49900.00",
+                "close": "50000.00",
+                "volume": "123.45"
+            },
+            {
+                "start": "1705316400",
+                "low": "49500.00",
+                "high": "50000.00",
+                "open": "49600.00",
+                "close": "49900.00",
+                "volume": "98.76"
+            }
+        ]
+    })
+}
+
+/// Body of `GET /products/{id}/ticker` ([`GetMarketTradesResponse`](crate::models::GetMarketTradesResponse)).
+pub fn get_market_trades() -> Value {
+    json!({
+        "trades": [{
+            "trade_id": "1234567",
+            "product_id": "BTC-USD",
+            "price": "50000.00",
+            "size": "0.01",
+            "time": "2024-01-15T12:00:00Z",
+            "side": "BUY"
+        }],
+        "best_bid": "49999.99",
+        "best_ask": "50000.01"
+    })
+}
+
+/// Body of `GET /time` ([`ServerTime`](crate::ServerTime)).
+pub fn server_time() -> Value {
+    json!({
+        "iso": "2024-01-15T12:00:00Z",
+        "epochSeconds": "1705320000",
+        "epochMillis": "1705320000000"
+    })
+}
+
+/// A BTC account, as returned inside [`list_accounts`].
+pub fn account() -> Value {
+    json!({
+        "uuid": "8bfc20d7-f7c6-4422-bf07-8243ca4169fe",
+        "name": "BTC Wallet",
+        "currency": "BTC",
+        "available_balance": { "value": "1.5", "currency": "BTC" },
+        "default": true,
+        "active": true,
+        "created_at": "2024-01-01T00:00:00Z",
+        "updated_at": "2024-01-01T00:00:00Z",
+        "deleted_at": null,
+        "type": "ACCOUNT_TYPE_CRYPTO",
+        "ready": true,
+        "hold": { "value": "0.1", "currency": "BTC" },
+        "retail_portfolio_id": "0dd2ba4a-5a9e-4a6c-9d1e-7c1a0c1b2f00"
+    })
+}
+
+/// Body of `GET /accounts` ([`ListAccountsResponse`](crate::models::ListAccountsResponse)).
+pub fn list_accounts() -> Value {
+    json!({ "accounts": [account()], "has_next": false, "cursor": "", "size": 1 })
+}
+
+/// Body of `GET /accounts/{id}` ([`GetAccountResponse`](crate::models::GetAccountResponse)).
+pub fn get_account() -> Value {
+    json!({ "account": account() })
+}
+
+/// An open limit order, as returned inside [`list_orders`].
+pub fn order() -> Value {
+    json!({
+        "order_id": "0000-000000-000000",
+        "product_id": "BTC-USD",
+        "user_id": "2222-000000-000000",
+        "order_configuration": {
+            "limit_limit_gtc": {
+                "base_size": "0.001",
+                "limit_price": "45000.00",
+                "post_only": false
+            }
+        },
+        "side": "BUY",
+        "client_order_id": "11111-000000-000000",
+        "status": "OPEN",
+        "time_in_force": "GOOD_UNTIL_CANCELLED",
+        "created_time": "2024-01-15T12:00:00Z",
+        "completion_percentage": "0",
+        "filled_size": "0",
+        "average_filled_price": "0",
+        "fee": "",
+        "number_of_fills": "0",
+        "filled_value": "0",
+        "pending_cancel": false,
+        "size_in_quote": false,
+        "total_fees": "0",
+        "size_inclusive_of_fees": false,
+        "total_value_after_fees": "45.27",
+        "trigger_status": "INVALID_ORDER_TYPE",
+        "order_type": "LIMIT",
+        "reject_reason": "",
+        "settled": false,
+        "product_type": "SPOT",
+        "reject_message": "",
+        "cancel_message": "",
+        "order_placement_source": "RETAIL_ADVANCED",
+        "outstanding_hold_amount": "45.27"
+    })
+}
+
+/// Body of `GET /orders/historical/batch` ([`ListOrdersResponse`](crate::models::ListOrdersResponse)).
+pub fn list_orders() -> Value {
+    json!({ "orders": [order()], "sequence": "0", "has_next": false, "cursor": "" })
+}
+
+/// Body of `GET /orders/historical/{id}`.
+pub fn get_order() -> Value {
+    json!({ "order": order() })
+}
+
+/// A fill, as returned inside [`list_fills`].
+pub fn fill() -> Value {
+    json!({
+        "entry_id": "22222-2222222-22222222",
+        "trade_id": "1111-11111-111111",
+        "order_id": "0000-000000-000000",
+        "trade_time": "2024-01-15T12:00:00Z",
+        "trade_type": "FILL",
+        "price": "50000.00",
+        "size": "0.001",
+        "commission": "0.30",
+        "product_id": "BTC-USD",
+        "sequence_timestamp": "2024-01-15T12:00:00.000001Z",
+        "liquidity_indicator": "TAKER",
+        "size_in_quote": false,
+        "user_id": "2222-000000-000000",
+        "side": "BUY"
+    })
+}
+
+/// Body of `GET /orders/historical/fills` ([`ListFillsResponse`](crate::models::ListFillsResponse)).
+pub fn list_fills() -> Value {
+    json!({ "fills": [fill()], "cursor": "" })
+}
+
+/// Body of a successful `POST /orders` ([`CreateOrderResponse`](crate::models::CreateOrderResponse)).
+pub fn create_order() -> Value {
+    json!({
+        "success": true,
+        "failure_reason": "UNKNOWN_FAILURE_REASON",
+        "order_id": "0000-000000-000000",
+        "success_response": {
+            "order_id": "0000-000000-000000",
+            "product_id": "BTC-USD",
+            "side": "BUY",
+            "client_order_id": "11111-000000-000000"
+        },
+        "error_response": null
+    })
+}
+
+/// Body of `POST /orders/preview` ([`PreviewOrderResponse`](crate::models::PreviewOrderResponse)).
+pub fn preview_order() -> Value {
+    json!({
+        "order_total": "50.30",
+        "commission_total": "0.30",
+        "errs": [],
+        "warning": [],
+        "quote_size": "50",
+        "base_size": "0.001",
+        "best_bid": "49999.99",
+        "best_ask": "50000.01",
+        "is_max": false,
+        "slippage": "0",
+        "preview_id": "b40bbff9-17ce-4726-8b64-9de7ae57ad26"
+    })
+}
+
+/// Body of `POST /orders/batch_cancel` ([`CancelOrdersResponse`](crate::models::CancelOrdersResponse)).
+pub fn cancel_orders() -> Value {
+    json!({
+        "results": [{
+            "success": true,
+            "failure_reason": "UNKNOWN_CANCEL_FAILURE_REASON",
+            "order_id": "0000-000000-000000"
+        }]
+    })
+}
+
+/// Body of `POST /orders/edit` ([`EditOrderResponse`](crate::models::EditOrderResponse)).
+pub fn edit_order() -> Value {
+    json!({ "success": true, "errors": [] })
+}
+
+/// Body of `GET /transaction_summary` ([`TransactionSummary`](crate::models::TransactionSummary)).
+pub fn transaction_summary() -> Value {
+    json!({
+        "total_volume": 1000.0,
+        "total_fees": 6.0,
+        "fee_tier": {
+            "pricing_tier": "Advanced 1",
+            "usd_from": "0",
+            "usd_to": "10000",
+            "taker_fee_rate": "0.006",
+            "maker_fee_rate": "0.004",
+            "aop_from": "",
+            "aop_to": ""
+        },
+        "margin_rate": { "value": "0" },
+        "goods_and_services_tax": { "rate": "0", "type": "INCLUSIVE" },
+        "advanced_trade_only_volume": 1000.0,
+        "advanced_trade_only_fees": 6.0,
+        "coinbase_pro_volume": 0.0,
+        "coinbase_pro_fees": 0.0,
+        "total_balance": "5000.00",
+        "has_promo_fee": false,
+        "venue_summaries": [
+            { "product_venue": "CBE", "volume": "1000", "fees": "6" },
+            { "product_venue": "FCM", "volume": "0", "fees": "0" }
+        ]
+    })
+}
+
+/// Body of `GET /key_permissions` ([`ApiKeyPermissions`](crate::models::ApiKeyPermissions)).
+pub fn key_permissions() -> Value {
+    json!({
+        "can_view": true,
+        "can_trade": true,
+        "can_transfer": false,
+        "portfolio_uuid": "0dd2ba4a-5a9e-4a6c-9d1e-7c1a0c1b2f00",
+        "portfolio_type": "DEFAULT"
+    })
+}
+
+/// A portfolio, as returned inside [`list_portfolios`].
+pub fn portfolio() -> Value {
+    json!({
+        "name": "Default",
+        "uuid": "0dd2ba4a-5a9e-4a6c-9d1e-7c1a0c1b2f00",
+        "type": "DEFAULT",
+        "deleted": false
+    })
+}
+
+/// Body of `GET /portfolios` ([`ListPortfoliosResponse`](crate::models::ListPortfoliosResponse)).
+pub fn list_portfolios() -> Value {
+    json!({ "portfolios": [portfolio()] })
+}
+
+/// Body of `POST /portfolios` and `PUT /portfolios/{id}`
+/// ([`PortfolioResponse`](crate::models::PortfolioResponse)).
+pub fn portfolio_response() -> Value {
+    json!({ "portfolio": portfolio() })
+}
+
+/// Body of `GET /portfolios/{id}` ([`GetPortfolioBreakdownResponse`](crate::models::GetPortfolioBreakdownResponse)).
+pub fn get_portfolio_breakdown() -> Value {
+    json!({
+        "breakdown": {
+            "portfolio": portfolio(),
+            "portfolio_balances": {
+                "total_balance": { "value": "5000.00", "currency": "USD" },
+                "total_futures_balance": { "value": "0", "currency": "USD" },
+                "total_cash_equivalent_balance": { "value": "1000.00", "currency": "USD" },
+                "total_crypto_balance": { "value": "4000.00", "currency": "USD" },
+                "futures_unrealized_pnl": { "value": "0", "currency": "USD" },
+                "perp_unrealized_pnl": { "value": "0", "currency": "USD" }
+            },
+            "spot_positions": [{
+                "asset": "BTC",
+                "account_uuid": "8bfc20d7-f7c6-4422-bf07-8243ca4169fe",
+                "total_balance_fiat": 4000.0,
+                "total_balance_crypto": 0.08,
+                "available_to_trade_fiat": 4000.0,
+                "allocation": 0.8,
+                "cost_basis": { "value": "3500.00", "currency": "USD" },
+                "asset_img_url": "",
+                "is_cash": false
+            }],
+            "perp_positions": [],
+            "futures_positions": []
+        }
+    })
+}
+
+/// Body of `POST /portfolios/move_funds` ([`MoveFundsResponse`](crate::models::MoveFundsResponse)).
+pub fn move_funds() -> Value {
+    json!({
+        "source_portfolio_uuid": "0dd2ba4a-5a9e-4a6c-9d1e-7c1a0c1b2f00",
+        "target_portfolio_uuid": "5c8d7a31-9b2e-4f4a-8e7b-3f0c1d2e4a5b"
+    })
+}
+
+/// Body of the convert quote and trade endpoints
+/// ([`ConvertTradeResponse`](crate::models::ConvertTradeResponse)).
+pub fn convert_trade() -> Value {
+    json!({
+        "trade": {
+            "id": "a1b2c3d4-0000-0000-0000-000000000000",
+            "status": "TRADE_STATUS_CREATED",
+            "user_entered_amount": { "value": "100", "currency": "USD" },
+            "amount": { "value": "100", "currency": "USDC" },
+            "subtotal": { "value": "100", "currency": "USD" },
+            "total": { "value": "100", "currency": "USD" },
+            "fees": [{
+                "title": "Coinbase fee",
+                "description": "Fee for converting",
+                "amount": { "value": "0", "currency": "USD" },
+                "label": "Coinbase fee"
+            }],
+            "total_fee": {
+                "title": "Total fee",
+                "amount": { "value": "0", "currency": "USD" }
+            },
+            "source": {
+                "type": "LEDGER_ACCOUNT",
+                "network": "",
+                "ledger_account": {
+                    "account_id": "usd-account",
+                    "currency": "USD",
+                    "owner": { "id": "", "uuid": "", "user_uuid": "user-uuid", "type": "USER" }
+                }
+            },
+            "target": {
+                "type": "LEDGER_ACCOUNT",
+                "network": "",
+                "ledger_account": {
+                    "account_id": "usdc-account",
+                    "currency": "USDC",
+                    "owner": { "id": "", "uuid": "", "user_uuid": "user-uuid", "type": "USER" }
+                }
+            },
+            "unit_price": {
+                "target_to_fiat": { "amount": { "value": "1", "currency": "USD" }, "scale": 2 },
+                "target_to_source": { "amount": { "value": "1", "currency": "USD" }, "scale": 2 },
+                "fiat_to_target": { "amount": { "value": "1", "currency": "USDC" }, "scale": 6 }
+            },
+            "user_warnings": [],
+            "source_currency": "USD",
+            "target_currency": "USDC",
+            "source_id": "usd-account",
+            "target_id": "usdc-account",
+            "exchange_rate": { "value": "1", "currency": "USDC" },
+            "tax_details": [{ "name": "GST", "amount": { "value": "0", "currency": "USD" } }],
+            "fiat_denoted_total": { "value": "100", "currency": "USD" }
+        }
+    })
+}
+
+/// A bank account payment method, as returned inside [`list_payment_methods`].
+pub fn payment_method() -> Value {
+    json!({
+        "id": "8bfc20d7-f7c6-4422-bf07-8243ca4169fe",
+        "type": "ach_bank_account",
+        "name": "ALLY BANK ******1234",
+        "currency": "USD",
+        "verified": true,
+        "allow_buy": true,
+        "allow_sell": true,
+        "allow_deposit": true,
+        "allow_withdraw": true,
+        "created_at": "2024-01-01T00:00:00Z",
+        "updated_at": "2024-01-01T00:00:00Z"
+    })
+}
+
+/// Body of `GET /payment_methods` ([`ListPaymentMethodsResponse`](crate::models::ListPaymentMethodsResponse)).
+pub fn list_payment_methods() -> Value {
+    json!({ "payment_methods": [payment_method()] })
+}
+
+/// Body of `GET /payment_methods/{id}` ([`GetPaymentMethodResponse`](crate::models::GetPaymentMethodResponse)).
+pub fn get_payment_method() -> Value {
+    json!({ "payment_method": payment_method() })
+}
+
+/// Body of the v2 deposit and withdrawal endpoints
+/// ([`TransferResponse`](crate::models::TransferResponse)).
+pub fn transfer() -> Value {
+    json!({
+        "data": {
+            "id": "67e0eaec-07d7-54c4-a72c-2e92826897df",
+            "status": "created",
+            "payment_method": {
+                "id": "8bfc20d7-f7c6-4422-bf07-8243ca4169fe",
+                "resource": "payment_method",
+                "resource_path": "/v2/payment-methods/8bfc20d7-f7c6-4422-bf07-8243ca4169fe"
+            },
+            "amount": { "amount": "100.00", "currency": "USD" },
+            "subtotal": { "amount": "100.00", "currency": "USD" },
+            "fee": { "amount": "0.00", "currency": "USD" },
+            "committed": true,
+            "instant": false,
+            "created_at": "2024-01-15T12:00:00Z",
+            "updated_at": "2024-01-15T12:00:00Z",
+            "payout_at": "2024-01-18T12:00:00Z"
+        }
+    })
+}
+
+/// A futures position, as returned inside [`list_futures_positions`].
+pub fn futures_position() -> Value {
+    json!({
+        "product_id": "BIT-31JAN24-CDE",
+        "expiration_time": "2024-01-31T16:00:00Z",
+        "side": "LONG",
+        "number_of_contracts": "2",
+        "current_price": "50000",
+        "avg_entry_price": "49000",
+        "unrealized_pnl": "20",
+        "daily_realized_pnl": "0"
+    })
+}
+
+/// Body of `GET /cfm/positions` ([`ListFuturesPositionsResponse`](crate::models::ListFuturesPositionsResponse)).
+pub fn list_futures_positions() -> Value {
+    json!({ "positions": [futures_position()] })
+}
+
+/// Body of `GET /cfm/positions/{id}` ([`GetFuturesPositionResponse`](crate::models::GetFuturesPositionResponse)).
+pub fn get_futures_position() -> Value {
+    json!({ "position": futures_position() })
+}
+
+/// Body of `GET /cfm/balance_summary`
+/// ([`GetFuturesBalanceSummaryResponse`](crate::models::GetFuturesBalanceSummaryResponse)).
+pub fn get_futures_balance_summary() -> Value {
+    json!({
+        "balance_summary": {
+            "futures_buying_power": "1000",
+            "total_usd_balance": "2000",
+            "cbi_usd_balance": "1500",
+            "cfm_usd_balance": "500",
+            "total_open_orders_hold_amount": "0",
+            "unrealized_pnl": "20",
+            "daily_realized_pnl": "0",
+            "initial_margin": "200",
+            "available_margin": "300",
+            "liquidation_threshold": "100",
+            "liquidation_buffer_amount": "200",
+            "liquidation_buffer_percentage": "200"
+        }
+    })
+}
+
+/// Body of `GET /cfm/intraday/margin_setting`
+/// ([`GetIntradayMarginSettingResponse`](crate::models::GetIntradayMarginSettingResponse)).
+pub fn get_intraday_margin_setting() -> Value {
+    json!({ "setting": "INTRADAY_MARGIN_SETTING_STANDARD" })
+}
+
+/// Body of `GET /cfm/intraday/current_margin_window`
+/// ([`GetCurrentMarginWindowResponse`](crate::models::GetCurrentMarginWindowResponse)).
+pub fn get_current_margin_window() -> Value {
+    json!({
+        "margin_window": {
+            "margin_window_type": "FCM_MARGIN_WINDOW_TYPE_INTRADAY",
+            "end_time": "2024-01-15T20:00:00Z",
+            "is_intraday_margin_killswitch_enabled": false,
+            "is_intraday_margin_enrollment_killswitch_enabled": false
+        }
+    })
+}
+
+/// Body of `GET /cfm/sweeps` ([`ListFuturesSweepsResponse`](crate::models::ListFuturesSweepsResponse)).
+pub fn list_futures_sweeps() -> Value {
+    json!({
+        "sweeps": [{
+            "id": "sweep-1",
+            "requested_amount": "100",
+            "should_sweep_all": false,
+            "status": "PENDING",
+            "scheduled_time": "2024-01-16T10:00:00Z"
+        }]
+    })
+}
+
+/// Body of `POST /cfm/sweeps/schedule`
+/// ([`ScheduleFuturesSweepResponse`](crate::models::ScheduleFuturesSweepResponse)).
+pub fn schedule_futures_sweep() -> Value {
+    json!({ "success": true })
+}
+
+/// A perpetuals position, as returned inside [`list_perpetuals_positions`].
+pub fn perpetuals_position() -> Value {
+    json!({
+        "product_id": "BTC-PERP-INTX",
+        "product_uuid": "cd34c18b-3665-4ed8-9305-3db277c49fc5",
+        "portfolio_uuid": "5c8d7a31-9b2e-4f4a-8e7b-3f0c1d2e4a5b",
+        "symbol": "BTC-PERP-INTX",
+        "vwap": { "value": "49000", "currency": "USDC" },
+        "position_side": "POSITION_SIDE_LONG",
+        "net_size": "0.1",
+        "buy_order_size": "0",
+        "sell_order_size": "0",
+        "im_contribution": "0.2",
+        "unrealized_pnl": { "value": "100", "currency": "USDC" },
+        "mark_price": { "value": "50000", "currency": "USDC" },
+        "liquidation_price": { "value": "30000", "currency": "USDC" },
+        "leverage": "5",
+        "im_notional": { "value": "1000", "currency": "USDC" },
+        "mm_notional": { "value": "500", "currency": "USDC" },
+        "position_notional": "5000"
+    })
+}
+
+/// Body of `GET /intx/positions/{portfolio}`
+/// ([`ListPerpetualsPositionsResponse`](crate::models::ListPerpetualsPositionsResponse)).
+pub fn list_perpetuals_positions() -> Value {
+    json!({
+        "positions": [perpetuals_position()],
+        "summary": { "aggregated_pnl": { "value": "100", "currency": "USDC" } }
+    })
+}
+
+/// Body of `GET /intx/positions/{portfolio}/{symbol}`
+/// ([`GetPerpetualsPositionResponse`](crate::models::GetPerpetualsPositionResponse)).
+pub fn get_perpetuals_position() -> Value {
+    json!({ "position": perpetuals_position() })
+}
+
+/// Body of `GET /intx/balances/{portfolio}`
+/// ([`GetPortfolioBalancesResponse`](crate::models::GetPortfolioBalancesResponse)).
+pub fn get_portfolio_balances() -> Value {
+    json!({
+        "portfolio_balances": [{
+            "asset": "USDC",
+            "quantity": "1000",
+            "hold": "0",
+            "transfer_hold": "0",
+            "collateral_value": "1000",
+            "max_withdraw_amount": "800"
+        }]
+    })
+}
+
+/// Body of `GET /intx/portfolio/{portfolio}`
+/// ([`GetPerpetualsPortfolioSummaryResponse`](crate::models::GetPerpetualsPortfolioSummaryResponse)).
+pub fn get_perpetuals_portfolio_summary() -> Value {
+    json!({
+        "portfolios": [{
+            "portfolio_uuid": "intx-portfolio-uuid",
+            "collateral": "1100",
+            "position_notional": "5000",
+            "open_position_notional": "5500",
+            "pending_fees": "0",
+            "borrow": "0",
+            "accrued_interest": "0",
+            "rolling_debt": "0",
+            "portfolio_initial_margin": "0.2",
+            "portfolio_im_notional": { "value": "1000", "currency": "USDC" },
+            "portfolio_maintenance_margin": "0.1",
+            "portfolio_mm_notional": { "value": "500", "currency": "USDC" },
+            "liquidation_percentage": "45",
+            "liquidation_buffer": "600",
+            "margin_type": "MARGIN_TYPE_CROSS",
+            "margin_flags": "PORTFOLIO_MARGIN_FLAGS_UNSPECIFIED",
+            "liquidation_status": "PORTFOLIO_LIQUIDATION_STATUS_NOT_LIQUIDATING",
+            "unrealized_pnl": { "value": "100", "currency": "USDC" },
+            "total_balance": { "value": "1100", "currency": "USDC" }
+        }],
+        "summary": {
+            "unrealized_pnl": { "value": "100", "currency": "USDC" },
+            "buying_power": { "value": "4000", "currency": "USDC" },
+            "total_balance": { "value": "1100", "currency": "USDC" },
+            "max_withdrawal_amount": { "value": "800", "currency": "USDC" }
+        }
+    })
+}
+
+/// Body of `GET /intx/multi_asset_collateral`
+/// ([`MultiAssetCollateral`](crate::models::MultiAssetCollateral)).
+pub fn multi_asset_collateral() -> Value {
+    json!({ "multi_asset_collateral_enabled": true })
+}
+
+/// Body of an error response, as parsed by [`ApiErrorBody`](crate::ApiErrorBody).
+pub fn error(code: &str, message: &str) -> Value {
+    json!({
+        "error": code,
+        "message": message,
+        "error_details": message
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+    use crate::ServerTime;
+    use crate::models::*;
+
+    fn parse<T: DeserializeOwned>(value: Value) -> T {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_fixtures_parse() {
+        parse::<ListProductsResponse>(list_products());
+        parse::<Product>(product());
+        parse::<GetProductBookResponse>(get_product_book());
+        parse::<GetBestBidAskResponse>(get_best_bid_ask());
+        parse::<GetCandlesResponse>(get_candles());
+        parse::<GetMarketTradesResponse>(get_market_trades());
+        parse::<ServerTime>(server_time());
+        parse::<ListAccountsResponse>(list_accounts());
+        parse::<GetAccountResponse>(get_account());
+        parse::<ListOrdersResponse>(list_orders());
+        parse::<Order>(get_order()["order"].clone());
+        parse::<ListFillsResponse>(list_fills());
+        parse::<CreateOrderResponse>(create_order());
+        parse::<PreviewOrderResponse>(preview_order());
+        parse::<CancelOrdersResponse>(cancel_orders());
+        parse::<EditOrderResponse>(edit_order());
+        parse::<TransactionSummary>(transaction_summary());
+        parse::<ApiKeyPermissions>(key_permissions());
+        parse::<ListPortfoliosResponse>(list_portfolios());
+        parse::<PortfolioResponse>(portfolio_response());
+        parse::<GetPortfolioBreakdownResponse>(get_portfolio_breakdown());
+        parse::<MoveFundsResponse>(move_funds());
+        parse::<ConvertTradeResponse>(convert_trade());
+        parse::<ListPaymentMethodsResponse>(list_payment_methods());
+        parse::<GetPaymentMethodResponse>(get_payment_method());
+        parse::<TransferResponse>(transfer());
+        parse::<ListFuturesPositionsResponse>(list_futures_positions());
+        parse::<GetFuturesPositionResponse>(get_futures_position());
+        parse::<GetFuturesBalanceSummaryResponse>(get_futures_balance_summary());
+        parse::<GetIntradayMarginSettingResponse>(get_intraday_margin_setting());
+        parse::<GetCurrentMarginWindowResponse>(get_current_margin_window());
+        parse::<ListFuturesSweepsResponse>(list_futures_sweeps());
+        parse::<ScheduleFuturesSweepResponse>(schedule_futures_sweep());
+        parse::<ListPerpetualsPositionsResponse>(list_perpetuals_positions());
+        parse::<GetPerpetualsPositionResponse>(get_perpetuals_position());
+        parse::<GetPortfolioBalancesResponse>(get_portfolio_balances());
+        parse::<GetPerpetualsPortfolioSummaryResponse>(get_perpetuals_portfolio_summary());
+        parse::<MultiAssetCollateral>(multi_asset_collateral());
+        assert!(
+            crate::ApiErrorBody::parse(&error("NOT_FOUND", "order not found").to_string())
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! Builders for fake WebSocket messages.
+//!
+//! [`MessageBuilder`] wraps any [`Events`] in a message envelope, and the
+//! functions here build the events most tests need with realistic defaults.
+//!
+//! ```
+//! use coinbase_advanced::test_utils::messages::{self, MessageBuilder};
+//! use coinbase_advanced::ws::{Events, Level2Side};
+//!
+//! let tick = messages::ticker("BTC-USD", "50000.00");
+//! let book = MessageBuilder::new(messages::level2_update(
+//!     "BTC-USD",
+//!     &[(Level2Side::Bid, "49999.99", "0.5")],
+//! ))
+//! .sequence_num(7)
+//! .build();
+//! assert!(matches!(book.events, Events::Level2(_)));
+//! ```
+
+use serde_json::json;
+
+use crate::ws::{
+    CandleUpdate, CandlesEvent, ChannelName, EventType, Events, HeartbeatsEvent, Level2Event,
+    Level2Side, Level2Update, MarketTradesEvent, Message, OrderUpdate, TickerEvent, TickerUpdate,
+    TradeUpdate, UserEvent, UserPositions,
+};
+
+/// Timestamp used for messages and events unless one is set.
+pub const DEFAULT_TIMESTAMP: &str = "2024-01-15T12:00:00Z";
+
+/// Builds a [`Message`] around a set of events.
+#[derive(Debug, Clone)]
+pub struct MessageBuilder {
+    channel: ChannelName,
+    client_id: String,
+    timestamp: String,
+    sequence_num: u64,
+    events: Events,
+}
+
+impl MessageBuilder {
+    /// Start a message carrying `events`, on the channel that sends them.
+    pub fn new(events: Events) -> Self {
+        let channel = match &events {
+            Events::Status(_) => ChannelName::Status,
+            Events::Candles(_) => ChannelName::Candles,
+            Events::Ticker(_) => ChannelName::Ticker,
+            Events::Level2(_) => ChannelName::Level2,
+            Events::User(_) => ChannelName::User,
+            Events::MarketTrades(_) => ChannelName::MarketTrades,
+            Events::Heartbeats(_) => ChannelName::Heartbeats,
+            Events::Subscriptions(_) => ChannelName::Subscriptions,
+            Events::FuturesBalanceSummary(_) => ChannelName::FuturesBalanceSummary,
+            Events::Unknown(_) => ChannelName::Unknown,
+        };
+        Self {
+            channel,
+            client_id: String::new(),
+            timestamp: DEFAULT_TIMESTAMP.to_string(),
+            sequence_num: 0,
+            events,
+        }
+    }
+
+    /// Override the channel, e.g. [`ChannelName::TickerBatch`] for ticker events.
+    pub fn channel(mut self, channel: ChannelName) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Set the client ID.
+    pub fn client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = client_id.into();
+        self
+    }
+
+    /// Set the message timestamp.
+    pub fn timestamp(mut self, timestamp: impl Into<String>) -> Self {
+        self.timestamp = timestamp.into();
+        self
+    }
+
+    /// Set the sequence number.
+    pub fn sequence_num(mut self, sequence_num: u64) -> Self {
+        self.sequence_num = sequence_num;
+        self
+    }
+
+    /// Build the message.
+    pub fn build(self) -> Message {
+        Message {
+            channel: self.channel,
+            client_id: self.client_id,
+            timestamp: self.timestamp,
+            sequence_num: self.sequence_num,
+            events: self.events,
+            received_at: None,
+        }
+    }
+}
+
+/// A heartbeat message.
+pub fn heartbeat(counter: u64) -> Message {
+    MessageBuilder::new(Events::Heartbeats(vec![HeartbeatsEvent {
+        current_time: DEFAULT_TIMESTAMP.to_string(),
+        heartbeat_counter: counter,
+    }]))
+    .build()
+}
+
+/// A ticker update message for one product.
+pub fn ticker(product_id: &str, price: &str) -> Message {
+    MessageBuilder::new(Events::Ticker(vec![TickerEvent {
+        r#type: EventType::Update,
+        tickers: vec![TickerUpdate {
+            r#type: "ticker".to_string(),
+            product_id: product_id.to_string(),
+            price: price.to_string(),
+            volume_24_h: "1000".to_string(),
+            low_24_h: price.to_string(),
+            high_24_h: price.to_string(),
+            low_52_w: price.to_string(),
+            high_52_w: price.to_string(),
+            price_percent_chg_24_h: "0".to_string(),
+            best_bid: price.to_string(),
+            best_bid_quantity: "1".to_string(),
+            best_ask: price.to_string(),
+            best_ask_quantity: "1".to_string(),
+            #[cfg(feature = "capture-unknown")]
+            extra: Default::default(),
+        }],
+    }]))
+    .build()
+}
+
+/// Level 2 update events for one product, from `(side, price, quantity)` levels.
+pub fn level2_update(product_id: &str, levels: &[(Level2Side, &str, &str)]) -> Events {
+    level2(EventType::Update, product_id, levels)
+}
+
+/// Level 2 snapshot events for one product, from `(side, price, quantity)` levels.
+pub fn level2_snapshot(product_id: &str, levels: &[(Level2Side, &str, &str)]) -> Events {
+    level2(EventType::Snapshot, product_id, levels)
+}
+
+fn level2(r#type: EventType, product_id: &str, levels: &[(Level2Side, &str, &str)]) -> Events {
+    Events::Level2(vec![Level2Event {
+        r#type,
+        product_id: product_id.to_string(),
+        updates: levels
+            .iter()
+            .map(|(side, price, quantity)| Level2Update {
+                side: side.clone(),
+                event_time: DEFAULT_TIMESTAMP.to_string(),
+                price_level: price.to_string(),
+                new_quantity: quantity.to_string(),
+            })
+            .collect(),
+    }])
+}
+
+/// A candle update message for a five-minute candle starting at `start`
+/// (Unix seconds), with `[open, high, low, close, volume]`.
+pub fn candle(product_id: &str, start: i64, ohlcv: [&str; 5]) -> Message {
+    let [open, high, low, close, volume] = ohlcv;
+    MessageBuilder::new(Events::Candles(vec![CandlesEvent {
+        r#type: EventType::Update,
+        candles: vec![CandleUpdate {
+            product_id: product_id.to_string(),
+            start: start.to_string(),
+            open: open.to_string(),
+            high: high.to_string(),
+            low: low.to_string(),
+            close: close.to_string(),
+            volume: volume.to_string(),
+        }],
+    }]))
+    .build()
+}
+
+/// A market trade message.
+pub fn market_trade(
+    product_id: &str,
+    trade_id: &str,
+    side: &str,
+    price: &str,
+    size: &str,
+) -> Message {
+    MessageBuilder::new(Events::MarketTrades(vec![MarketTradesEvent {
+        r#type: EventType::Update,
+        trades: vec![TradeUpdate {
+            trade_id: trade_id.to_string(),
+            product_id: product_id.to_string(),
+            price: price.to_string(),
+            size: size.to_string(),
+            side: side.to_string(),
+            time: DEFAULT_TIMESTAMP.to_string(),
+            #[cfg(feature = "capture-unknown")]
+            extra: Default::default(),
+        }],
+    }]))
+    .build()
+}
+
+/// An order update for the `user` channel, with every other field empty.
+///
+/// Set further fields on the returned value before wrapping it with
+/// [`user`].
+pub fn order_update(order_id: &str, product_id: &str, side: &str, status: &str) -> OrderUpdate {
+    serde_json::from_value(json!({
+        "order_id": order_id,
+        "product_id": product_id,
+        "order_side": side,
+        "order_type": "Limit",
+        "status": status,
+        "creation_time": DEFAULT_TIMESTAMP
+    }))
+    .expect("order update fixture is valid")
+}
+
+/// A `user` channel message with order updates.
+pub fn user(r#type: EventType, orders: Vec<OrderUpdate>) -> Message {
+    MessageBuilder::new(Events::User(vec![UserEvent {
+        r#type,
+        orders,
+        positions: UserPositions::default(),
+    }]))
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_round_trip() {
+        let messages = [
+            heartbeat(3),
+            ticker("BTC-USD", "50000"),
+            MessageBuilder::new(level2_snapshot(
+                "BTC-USD",
+                &[(Level2Side::Ask, "50000.01", "1")],
+            ))
+            .build(),
+            candle("BTC-USD", 1_705_320_000, ["1", "2", "0.5", "1.5", "10"]),
+            market_trade("BTC-USD", "1", "BUY", "50000", "0.1"),
+            user(
+                EventType::Snapshot,
+                vec![order_update("order-1", "BTC-USD", "BUY", "OPEN")],
+            ),
+        ];
+
+        for message in messages {
+            let json = serde_json::to_string(&message).unwrap();
+            let parsed: Message = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed.channel, message.channel);
+            assert_eq!(
+                std::mem::discriminant(&parsed.events),
+                std::mem::discriminant(&message.events)
+            );
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! A mock Coinbase REST API.
+
+use serde_json::Value;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use crate::client::{RestClient, RestClientBuilder};
+use crate::constants::{API_PATH_PREFIX, API_V2_PATH_PREFIX};
+use crate::environment::Environment;
+
+/// A [`wiremock`] server that answers like the Coinbase REST API.
+///
+/// Endpoints are given the way the client's request methods take them, such
+/// as `/accounts` or `/orders/historical/batch`, and the Advanced Trade path
+/// prefix is added automatically. Requests match on method and path only, so
+/// they match whether or not the client signs them and whatever JWT it sends.
+pub struct MockCoinbase {
+    server: MockServer,
+}
+
+impl MockCoinbase {
+    /// Start a mock server on a random local port.
+    pub async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+        }
+    }
+
+    /// Get the underlying server, for mounting custom mocks.
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+
+    /// Get the base URL of the server.
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// Get an environment pointing at the server.
+    ///
+    /// The WebSocket URL is unused by the mock and points at the same host.
+    pub fn environment(&self) -> Environment {
+        let uri = self.uri();
+        let ws_url = uri.replacen("http", "ws", 1);
+        Environment::custom(uri, ws_url)
+    }
+
+    /// Build an unauthenticated client that sends requests to the server.
+    pub fn client(&self) -> RestClient {
+        self.client_with(RestClient::builder())
+    }
+
+    /// Build a client from a configured builder, pointing it at the server.
+    ///
+    /// Use this to test with credentials, rate limiting, or an order guard.
+    pub fn client_with(&self, builder: RestClientBuilder) -> RestClient {
+        builder
+            .environment(self.environment())
+            .build()
+            .expect("failed to build client for mock server")
+    }
+
+    /// Answer `GET` requests to an endpoint with a JSON body.
+    pub async fn get(&self, endpoint: &str, body: Value) {
+        self.respond(
+            "GET",
+            endpoint,
+            ResponseTemplate::new(200).set_body_json(body),
+        )
+        .await;
+    }
+
+    /// Answer `POST` requests to an endpoint with a JSON body.
+    pub async fn post(&self, endpoint: &str, body: Value) {
+        self.respond(
+            "POST",
+            endpoint,
+            ResponseTemplate::new(200).set_body_json(body),
+        )
+        .await;
+    }
+
+    /// Answer `PUT` requests to an endpoint with a JSON body.
+    pub async fn put(&self, endpoint: &str, body: Value) {
+        self.respond(
+            "PUT",
+            endpoint,
+            ResponseTemplate::new(200).set_body_json(body),
+        )
+        .await;
+    }
+
+    /// Answer `DELETE` requests to an endpoint with a JSON body.
+    pub async fn delete(&self, endpoint: &str, body: Value) {
+        self.respond(
+            "DELETE",
+            endpoint,
+            ResponseTemplate::new(200).set_body_json(body),
+        )
+        .await;
+    }
+
+    /// Answer requests to an endpoint with an error status and body.
+    ///
+    /// See [`fixtures::error`](super::fixtures::error) for the error body
+    /// shape.
+    pub async fn error(&self, http_method: &str, endpoint: &str, status: u16, body: Value) {
+        self.respond(
+            http_method,
+            endpoint,
+            ResponseTemplate::new(status).set_body_json(body),
+        )
+        .await;
+    }
+
+    /// Answer requests to an endpoint with `429 Too Many Requests`.
+    pub async fn rate_limited(&self, http_method: &str, endpoint: &str, retry_after_secs: u64) {
+        let response = ResponseTemplate::new(429)
+            .insert_header("retry-after", retry_after_secs.to_string().as_str())
+            .set_body_json(serde_json::json!({ "message": "Too many requests" }));
+        self.respond(http_method, endpoint, response).await;
+    }
+
+    /// Answer requests to an endpoint with a custom response.
+    pub async fn respond(&self, http_method: &str, endpoint: &str, response: ResponseTemplate) {
+        Mock::given(method(http_method))
+            .and(path(api_path(endpoint)))
+            .respond_with(response)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Get the requests the server has received, oldest first.
+    pub async fn requests(&self) -> Vec<Request> {
+        self.server.received_requests().await.unwrap_or_default()
+    }
+}
+
+/// Full request path of an endpoint, matching the client's URL building.
+fn api_path(endpoint: &str) -> String {
+    let is_v2 = endpoint
+        .strip_prefix(API_V2_PATH_PREFIX)
+        .is_some_and(|rest| rest.starts_with('/'));
+    if is_v2 {
+        endpoint.to_string()
+    } else {
+        format!("{}{}", API_PATH_PREFIX, endpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixtures;
+
+    #[tokio::test]
+    async fn test_mock_coinbase() {
+        let mock = MockCoinbase::start().await;
+        mock.get("/accounts", fixtures::list_accounts()).await;
+        mock.error(
+            "GET",
+            "/products/BTC-USD",
+            404,
+            fixtures::error("NOT_FOUND", "product not found"),
+        )
+        .await;
+
+        let client = mock.client();
+        let response = client.accounts().list_all().await.unwrap();
+        assert_eq!(response.accounts.len(), 1);
+        assert_eq!(response.accounts[0].currency, "BTC");
+
+        let err = client.products().get("BTC-USD").await.unwrap_err();
+        assert!(matches!(err, crate::Error::Api { status: 404, .. }));
+        assert_eq!(mock.requests().await.len(), 2);
+    }
+
+    #[test]
+    fn test_api_path() {
+        assert_eq!(api_path("/accounts"), "/api/v3/brokerage/accounts");
+        assert_eq!(
+            api_path("/v2/accounts/abc/deposits"),
+            "/v2/accounts/abc/deposits"
+        );
+    }
+}
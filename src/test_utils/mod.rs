@@ -0,0 +1,36 @@
+//! Helpers for testing code built on this crate.
+//!
+//! Enabled by the `test-utils` feature, usually as a dev-dependency:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! coinbase-advanced = { version = "1", features = ["test-utils"] }
+//! ```
+//!
+//! - [`fixtures`] has a canned JSON body for every REST response.
+//! - [`MockCoinbase`] is a local mock of the REST API that clients can be
+//!   pointed at.
+//! - [`messages`] builds fake WebSocket messages to feed into stream
+//!   consumers.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::test_utils::{MockCoinbase, fixtures};
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let mock = MockCoinbase::start().await;
+//! mock.get("/accounts", fixtures::list_accounts()).await;
+//!
+//! let client = mock.client();
+//! let accounts = client.accounts().list_all().await?;
+//! assert_eq!(accounts.accounts.len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod fixtures;
+pub mod messages;
+mod mock;
+
+pub use mock::MockCoinbase;
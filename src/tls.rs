@@ -0,0 +1,173 @@
+//! Custom TLS configuration.
+
+use std::fmt;
+use std::sync::Arc;
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::{ClientConfig, RootCertStore};
+
+use crate::error::{Error, Result};
+
+/// TLS settings shared by the REST client and the WebSocket connector.
+///
+/// By default both clients trust the usual public certificate authorities.
+/// Supply a `TlsConfig` where that is not what you want: behind a proxy that
+/// intercepts TLS with its own CA, or under a policy that pins the roots
+/// Coinbase may present. Set it with
+/// [`RestClientBuilder::tls_config`](crate::RestClientBuilder::tls_config)
+/// and [`WebSocketClientBuilder::tls_config`](crate::ws::WebSocketClientBuilder::tls_config).
+///
+/// Use [`TlsConfig::builder`] for the common cases, or wrap a
+/// [`rustls::ClientConfig`] with [`TlsConfig::new`] for full control.
+///
+/// # Example
+///
+/// ```no_run
+/// use coinbase_advanced::{RestClient, TlsConfig};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let tls = TlsConfig::builder()
+///     .root_certificates_pem(std::fs::read("corporate-ca.pem")?)
+///     .build()?;
+/// let client = RestClient::builder().tls_config(tls).build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TlsConfig {
+    config: Arc<ClientConfig>,
+}
+
+impl TlsConfig {
+    /// Use a prepared rustls configuration as is.
+    pub fn new(config: ClientConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+
+    /// Build a configuration from root certificates and a client identity.
+    pub fn builder() -> TlsConfigBuilder {
+        TlsConfigBuilder::default()
+    }
+
+    /// Get the underlying rustls configuration.
+    pub fn client_config(&self) -> &Arc<ClientConfig> {
+        &self.config
+    }
+}
+
+impl From<ClientConfig> for TlsConfig {
+    fn from(config: ClientConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsConfig")
+            .field("alpn_protocols", &self.config.alpn_protocols)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Builder for a [`TlsConfig`].
+///
+/// Only the root certificates added here are trusted; the public
+/// certificate authorities are not. Certificates are parsed when
+/// [`build`](Self::build) is called.
+#[derive(Default)]
+pub struct TlsConfigBuilder {
+    roots_pem: Vec<Vec<u8>>,
+    roots_der: Vec<CertificateDer<'static>>,
+    identity_pem: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl TlsConfigBuilder {
+    /// Trust every certificate in a PEM bundle.
+    pub fn root_certificates_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.roots_pem.push(pem.into());
+        self
+    }
+
+    /// Trust a DER-encoded certificate.
+    pub fn root_certificate_der(mut self, der: impl Into<Vec<u8>>) -> Self {
+        self.roots_der.push(CertificateDer::from(der.into()));
+        self
+    }
+
+    /// Present a client certificate for mutual TLS.
+    ///
+    /// `certificate_chain` holds the PEM certificates, leaf first, and
+    /// `private_key` the matching PEM key (PKCS#8, PKCS#1 or SEC1).
+    pub fn client_identity_pem(
+        mut self,
+        certificate_chain: impl Into<Vec<u8>>,
+        private_key: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.identity_pem = Some((certificate_chain.into(), private_key.into()));
+        self
+    }
+
+    /// Build the configuration.
+    ///
+    /// Fails if a certificate or key cannot be parsed, or if no root
+    /// certificate was given.
+    pub fn build(self) -> Result<TlsConfig> {
+        let mut roots = RootCertStore::empty();
+        for pem in &self.roots_pem {
+            for cert in parse_certificates(pem)? {
+                roots
+                    .add(cert)
+                    .map_err(|e| Error::config(format!("Invalid root certificate: {}", e)))?;
+            }
+        }
+        for cert in self.roots_der {
+            roots
+                .add(cert)
+                .map_err(|e| Error::config(format!("Invalid root certificate: {}", e)))?;
+        }
+        if roots.is_empty() {
+            return Err(Error::config("TLS configuration has no root certificates"));
+        }
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|e| Error::config(format!("Invalid TLS configuration: {}", e)))?
+            .with_root_certificates(roots);
+        let config = match self.identity_pem {
+            Some((chain, key)) => {
+                let chain = parse_certificates(&chain)?;
+                let key = PrivateKeyDer::from_pem_slice(&key)
+                    .map_err(|e| Error::config(format!("Invalid client private key: {}", e)))?;
+                builder
+                    .with_client_auth_cert(chain, key)
+                    .map_err(|e| Error::config(format!("Invalid client identity: {}", e)))?
+            }
+            None => builder.with_no_client_auth(),
+        };
+        Ok(TlsConfig::new(config))
+    }
+}
+
+impl fmt::Debug for TlsConfigBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsConfigBuilder")
+            .field("roots", &(self.roots_pem.len() + self.roots_der.len()))
+            .field("client_identity", &self.identity_pem.is_some())
+            .finish()
+    }
+}
+
+/// Parse every certificate in a PEM bundle, which must hold at least one.
+fn parse_certificates(pem: &[u8]) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_slice_iter(pem)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| Error::config(format!("Invalid PEM certificate: {}", e)))?;
+    if certs.is_empty() {
+        return Err(Error::config("No certificates found in PEM data"));
+    }
+    Ok(certs)
+}
@@ -0,0 +1,200 @@
+//! A locally managed set of tracked products.
+//!
+//! Products report whether they are on the account's watchlist through
+//! [`Product::watched`], but the Advanced Trade API has no endpoints to add or
+//! remove them. [`Watchlist`] keeps the set locally instead, persists it as a
+//! JSON file, and turns it into the product filters used by REST requests and
+//! WebSocket subscriptions, so one list drives both.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::watchlist::Watchlist;
+//! use coinbase_advanced::ws::ChannelName;
+//! use coinbase_advanced::RestClient;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let mut watchlist = Watchlist::load("watchlist.json")?;
+//! watchlist.add("BTC-USD");
+//! watchlist.add("ETH-USD");
+//! watchlist.save("watchlist.json")?;
+//!
+//! let client = RestClient::builder().build()?;
+//! let quotes = client
+//!     .products()
+//!     .get_best_bid_ask(watchlist.best_bid_ask_params())
+//!     .await?;
+//!
+//! let channels: Vec<_> = [ChannelName::Ticker, ChannelName::Candles]
+//!     .into_iter()
+//!     .filter_map(|name| watchlist.channel(name))
+//!     .collect();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeSet;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::models::{GetBestBidAskParams, ListProductsParams, Product};
+use crate::ws::{Channel, ChannelName};
+
+/// A set of product IDs to track.
+///
+/// Product IDs are kept sorted, so requests and subscriptions built from the
+/// same watchlist are identical.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Watchlist {
+    product_ids: BTreeSet<String>,
+}
+
+impl Watchlist {
+    /// Create an empty watchlist.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a watchlist from the products marked as watched on the account.
+    pub fn from_products<'a>(products: impl IntoIterator<Item = &'a Product>) -> Self {
+        products
+            .into_iter()
+            .filter(|p| p.watched)
+            .map(|p| p.product_id.clone())
+            .collect()
+    }
+
+    /// Load a watchlist saved with [`save`](Self::save).
+    ///
+    /// Returns an empty watchlist if the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_str(&contents).map_err(|e| {
+            Error::parse(
+                format!("invalid watchlist {}: {}", path.display(), e),
+                Some(contents),
+            )
+        })
+    }
+
+    /// Write the watchlist to a JSON file, replacing it if it exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::parse(format!("failed to serialize watchlist: {}", e), None))?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Add a product. Returns false if it was already tracked.
+    pub fn add(&mut self, product_id: impl Into<String>) -> bool {
+        self.product_ids.insert(product_id.into())
+    }
+
+    /// Remove a product. Returns false if it was not tracked.
+    pub fn remove(&mut self, product_id: &str) -> bool {
+        self.product_ids.remove(product_id)
+    }
+
+    /// Returns true if the product is tracked.
+    pub fn contains(&self, product_id: &str) -> bool {
+        self.product_ids.contains(product_id)
+    }
+
+    /// Get the number of tracked products.
+    pub fn len(&self) -> usize {
+        self.product_ids.len()
+    }
+
+    /// Returns true if no products are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.product_ids.is_empty()
+    }
+
+    /// Iterate over the tracked product IDs in sorted order.
+    pub fn product_ids(&self) -> impl Iterator<Item = &str> {
+        self.product_ids.iter().map(String::as_str)
+    }
+
+    /// Build a WebSocket subscription to a per-product channel for every
+    /// tracked product.
+    ///
+    /// Returns `None` for channels that do not take product IDs.
+    pub fn channel(&self, name: ChannelName) -> Option<Channel> {
+        let product_ids: Vec<String> = self.product_ids.iter().cloned().collect();
+        match name {
+            ChannelName::Ticker => Some(Channel::Ticker { product_ids }),
+            ChannelName::TickerBatch => Some(Channel::TickerBatch { product_ids }),
+            ChannelName::Level2 => Some(Channel::Level2 { product_ids }),
+            ChannelName::Candles => Some(Channel::Candles { product_ids }),
+            ChannelName::MarketTrades => Some(Channel::MarketTrades { product_ids }),
+            _ => None,
+        }
+    }
+
+    /// Build parameters to list the tracked products.
+    pub fn list_products_params(&self) -> ListProductsParams {
+        ListProductsParams::new().product_ids(self.product_ids())
+    }
+
+    /// Build parameters to get the best bid and ask of the tracked products.
+    pub fn best_bid_ask_params(&self) -> GetBestBidAskParams {
+        GetBestBidAskParams::new().product_ids(self.product_ids())
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for Watchlist {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self {
+            product_ids: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<S: Into<String>> Extend<S> for Watchlist {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        self.product_ids.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchlist_channels_and_params() {
+        let mut watchlist: Watchlist = ["ETH-USD", "BTC-USD"].into_iter().collect();
+        assert!(!watchlist.add("BTC-USD"));
+        assert!(watchlist.add("SOL-USD"));
+        assert!(watchlist.remove("SOL-USD"));
+        assert!(!watchlist.contains("SOL-USD"));
+
+        let channel = watchlist.channel(ChannelName::Ticker).unwrap();
+        assert_eq!(channel.product_ids(), ["BTC-USD", "ETH-USD"]);
+        assert!(watchlist.channel(ChannelName::User).is_none());
+        assert_eq!(
+            watchlist.list_products_params().product_ids,
+            ["BTC-USD", "ETH-USD"]
+        );
+    }
+
+    #[test]
+    fn test_watchlist_persistence() {
+        let path = std::env::temp_dir().join(format!("watchlist-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        assert!(Watchlist::load(&path).unwrap().is_empty());
+
+        let watchlist: Watchlist = ["BTC-USD", "ETH-USD"].into_iter().collect();
+        watchlist.save(&path).unwrap();
+        assert_eq!(Watchlist::load(&path).unwrap(), watchlist);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,592 @@
+//! Forwarding order and fill events to a webhook.
+//!
+//! [`WebhookPublisher`] consumes messages from the WebSocket `user` channel,
+//! turns each order update into a [`WebhookEvent`], and POSTs the events as
+//! JSON to a configured URL. It implements
+//! [`EventPublisher`], so it plugs into
+//! [`publish::forward`](crate::publish::forward) like any other publisher.
+//!
+//! # Signing
+//!
+//! With a secret set, every request carries the Unix time it was sent in the
+//! [`TIMESTAMP_HEADER`] and an HMAC-SHA256 of `"{timestamp}.{body}"` in the
+//! [`SIGNATURE_HEADER`], formatted as `sha256=<hex>`. Receivers can check it
+//! with [`verify`].
+//!
+//! # Delivery
+//!
+//! Events are delivered in order. When a delivery fails, it and every later
+//! event wait in a bounded queue and are retried with exponential backoff on
+//! the next publish or [`flush`](WebhookPublisher::flush). Events that fail
+//! [`max_attempts`](WebhookPublisherBuilder::max_attempts) times, or that no
+//! longer fit in the queue, are dropped with a warning.
+//!
+//! Requires the `webhook` feature.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::publish;
+//! use coinbase_advanced::webhook::WebhookPublisher;
+//! use coinbase_advanced::ws::{Channel, WebSocketClient};
+//! use coinbase_advanced::Credentials;
+//!
+//! # async fn example() -> coinbase_advanced::Result<()> {
+//! let client = WebSocketClient::builder()
+//!     .credentials(Credentials::from_env()?)
+//!     .build()?;
+//! let stream = client.connect().await?;
+//! client.subscribe(&[Channel::User]).await?;
+//!
+//! let webhook = WebhookPublisher::builder("https://hooks.example.com/coinbase")
+//!     .secret("shared-secret")
+//!     .build()?;
+//! publish::forward(stream, &webhook).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+
+use reqwest::header::CONTENT_TYPE;
+use ring::hmac;
+use rust_decimal::Decimal;
+use secrecy::{ExposeSecret, SecretSlice};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::error::{Error, Result};
+use crate::models::{OrderSide, OrderStatus};
+use crate::publish::EventPublisher;
+use crate::runtime::{Instant, SystemTime, UNIX_EPOCH};
+use crate::ws::{EventType, Events, Message};
+
+/// Header carrying the HMAC-SHA256 signature of a delivery.
+pub const SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// Header carrying the Unix time a delivery was signed.
+pub const TIMESTAMP_HEADER: &str = "x-webhook-timestamp";
+
+/// Default number of attempts before an event is dropped.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Default number of events held for retry.
+pub const DEFAULT_QUEUE_CAPACITY: usize = 1000;
+
+/// Longest wait between retries.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+/// An order or fill from the user channel, as delivered to the webhook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// An order changed state.
+    Order(OrderEvent),
+    /// Part or all of an order was filled.
+    Fill(FillEvent),
+}
+
+/// The state of an order after an update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderEvent {
+    /// Order ID.
+    pub order_id: String,
+    /// Client order ID.
+    pub client_order_id: String,
+    /// Product ID.
+    pub product_id: String,
+    /// Order side.
+    pub side: Option<OrderSide>,
+    /// Order type (e.g., "LIMIT").
+    pub order_type: String,
+    /// Order status.
+    pub status: OrderStatus,
+    /// Total size filled so far.
+    pub filled_size: Decimal,
+    /// Average fill price, if anything has filled.
+    pub average_price: Option<Decimal>,
+    /// Time of the message that reported the update.
+    pub timestamp: String,
+}
+
+/// A fill, derived from the growth of an order's filled size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FillEvent {
+    /// Order ID.
+    pub order_id: String,
+    /// Client order ID.
+    pub client_order_id: String,
+    /// Product ID.
+    pub product_id: String,
+    /// Order side.
+    pub side: Option<OrderSide>,
+    /// Size filled.
+    pub size: Decimal,
+    /// Average price of this fill.
+    pub price: Decimal,
+    /// Fees charged for this fill.
+    pub commission: Decimal,
+    /// Time of the message that reported the fill.
+    pub timestamp: String,
+}
+
+/// How far an order had filled when last seen.
+#[derive(Debug, Clone, Copy, Default)]
+struct OrderProgress {
+    size: Decimal,
+    value: Decimal,
+    fees: Decimal,
+}
+
+/// Turns user channel messages into [`WebhookEvent`]s.
+///
+/// The snapshot sent on subscribing only records how far each open order
+/// has filled; updates then produce an order event, preceded by a fill
+/// event when the filled size grew.
+#[derive(Debug, Default)]
+pub struct EventNormalizer {
+    orders: HashMap<String, OrderProgress>,
+}
+
+impl EventNormalizer {
+    /// Create a normalizer that has seen no orders.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the events reported by a message.
+    ///
+    /// Messages from other channels produce no events.
+    pub fn normalize(&mut self, message: &Message) -> Result<Vec<WebhookEvent>> {
+        let Events::User(events) = &message.events else {
+            return Ok(Vec::new());
+        };
+
+        let mut normalized = Vec::new();
+        for event in events {
+            for update in &event.orders {
+                let update = update.typed()?;
+                let current = OrderProgress {
+                    size: update.cumulative_quantity.unwrap_or_default(),
+                    value: update.filled_value.unwrap_or_default(),
+                    fees: update.total_fees.unwrap_or_default(),
+                };
+                let previous = self
+                    .orders
+                    .insert(update.order_id.clone(), current)
+                    .unwrap_or_default();
+                if is_terminal(update.status) {
+                    self.orders.remove(&update.order_id);
+                }
+                if event.r#type != EventType::Update {
+                    continue;
+                }
+
+                let size = current.size - previous.size;
+                if size > Decimal::ZERO {
+                    normalized.push(WebhookEvent::Fill(FillEvent {
+                        order_id: update.order_id.clone(),
+                        client_order_id: update.client_order_id.clone(),
+                        product_id: update.product_id.clone(),
+                        side: update.order_side,
+                        size,
+                        price: (current.value - previous.value) / size,
+                        commission: current.fees - previous.fees,
+                        timestamp: message.timestamp.clone(),
+                    }));
+                }
+                normalized.push(WebhookEvent::Order(OrderEvent {
+                    order_id: update.order_id,
+                    client_order_id: update.client_order_id,
+                    product_id: update.product_id,
+                    side: update.order_side,
+                    order_type: update.order_type,
+                    status: update.status,
+                    filled_size: current.size,
+                    average_price: update.avg_price.filter(|p| !p.is_zero()),
+                    timestamp: message.timestamp.clone(),
+                }));
+            }
+        }
+        Ok(normalized)
+    }
+}
+
+fn is_terminal(status: OrderStatus) -> bool {
+    matches!(
+        status,
+        OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Expired | OrderStatus::Failed
+    )
+}
+
+/// Sign a delivery body sent at `timestamp` (Unix seconds).
+///
+/// Returns the value of the [`SIGNATURE_HEADER`], `sha256=<hex>`.
+pub fn sign(secret: &[u8], timestamp: u64, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    format!("sha256={}", hex::encode(signature(&key, timestamp, body)))
+}
+
+/// Check the signature of a received delivery in constant time.
+pub fn verify(secret: &[u8], timestamp: u64, body: &[u8], signature: &str) -> bool {
+    let Some(tag) = signature
+        .strip_prefix("sha256=")
+        .and_then(|hex_tag| hex::decode(hex_tag).ok())
+    else {
+        return false;
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    hmac::verify(&key, &signed_payload(timestamp, body), &tag).is_ok()
+}
+
+fn signature(key: &hmac::Key, timestamp: u64, body: &[u8]) -> hmac::Tag {
+    hmac::sign(key, &signed_payload(timestamp, body))
+}
+
+fn signed_payload(timestamp: u64, body: &[u8]) -> Vec<u8> {
+    let mut payload = format!("{}.", timestamp).into_bytes();
+    payload.extend_from_slice(body);
+    payload
+}
+
+/// An event waiting to be delivered.
+#[derive(Debug)]
+struct Delivery {
+    body: Vec<u8>,
+    attempts: u32,
+    next_attempt: Instant,
+}
+
+/// Builder for a [`WebhookPublisher`].
+#[derive(Debug)]
+pub struct WebhookPublisherBuilder {
+    url: String,
+    secret: Option<SecretSlice<u8>>,
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    timeout: Duration,
+    max_attempts: u32,
+    queue_capacity: usize,
+    retry_backoff: Duration,
+}
+
+impl WebhookPublisherBuilder {
+    /// Sign deliveries with an HMAC-SHA256 secret.
+    pub fn secret(mut self, secret: impl AsRef<[u8]>) -> Self {
+        self.secret = Some(secret.as_ref().to_vec().into());
+        self
+    }
+
+    /// Set the timeout of each delivery request.
+    ///
+    /// Default is 10 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set how many times an event is tried before it is dropped.
+    ///
+    /// Default is [`DEFAULT_MAX_ATTEMPTS`].
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set how many events may wait for retry.
+    ///
+    /// When the queue is full the oldest event is dropped. Default is
+    /// [`DEFAULT_QUEUE_CAPACITY`].
+    pub fn queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = capacity.max(1);
+        self
+    }
+
+    /// Set the wait before the first retry; later retries double it, up to a minute.
+    ///
+    /// Default is one second.
+    pub fn retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Build the publisher.
+    pub fn build(self) -> Result<WebhookPublisher> {
+        let url = url::Url::parse(&self.url)
+            .map_err(|e| Error::config(format!("Invalid webhook URL {:?}: {}", self.url, e)))?;
+
+        let builder = reqwest::Client::builder();
+        #[cfg(not(target_arch = "wasm32"))]
+        let builder = builder.timeout(self.timeout);
+        let http_client = builder
+            .build()
+            .map_err(|e| Error::config(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(WebhookPublisher {
+            http_client,
+            url,
+            key: self
+                .secret
+                .map(|secret| hmac::Key::new(hmac::HMAC_SHA256, secret.expose_secret())),
+            max_attempts: self.max_attempts,
+            queue_capacity: self.queue_capacity,
+            retry_backoff: self.retry_backoff,
+            normalizer: StdMutex::new(EventNormalizer::new()),
+            queue: Mutex::new(VecDeque::new()),
+        })
+    }
+}
+
+/// Publishes user channel events to an HTTP endpoint.
+pub struct WebhookPublisher {
+    http_client: reqwest::Client,
+    url: url::Url,
+    key: Option<hmac::Key>,
+    max_attempts: u32,
+    queue_capacity: usize,
+    retry_backoff: Duration,
+    normalizer: StdMutex<EventNormalizer>,
+    queue: Mutex<VecDeque<Delivery>>,
+}
+
+impl WebhookPublisher {
+    /// Create a builder for a publisher that POSTs to `url`.
+    pub fn builder(url: impl Into<String>) -> WebhookPublisherBuilder {
+        WebhookPublisherBuilder {
+            url: url.into(),
+            secret: None,
+            timeout: Duration::from_secs(10),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            retry_backoff: Duration::from_secs(1),
+        }
+    }
+
+    /// Queue events for delivery and deliver everything that is due.
+    pub async fn send(&self, events: &[WebhookEvent]) -> Result<()> {
+        {
+            let mut queue = self.queue.lock().await;
+            for event in events {
+                let body = serde_json::to_vec(event)
+                    .map_err(|e| Error::publish(format!("Failed to serialize event: {}", e)))?;
+                if queue.len() >= self.queue_capacity {
+                    queue.pop_front();
+                    tracing::warn!(url = %self.url, "webhook queue full, dropped oldest event");
+                }
+                queue.push_back(Delivery {
+                    body,
+                    attempts: 0,
+                    next_attempt: Instant::now(),
+                });
+            }
+        }
+        self.flush().await.map(|_| ())
+    }
+
+    /// Deliver queued events in order until one fails or is not yet due.
+    ///
+    /// Returns the number of events delivered.
+    pub async fn flush(&self) -> Result<usize> {
+        let mut queue = self.queue.lock().await;
+        let mut delivered = 0;
+        while let Some(delivery) = queue.front_mut() {
+            if delivery.next_attempt > Instant::now() {
+                break;
+            }
+            match self.deliver(&delivery.body).await {
+                Ok(()) => {
+                    queue.pop_front();
+                    delivered += 1;
+                }
+                Err(e) => {
+                    delivery.attempts += 1;
+                    if delivery.attempts >= self.max_attempts {
+                        tracing::warn!(url = %self.url, error = %e, attempts = delivery.attempts, "dropping webhook event");
+                        queue.pop_front();
+                        continue;
+                    }
+                    let backoff = self
+                        .retry_backoff
+                        .saturating_mul(2u32.saturating_pow(delivery.attempts - 1))
+                        .min(MAX_RETRY_BACKOFF);
+                    tracing::debug!(url = %self.url, error = %e, ?backoff, "webhook delivery failed, will retry");
+                    delivery.next_attempt = Instant::now() + backoff;
+                    break;
+                }
+            }
+        }
+        Ok(delivered)
+    }
+
+    /// Number of events waiting for delivery.
+    pub async fn pending(&self) -> usize {
+        self.queue.lock().await.len()
+    }
+
+    /// POST one event body.
+    async fn deliver(&self, body: &[u8]) -> Result<()> {
+        let mut request = self
+            .http_client
+            .post(self.url.clone())
+            .header(CONTENT_TYPE, "application/json");
+        if let Some(key) = &self.key {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            request = request.header(TIMESTAMP_HEADER, timestamp).header(
+                SIGNATURE_HEADER,
+                format!("sha256={}", hex::encode(signature(key, timestamp, body))),
+            );
+        }
+
+        let response = request
+            .body(body.to_vec())
+            .send()
+            .await
+            .map_err(|e| Error::publish(format!("Webhook request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(Error::publish(format!(
+                "Webhook responded with {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl EventPublisher for WebhookPublisher {
+    /// Normalize a message and deliver its events.
+    ///
+    /// Failed deliveries stay queued instead of failing the publish, so
+    /// [`publish::forward`](crate::publish::forward) keeps running while the
+    /// endpoint is down.
+    async fn publish(&self, message: &Message) -> Result<()> {
+        let events = self
+            .normalizer
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .normalize(message)?;
+        if events.is_empty() {
+            return self.flush().await.map(|_| ());
+        }
+        self.send(&events).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    fn user_message(r#type: &str, filled: &str, value: &str, status: &str) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "channel": "user",
+            "client_id": "",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "sequence_num": 1,
+            "events": [{
+                "type": r#type,
+                "orders": [{
+                    "order_id": "order-1",
+                    "client_order_id": "client-1",
+                    "product_id": "BTC-USD",
+                    "order_side": "BUY",
+                    "order_type": "LIMIT",
+                    "status": status,
+                    "cumulative_quantity": filled,
+                    "filled_value": value,
+                    "total_fees": "0"
+                }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_normalize_fills() {
+        let mut normalizer = EventNormalizer::new();
+        let events = normalizer
+            .normalize(&user_message("snapshot", "0.1", "5000", "OPEN"))
+            .unwrap();
+        assert!(events.is_empty());
+
+        let events = normalizer
+            .normalize(&user_message("update", "0.3", "15200", "FILLED"))
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        let WebhookEvent::Fill(fill) = &events[0] else {
+            panic!("expected a fill, got {:?}", events[0]);
+        };
+        assert_eq!(fill.size, Decimal::new(2, 1));
+        assert_eq!(fill.price, Decimal::new(51000, 0));
+        assert!(matches!(&events[1], WebhookEvent::Order(o) if o.status == OrderStatus::Filled));
+
+        let json = serde_json::to_value(&events[0]).unwrap();
+        assert_eq!(json["type"], "fill");
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signature = sign(b"secret", 1_705_320_000, b"{}");
+        assert!(signature.starts_with("sha256="));
+        assert!(verify(b"secret", 1_705_320_000, b"{}", &signature));
+        assert!(!verify(b"secret", 1_705_320_001, b"{}", &signature));
+        assert!(!verify(b"other", 1_705_320_000, b"{}", &signature));
+        assert!(!verify(b"secret", 1_705_320_000, b"{}", "garbage"));
+    }
+
+    #[tokio::test]
+    async fn test_delivery_retries() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header_exists(SIGNATURE_HEADER))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let webhook = WebhookPublisher::builder(format!("{}/hook", server.uri()))
+            .secret("secret")
+            .retry_backoff(Duration::ZERO)
+            .build()
+            .unwrap();
+
+        webhook
+            .publish(&user_message("update", "0", "0", "OPEN"))
+            .await
+            .unwrap();
+        assert_eq!(webhook.pending().await, 1);
+
+        assert_eq!(webhook.flush().await.unwrap(), 1);
+        assert_eq!(webhook.pending().await, 0);
+
+        let requests = server.received_requests().await.unwrap();
+        let last = requests.last().unwrap();
+        let timestamp: u64 = last.headers[TIMESTAMP_HEADER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let signature = last.headers[SIGNATURE_HEADER].to_str().unwrap();
+        assert!(verify(b"secret", timestamp, &last.body, signature));
+    }
+
+    #[test]
+    fn test_invalid_url() {
+        let err = WebhookPublisher::builder("not a url")
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(err, Error::Config(_)));
+    }
+}
@@ -2,6 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::Result;
+use crate::models::ProductId;
+
 /// Endpoint types for WebSocket connections.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EndpointType {
@@ -87,6 +90,25 @@ impl Channel {
         }
     }
 
+    /// Get a copy of this channel with its product IDs checked and
+    /// normalized through [`ProductId`].
+    ///
+    /// Returns an error naming the first invalid ID, such as "BTCUSD".
+    pub fn normalized(&self) -> Result<Channel> {
+        let mut channel = self.clone();
+        if let Channel::Ticker { product_ids }
+        | Channel::TickerBatch { product_ids }
+        | Channel::Level2 { product_ids }
+        | Channel::Candles { product_ids }
+        | Channel::MarketTrades { product_ids } = &mut channel
+        {
+            for id in product_ids.iter_mut() {
+                *id = id.parse::<ProductId>()?.into();
+            }
+        }
+        Ok(channel)
+    }
+
     /// Get the endpoint type for this channel.
     pub fn endpoint_type(&self) -> EndpointType {
         match self {
@@ -116,6 +138,28 @@ pub enum ChannelName {
     User,
     FuturesBalanceSummary,
     Subscriptions,
+    /// A channel name not recognized by this crate.
+    #[serde(other)]
+    Unknown,
+}
+
+impl ChannelName {
+    /// Get the channel name as it appears on the wire.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChannelName::Heartbeats => "heartbeats",
+            ChannelName::Status => "status",
+            ChannelName::Ticker => "ticker",
+            ChannelName::TickerBatch => "ticker_batch",
+            ChannelName::Level2 => "level2",
+            ChannelName::Candles => "candles",
+            ChannelName::MarketTrades => "market_trades",
+            ChannelName::User => "user",
+            ChannelName::FuturesBalanceSummary => "futures_balance_summary",
+            ChannelName::Subscriptions => "subscriptions",
+            ChannelName::Unknown => "unknown",
+        }
+    }
 }
 
 impl From<&Channel> for ChannelName {
@@ -0,0 +1,183 @@
+//! Split WebSocket handles.
+//!
+//! [`WebSocketClient::connect_split`] moves the client into a background
+//! task and returns a [`WsHandle`] that controls it and a [`WsEvents`]
+//! stream of its messages. Handles are cheap to clone and send commands to
+//! the task over a channel, so any task can change subscriptions without
+//! sharing the client.
+
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use tokio::sync::{mpsc, oneshot};
+
+use super::channels::{Channel, EndpointType};
+use super::client::{MessageStream, WebSocketClient};
+use super::health::{ConnectionHealth, ConnectionState, ConnectionStats, StateChange};
+use super::messages::Message;
+use crate::error::{Error, Result};
+
+/// A request from a [`WsHandle`] to the client task.
+enum Command {
+    Subscribe(Vec<Channel>, oneshot::Sender<Result<()>>),
+    Unsubscribe(Vec<Channel>, oneshot::Sender<Result<()>>),
+    Subscriptions(oneshot::Sender<Vec<Channel>>),
+    Close(oneshot::Sender<Result<()>>),
+}
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Subscribe(channels, _) => f.debug_tuple("Subscribe").field(channels).finish(),
+            Self::Unsubscribe(channels, _) => f.debug_tuple("Unsubscribe").field(channels).finish(),
+            Self::Subscriptions(_) => f.write_str("Subscriptions"),
+            Self::Close(_) => f.write_str("Close"),
+        }
+    }
+}
+
+/// Controls a WebSocket client running in a background task.
+///
+/// Commands are applied in the order they are sent, across all clones. The
+/// task stops when the last handle is dropped, closing the connections once
+/// the [`WsEvents`] stream is dropped as well.
+#[derive(Debug, Clone)]
+pub struct WsHandle {
+    commands: mpsc::UnboundedSender<Command>,
+    health: Arc<ConnectionHealth>,
+}
+
+impl WsHandle {
+    /// Move a client into a background task.
+    pub(super) fn spawn(client: WebSocketClient) -> Self {
+        let (commands, rx) = mpsc::unbounded_channel();
+        let health = client.health();
+        tokio::spawn(run(client, rx));
+        Self { commands, health }
+    }
+
+    /// Subscribe to one or more channels.
+    pub async fn subscribe(&self, channels: &[Channel]) -> Result<()> {
+        self.request(|reply| Command::Subscribe(channels.to_vec(), reply))
+            .await?
+    }
+
+    /// Unsubscribe from one or more channels.
+    pub async fn unsubscribe(&self, channels: &[Channel]) -> Result<()> {
+        self.request(|reply| Command::Unsubscribe(channels.to_vec(), reply))
+            .await?
+    }
+
+    /// Get the channels currently subscribed to.
+    ///
+    /// See [`WebSocketClient::subscriptions`].
+    pub async fn subscriptions(&self) -> Result<Vec<Channel>> {
+        self.request(Command::Subscriptions).await
+    }
+
+    /// Close the connections gracefully.
+    ///
+    /// See [`WebSocketClient::close`]; the [`WsEvents`] stream must still be
+    /// polled for the close to be acknowledged.
+    pub async fn close(&self) -> Result<()> {
+        self.request(Command::Close).await?
+    }
+
+    /// Get the state of a connection.
+    ///
+    /// See [`WebSocketClient::connection_state`].
+    pub fn connection_state(&self, endpoint: EndpointType) -> ConnectionState {
+        self.health.state(&endpoint)
+    }
+
+    /// Get the message counters of a connection.
+    ///
+    /// See [`WebSocketClient::connection_stats`].
+    pub fn connection_stats(&self, endpoint: EndpointType) -> ConnectionStats {
+        self.health.stats(&endpoint)
+    }
+
+    /// Stream every connection state change from now on.
+    ///
+    /// See [`WebSocketClient::state_changes`].
+    pub fn state_changes(&self) -> impl Stream<Item = StateChange> + Send + 'static {
+        self.health.changes()
+    }
+
+    /// Send a command and wait for the task's reply.
+    async fn request<T>(&self, command: impl FnOnce(oneshot::Sender<T>) -> Command) -> Result<T> {
+        let (reply, rx) = oneshot::channel();
+        self.commands
+            .send(command(reply))
+            .map_err(|_| Error::websocket("WebSocket client task has stopped"))?;
+        rx.await
+            .map_err(|_| Error::websocket("WebSocket client task has stopped"))
+    }
+}
+
+/// Apply commands to the client until every handle is dropped.
+async fn run(client: WebSocketClient, mut commands: mpsc::UnboundedReceiver<Command>) {
+    while let Some(command) = commands.recv().await {
+        tracing::trace!(?command, "WebSocket handle command");
+        // A dropped reply means the caller stopped waiting; the command
+        // still ran, so there is nothing to undo.
+        match command {
+            Command::Subscribe(channels, reply) => {
+                let _ = reply.send(client.subscribe(&channels).await);
+            }
+            Command::Unsubscribe(channels, reply) => {
+                let _ = reply.send(client.unsubscribe(&channels).await);
+            }
+            Command::Subscriptions(reply) => {
+                let _ = reply.send(client.subscriptions().await);
+            }
+            Command::Close(reply) => {
+                let _ = reply.send(client.close().await);
+            }
+        }
+    }
+}
+
+/// The messages of a client controlled by a [`WsHandle`].
+pub struct WsEvents {
+    stream: MessageStream,
+}
+
+impl WsEvents {
+    pub(super) fn new(stream: MessageStream) -> Self {
+        Self { stream }
+    }
+}
+
+impl Stream for WsEvents {
+    type Item = Result<Message>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.stream).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_handle_commands() {
+        let handle = WsHandle::spawn(WebSocketClient::builder().build().unwrap());
+        let other = handle.clone();
+
+        let ticker = Channel::Ticker {
+            product_ids: vec!["BTC-USD".to_string()],
+        };
+        let err = other.subscribe(&[ticker]).await.unwrap_err();
+        assert!(err.to_string().contains("not connected"));
+        let err = handle.subscribe(&[Channel::User]).await.unwrap_err();
+        assert!(err.to_string().contains("requires authentication"));
+
+        assert!(handle.subscriptions().await.unwrap().is_empty());
+        handle.close().await.unwrap();
+    }
+}
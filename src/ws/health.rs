@@ -0,0 +1,249 @@
+//! Connection state and traffic counters.
+//!
+//! Every [`WebSocketClient`](super::WebSocketClient) tracks the state of its
+//! public and user connections and counts the messages sent and received on
+//! each, so supervisory tasks can export connection health without reading
+//! the message stream themselves.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use tokio::sync::broadcast;
+
+use super::channels::EndpointType;
+
+/// How many state changes a slow [`state_changes`](super::WebSocketClient::state_changes)
+/// subscriber may fall behind before older ones are dropped.
+const STATE_CHANGE_CAPACITY: usize = 64;
+
+/// The state of one WebSocket connection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Not connected.
+    #[default]
+    Disconnected,
+    /// Opening the connection.
+    Connecting,
+    /// Connected and reading messages.
+    Connected {
+        /// When the connection opened.
+        since: DateTime<Utc>,
+    },
+    /// Waiting for or making a reconnect attempt.
+    Reconnecting {
+        /// The attempt number, starting at 1.
+        attempt: u32,
+    },
+}
+
+impl ConnectionState {
+    /// Whether the connection is open.
+    pub fn is_connected(&self) -> bool {
+        matches!(self, Self::Connected { .. })
+    }
+}
+
+/// Message counters for one WebSocket connection.
+///
+/// Counts text and binary frames only; pings, pongs and close frames are
+/// not included. Counters are kept across reconnects.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionStats {
+    /// Messages received from the server.
+    pub messages_received: u64,
+    /// Messages sent to the server, such as subscriptions.
+    pub messages_sent: u64,
+    /// When the last message was received.
+    pub last_received: Option<DateTime<Utc>>,
+    /// When the last message was sent.
+    pub last_sent: Option<DateTime<Utc>>,
+}
+
+/// A connection entering a new state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChange {
+    /// The connection whose state changed.
+    pub endpoint: EndpointType,
+    /// The state it entered.
+    pub state: ConnectionState,
+}
+
+/// State and counters of one connection.
+#[derive(Debug, Default)]
+struct EndpointHealth {
+    state: Mutex<ConnectionState>,
+    received: AtomicU64,
+    sent: AtomicU64,
+    last_received: Mutex<Option<DateTime<Utc>>>,
+    last_sent: Mutex<Option<DateTime<Utc>>>,
+}
+
+/// Health of both connections of a client, shared with its message stream.
+#[derive(Debug)]
+pub(crate) struct ConnectionHealth {
+    public: EndpointHealth,
+    user: EndpointHealth,
+    changes: broadcast::Sender<StateChange>,
+}
+
+impl Default for ConnectionHealth {
+    fn default() -> Self {
+        Self {
+            public: EndpointHealth::default(),
+            user: EndpointHealth::default(),
+            changes: broadcast::Sender::new(STATE_CHANGE_CAPACITY),
+        }
+    }
+}
+
+impl ConnectionHealth {
+    fn endpoint(&self, endpoint: &EndpointType) -> &EndpointHealth {
+        match endpoint {
+            EndpointType::Public => &self.public,
+            EndpointType::User => &self.user,
+        }
+    }
+
+    /// Get the current state of a connection.
+    pub(crate) fn state(&self, endpoint: &EndpointType) -> ConnectionState {
+        self.endpoint(endpoint)
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Move a connection to a new state, announcing it if it changed.
+    pub(crate) fn set_state(&self, endpoint: &EndpointType, state: ConnectionState) {
+        let mut current = self
+            .endpoint(endpoint)
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if *current == state {
+            return;
+        }
+        *current = state.clone();
+        drop(current);
+        tracing::debug!(?endpoint, ?state, "WebSocket connection state changed");
+        // Nobody listening is not an error.
+        let _ = self.changes.send(StateChange {
+            endpoint: endpoint.clone(),
+            state,
+        });
+    }
+
+    /// Mark a connection as open from now on.
+    pub(crate) fn connected(&self, endpoint: &EndpointType) {
+        self.set_state(endpoint, ConnectionState::Connected { since: Utc::now() });
+    }
+
+    /// Mark a connection as closed.
+    pub(crate) fn disconnected(&self, endpoint: &EndpointType) {
+        self.set_state(endpoint, ConnectionState::Disconnected);
+    }
+
+    /// Count a message received on a connection.
+    pub(crate) fn record_received(&self, endpoint: &EndpointType) {
+        let health = self.endpoint(endpoint);
+        health.received.fetch_add(1, Ordering::Relaxed);
+        *health
+            .last_received
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(Utc::now());
+    }
+
+    /// Count a message sent on a connection.
+    pub(crate) fn record_sent(&self, endpoint: &EndpointType) {
+        let health = self.endpoint(endpoint);
+        health.sent.fetch_add(1, Ordering::Relaxed);
+        *health.last_sent.lock().unwrap_or_else(|e| e.into_inner()) = Some(Utc::now());
+    }
+
+    /// Get the counters of a connection.
+    pub(crate) fn stats(&self, endpoint: &EndpointType) -> ConnectionStats {
+        let health = self.endpoint(endpoint);
+        ConnectionStats {
+            messages_received: health.received.load(Ordering::Relaxed),
+            messages_sent: health.sent.load(Ordering::Relaxed),
+            last_received: *health
+                .last_received
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()),
+            last_sent: *health.last_sent.lock().unwrap_or_else(|e| e.into_inner()),
+        }
+    }
+
+    /// Stream the state changes made from now on.
+    ///
+    /// Changes missed by a subscriber that fell too far behind are skipped.
+    pub(crate) fn changes(&self) -> impl Stream<Item = StateChange> + Send + 'static {
+        futures::stream::unfold(self.changes.subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(change) => return Some((change, rx)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "dropped WebSocket state changes");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_state_changes() {
+        let health = ConnectionHealth::default();
+        let mut changes = Box::pin(health.changes());
+
+        assert_eq!(
+            health.state(&EndpointType::Public),
+            ConnectionState::Disconnected
+        );
+        health.set_state(&EndpointType::Public, ConnectionState::Connecting);
+        // Unchanged states are not announced again.
+        health.set_state(&EndpointType::Public, ConnectionState::Connecting);
+        health.set_state(
+            &EndpointType::User,
+            ConnectionState::Reconnecting { attempt: 1 },
+        );
+        health.connected(&EndpointType::Public);
+
+        let change = changes.next().await.unwrap();
+        assert_eq!(change.endpoint, EndpointType::Public);
+        assert_eq!(change.state, ConnectionState::Connecting);
+        let change = changes.next().await.unwrap();
+        assert_eq!(change.endpoint, EndpointType::User);
+        assert_eq!(change.state, ConnectionState::Reconnecting { attempt: 1 });
+        let change = changes.next().await.unwrap();
+        assert!(change.state.is_connected());
+        assert!(health.state(&EndpointType::Public).is_connected());
+    }
+
+    #[test]
+    fn test_stats() {
+        let health = ConnectionHealth::default();
+        health.record_received(&EndpointType::Public);
+        health.record_received(&EndpointType::Public);
+        health.record_sent(&EndpointType::Public);
+
+        let stats = health.stats(&EndpointType::Public);
+        assert_eq!(stats.messages_received, 2);
+        assert_eq!(stats.messages_sent, 1);
+        assert!(stats.last_received.is_some());
+        assert!(stats.last_sent.is_some());
+        assert_eq!(
+            health.stats(&EndpointType::User),
+            ConnectionStats::default()
+        );
+    }
+}
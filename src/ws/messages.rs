@@ -1,8 +1,17 @@
 //! WebSocket message types.
 
+#[cfg(feature = "capture-unknown")]
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-use super::channels::ChannelName;
+use super::channels::{Channel, ChannelName};
+use crate::error::{self, Error};
+use crate::models::{OrderSide, OrderStatus};
+use crate::runtime::Instant;
 
 /// A message received from the WebSocket.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,12 +20,85 @@ pub struct Message {
     pub channel: ChannelName,
     /// The client ID for the message.
     pub client_id: String,
-    /// The timestamp for the message.
+    /// The timestamp for the message, as sent by the server.
+    ///
+    /// See [`time`](Self::time) for the parsed value.
     pub timestamp: String,
     /// The sequence number for the message.
     pub sequence_num: u64,
     /// The events in the message.
     pub events: Events,
+    /// When the client received the message.
+    ///
+    /// Set on messages read from a connection; `None` on messages that were
+    /// built or deserialized, such as replayed recordings.
+    #[serde(skip)]
+    pub received_at: Option<Instant>,
+}
+
+impl Message {
+    /// Wrap a payload that could not be parsed into a typed message.
+    ///
+    /// The envelope fields are filled in on a best-effort basis and the
+    /// complete payload is kept in [`Events::Unknown`].
+    pub fn raw(value: serde_json::Value) -> Self {
+        let channel = value
+            .get("channel")
+            .and_then(|c| serde_json::from_value(c.clone()).ok())
+            .unwrap_or(ChannelName::Unknown);
+        let client_id = value
+            .get("client_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let timestamp = value
+            .get("timestamp")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let sequence_num = value
+            .get("sequence_num")
+            .and_then(|v| v.as_u64())
+            .unwrap_or_default();
+
+        Self {
+            channel,
+            client_id,
+            timestamp,
+            sequence_num,
+            events: Events::Unknown(value),
+            received_at: None,
+        }
+    }
+
+    /// Parse the server timestamp.
+    ///
+    /// Returns `None` if the message has no timestamp.
+    pub fn time(&self) -> error::Result<Option<DateTime<Utc>>> {
+        parse_time("timestamp", &self.timestamp)
+    }
+
+    /// Time since the client received the message.
+    pub fn age(&self) -> Option<Duration> {
+        self.received_at.map(|t| t.elapsed())
+    }
+
+    /// Delay between the server timestamp and the client receiving the
+    /// message.
+    ///
+    /// Measured against the local clock, so it includes any clock skew.
+    /// Returns `None` if the receive time or timestamp is unknown, or if the
+    /// timestamp is later than the receive time.
+    pub fn latency(&self) -> Option<Duration> {
+        let age = chrono::Duration::from_std(self.age()?).ok()?;
+        let sent = self.time().ok()??;
+        (Utc::now() - age - sent).to_std().ok()
+    }
+
+    /// Check if the events in this message could not be parsed into a typed variant.
+    pub fn is_unknown(&self) -> bool {
+        matches!(self.events, Events::Unknown(_))
+    }
 }
 
 /// Events that can be received in a message.
@@ -41,6 +123,11 @@ pub enum Events {
     Subscriptions(Vec<SubscriptionsEvent>),
     /// Futures balance summary events.
     FuturesBalanceSummary(Vec<FuturesBalanceSummaryEvent>),
+    /// Events whose shape is not recognized by this crate.
+    ///
+    /// This keeps the stream alive when Coinbase introduces new fields or
+    /// event types that the typed variants cannot represent yet.
+    Unknown(serde_json::Value),
 }
 
 /// Event type (snapshot or update).
@@ -126,7 +213,11 @@ pub struct TickerEvent {
 }
 
 /// A ticker update.
+///
+/// Fields may be added in minor releases as the feed grows; build test
+/// values with `test_utils::messages::ticker` or by deserializing JSON.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct TickerUpdate {
     /// Ticker type.
     pub r#type: String,
@@ -146,6 +237,39 @@ pub struct TickerUpdate {
     pub high_52_w: String,
     /// 24-hour price percentage change.
     pub price_percent_chg_24_h: String,
+    /// Best bid price.
+    #[serde(default)]
+    pub best_bid: String,
+    /// Quantity at the best bid.
+    #[serde(default)]
+    pub best_bid_quantity: String,
+    /// Best ask price.
+    #[serde(default)]
+    pub best_ask: String,
+    /// Quantity at the best ask.
+    #[serde(default)]
+    pub best_ask_quantity: String,
+    /// Fields not yet modelled by this crate.
+    #[cfg(feature = "capture-unknown")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl TickerUpdate {
+    /// Parse the current price.
+    pub fn price(&self) -> error::Result<Option<Decimal>> {
+        parse_decimal("price", &self.price)
+    }
+
+    /// Parse the best bid price.
+    pub fn best_bid(&self) -> error::Result<Option<Decimal>> {
+        parse_decimal("best_bid", &self.best_bid)
+    }
+
+    /// Parse the best ask price.
+    pub fn best_ask(&self) -> error::Result<Option<Decimal>> {
+        parse_decimal("best_ask", &self.best_ask)
+    }
 }
 
 /// Level 2 order book event.
@@ -183,13 +307,158 @@ pub enum Level2Side {
     Ask,
 }
 
-/// User event containing order updates.
+/// User event containing order and position updates.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserEvent {
     /// Event type.
     pub r#type: EventType,
     /// Order updates.
     pub orders: Vec<OrderUpdate>,
+    /// Futures and perpetuals positions.
+    #[serde(default)]
+    pub positions: UserPositions,
+}
+
+/// Futures and perpetuals positions reported on the user channel.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserPositions {
+    /// Open perpetual futures positions.
+    #[serde(default)]
+    pub perpetual_futures_positions: Vec<PerpetualFuturesPosition>,
+    /// Open expiring futures positions.
+    #[serde(default)]
+    pub expiring_futures_positions: Vec<ExpiringFuturesPosition>,
+}
+
+impl UserPositions {
+    /// Check if no positions were reported.
+    pub fn is_empty(&self) -> bool {
+        self.perpetual_futures_positions.is_empty() && self.expiring_futures_positions.is_empty()
+    }
+}
+
+/// Side of a futures or perpetuals position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PositionSide {
+    /// Long position.
+    #[serde(alias = "Long", alias = "long", alias = "POSITION_SIDE_LONG")]
+    Long,
+    /// Short position.
+    #[serde(alias = "Short", alias = "short", alias = "POSITION_SIDE_SHORT")]
+    Short,
+    /// Side not recognized by this crate.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A perpetual futures position update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerpetualFuturesPosition {
+    /// Product ID (e.g., "BTC-PERP-INTX").
+    pub product_id: String,
+    /// Portfolio UUID.
+    #[serde(default)]
+    pub portfolio_uuid: String,
+    /// Volume weighted average price.
+    #[serde(default)]
+    pub vwap: String,
+    /// Volume weighted average entry price.
+    #[serde(default)]
+    pub entry_vwap: String,
+    /// Position side.
+    pub position_side: PositionSide,
+    /// Margin type (e.g., "Cross").
+    #[serde(default)]
+    pub margin_type: String,
+    /// Net position size.
+    #[serde(default)]
+    pub net_size: String,
+    /// Size of open buy orders.
+    #[serde(default)]
+    pub buy_order_size: String,
+    /// Size of open sell orders.
+    #[serde(default)]
+    pub sell_order_size: String,
+    /// Leverage.
+    #[serde(default)]
+    pub leverage: String,
+    /// Mark price.
+    #[serde(default)]
+    pub mark_price: String,
+    /// Liquidation price.
+    #[serde(default)]
+    pub liquidation_price: String,
+    /// Initial margin notional.
+    #[serde(default)]
+    pub im_notional: String,
+    /// Maintenance margin notional.
+    #[serde(default)]
+    pub mm_notional: String,
+    /// Position notional.
+    #[serde(default)]
+    pub position_notional: String,
+    /// Unrealized profit and loss.
+    #[serde(default)]
+    pub unrealized_pnl: String,
+    /// Aggregated profit and loss.
+    #[serde(default)]
+    pub aggregated_pnl: String,
+}
+
+impl PerpetualFuturesPosition {
+    /// Parse the net position size.
+    pub fn net_size(&self) -> error::Result<Option<Decimal>> {
+        parse_decimal("net_size", &self.net_size)
+    }
+
+    /// Parse the average entry price.
+    pub fn entry_price(&self) -> error::Result<Option<Decimal>> {
+        parse_decimal("entry_vwap", &self.entry_vwap)
+    }
+
+    /// Parse the unrealized profit and loss.
+    pub fn unrealized_pnl(&self) -> error::Result<Option<Decimal>> {
+        parse_decimal("unrealized_pnl", &self.unrealized_pnl)
+    }
+}
+
+/// An expiring futures position update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpiringFuturesPosition {
+    /// Product ID (e.g., "BIT-28JUN24-CDE").
+    pub product_id: String,
+    /// Position side.
+    pub side: PositionSide,
+    /// Number of contracts held.
+    #[serde(default)]
+    pub number_of_contracts: String,
+    /// Realized profit and loss.
+    #[serde(default)]
+    pub realized_pnl: String,
+    /// Unrealized profit and loss.
+    #[serde(default)]
+    pub unrealized_pnl: String,
+    /// Average entry price.
+    #[serde(default)]
+    pub entry_price: String,
+}
+
+impl ExpiringFuturesPosition {
+    /// Parse the number of contracts held.
+    pub fn contracts(&self) -> error::Result<Option<Decimal>> {
+        parse_decimal("number_of_contracts", &self.number_of_contracts)
+    }
+
+    /// Parse the average entry price.
+    pub fn entry_price(&self) -> error::Result<Option<Decimal>> {
+        parse_decimal("entry_price", &self.entry_price)
+    }
+
+    /// Parse the unrealized profit and loss.
+    pub fn unrealized_pnl(&self) -> error::Result<Option<Decimal>> {
+        parse_decimal("unrealized_pnl", &self.unrealized_pnl)
+    }
 }
 
 /// An order update for the user channel.
@@ -277,6 +546,141 @@ pub struct OrderUpdate {
     /// Start time.
     #[serde(default)]
     pub start_time: String,
+    /// Fields not yet modelled by this crate.
+    #[cfg(feature = "capture-unknown")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl OrderUpdate {
+    /// Convert the string fields into typed values.
+    ///
+    /// Empty strings, which the API sends for fields that do not apply to an
+    /// order, become `None`. Non-empty values that cannot be parsed are errors.
+    pub fn typed(&self) -> error::Result<TypedOrderUpdate> {
+        Ok(TypedOrderUpdate {
+            order_id: self.order_id.clone(),
+            client_order_id: self.client_order_id.clone(),
+            product_id: self.product_id.clone(),
+            order_side: parse_side(&self.order_side)?,
+            order_type: self.order_type.clone(),
+            status: self.status.parse().unwrap_or(OrderStatus::Unknown),
+            avg_price: parse_decimal("avg_price", &self.avg_price)?,
+            cumulative_quantity: parse_decimal("cumulative_quantity", &self.cumulative_quantity)?,
+            leaves_quantity: parse_decimal("leaves_quantity", &self.leaves_quantity)?,
+            filled_value: parse_decimal("filled_value", &self.filled_value)?,
+            limit_price: parse_decimal("limit_price", &self.limit_price)?,
+            stop_price: parse_decimal("stop_price", self.stop_price.as_deref().unwrap_or(""))?,
+            completion_percentage: parse_decimal(
+                "completion_percentage",
+                &self.completion_percentage,
+            )?,
+            number_of_fills: parse_optional("number_of_fills", &self.number_of_fills)?,
+            outstanding_hold_amount: parse_decimal(
+                "outstanding_hold_amount",
+                &self.outstanding_hold_amount,
+            )?,
+            total_fees: parse_decimal("total_fees", &self.total_fees)?,
+            total_value_after_fees: parse_decimal(
+                "total_value_after_fees",
+                &self.total_value_after_fees,
+            )?,
+            creation_time: parse_time("creation_time", &self.creation_time)?,
+            start_time: parse_time("start_time", &self.start_time)?,
+            end_time: parse_time("end_time", &self.end_time)?,
+        })
+    }
+}
+
+/// An [`OrderUpdate`] with numeric fields as decimals and timestamps as UTC times.
+///
+/// Fields that the API left empty are `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedOrderUpdate {
+    /// Order ID.
+    pub order_id: String,
+    /// Client order ID.
+    pub client_order_id: String,
+    /// Product ID.
+    pub product_id: String,
+    /// Order side.
+    pub order_side: Option<OrderSide>,
+    /// Order type.
+    pub order_type: String,
+    /// Order status.
+    pub status: OrderStatus,
+    /// Average fill price.
+    pub avg_price: Option<Decimal>,
+    /// Cumulative filled quantity.
+    pub cumulative_quantity: Option<Decimal>,
+    /// Remaining quantity.
+    pub leaves_quantity: Option<Decimal>,
+    /// Filled value.
+    pub filled_value: Option<Decimal>,
+    /// Limit price.
+    pub limit_price: Option<Decimal>,
+    /// Stop price.
+    pub stop_price: Option<Decimal>,
+    /// Completion percentage.
+    pub completion_percentage: Option<Decimal>,
+    /// Number of fills.
+    pub number_of_fills: Option<u64>,
+    /// Outstanding hold amount.
+    pub outstanding_hold_amount: Option<Decimal>,
+    /// Total fees.
+    pub total_fees: Option<Decimal>,
+    /// Total value after fees.
+    pub total_value_after_fees: Option<Decimal>,
+    /// Creation time.
+    pub creation_time: Option<DateTime<Utc>>,
+    /// Start time.
+    pub start_time: Option<DateTime<Utc>>,
+    /// End time.
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+/// Parse a possibly empty string field, naming the field on failure.
+fn parse_optional<T: std::str::FromStr>(field: &str, value: &str) -> error::Result<Option<T>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| Error::parse(format!("invalid {}: {:?}", field, value), None))
+}
+
+fn parse_decimal(field: &str, value: &str) -> error::Result<Option<Decimal>> {
+    // Scientific notation shows up for very small quantities.
+    match parse_optional::<Decimal>(field, value) {
+        Err(_) => Decimal::from_scientific(value.trim())
+            .map(Some)
+            .map_err(|_| Error::parse(format!("invalid {}: {:?}", field, value), None)),
+        result => result,
+    }
+}
+
+fn parse_time(field: &str, value: &str) -> error::Result<Option<DateTime<Utc>>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| Some(t.with_timezone(&Utc)))
+        .map_err(|_| Error::parse(format!("invalid {}: {:?}", field, value), None))
+}
+
+fn parse_side(value: &str) -> error::Result<Option<OrderSide>> {
+    match value.trim() {
+        "" => Ok(None),
+        "BUY" => Ok(Some(OrderSide::Buy)),
+        "SELL" => Ok(Some(OrderSide::Sell)),
+        other => Err(Error::parse(
+            format!("invalid order_side: {:?}", other),
+            None,
+        )),
+    }
 }
 
 /// Market trades event.
@@ -303,6 +707,10 @@ pub struct TradeUpdate {
     pub side: String,
     /// Trade time.
     pub time: String,
+    /// Fields not yet modelled by this crate.
+    #[cfg(feature = "capture-unknown")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Heartbeat event.
@@ -345,6 +753,122 @@ pub struct SubscriptionStatus {
     /// Heartbeats channel subscriptions.
     #[serde(default)]
     pub heartbeats: Option<Vec<String>>,
+    /// Candles channel subscriptions.
+    #[serde(default)]
+    pub candles: Option<Vec<String>>,
+    /// Futures balance summary channel subscriptions.
+    #[serde(default)]
+    pub futures_balance_summary: Option<Vec<String>>,
+}
+
+impl SubscriptionStatus {
+    /// List the confirmed channels with their product IDs.
+    pub fn channels(&self) -> Vec<(ChannelName, &[String])> {
+        let listed = [
+            (ChannelName::Status, Some(&self.status)),
+            (ChannelName::Ticker, Some(&self.ticker)),
+            (ChannelName::TickerBatch, Some(&self.ticker_batch)),
+        ]
+        .into_iter()
+        .filter(|(_, ids)| ids.is_some_and(|ids| !ids.is_empty()));
+        let optional = [
+            (ChannelName::Level2, self.level2.as_ref()),
+            (ChannelName::User, self.user.as_ref()),
+            (ChannelName::MarketTrades, self.market_trades.as_ref()),
+            (ChannelName::Heartbeats, self.heartbeats.as_ref()),
+            (ChannelName::Candles, self.candles.as_ref()),
+            (
+                ChannelName::FuturesBalanceSummary,
+                self.futures_balance_summary.as_ref(),
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, ids)| ids.is_some());
+        listed
+            .chain(optional)
+            .filter_map(|(name, ids)| Some((name, ids?.as_slice())))
+            .collect()
+    }
+
+    /// Compare the confirmed subscriptions with the channels subscribed on
+    /// the same connection.
+    ///
+    /// Pass only the channels of the connection that sent this status, as
+    /// public and user channels are confirmed separately. Product IDs are
+    /// only compared for channels that take them.
+    pub fn diff<'a>(
+        &self,
+        tracked: impl IntoIterator<Item = &'a Channel>,
+    ) -> Vec<SubscriptionMismatch> {
+        let confirmed = self.channels();
+        let tracked: Vec<&Channel> = tracked.into_iter().collect();
+        let mut mismatches = Vec::new();
+
+        for channel in &tracked {
+            let name = ChannelName::from(*channel);
+            let server = confirmed
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, ids)| *ids);
+            let missing: Vec<String> = match server {
+                None => channel.product_ids().to_vec(),
+                Some(ids) => channel
+                    .product_ids()
+                    .iter()
+                    .filter(|id| !ids.contains(*id))
+                    .cloned()
+                    .collect(),
+            };
+            if server.is_none() || !missing.is_empty() {
+                mismatches.push(SubscriptionMismatch::Missing {
+                    channel: name,
+                    product_ids: missing,
+                });
+            }
+        }
+
+        for (name, ids) in confirmed {
+            let local = tracked.iter().find(|c| ChannelName::from(**c) == name);
+            let unexpected: Vec<String> = match local {
+                None => ids.to_vec(),
+                Some(channel) if channel.product_ids().is_empty() => Vec::new(),
+                Some(channel) => ids
+                    .iter()
+                    .filter(|id| !channel.product_ids().contains(*id))
+                    .cloned()
+                    .collect(),
+            };
+            if local.is_none() || !unexpected.is_empty() {
+                mismatches.push(SubscriptionMismatch::Unexpected {
+                    channel: name,
+                    product_ids: unexpected,
+                });
+            }
+        }
+        mismatches
+    }
+}
+
+/// A difference between tracked and server-confirmed subscriptions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionMismatch {
+    /// Subscribed locally but not confirmed by the server.
+    ///
+    /// `product_ids` lists the unconfirmed products, or is empty when the
+    /// whole channel is missing and takes no products.
+    Missing {
+        /// The channel.
+        channel: ChannelName,
+        /// Unconfirmed product IDs.
+        product_ids: Vec<String>,
+    },
+    /// Confirmed by the server but not subscribed locally.
+    Unexpected {
+        /// The channel.
+        channel: ChannelName,
+        /// Product IDs not subscribed locally.
+        product_ids: Vec<String>,
+    },
 }
 
 /// Futures balance summary event.
@@ -423,6 +947,61 @@ mod tests {
         assert!(msg.is_ok());
     }
 
+    #[test]
+    fn test_unknown_events_deserialize() {
+        let data = r#"
+            {
+                "channel":"user",
+                "client_id":"",
+                "timestamp":"2025-01-14T22:11:18.791273556Z",
+                "sequence_num":3,
+                "events":[{"type":"update","brand_new_field":{"value":1}}]
+            }
+        "#;
+
+        let msg: Message = serde_json::from_str(data).unwrap();
+        assert!(msg.is_unknown());
+        assert_eq!(msg.sequence_num, 3);
+    }
+
+    #[test]
+    fn test_raw_message() {
+        let value = serde_json::json!({
+            "channel": "some_new_channel",
+            "sequence_num": 7,
+            "payload": true
+        });
+
+        let msg = Message::raw(value.clone());
+        assert_eq!(msg.channel, ChannelName::Unknown);
+        assert_eq!(msg.sequence_num, 7);
+        assert!(msg.client_id.is_empty());
+        match msg.events {
+            Events::Unknown(raw) => assert_eq!(raw, value),
+            other => panic!("unexpected events: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_message_time() {
+        let mut msg = Message::raw(serde_json::json!({
+            "timestamp": "2025-01-14T22:11:18.791273556Z"
+        }));
+        let time = msg.time().unwrap().unwrap();
+        assert_eq!(time.timestamp_subsec_nanos(), 791273556);
+        assert!(msg.age().is_none());
+        assert!(msg.latency().is_none());
+
+        msg.received_at = Some(Instant::now());
+        assert!(msg.age().is_some());
+        assert!(msg.latency().unwrap() > Duration::from_secs(3600));
+
+        msg.timestamp = String::new();
+        assert!(msg.time().unwrap().is_none());
+        msg.timestamp = "yesterday".to_string();
+        assert!(msg.time().is_err());
+    }
+
     #[test]
     fn test_level2_side_deserialize() {
         // Test normal cases
@@ -440,4 +1019,185 @@ mod tests {
             Level2Side::Ask
         );
     }
+
+    #[test]
+    fn test_order_update_typed() {
+        let update: OrderUpdate = serde_json::from_value(serde_json::json!({
+            "order_id": "order-1",
+            "order_side": "BUY",
+            "order_type": "LIMIT",
+            "product_id": "BTC-USD",
+            "status": "FILLED",
+            "avg_price": "50000.12",
+            "cumulative_quantity": "0.001",
+            "leaves_quantity": "0",
+            "limit_price": "",
+            "number_of_fills": "2",
+            "total_fees": "1e-8",
+            "creation_time": "2024-01-15T12:00:00.123Z",
+            "end_time": ""
+        }))
+        .unwrap();
+
+        let typed = update.typed().unwrap();
+        assert_eq!(typed.order_side, Some(OrderSide::Buy));
+        assert_eq!(typed.status, OrderStatus::Filled);
+        assert_eq!(typed.avg_price, Some("50000.12".parse().unwrap()));
+        assert_eq!(typed.limit_price, None);
+        assert_eq!(typed.stop_price, None);
+        assert_eq!(typed.number_of_fills, Some(2));
+        assert_eq!(typed.total_fees, Some(Decimal::new(1, 8)));
+        assert_eq!(
+            typed.creation_time.unwrap().to_rfc3339(),
+            "2024-01-15T12:00:00.123+00:00"
+        );
+        assert_eq!(typed.end_time, None);
+
+        let mut bad = update.clone();
+        bad.avg_price = "not-a-number".to_string();
+        assert!(bad.typed().is_err());
+    }
+
+    #[test]
+    fn test_user_positions_deserialize() {
+        let data = r#"
+            {
+                "channel":"user",
+                "client_id":"",
+                "timestamp":"2024-05-20T14:08:11.614218471Z",
+                "sequence_num":4,
+                "events":[{
+                    "type":"update",
+                    "orders":[],
+                    "positions":{
+                        "perpetual_futures_positions":[{
+                            "product_id":"BTC-PERP-INTX",
+                            "portfolio_uuid":"018f3a2c-6b4e-7d8f-9a0b-1c2d3e4f5a6b",
+                            "vwap":"67012.5",
+                            "entry_vwap":"66950.1",
+                            "position_side":"Long",
+                            "margin_type":"Cross",
+                            "net_size":"0.0125",
+                            "buy_order_size":"0",
+                            "sell_order_size":"0",
+                            "leverage":"2",
+                            "mark_price":"67100.3",
+                            "liquidation_price":"0",
+                            "im_notional":"419.38",
+                            "mm_notional":"12.58",
+                            "position_notional":"838.75",
+                            "unrealized_pnl":"1.88",
+                            "aggregated_pnl":"1.88"
+                        }],
+                        "expiring_futures_positions":[{
+                            "product_id":"BIT-31MAY24-CDE",
+                            "side":"Short",
+                            "number_of_contracts":"3",
+                            "realized_pnl":"0",
+                            "unrealized_pnl":"-4.5",
+                            "entry_price":"67250"
+                        }]
+                    }
+                }]
+            }
+        "#;
+
+        let msg: Message = serde_json::from_str(data).unwrap();
+        let Events::User(events) = msg.events else {
+            panic!("expected user events");
+        };
+        let positions = &events[0].positions;
+        assert!(events[0].orders.is_empty());
+        assert!(!positions.is_empty());
+
+        let perp = &positions.perpetual_futures_positions[0];
+        assert_eq!(perp.position_side, PositionSide::Long);
+        assert_eq!(perp.net_size().unwrap(), Some("0.0125".parse().unwrap()));
+        assert_eq!(
+            perp.entry_price().unwrap(),
+            Some("66950.1".parse().unwrap())
+        );
+
+        let future = &positions.expiring_futures_positions[0];
+        assert_eq!(future.side, PositionSide::Short);
+        assert_eq!(future.contracts().unwrap(), Some(Decimal::from(3)));
+        assert_eq!(future.entry_price().unwrap(), Some(Decimal::from(67250)));
+        assert_eq!(
+            future.unrealized_pnl().unwrap(),
+            Some("-4.5".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_position_side_deserialize() {
+        for (raw, side) in [
+            (r#""LONG""#, PositionSide::Long),
+            (r#""Short""#, PositionSide::Short),
+            (r#""POSITION_SIDE_LONG""#, PositionSide::Long),
+            (r#""UNKNOWN_POSITION_SIDE""#, PositionSide::Unknown),
+        ] {
+            assert_eq!(serde_json::from_str::<PositionSide>(raw).unwrap(), side);
+        }
+    }
+
+    #[test]
+    fn test_user_event_without_positions() {
+        let event: UserEvent =
+            serde_json::from_value(serde_json::json!({"type": "snapshot", "orders": []})).unwrap();
+        assert!(event.positions.is_empty());
+    }
+
+    #[test]
+    fn test_subscription_status_diff() {
+        let status: SubscriptionStatus = serde_json::from_value(serde_json::json!({
+            "ticker": ["BTC-USD", "SOL-USD"],
+            "heartbeats": ["heartbeats"],
+            "level2": ["ETH-USD"]
+        }))
+        .unwrap();
+
+        let tracked = [
+            Channel::Ticker {
+                product_ids: vec!["BTC-USD".to_string(), "ETH-USD".to_string()],
+            },
+            Channel::Heartbeats,
+            Channel::Candles {
+                product_ids: vec!["BTC-USD".to_string()],
+            },
+        ];
+        let mismatches = status.diff(&tracked);
+
+        assert_eq!(
+            mismatches,
+            vec![
+                SubscriptionMismatch::Missing {
+                    channel: ChannelName::Ticker,
+                    product_ids: vec!["ETH-USD".to_string()],
+                },
+                SubscriptionMismatch::Missing {
+                    channel: ChannelName::Candles,
+                    product_ids: vec!["BTC-USD".to_string()],
+                },
+                SubscriptionMismatch::Unexpected {
+                    channel: ChannelName::Ticker,
+                    product_ids: vec!["SOL-USD".to_string()],
+                },
+                SubscriptionMismatch::Unexpected {
+                    channel: ChannelName::Level2,
+                    product_ids: vec!["ETH-USD".to_string()],
+                },
+            ]
+        );
+
+        let tracked = [
+            Channel::Ticker {
+                product_ids: vec!["BTC-USD".to_string(), "SOL-USD".to_string()],
+            },
+            Channel::Heartbeats,
+            Channel::Level2 {
+                product_ids: vec!["ETH-USD".to_string()],
+            },
+        ];
+        assert!(status.diff(&tracked).is_empty());
+    }
 }
@@ -6,6 +6,9 @@
 //! WebSocket API. It supports both public channels (market data) and authenticated user
 //! channels (order updates, fills, etc.).
 //!
+//! The message types are always available. The client itself requires the
+//! `tokio` feature, which is enabled by default.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -34,9 +37,29 @@
 //! ```
 
 mod channels;
+#[cfg(feature = "tokio")]
 mod client;
+#[cfg(feature = "tokio")]
+mod handle;
+#[cfg(feature = "tokio")]
+mod health;
 mod messages;
+#[cfg(feature = "tokio")]
+mod planner;
+#[cfg(feature = "tokio")]
+mod reconnect;
 
 pub use channels::{Channel, ChannelName, EndpointType};
-pub use client::{WebSocketClient, WebSocketClientBuilder};
+#[cfg(feature = "tokio")]
+pub use client::{MessageStream, WebSocketClient, WebSocketClientBuilder};
+#[cfg(feature = "tokio")]
+pub use handle::{WsEvents, WsHandle};
+#[cfg(feature = "tokio")]
+pub use health::{ConnectionState, ConnectionStats, StateChange};
 pub use messages::*;
+#[cfg(feature = "tokio")]
+pub use planner::{
+    DEFAULT_MAX_PRODUCTS_PER_CONNECTION, MergedStream, ShardedClient, SubscriptionPlanner,
+};
+#[cfg(feature = "tokio")]
+pub use reconnect::{BackoffPolicy, ReconnectEvent};
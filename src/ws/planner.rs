@@ -0,0 +1,282 @@
+//! Spreading subscriptions over several WebSocket connections.
+//!
+//! Coinbase limits how many subscriptions one connection may carry, so a
+//! large watchlist has to be split across connections. A
+//! [`SubscriptionPlanner`] balances the products over as few connections as
+//! the per-connection limit allows, connects them, and merges their messages
+//! into a single stream.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use coinbase_advanced::ws::{ChannelName, SubscriptionPlanner, WebSocketClient};
+//! use futures::StreamExt;
+//!
+//! # async fn example(product_ids: Vec<String>) -> coinbase_advanced::Result<()> {
+//! let (client, mut stream) = SubscriptionPlanner::new()
+//!     .channel(ChannelName::Heartbeats)
+//!     .channel(ChannelName::Ticker)
+//!     .max_products_per_connection(50)
+//!     .connect(WebSocketClient::builder(), product_ids)
+//!     .await?;
+//! println!("using {} connections", client.len());
+//!
+//! while let Some(message) = stream.next().await {
+//!     println!("{:?}", message?.events);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use futures::future;
+use futures::stream::SelectAll;
+
+use super::channels::{Channel, ChannelName};
+use super::client::{MessageStream, WebSocketClient, WebSocketClientBuilder, channel_from_name};
+use crate::error::{Error, Result};
+
+/// Default number of products subscribed on one connection.
+pub const DEFAULT_MAX_PRODUCTS_PER_CONNECTION: usize = 100;
+
+/// Messages from every connection of a [`ShardedClient`], in arrival order.
+pub type MergedStream = SelectAll<MessageStream>;
+
+/// Plans and opens the connections needed for a set of products.
+///
+/// Product channels (ticker, level 2, candles, ...) are subscribed for each
+/// connection's share of the products. Heartbeats are subscribed on every
+/// connection to keep each one alive; the remaining channels, such as
+/// status and user, are subscribed on the first connection only.
+#[derive(Debug, Clone)]
+pub struct SubscriptionPlanner {
+    channels: Vec<ChannelName>,
+    max_products_per_connection: usize,
+    max_connections: Option<usize>,
+}
+
+impl Default for SubscriptionPlanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SubscriptionPlanner {
+    /// Create a planner with no channels and the default per-connection limit.
+    pub fn new() -> Self {
+        Self {
+            channels: Vec::new(),
+            max_products_per_connection: DEFAULT_MAX_PRODUCTS_PER_CONNECTION,
+            max_connections: None,
+        }
+    }
+
+    /// Add a channel to subscribe to.
+    pub fn channel(mut self, name: ChannelName) -> Self {
+        if !self.channels.contains(&name) {
+            self.channels.push(name);
+        }
+        self
+    }
+
+    /// Set the most products to subscribe on one connection.
+    ///
+    /// Default is [`DEFAULT_MAX_PRODUCTS_PER_CONNECTION`].
+    pub fn max_products_per_connection(mut self, max: usize) -> Self {
+        self.max_products_per_connection = max.max(1);
+        self
+    }
+
+    /// Fail planning instead of opening more than `max` connections.
+    pub fn max_connections(mut self, max: usize) -> Self {
+        self.max_connections = Some(max);
+        self
+    }
+
+    /// Split the products into the channels to subscribe on each connection.
+    ///
+    /// Products are deduplicated and sorted, then spread so that connection
+    /// sizes differ by at most one. At least one connection is always
+    /// planned, so channels without products are still subscribed.
+    pub fn plan<I, S>(&self, product_ids: I) -> Result<Vec<Vec<Channel>>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut products: Vec<String> = product_ids.into_iter().map(Into::into).collect();
+        products.sort_unstable();
+        products.dedup();
+
+        let connections = products
+            .len()
+            .div_ceil(self.max_products_per_connection)
+            .max(1);
+        if let Some(max) = self.max_connections {
+            if connections > max {
+                return Err(Error::config(format!(
+                    "{} products need {} connections at {} products each, more than the limit of {}",
+                    products.len(),
+                    connections,
+                    self.max_products_per_connection,
+                    max
+                )));
+            }
+        }
+
+        let base = products.len() / connections;
+        let extra = products.len() % connections;
+        let mut rest = products.as_slice();
+        let mut plan = Vec::with_capacity(connections);
+        for index in 0..connections {
+            let (shard, tail) = rest.split_at(base + usize::from(index < extra));
+            rest = tail;
+
+            let mut channels = Vec::new();
+            for name in &self.channels {
+                let product_ids = if is_product_channel(name) {
+                    if shard.is_empty() {
+                        continue;
+                    }
+                    shard.to_vec()
+                } else if *name == ChannelName::Heartbeats || index == 0 {
+                    Vec::new()
+                } else {
+                    continue;
+                };
+                channels.extend(channel_from_name(name.clone(), product_ids));
+            }
+            plan.push(channels);
+        }
+        Ok(plan)
+    }
+
+    /// Open one client per planned connection and subscribe it.
+    ///
+    /// Every client is built from `builder`, so they share credentials,
+    /// environment and reconnect settings. Returns the clients together with
+    /// their merged message stream.
+    pub async fn connect<I, S>(
+        &self,
+        builder: WebSocketClientBuilder,
+        product_ids: I,
+    ) -> Result<(ShardedClient, MergedStream)>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let plan = self.plan(product_ids)?;
+
+        let mut clients = Vec::with_capacity(plan.len());
+        let mut streams = SelectAll::new();
+        for channels in &plan {
+            let client = builder.clone().build()?;
+            let stream = client.connect().await?;
+            client.subscribe(channels).await?;
+            streams.push(stream);
+            clients.push(client);
+        }
+        tracing::debug!(connections = clients.len(), "opened planned connections");
+
+        Ok((ShardedClient { clients, plan }, streams))
+    }
+}
+
+/// Returns true for channels subscribed per product.
+fn is_product_channel(name: &ChannelName) -> bool {
+    matches!(
+        name,
+        ChannelName::Ticker
+            | ChannelName::TickerBatch
+            | ChannelName::Level2
+            | ChannelName::Candles
+            | ChannelName::MarketTrades
+    )
+}
+
+/// The connections opened by a [`SubscriptionPlanner`].
+pub struct ShardedClient {
+    clients: Vec<WebSocketClient>,
+    plan: Vec<Vec<Channel>>,
+}
+
+impl ShardedClient {
+    /// Get the client of each connection.
+    pub fn clients(&self) -> &[WebSocketClient] {
+        &self.clients
+    }
+
+    /// Get the channels subscribed on each connection when it was opened.
+    pub fn plan(&self) -> &[Vec<Channel>] {
+        &self.plan
+    }
+
+    /// Number of connections.
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Returns true if there are no connections.
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    /// Close every connection.
+    ///
+    /// As with [`WebSocketClient::close`], the merged stream must still be
+    /// polled for the closes to be acknowledged. All connections are closed
+    /// even if one fails; the first error is returned.
+    pub async fn close(&self) -> Result<()> {
+        future::join_all(self.clients.iter().map(WebSocketClient::close))
+            .await
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn products(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("P{:03}-USD", i)).collect()
+    }
+
+    #[test]
+    fn test_plan_balances_products() {
+        let planner = SubscriptionPlanner::new()
+            .channel(ChannelName::Heartbeats)
+            .channel(ChannelName::Ticker)
+            .channel(ChannelName::Level2)
+            .channel(ChannelName::User)
+            .max_products_per_connection(4);
+
+        let plan = planner.plan(products(10)).unwrap();
+        assert_eq!(plan.len(), 3);
+        let sizes: Vec<usize> = plan.iter().map(|c| c[1].product_ids().len()).collect();
+        assert_eq!(sizes, [4, 3, 3]);
+
+        // Heartbeats everywhere, user only on the first connection.
+        assert!(plan.iter().all(|c| c[0] == Channel::Heartbeats));
+        assert_eq!(plan[0].last(), Some(&Channel::User));
+        assert_eq!(plan[1].len(), 3);
+        assert_eq!(plan[0][1].product_ids(), plan[0][2].product_ids());
+    }
+
+    #[test]
+    fn test_plan_limits() {
+        let planner = SubscriptionPlanner::new()
+            .channel(ChannelName::Status)
+            .channel(ChannelName::Ticker)
+            .max_products_per_connection(2)
+            .max_connections(2);
+
+        let plan = planner.plan(Vec::<String>::new()).unwrap();
+        assert_eq!(plan, [vec![Channel::Status]]);
+
+        let plan = planner.plan(["ETH-USD", "BTC-USD", "BTC-USD"]).unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0][1].product_ids(), ["BTC-USD", "ETH-USD"]);
+
+        let err = planner.plan(products(5)).unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+    }
+}
@@ -0,0 +1,180 @@
+//! Reconnect backoff and attempt notifications.
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long to wait before each reconnect attempt.
+///
+/// The default waits 1 second before the first attempt and doubles the
+/// delay after every failure, up to 60 seconds.
+#[derive(Clone)]
+pub enum BackoffPolicy {
+    /// Wait the same time before every attempt.
+    Fixed(Duration),
+    /// Wait `initial` before the first attempt and double the delay after
+    /// every failure, up to `max`.
+    Exponential {
+        /// Delay before the first attempt.
+        initial: Duration,
+        /// Longest delay.
+        max: Duration,
+        /// Fraction of each delay, between 0 and 1, to randomly shave off
+        /// so that many clients dropped at once do not reconnect in step.
+        jitter: f64,
+    },
+    /// Compute the delay from the attempt number, starting at 1.
+    Custom(Arc<dyn Fn(u32) -> Duration + Send + Sync>),
+}
+
+impl BackoffPolicy {
+    /// Wait the same time before every attempt.
+    pub fn fixed(delay: Duration) -> Self {
+        Self::Fixed(delay)
+    }
+
+    /// Double the delay after every failure, without jitter.
+    pub fn exponential(initial: Duration, max: Duration) -> Self {
+        Self::Exponential {
+            initial,
+            max,
+            jitter: 0.0,
+        }
+    }
+
+    /// Compute the delay from the attempt number, starting at 1.
+    pub fn custom(delay: impl Fn(u32) -> Duration + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(delay))
+    }
+
+    /// Randomly shorten exponential delays by up to `jitter` of their length.
+    ///
+    /// `jitter` is clamped to between 0 and 1. Other policies are returned
+    /// unchanged.
+    pub fn with_jitter(self, jitter: f64) -> Self {
+        match self {
+            Self::Exponential { initial, max, .. } => Self::Exponential {
+                initial,
+                max,
+                jitter: jitter.clamp(0.0, 1.0),
+            },
+            other => other,
+        }
+    }
+
+    /// Get the delay before an attempt, starting at 1.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match self {
+            Self::Fixed(delay) => *delay,
+            Self::Exponential {
+                initial,
+                max,
+                jitter,
+            } => {
+                let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+                let delay = initial.saturating_mul(factor).min(*max);
+                if *jitter > 0.0 {
+                    delay.mul_f64(1.0 - jitter * random_unit())
+                } else {
+                    delay
+                }
+            }
+            Self::Custom(delay) => delay(attempt),
+        }
+    }
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self::exponential(Duration::from_secs(1), Duration::from_secs(60))
+    }
+}
+
+impl fmt::Debug for BackoffPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed(delay) => f.debug_tuple("Fixed").field(delay).finish(),
+            Self::Exponential {
+                initial,
+                max,
+                jitter,
+            } => f
+                .debug_struct("Exponential")
+                .field("initial", initial)
+                .field("max", max)
+                .field("jitter", jitter)
+                .finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// A random number between 0 and 1.
+///
+/// Jitter does not need a good random source, so this uses the randomly
+/// seeded keys of the standard library's hasher instead of a dependency.
+fn random_unit() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Progress of a reconnect, passed to
+/// [`on_reconnect`](super::WebSocketClientBuilder::on_reconnect) callbacks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReconnectEvent {
+    /// An attempt will be made after `delay`.
+    Attempt {
+        /// Attempt number, starting at 1.
+        attempt: u32,
+        /// Time waited before the attempt.
+        delay: Duration,
+    },
+    /// An attempt failed.
+    Failed {
+        /// Attempt number, starting at 1.
+        attempt: u32,
+        /// Why it failed.
+        error: String,
+    },
+    /// An attempt succeeded and the subscriptions were restored.
+    Reconnected {
+        /// Attempt number, starting at 1.
+        attempt: u32,
+    },
+    /// Every allowed attempt failed.
+    GaveUp {
+        /// Number of attempts made.
+        attempts: u32,
+    },
+}
+
+pub(super) type ReconnectCallback = Arc<dyn Fn(&ReconnectEvent) + Send + Sync>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delays() {
+        let default = BackoffPolicy::default();
+        let delays: Vec<u64> = (1..=8).map(|n| default.delay(n).as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(default.delay(u32::MAX), Duration::from_secs(60));
+
+        let fixed = BackoffPolicy::fixed(Duration::from_millis(250));
+        assert_eq!(fixed.delay(1), fixed.delay(100));
+
+        let custom = BackoffPolicy::custom(|attempt| Duration::from_secs(attempt.into()));
+        assert_eq!(custom.delay(3), Duration::from_secs(3));
+
+        let jittered = BackoffPolicy::exponential(Duration::from_secs(10), Duration::from_secs(10))
+            .with_jitter(0.5);
+        for _ in 0..100 {
+            let delay = jittered.delay(1);
+            assert!(delay >= Duration::from_secs(5) && delay <= Duration::from_secs(10));
+        }
+        assert!(format!("{:?}", custom).contains("Custom"));
+    }
+}